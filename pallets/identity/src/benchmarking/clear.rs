@@ -0,0 +1,78 @@
+//! Worst-case setup for the benchmarks clearing identities, judgements and fields, and releasing
+//! what they held.
+
+use super::*;
+
+/// Set up an identity for `caller` with every field `bytes` long and `j` non-sticky remote
+/// judgements, each with a maximum length note and a remote source to remove
+pub(super) fn set_up_remote_judgements<T: Config<I>, I: 'static>(
+	caller: &T::AccountId,
+	bytes: u32,
+	j: u32,
+) {
+	set_up_identity::<T, I>(caller, bytes);
+	give_remote_judgements::<T, I>(
+		caller,
+		0..j,
+		Judgement::Reasonable,
+		Some(create_note::<T, I>(T::MaxNoteLength::get())),
+	);
+}
+
+/// Set up an identity for `caller` with `j` non-sticky double map judgements, each with a
+/// maximum length note to remove
+pub(super) fn set_up_double_map_judgements<T: Config<I>, I: 'static>(
+	caller: &T::AccountId,
+	j: u32,
+) {
+	set_up_identity::<T, I>(caller, 10);
+	give_double_map_judgements::<T, I>(
+		caller,
+		0..j,
+		Some(create_note::<T, I>(T::MaxNoteLength::get())),
+	);
+}
+
+/// Reserve a deposit of `caller` held until the current block, so that it can be withdrawn
+pub(super) fn set_up_held_deposit<T: Config<I>, I: 'static>(caller: &T::AccountId) {
+	fund_account::<T, I>(caller);
+	let amount = T::BasicDeposit::get();
+	T::Currency::reserve(caller, amount).expect("Reserving the deposit failed.");
+	TotalDeposits::<T, I>::mutate(|total| *total += amount);
+	let at = T::BlockNumberProvider::current_block_number();
+	PendingReleases::<T, I>::insert(caller, PendingRelease { amount, at });
+}
+
+/// Reserve fees accrued by `registrar`, so that they can be claimed
+pub(super) fn set_up_accrued_fees<T: Config<I>, I: 'static>(registrar: &T::AccountId) {
+	fund_account::<T, I>(registrar);
+	let amount = T::Currency::minimum_balance();
+	T::Currency::reserve(registrar, amount).expect("Reserving the fees failed.");
+	TotalDeposits::<T, I>::mutate(|total| *total += amount);
+	AccruedFees::<T, I>::insert(registrar, amount);
+}
+
+/// Set up an identity for `caller` with `j` sticky inline judgements and `j` sticky double map
+/// judgements under other IDs, all of which a tombstone keeps
+pub(super) fn set_up_sticky_judgements<T: Config<I>, I: 'static>(caller: &T::AccountId, j: u32) {
+	set_up_identity::<T, I>(caller, max_field_length::<T, I>());
+	push_inline_judgements::<T, I>(caller, 0..j, Judgement::KnownGood);
+	for i in j..(j * 2) {
+		JudgementsDoubleMap::<T, I>::insert(
+			caller,
+			judgement_id::<T, I>(i),
+			dated::<T, I>(Judgement::KnownGood),
+		);
+	}
+}
+
+/// Leave a tombstone of `caller`'s identity, with as many sticky judgements as it can hold, and
+/// let `T::TombstoneLifetime` pass so that it can be reaped
+pub(super) fn set_up_expired_tombstone<T: Config<I>, I: 'static>(caller: &T::AccountId) {
+	set_up_sticky_judgements::<T, I>(caller, T::MaxJudgements::get());
+	let reg = IdentityOf::<T, I>::get(caller).expect("The identity was just set.");
+	Identity::<T, I>::keep_tombstone(caller, &reg);
+	T::BlockNumberProvider::set_block_number(
+		T::BlockNumberProvider::current_block_number() + T::TombstoneLifetime::get(),
+	);
+}
@@ -0,0 +1,63 @@
+//! Worst-case setup for the benchmarks of what is hooked into the pallet rather than dispatched
+//! as one of its calls: judgements fed in by a bridge, the search and score update every
+//! judgement goes through, and the reset of the judgement rate limits and the expiry checks every
+//! block starts with.
+
+use super::*;
+
+/// An origin passing `T::BridgeOrigin`, and the first judgement ID reserved for bridged
+/// judgements
+pub(super) fn bridged_judgement<T: Config<I>, I: 'static>() -> (T::RuntimeOrigin, T::JudgementId) {
+	let origin = T::BridgeOrigin::try_successful_origin()
+		.expect("`BridgeOrigin` has a successful origin to benchmark with.");
+	let bridged_ids = T::BridgedJudgementIds::get();
+	assert!(!bridged_ids.is_empty(), "Judgement IDs must be reserved for bridged judgements.");
+	(origin, bridged_ids.start().clone())
+}
+
+/// Count a judgement in this block under each of `p` judgement IDs, as if `p` providers had
+/// judged, for `on_initialize` to clear
+pub(super) fn fill_judgements_in_block<T: Config<I>, I: 'static>(p: u32) {
+	for i in 0..p {
+		JudgementsInBlock::<T, I>::insert(judgement_id::<T, I>(i), 1);
+	}
+}
+
+/// Have `n` identities each hold a `KnownGood` judgement, under a judgement ID of their own and
+/// held in both storage patterns, that is due for revalidation in the current block, for
+/// `on_initialize` to downgrade. Each is the only `KnownGood` judgement of its identity, which
+/// loses its verification with it. Without room for judgements, the identities are due for
+/// revalidation of judgements they don't hold.
+pub(super) fn set_up_due_revalidations<T: Config<I>, I: 'static>(n: u32) {
+	let due = T::BlockNumberProvider::current_block_number() + T::RevalidationPeriod::get();
+	for i in 0..n {
+		let target: T::AccountId = account("target", i, 0);
+		set_up_identity::<T, I>(&target, 10);
+		if T::MaxJudgements::get() > 0 {
+			give_inline_judgements::<T, I>(&target, [i], Judgement::KnownGood, None);
+			Identity::<T, I>::provide_judgement_double_map(
+				RawOrigin::Root.into(),
+				judgement_id::<T, I>(i),
+				T::Lookup::unlookup(target.clone()),
+				Judgement::KnownGood,
+				None,
+			)
+			.expect("Giving the double map judgement failed.");
+		}
+		// Already scheduled by giving the judgement, unless there is no room for it
+		RevalidationDue::<T, I>::insert(due, (&target, judgement_id::<T, I>(i)), ());
+	}
+	RevalidationCursor::<T, I>::put(due);
+	T::BlockNumberProvider::set_block_number(due);
+	// Measured on its own by `reset_judgements_in_block`
+	let _ = JudgementsInBlock::<T, I>::clear(u32::MAX, None);
+}
+
+/// `j` judgements under the odd judgement IDs 1, 3, 5, ..., as they are stored inline
+pub(super) fn odd_judgements<T: Config<I>, I: 'static>(j: u32) -> DatedJudgementsOf<T, I> {
+	(0..j)
+		.map(|i| (judgement_id::<T, I>((i * 2) + 1), dated::<T, I>(Judgement::Reasonable)))
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("At most `MaxJudgements` judgements.")
+}
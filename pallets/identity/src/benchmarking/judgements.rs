@@ -0,0 +1,155 @@
+//! Worst-case setup for the benchmarks giving, vouching for and disputing judgements, including
+//! those signed off-chain.
+
+use super::*;
+
+/// Set up an identity for `target` with `j` inline judgements under the odd judgement IDs
+/// 1, 3, 5, ..., so that a new judgement under an even ID lands in between them
+pub(super) fn set_up_odd_judgements<T: Config<I>, I: 'static>(
+	target: &T::AccountId,
+	bytes: u32,
+	j: u32,
+) {
+	set_up_identity::<T, I>(target, bytes);
+	push_inline_judgements::<T, I>(target, (0..j).map(|i| (i * 2) + 1), Judgement::Reasonable);
+}
+
+/// Set up an identity for `who` with `j` double map judgements and `j` `KnownGood` inline
+/// judgements in reverse order of their IDs, under a double map counter of zero, so that an
+/// audit finds everything to repair
+pub(super) fn set_up_inconsistent_judgements<T: Config<I>, I: 'static>(who: &T::AccountId, j: u32) {
+	set_up_identity::<T, I>(who, 10);
+	give_double_map_judgements::<T, I>(who, 0..j, None);
+	IdentityOf::<T, I>::mutate(who, |maybe_reg| {
+		let reg = maybe_reg.as_mut().expect("The identity was just set.");
+		for i in (0..j).rev() {
+			let _ = reg
+				.judgements
+				.try_push((judgement_id::<T, I>(i), dated::<T, I>(Judgement::KnownGood)));
+		}
+	});
+	CounterForJudgements::<T, I>::remove(who);
+}
+
+/// Set up an identity for `caller` with `d + 1` double map judgements, all but the first of them
+/// disputed
+pub(super) fn set_up_open_disputes<T: Config<I>, I: 'static>(caller: &T::AccountId, d: u32) {
+	set_up_identity::<T, I>(caller, 10);
+	give_double_map_judgements::<T, I>(caller, 0..=d, None);
+	dispute_judgements::<T, I>(caller, 1..=d);
+}
+
+/// Set up an identity for `target` whose `Erroneous` judgement under the first judgement ID is
+/// disputed
+pub(super) fn set_up_disputed_judgement<T: Config<I>, I: 'static>(target: &T::AccountId) {
+	set_up_identity::<T, I>(target, 10);
+	give_inline_judgements::<T, I>(target, [0], Judgement::Erroneous, None);
+	dispute_judgements::<T, I>(target, [0]);
+}
+
+/// Set up an identity for `target` whose disputed judgement is as costly to overturn as there
+/// can be: a `KnownGood` one under the first judgement ID, held in both storage patterns with a
+/// maximum length note, in front of `j - 1` other inline judgements, and given by a
+/// self-registered provider whose provider bond is slashed. Remote registrars can't judge under
+/// the provider's ID, and forgetting a remote source costs the same write either way.
+pub(super) fn set_up_overturnable_judgement<T: Config<I>, I: 'static>(
+	target: &T::AccountId,
+	j: u32,
+) {
+	set_up_identity::<T, I>(target, 10);
+	add_provider::<T, I>(0, T::MinProviderBond::get());
+	let max_note = create_note::<T, I>(T::MaxNoteLength::get());
+	give_inline_judgements::<T, I>(target, [0], Judgement::KnownGood, Some(max_note.clone()));
+	Identity::<T, I>::provide_judgement_double_map(
+		RawOrigin::Root.into(),
+		judgement_id::<T, I>(0),
+		T::Lookup::unlookup(target.clone()),
+		Judgement::KnownGood,
+		Some(max_note),
+	)
+	.expect("Giving the double map judgement failed.");
+	give_inline_judgements::<T, I>(target, 1..j, Judgement::Reasonable, None);
+	dispute_judgements::<T, I>(target, [0]);
+}
+
+/// Set up an identity for `target` whose only `KnownGood` judgement, under the first judgement ID
+/// and held in both storage patterns, expires in the current block, in front of `j - 1` other
+/// inline judgements
+pub(super) fn set_up_expiring_judgement<T: Config<I>, I: 'static>(target: &T::AccountId, j: u32) {
+	set_up_identity::<T, I>(target, 10);
+	give_inline_judgements::<T, I>(target, [0], Judgement::KnownGood, None);
+	Identity::<T, I>::provide_judgement_double_map(
+		RawOrigin::Root.into(),
+		judgement_id::<T, I>(0),
+		T::Lookup::unlookup(target.clone()),
+		Judgement::KnownGood,
+		None,
+	)
+	.expect("Giving the double map judgement failed.");
+	push_inline_judgements::<T, I>(target, 1..j, Judgement::Reasonable);
+
+	T::BlockNumberProvider::set_block_number(
+		T::BlockNumberProvider::current_block_number() + T::RevalidationPeriod::get(),
+	);
+}
+
+/// Set up `target` one vouch short of the threshold, with all but one inline judgement, and
+/// `caller` verified so that it may vouch
+pub(super) fn set_up_last_vouch<T: Config<I>, I: 'static>(
+	target: &T::AccountId,
+	caller: &T::AccountId,
+) {
+	set_up_identity::<T, I>(target, 10);
+	give_inline_judgements::<T, I>(
+		target,
+		0..T::MaxJudgements::get() - 1,
+		Judgement::Reasonable,
+		None,
+	);
+	let vouches: Vec<T::AccountId> =
+		(0..T::MaxVouches::get() - 1).map(|i| account("voucher", i, 0)).collect();
+	Vouches::<T, I>::insert(target, BoundedVec::truncate_from(vouches));
+
+	set_up_identity::<T, I>(caller, 10);
+	give_inline_judgements::<T, I>(caller, [0], Judgement::KnownGood, None);
+}
+
+/// Sign a `KnownGood` judgement on `target` under the first judgement ID with the key of its
+/// registrar, which is then rotated out, so that the signature is checked against both keys
+pub(super) fn signed_judgement<T: Config<I>, I: 'static>(
+	target: &T::AccountId,
+) -> T::OffchainSignature {
+	let registrar: T::AccountId = account("registrar", 0, 0);
+	let payload = Identity::<T, I>::judgement_payload(
+		&judgement_id::<T, I>(0),
+		target,
+		Judgement::KnownGood,
+		0,
+	);
+	let (key, signature) = T::BenchmarkHelper::sign_message(&payload);
+	Identity::<T, I>::set_provider_key(
+		RawOrigin::Signed(registrar.clone()).into(),
+		judgement_id::<T, I>(0),
+		Some(key.into_account()),
+	)
+	.expect("Setting the provider key failed.");
+	Identity::<T, I>::rotate_provider_key(
+		RawOrigin::Signed(registrar).into(),
+		judgement_id::<T, I>(0),
+		account("key", 1, 0),
+	)
+	.expect("Rotating the provider key failed.");
+	signature
+}
+
+/// Set up an identity for `target` with maximum length fields and `j` email field judgements
+/// under the odd judgement IDs 1, 3, 5, ...
+pub(super) fn set_up_odd_field_judgements<T: Config<I>, I: 'static>(target: &T::AccountId, j: u32) {
+	set_up_identity::<T, I>(target, max_field_length::<T, I>());
+	let judgements: FieldJudgementsOf<T, I> = (0..j)
+		.map(|i| (judgement_id::<T, I>((i * 2) + 1), IdentityField::Email, Judgement::Reasonable))
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("At most `MaxJudgements` judgements.");
+	FieldJudgements::<T, I>::insert(target, judgements);
+}
@@ -0,0 +1,1940 @@
+//! Benchmarking setup for pallet-identity
+//!
+//! This module contains comprehensive benchmarks for the Identity pallet,
+//! designed to showcase various benchmarking patterns and complexities:
+//!
+//! 1. **Linear complexity** - `set_identity` scales with identity data size O(n)
+//! 2. **Logarithmic complexity** - `provide_judgement_inline` uses binary search O(log n), measured
+//!    on its own by `binary_search_judgements`
+//! 3. **Storage pattern comparison** - Different usage patterns with one unified clear extrinsic:
+//!    - `provide_judgement_inline`: Uses BoundedVec with O(log n) binary search
+//!    - `provide_judgement_double_map`: Uses DoubleMap with O(1) insertion
+//!    - `clear_identity`: Single extrinsic with complexity depending on prior usage
+//!      - `clear_identity_inline_usage`: Effectively O(1) cleanup when only inline judgements used
+//!      - `clear_identity_double_map_usage`: O(n) cleanup where n = actual double map judgements
+//! 4. **Economic operations** - Currency operations (reserve, unreserve)
+//! 5. **Vector operations** - Sorted insertion and binary search in bounded collections
+//! 6. **Storage operations** - Multiple storage interactions with proper state management
+//!
+//! ## Learning Objectives
+//!
+//! - Understanding different complexity patterns (linear vs logarithmic vs constant)
+//! - **Storage design tradeoffs** - Comparing BoundedVec vs DoubleMap performance
+//! - Using multiple complexity parameters (b for bytes, j for judgements)
+//! - Measuring worst-case execution paths for different algorithms
+//! - **Real-world performance implications** - Why storage choice matters for cleanup operations
+//! - Binary search benchmarking with sorted data structures
+//! - Vector operations with bounded collections
+//! - Verifying benchmark correctness with comprehensive assertions
+//! - **Whitelisting** - Leaving keys already paid for earlier in the block out of the DB weight,
+//!   compared by `read_identity` and `read_identity_whitelisted`
+//!
+//! ## Layout
+//!
+//! The benchmarks are all in `benchmarks` below, since `#[instance_benchmarks]` expands a single
+//! module. What they start from is set up in the neighbouring modules, so that a new benchmark
+//! can reuse the worst case of an existing one:
+//!
+//! - `setup`: helpers shared by every area, from funding accounts to giving judgements
+//! - `set_identity`: setting, repricing, claiming and mirroring identities
+//! - `judgements`: giving, vouching for and disputing judgements
+//! - `clear`: clearing identities, judgements and fields, and releasing what they held
+//! - `hooks`: what is hooked into the pallet rather than dispatched as a call
+//! - `rent`: paying and collecting the rent of identities
+//!
+//! ## Unsupported Configurations
+//!
+//! A runtime may configure paths away, e.g. with a `MaxJudgements` of zero no judgement can be
+//! requested or given. Their benchmarks return `BenchmarkError::Override`, weighing the call at a
+//! whole block so that it's never included, or `BenchmarkError::Weightless` for a path only ever
+//! refunded to, rather than failing, so that the rest of the suite still runs. The `no_judgements`
+//! test suite keeps them in check.
+
+use super::*;
+
+use crate::{
+	Config, IdentityInfo, IdentityInfoOf, Judgement, JudgementNote, LegacyFieldLength,
+	Pallet as Identity,
+};
+use frame_benchmarking::v2::*;
+use frame_support::{
+	traits::{Currency, Get, Hooks, ReservableCurrency},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+mod clear;
+mod hooks;
+mod judgements;
+mod rent;
+mod set_identity;
+mod setup;
+
+use clear::*;
+use hooks::*;
+use judgements::*;
+use rent::*;
+use set_identity::*;
+use setup::*;
+
+#[instance_benchmarks]
+mod benchmarks {
+	use super::*;
+
+	/// Benchmark: set_identity
+	///
+	/// Complexity: Linear in the number of bytes of each identity field (d, l, w, e, a)
+	/// This benchmark demonstrates:
+	/// - Linear complexity with respect to data size
+	/// - Economic operations (currency reservation)
+	/// - Storage operations (conditional insertion/update)
+	/// - Event emission
+	#[benchmark]
+	fn set_identity(
+		// Parameters 'd', 'l', 'w', 'e' and 'a' represent the number of bytes in the display,
+		// legal, web, email and avatar fields. Each field has its own bound and creates its own
+		// linear relationship between input size and execution time
+		d: Linear<0, { T::MaxDisplayLength::get() }>,
+		l: Linear<0, { T::MaxLegalLength::get() }>,
+		w: Linear<0, { T::MaxWebLength::get() }>,
+		e: Linear<0, { T::MaxEmailLength::get() }>,
+		a: Linear<0, { T::MaxCidLength::get() }>,
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+		fund_account::<T, I>(&caller);
+
+		let identity_info = create_identity_info::<T, I>(d, l, w, e, a);
+		let expected_deposit =
+			T::BasicDeposit::get() + T::ByteDeposit::get() * identity_info.encoded_size().into();
+
+		#[extrinsic_call]
+		set_identity(RawOrigin::Signed(caller.clone()), identity_info.clone());
+
+		// Verify the benchmark worked correctly
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert_eq!(registration.info, identity_info);
+		assert_eq!(registration.deposit, expected_deposit);
+		assert_eq!(registration.judgements.len(), 0);
+		assert_eq!(T::Currency::reserved_balance(&caller), expected_deposit);
+		let event: <T as Config<I>>::RuntimeEvent = Event::<T, I>::IdentitySet {
+			who: caller,
+			display: identity_info.display,
+			bytes: d + l + w + e + a,
+		}
+		.into();
+		frame_system::Pallet::<T>::assert_last_event(event.into());
+	}
+
+	/// Benchmark: set_identity_update
+	///
+	/// This benchmark tests the update path when an identity already exists with judgements.
+	/// It demonstrates the worst case where we have maximum inline judgements that need to be
+	/// filtered for sticky ones. This measures the cost of retaining sticky judgements, the filter
+	/// itself being measured on its own by `retain_sticky_judgements`.
+	#[benchmark]
+	fn set_identity_update(
+		d: Linear<0, { T::MaxDisplayLength::get() }>,
+		l: Linear<0, { T::MaxLegalLength::get() }>,
+		w: Linear<0, { T::MaxWebLength::get() }>,
+		e: Linear<0, { T::MaxEmailLength::get() }>,
+		a: Linear<0, { T::MaxCidLength::get() }>,
+		j: Linear<0, { T::MaxJudgements::get() }>, // Number of existing judgements
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+		// Maximum judgements (mix of sticky and non-sticky) for worst case, and field judgements
+		// on the fields about to change, which are filtered the same way
+		set_up_identity_to_update::<T, I>(&caller, (d, l, w, e, a), j);
+		set_up_field_judgements_to_update::<T, I>(&caller, j);
+
+		let new_identity_info = create_identity_info::<T, I>(d, l, w, e, a);
+
+		#[extrinsic_call]
+		set_identity(RawOrigin::Signed(caller.clone()), new_identity_info.clone());
+
+		// Verify the update worked and sticky judgements were retained
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert_eq!(registration.info, new_identity_info);
+		// Should have roughly half the judgements (only sticky ones retained)
+		assert!(registration.judgements.len() <= j as usize);
+		assert!(!KycAttestations::<T, I>::contains_key(&caller));
+	}
+
+	/// Benchmark: update_identity_fields
+	///
+	/// The update path of `set_identity` for the fields which are `d`, `l`, `w`, `e` and `a` bytes
+	/// long, a zero length standing for a field left out, which is neither compared nor measured.
+	/// Compared with `set_identity_update`, this is what leaving fields out saves.
+	///
+	/// Worst case: the fields left out are at max length in storage, and `j` inline judgements
+	/// and field judgements are filtered for sticky ones as in `set_identity_update`.
+	#[benchmark]
+	fn update_identity_fields(
+		d: Linear<0, { T::MaxDisplayLength::get() }>,
+		l: Linear<0, { T::MaxLegalLength::get() }>,
+		w: Linear<0, { T::MaxWebLength::get() }>,
+		e: Linear<0, { T::MaxEmailLength::get() }>,
+		a: Linear<0, { T::MaxCidLength::get() }>,
+		j: Linear<0, { T::MaxJudgements::get() }>,
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+		let max = max_field_length::<T, I>();
+		set_up_identity_to_update::<T, I>(&caller, (max, max, max, max, max), j);
+		set_up_field_judgements_to_update::<T, I>(&caller, j);
+		let old_info = IdentityOf::<T, I>::get(&caller).unwrap().info;
+
+		let new_identity_info = create_identity_info::<T, I>(d, l, w, e, a);
+		let fields = new_identity_info.populated_fields();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), fields, new_identity_info.clone());
+
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		for field in IdentityField::ALL {
+			let expected = if fields.contains(field) { &new_identity_info } else { &old_info };
+			assert_eq!(registration.info.field(field), expected.field(field));
+		}
+		assert_eq!(registration.field_bytes, registration.info.field_sizes());
+	}
+
+	/// Benchmark: set_identity_legacy
+	///
+	/// The deprecated four-argument variant of `set_identity`. It shares the update path with
+	/// `set_identity_update`, so we measure the same worst case: an existing identity with `j`
+	/// inline judgements to filter for sticky ones.
+	#[benchmark]
+	fn set_identity_legacy(
+		d: Linear<0, { T::MaxDisplayLength::get().min(<LegacyFieldLength as Get<u32>>::get()) }>,
+		l: Linear<0, { T::MaxLegalLength::get().min(<LegacyFieldLength as Get<u32>>::get()) }>,
+		w: Linear<0, { T::MaxWebLength::get().min(<LegacyFieldLength as Get<u32>>::get()) }>,
+		e: Linear<0, { T::MaxEmailLength::get().min(<LegacyFieldLength as Get<u32>>::get()) }>,
+		a: Linear<0, { T::MaxCidLength::get() }>,
+		j: Linear<0, { T::MaxJudgements::get() }>,
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+		// The legacy call keeps the avatar, so `a` is the length of the avatar it keeps, set up at
+		// full length rather than half
+		set_up_identity_to_update::<T, I>(&caller, (d, l, w, e, a * 2), j);
+
+		let avatar = IdentityOf::<T, I>::get(&caller).unwrap().info.avatar;
+		let new_identity_info =
+			IdentityInfo { avatar, ..create_identity_info::<T, I>(d, l, w, e, 0) };
+		let legacy = |field: &[u8]| -> BoundedVec<u8, LegacyFieldLength> {
+			field.to_vec().try_into().unwrap()
+		};
+
+		#[block]
+		{
+			#[allow(deprecated)]
+			let _ = Identity::<T, I>::set_identity_legacy(
+				RawOrigin::Signed(caller.clone()).into(),
+				legacy(&new_identity_info.display),
+				legacy(&new_identity_info.legal),
+				legacy(&new_identity_info.web),
+				legacy(&new_identity_info.email),
+			);
+		}
+
+		// Verify the legacy call stored the same identity the struct-based call would have
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert_eq!(registration.info, new_identity_info);
+		assert!(registration.judgements.len() <= j as usize);
+	}
+
+	/// Benchmark: set_judgement_limit
+	///
+	/// Constant complexity: a single storage write of the new limit.
+	#[benchmark]
+	fn set_judgement_limit() {
+		let limit = T::MaxJudgements::get() / 2;
+
+		#[extrinsic_call]
+		set_judgement_limit(RawOrigin::Root, limit);
+
+		assert_eq!(JudgementLimit::<T, I>::get(), limit);
+	}
+
+	/// Benchmark: set_registrar
+	///
+	/// Constant complexity: a single storage write of the registrar. Worst case, a previous
+	/// registrar is replaced and its bond returned.
+	#[benchmark]
+	fn set_registrar() {
+		add_registrar::<T, I>(0);
+		let registrar: T::AccountId = account("registrar", 0, 1);
+		fund_registrar::<T, I>(&registrar);
+		let fee = T::Currency::minimum_balance();
+		let bond = T::RegistrarBond::get();
+
+		#[extrinsic_call]
+		set_registrar(
+			RawOrigin::Root,
+			judgement_id::<T, I>(0),
+			T::Lookup::unlookup(registrar.clone()),
+			fee,
+		);
+
+		assert_eq!(
+			Registrars::<T, I>::get(judgement_id::<T, I>(0)),
+			Some(RegistrarInfo { account: registrar.clone(), fee, bond })
+		);
+		assert_eq!(T::Currency::reserved_balance(&registrar), bond);
+	}
+
+	/// Benchmark: request_judgement
+	///
+	/// Linear `O(j)` complexity in the number of requests already pending, which are counted
+	/// against the judgement limit.
+	#[benchmark]
+	fn request_judgement(
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_identity::<T, I>(&caller, 10);
+		for i in 1..=j {
+			add_judgement_request::<T, I>(&caller, i);
+		}
+		let fee = add_registrar::<T, I>(0);
+		let reserved = T::Currency::reserved_balance(&caller);
+
+		#[extrinsic_call]
+		request_judgement(RawOrigin::Signed(caller.clone()), judgement_id::<T, I>(0), fee);
+
+		assert_eq!(JudgementRequests::<T, I>::get(&caller, judgement_id::<T, I>(0)), Some(fee));
+		assert_eq!(T::Currency::reserved_balance(&caller), reserved + fee);
+
+		Ok(())
+	}
+
+	/// Benchmark: reconcile_deposits
+	///
+	/// Linear `O(n)` complexity in the number of registrations visited. Each is worst case: maximum
+	/// length fields, a maximum length note on every possible judgement ID of both storage
+	/// patterns, and a stale deposit which has to be reserved.
+	#[benchmark]
+	fn reconcile_deposits(n: Linear<0, 100>) {
+		let max_notes = 2 * T::MaxJudgements::get();
+		for i in 0..n {
+			set_up_stale_registration::<T, I>(&account("registrant", i, 0));
+		}
+
+		#[extrinsic_call]
+		reconcile_deposits(RawOrigin::Root, n);
+
+		assert_eq!(ReconcileCursor::<T, I>::get(), None);
+		for i in 0..n {
+			let who: T::AccountId = account("registrant", i, 0);
+			let registration = IdentityOf::<T, I>::get(&who).unwrap();
+			assert_eq!(
+				registration.notes_deposit,
+				T::ByteDeposit::get() * (max_notes * T::MaxNoteLength::get()).into()
+			);
+			assert_eq!(T::Currency::reserved_balance(&who), registration.total_deposit());
+		}
+	}
+
+	/// Benchmark: consolidate_judgements
+	///
+	/// Linear `O(d)` complexity in the number of double map judgements moved. The inline storage
+	/// is filled up to `T::MaxJudgements` with IDs interleaving the moved ones, so that every move
+	/// is an insertion in the middle of the vector.
+	#[benchmark]
+	fn consolidate_judgements(d: Linear<0, { T::MaxJudgements::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		// Inline judgements with odd IDs, double map ones with even IDs
+		set_up_odd_judgements::<T, I>(&caller, 10, T::MaxJudgements::get() - d);
+		give_double_map_judgements::<T, I>(&caller, (0..d).map(|i| i * 2), None);
+
+		#[extrinsic_call]
+		consolidate_judgements(
+			RawOrigin::Signed(caller.clone()),
+			T::Lookup::unlookup(caller.clone()),
+		);
+
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert_eq!(registration.judgements.len() as u32, T::MaxJudgements::get());
+		assert_eq!(CounterForJudgements::<T, I>::get(&caller), 0);
+		assert_eq!(JudgementsDoubleMap::<T, I>::iter_prefix(&caller).count(), 0);
+	}
+
+	/// Benchmark: force_recount_judgements
+	///
+	/// Linear `O(j)` complexity in the number of double map entries counted.
+	#[benchmark]
+	fn force_recount_judgements(j: Linear<0, { T::MaxJudgements::get() }>) {
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_identity::<T, I>(&target, 10);
+		give_double_map_judgements::<T, I>(&target, 0..j, None);
+		// Let the counter drift so it has to be written
+		CounterForJudgements::<T, I>::insert(&target, j + 1);
+
+		#[extrinsic_call]
+		force_recount_judgements(RawOrigin::Root, T::Lookup::unlookup(target.clone()));
+
+		assert_eq!(CounterForJudgements::<T, I>::get(&target), j);
+	}
+
+	/// Benchmark: provide_judgement_inline
+	///
+	/// This benchmark tests providing a judgement using inline storage (BoundedVec).
+	/// Complexity:
+	/// - Linear `O(b + j)` complexity in terms of encoding and decoding (with j being the number of
+	///   judgements).
+	/// - Logarithmic `O(log j)` complexity in the number of existing judgements for binary search
+	///   at insertion.
+	/// - Constant `O(1)` complexity in terms of storage reads and writes.
+	/// - Linear `O(n)` complexity in the length of the attached note, which is stored and paid for.
+	/// - Linear `O(j - p)` complexity in the judgements `try_insert` shifts to make room at
+	///   position `p` (clamped to `j`). The front is the worst case, so the weight function is
+	///   priced at `p = 0` and does not take `p`.
+	#[benchmark]
+	fn provide_judgement_inline(
+		b: Linear<1, { max_field_length::<T, I>() }>,
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+		n: Linear<0, { T::MaxNoteLength::get() }>,
+		p: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+
+		// Add existing judgements with IDs 1, 3, 5, 7, ... (odd numbers)
+		// Then insert with ID 2p, which lands between them at position p
+		set_up_odd_judgements::<T, I>(&target, b, j);
+		let p = p.min(j);
+
+		let new_index = p * 2; // This will be inserted at position p
+		let new_judgement_id = judgement_id::<T, I>(new_index);
+		let judgement = Judgement::KnownGood;
+		let note = create_note::<T, I>(n);
+		// The judgement was requested, so its fee is paid out
+		add_judgement_request::<T, I>(&target, new_index);
+
+		#[extrinsic_call]
+		provide_judgement_inline(
+			RawOrigin::Root,
+			new_judgement_id.clone(),
+			T::Lookup::unlookup(target.clone()),
+			judgement,
+			Some(note.clone()),
+		);
+
+		// Verify judgement was provided and inserted correctly
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.judgements.len(), (j + 1) as usize);
+		assert_eq!(
+			registration.judgements[p as usize],
+			(new_judgement_id.clone(), dated::<T, I>(Judgement::KnownGood))
+		);
+		// Verify ordering is maintained
+		for i in 1..registration.judgements.len() {
+			assert!(registration.judgements[i - 1].0 < registration.judgements[i].0);
+		}
+		// Verify the note was stored and paid for (empty notes are not stored)
+		assert_eq!(
+			JudgementNotes::<T, I>::get(&target, &new_judgement_id),
+			Some(note).filter(|_| n > 0)
+		);
+		assert_eq!(registration.notes_deposit, T::ByteDeposit::get() * n.into());
+		assert!(!JudgementRequests::<T, I>::contains_key(&target, &new_judgement_id));
+
+		Ok(())
+	}
+
+	/// Benchmark: provide_judgements_inline
+	///
+	/// Giving `k` judgements in one call costs a single read and write of the registration plus
+	/// `k` sorted insertions, where `k` calls to `provide_judgement_inline` would pay for the
+	/// registration `k` times. Compare `provide_judgements_inline(j, k)` against
+	/// `k * provide_judgement_inline(j, 0)` to see the saving.
+	///
+	/// Worst case: every item is a new judgement inserted among `j` existing ones, and every item
+	/// was requested, so its fee is paid out.
+	#[benchmark]
+	fn provide_judgements_inline(
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(max_batch::<T, I>()) }>,
+		k: Linear<1, { max_batch::<T, I>().max(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		// Existing judgements with odd IDs, the new ones take the even IDs in between
+		set_up_odd_judgements::<T, I>(&target, max_field_length::<T, I>(), j);
+		// In descending order, so that every insertion shifts the judgements after it
+		let items: Vec<_> = (0..k)
+			.rev()
+			.map(|i| (judgement_id::<T, I>(i * 2), Judgement::KnownGood))
+			.collect();
+		for i in 0..k {
+			add_judgement_request::<T, I>(&target, i * 2);
+		}
+		let items: BoundedVec<_, T::MaxBatch> = items.try_into().expect("Batch too long.");
+
+		#[extrinsic_call]
+		provide_judgements_inline(RawOrigin::Root, T::Lookup::unlookup(target.clone()), items);
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.judgements.len(), (j + k) as usize);
+		for i in 0..k {
+			assert!(registration
+				.judgements
+				.contains(&(judgement_id::<T, I>(i * 2), dated::<T, I>(Judgement::KnownGood))));
+			assert!(!JudgementRequests::<T, I>::contains_key(&target, judgement_id::<T, I>(i * 2)));
+		}
+
+		Ok(())
+	}
+
+	/// Benchmark: provide_judgement_remote
+	///
+	/// Same complexity as `provide_judgement_inline`, plus recording the remote registrar.
+	#[benchmark]
+	fn provide_judgement_remote(
+		b: Linear<1, { max_field_length::<T, I>() }>,
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+		n: Linear<0, { T::MaxNoteLength::get() }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		// Existing judgements with odd IDs, so the new one is inserted at the beginning
+		set_up_odd_judgements::<T, I>(&target, b, j);
+
+		let origin = remote_origin::<T, I>();
+		let registrar = T::RemoteJudgementOrigin::ensure_origin(origin.clone())
+			.expect("`remote_origin` is a successful origin");
+		let note = create_note::<T, I>(n);
+
+		#[extrinsic_call]
+		provide_judgement_remote(
+			origin as T::RuntimeOrigin,
+			judgement_id::<T, I>(0),
+			T::Lookup::unlookup(target.clone()),
+			Judgement::KnownGood,
+			Some(note),
+		);
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.judgements.len(), (j + 1) as usize);
+		assert_eq!(
+			registration.judgements[0],
+			(judgement_id::<T, I>(0), dated::<T, I>(Judgement::KnownGood))
+		);
+		assert_eq!(
+			RemoteJudgementSources::<T, I>::get(&target, judgement_id::<T, I>(0)),
+			Some(registrar)
+		);
+		assert_eq!(registration.notes_deposit, T::ByteDeposit::get() * n.into());
+
+		Ok(())
+	}
+
+	/// Benchmark: provide_judgement_double_map
+	///
+	/// This benchmark tests providing a judgement using double map storage
+	/// Complexity: Linear `O(b)` complexity, but independent of number of judgements! The judgement
+	/// is counted in `CounterForJudgements`, so the registration is only decoded for a note or a
+	/// `KnownGood` verdict; the benchmark gives both, as the worst case.
+	///
+	/// NOTE: We ignore the possibility of inline judgements for illustration purposes. This is
+	/// meant to showcase an alternative implementation after all. If we actually had both
+	/// implementation in parallel - inadvisable - we would need to cover the worst case of `j`
+	/// inline judgements as well.
+	#[benchmark]
+	fn provide_judgement_double_map(
+		b: Linear<1, { max_field_length::<T, I>() }>,
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+		n: Linear<0, { T::MaxNoteLength::get() }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		// Add existing judgements using the proper extrinsic
+		set_up_identity::<T, I>(&target, b);
+		give_double_map_judgements::<T, I>(&target, 0..j, None);
+		let given_at = T::BlockNumberProvider::current_block_number();
+
+		let new_index = j; // This will be a new entry
+		let new_judgement_id = judgement_id::<T, I>(new_index);
+		let judgement = Judgement::KnownGood;
+		let note = create_note::<T, I>(n);
+		add_judgement_request::<T, I>(&target, new_index);
+
+		#[extrinsic_call]
+		provide_judgement_double_map(
+			RawOrigin::Root,
+			new_judgement_id.clone(),
+			T::Lookup::unlookup(target.clone()),
+			judgement,
+			Some(note.clone()),
+		);
+
+		// Verify judgement was provided
+		assert_eq!(
+			JudgementsDoubleMap::<T, I>::get(&target, &new_judgement_id),
+			Some(dated::<T, I>(Judgement::KnownGood))
+		);
+		// Verify other judgements still exist
+		for i in 0..j {
+			assert_eq!(
+				JudgementsDoubleMap::<T, I>::get(&target, judgement_id::<T, I>(i)),
+				Some((Judgement::Reasonable, given_at))
+			);
+		}
+		// Verify the note was stored (empty notes are not stored)
+		assert_eq!(
+			JudgementNotes::<T, I>::get(&target, &new_judgement_id),
+			Some(note).filter(|_| n > 0)
+		);
+		assert!(!JudgementRequests::<T, I>::contains_key(&target, &new_judgement_id));
+
+		Ok(())
+	}
+
+	/// Benchmark: clear_identity_inline_usage
+	//
+	// Implement this benchmark taking into account best practices and the complexity of the code
+	// and storage.
+	#[benchmark]
+	fn clear_identity_inline_usage(
+		b: Linear<1, { max_field_length::<T, I>() }>,
+		j: Linear<0, { T::MaxJudgements::get() }>,
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+
+		// Add maximum remote judgements, each carrying a maximum length note and recording its
+		// source for worst case. A request predating the `Requested` state is pending with each
+		// registrar, whose fee has to be refunded, and each judgement is disputed, whose deposit
+		// has to be refunded.
+		set_up_remote_judgements::<T, I>(&caller, b / 2, j);
+		for i in 0..j {
+			add_legacy_judgement_request::<T, I>(&caller, i);
+		}
+		dispute_judgements::<T, I>(&caller, 0..j);
+
+		let _deposit_before = T::Currency::reserved_balance(&caller);
+
+		#[extrinsic_call]
+		clear_identity(RawOrigin::Signed(caller.clone()));
+
+		// Verify storage was cleared and deposit returned
+		let registration = IdentityOf::<T, I>::get(&caller);
+		assert_eq!(registration, None);
+		// Verify no double map entries exist (since we only used inline) and notes are gone
+		for i in 0..j {
+			assert!(!JudgementsDoubleMap::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(!JudgementNotes::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(!RemoteJudgementSources::<T, I>::contains_key(
+				&caller,
+				judgement_id::<T, I>(i)
+			));
+			assert!(!JudgementRequests::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(!Disputes::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+		}
+		// Everything is returned, unless held for `T::DepositReleaseDelay`
+		let held = PendingReleases::<T, I>::get(&caller).map_or(Zero::zero(), |p| p.amount);
+		assert_eq!(T::Currency::reserved_balance(&caller), held);
+		assert_eq!(T::Currency::free_balance(&caller) + held, T::Currency::total_balance(&caller));
+	}
+
+	/// Benchmark: clear_identity_double_map_usage
+	//
+	// Implement this benchmark taking into account best practices and the complexity of the code
+	// and storage.
+	#[benchmark]
+	fn clear_identity_double_map_usage(
+		j: Linear<0, { T::MaxJudgements::get() }>, // Number of judgements
+	) {
+		let caller: T::AccountId = account("caller", 0, 0);
+
+		// Add existing judgements using the proper extrinsic, each with a maximum length note
+		set_up_double_map_judgements::<T, I>(&caller, j);
+
+		let _deposit_before = T::Currency::reserved_balance(&caller);
+
+		#[extrinsic_call]
+		clear_identity(RawOrigin::Signed(caller.clone()));
+
+		// Verify all storage was cleared
+		let registration = IdentityOf::<T, I>::get(&caller);
+		assert_eq!(registration, None);
+		// Verify double map entries and their notes were also cleared
+		for i in 0..j {
+			assert!(!JudgementsDoubleMap::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(!JudgementNotes::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(!RemoteJudgementSources::<T, I>::contains_key(
+				&caller,
+				judgement_id::<T, I>(i)
+			));
+		}
+		// Everything is returned, unless held for `T::DepositReleaseDelay`
+		let held = PendingReleases::<T, I>::get(&caller).map_or(Zero::zero(), |p| p.amount);
+		assert_eq!(T::Currency::reserved_balance(&caller), held);
+		assert_eq!(T::Currency::free_balance(&caller) + held, T::Currency::total_balance(&caller));
+	}
+
+	/// Benchmark: clear_judgements_inline_usage
+	///
+	/// Mirrors `clear_identity_inline_usage`: `j` non-sticky inline judgements, each with a
+	/// maximum length note and a remote source, all of which are removed while the identity
+	/// stays.
+	#[benchmark]
+	fn clear_judgements_inline_usage(j: Linear<0, { T::MaxJudgements::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_remote_judgements::<T, I>(&caller, 10, j);
+
+		#[extrinsic_call]
+		clear_judgements(RawOrigin::Signed(caller.clone()));
+
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert!(registration.judgements.is_empty());
+		assert_eq!(registration.notes_deposit, Zero::zero());
+		for i in 0..j {
+			assert!(!JudgementNotes::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(!RemoteJudgementSources::<T, I>::contains_key(
+				&caller,
+				judgement_id::<T, I>(i)
+			));
+		}
+		assert_eq!(T::Currency::reserved_balance(&caller), registration.deposit);
+	}
+
+	/// Benchmark: clear_judgements_double_map_usage
+	///
+	/// Mirrors `clear_identity_double_map_usage`: `j` non-sticky double map judgements, each with
+	/// a maximum length note, all of which are removed while the identity stays.
+	#[benchmark]
+	fn clear_judgements_double_map_usage(j: Linear<0, { T::MaxJudgements::get() }>) {
+		let caller: T::AccountId = account("caller", 0, 0);
+		set_up_double_map_judgements::<T, I>(&caller, j);
+
+		#[extrinsic_call]
+		clear_judgements(RawOrigin::Signed(caller.clone()));
+
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert_eq!(CounterForJudgements::<T, I>::get(&caller), 0);
+		assert_eq!(registration.notes_deposit, Zero::zero());
+		for i in 0..j {
+			assert!(!JudgementsDoubleMap::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(!JudgementNotes::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+		}
+		assert_eq!(T::Currency::reserved_balance(&caller), registration.deposit);
+	}
+
+	/// Benchmark: clear_judgements_with_notes
+	///
+	/// An example of components which don't add up but multiply. `j` inline judgements are
+	/// cleared, each with a note of `n` bytes, and every note is read to work out the deposit it
+	/// frees: the cost is roughly `a + b * j + c * j * n`.
+	///
+	/// A weight formula is linear in each component, so the `j * n` term can't be expressed
+	/// directly. The benchmark CLI fits each component while the others are at their maximum,
+	/// which puts the cross term into both slopes: the `j` slope is measured with
+	/// `c * MaxNoteLength` per judgement and the `n` slope with `c * MaxJudgements` per byte. The
+	/// fitted formula then never undercharges, but it overcharges by up to the cross term at its
+	/// largest, e.g. for many judgements with short notes. Measuring either slope with the other
+	/// component at its minimum instead would undercharge, which is the pitfall to avoid.
+	///
+	/// The assertions before the call check that the setup really stores `j * n` note bytes, so
+	/// that the product is measured rather than one of its factors. `clear_judgements` itself is
+	/// charged with `clear_judgements_inline_usage`, measured at the maximum note length, so this
+	/// benchmark only serves as the example.
+	#[benchmark]
+	fn clear_judgements_with_notes(
+		j: Linear<0, { T::MaxJudgements::get() }>,
+		n: Linear<1, { T::MaxNoteLength::get() }>,
+	) {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_identity::<T, I>(&caller, 10);
+		give_inline_judgements::<T, I>(
+			&caller,
+			0..j,
+			Judgement::Reasonable,
+			Some(create_note::<T, I>(n)),
+		);
+		let note_bytes: u32 = JudgementNotes::<T, I>::iter_prefix_values(&caller)
+			.map(|note| note.len() as u32)
+			.sum();
+		assert_eq!(note_bytes, j * n);
+		assert_eq!(
+			IdentityOf::<T, I>::get(&caller).unwrap().notes_deposit,
+			T::ByteDeposit::get() * note_bytes.into()
+		);
+
+		#[extrinsic_call]
+		clear_judgements(RawOrigin::Signed(caller.clone()));
+
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert!(registration.judgements.is_empty());
+		assert_eq!(registration.notes_deposit, Zero::zero());
+		assert_eq!(JudgementNotes::<T, I>::iter_prefix(&caller).count(), 0);
+		assert_eq!(T::Currency::reserved_balance(&caller), registration.deposit);
+	}
+
+	/// Benchmark: dispute_judgement
+	///
+	/// Linear `O(d)` complexity in the number of disputes already open, which are counted against
+	/// the judgement limit. The disputed judgement is only in the double map, so it is looked for
+	/// in both storage patterns.
+	#[benchmark]
+	fn dispute_judgement(
+		d: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_open_disputes::<T, I>(&caller, d);
+		let reserved = T::Currency::reserved_balance(&caller);
+
+		#[extrinsic_call]
+		dispute_judgement(RawOrigin::Signed(caller.clone()), judgement_id::<T, I>(0));
+
+		let deposit = T::ChallengeDeposit::get();
+		assert_eq!(Disputes::<T, I>::get(&caller, judgement_id::<T, I>(0)), Some(deposit));
+		assert_eq!(T::Currency::reserved_balance(&caller), reserved + deposit);
+
+		Ok(())
+	}
+
+	/// Benchmark: resolve_dispute_upheld
+	///
+	/// Constant complexity: the judgement stays and the challenge deposit is slashed.
+	#[benchmark]
+	fn resolve_dispute_upheld() -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = whitelisted_caller();
+		set_up_disputed_judgement::<T, I>(&target);
+		let reserved = T::Currency::reserved_balance(&target);
+
+		#[extrinsic_call]
+		resolve_dispute(
+			RawOrigin::Root,
+			T::Lookup::unlookup(target.clone()),
+			judgement_id::<T, I>(0),
+			true,
+		);
+
+		assert!(!Disputes::<T, I>::contains_key(&target, judgement_id::<T, I>(0)));
+		assert_eq!(T::Currency::reserved_balance(&target), reserved - T::ChallengeDeposit::get());
+
+		Ok(())
+	}
+
+	/// Benchmark: resolve_dispute_overturned
+	///
+	/// Linear `O(j)` complexity in the number of inline judgements, one of which is removed from
+	/// the front of the vector. Worst case, the judgement is a `KnownGood` one held in both
+	/// storage patterns with a maximum length note, its removal revokes the identity's
+	/// verification, and the registrar's bond is slashed.
+	#[benchmark]
+	fn resolve_dispute_overturned(
+		j: Linear<1, { T::MaxJudgements::get().max(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = whitelisted_caller();
+		set_up_overturnable_judgement::<T, I>(&target, j);
+
+		#[extrinsic_call]
+		resolve_dispute(
+			RawOrigin::Root,
+			T::Lookup::unlookup(target.clone()),
+			judgement_id::<T, I>(0),
+			false,
+		);
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.judgements.len() as u32, j - 1);
+		assert_eq!(CounterForJudgements::<T, I>::get(&target), 0);
+		assert_eq!(registration.notes_deposit, Zero::zero());
+		assert!(!Disputes::<T, I>::contains_key(&target, judgement_id::<T, I>(0)));
+		assert!(!JudgementNotes::<T, I>::contains_key(&target, judgement_id::<T, I>(0)));
+		assert!(!RemoteJudgementSources::<T, I>::contains_key(&target, judgement_id::<T, I>(0)));
+		assert_eq!(T::Currency::reserved_balance(&target), registration.deposit);
+
+		Ok(())
+	}
+
+	/// Benchmark: withdraw_deposit
+	///
+	/// Constant complexity: the held deposit is read, removed and unreserved.
+	#[benchmark]
+	fn withdraw_deposit() {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_held_deposit::<T, I>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!PendingReleases::<T, I>::contains_key(&caller));
+		assert_eq!(T::Currency::reserved_balance(&caller), Zero::zero());
+	}
+
+	/// Benchmark: clear_field
+	///
+	/// Constant complexity: the deposit is re-priced from the stored field sizes, so the size of
+	/// the other fields doesn't matter. All fields are at their maximum length.
+	#[benchmark]
+	fn clear_field() {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_identity::<T, I>(&caller, max_field_length::<T, I>());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), IdentityField::Web);
+
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert!(registration.info.web.is_empty());
+		assert_eq!(registration.field_bytes, registration.info.field_sizes());
+		assert_eq!(
+			registration.deposit,
+			T::BasicDeposit::get() +
+				T::ByteDeposit::get() * registration.info.encoded_size().into()
+		);
+	}
+
+	/// Benchmark: redact_fields
+	///
+	/// Constant complexity, benchmarked at the worst case: every field is at max length and
+	/// redacted, the field judgements are all filtered, and the attested KYC hash is invalidated.
+	#[benchmark]
+	fn redact_fields() {
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_identity::<T, I>(&target, max_field_length::<T, I>());
+		set_up_field_judgements_to_update::<T, I>(&target, T::MaxJudgements::get());
+		attest_kyc_hash::<T, I>(&target);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, T::Lookup::unlookup(target.clone()), IdentityFields::all());
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.info, Default::default());
+		assert_eq!(registration.field_bytes, registration.info.field_sizes());
+		assert!(!KycAttestations::<T, I>::contains_key(&target));
+	}
+
+	/// Benchmark: claim_fees
+	///
+	/// Constant complexity: the accrued fees are read, removed and unreserved at once, however
+	/// many judgements earned them.
+	#[benchmark]
+	fn claim_fees() {
+		let registrar: T::AccountId = whitelisted_caller();
+		set_up_accrued_fees::<T, I>(&registrar);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(registrar.clone()));
+
+		assert!(!AccruedFees::<T, I>::contains_key(&registrar));
+		assert_eq!(T::Currency::reserved_balance(&registrar), Zero::zero());
+	}
+
+	/// Benchmark: set_beneficiary
+	///
+	/// Constant complexity: a single entry is written, whichever beneficiary it names.
+	#[benchmark]
+	fn set_beneficiary() {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_identity::<T, I>(&caller, 10);
+		let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(beneficiary.clone()));
+
+		assert_eq!(Beneficiaries::<T, I>::get(&caller).map(|b| b.account), Some(beneficiary));
+	}
+
+	/// Benchmark: claim_identity
+	///
+	/// Linear `O(j)` complexity in the number of judgement IDs, each of which has an entry in
+	/// every map moved to the beneficiary: a remote judgement, a double map judgement with a
+	/// maximum length note, a pending request and a dispute.
+	#[benchmark]
+	fn claim_identity(j: Linear<0, { T::MaxJudgements::get() }>) {
+		let original: T::AccountId = account("original", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_claimable_identity::<T, I>(&original, &caller, j);
+		let reserved = T::Currency::reserved_balance(&original);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(original.clone()));
+
+		assert!(IdentityOf::<T, I>::get(&original).is_none());
+		assert!(IdentityOf::<T, I>::get(&caller).is_some());
+		assert!(KycAttestations::<T, I>::contains_key(&caller));
+		for i in 0..j {
+			assert!(JudgementsDoubleMap::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(JudgementNotes::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(RemoteJudgementSources::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(JudgementRequests::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+			assert!(Disputes::<T, I>::contains_key(&caller, judgement_id::<T, I>(i)));
+		}
+		assert_eq!(T::Currency::reserved_balance(&original), Zero::zero());
+		assert_eq!(T::Currency::reserved_balance(&caller), reserved);
+	}
+
+	/// Benchmark: vouch_for
+	///
+	/// Constant complexity, benchmarked at the worst case: the identity has all but one vouch,
+	/// the last of which reaches the threshold and inserts the system judgement among all but one
+	/// inline judgements.
+	#[benchmark]
+	fn vouch_for() -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_last_vouch::<T, I>(&target, &caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(target.clone()));
+
+		assert!(Vouches::<T, I>::get(&target).contains(&caller));
+		if T::MaxVouches::get() >= T::VouchThreshold::get() {
+			let registration = IdentityOf::<T, I>::get(&target).unwrap();
+			assert!(registration
+				.judgements
+				.iter()
+				.any(|(id, _)| *id == T::SystemJudgementId::get()));
+		}
+
+		Ok(())
+	}
+
+	/// Benchmark: set_provider_key
+	///
+	/// Constant complexity: the registrar is looked up and a single key written.
+	#[benchmark]
+	fn set_provider_key() {
+		add_registrar::<T, I>(0);
+		let registrar: T::AccountId = account("registrar", 0, 0);
+		let key: T::AccountId = account("key", 0, 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(registrar), judgement_id::<T, I>(0), Some(key.clone()));
+
+		assert_eq!(ProviderKeys::<T, I>::get(judgement_id::<T, I>(0)), Some(key));
+	}
+
+	/// Benchmark: register_provider
+	///
+	/// Constant complexity: the bond is held, and the registrar and its bond written.
+	#[benchmark]
+	fn register_provider() {
+		let provider: T::AccountId = whitelisted_caller();
+		let bond = T::MinProviderBond::get();
+		fund_provider::<T, I>(&provider, bond);
+		let fee = T::Currency::minimum_balance();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(provider.clone()), judgement_id::<T, I>(0), fee, bond);
+
+		assert_eq!(
+			Registrars::<T, I>::get(judgement_id::<T, I>(0)),
+			Some(RegistrarInfo { account: provider, fee, bond: Zero::zero() })
+		);
+		assert_eq!(ProviderBonds::<T, I>::get(judgement_id::<T, I>(0)), Some(bond));
+	}
+
+	/// Benchmark: top_up_provider_bond
+	///
+	/// Constant complexity: the registrar and its bond are read, and the top-up held.
+	#[benchmark]
+	fn top_up_provider_bond() {
+		let bond = T::MinProviderBond::get();
+		add_provider::<T, I>(0, bond);
+		let provider: T::AccountId = account("registrar", 0, 0);
+		let amount = T::Currency::minimum_balance();
+		fund_provider::<T, I>(&provider, amount);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(provider), judgement_id::<T, I>(0), amount);
+
+		assert_eq!(ProviderBonds::<T, I>::get(judgement_id::<T, I>(0)), Some(bond + amount));
+	}
+
+	/// Benchmark: withdraw_provider_bond
+	///
+	/// Constant complexity: the registrar and its bond are read, and the withdrawal released.
+	#[benchmark]
+	fn withdraw_provider_bond() {
+		let bond = T::MinProviderBond::get();
+		let amount = T::Currency::minimum_balance();
+		add_provider::<T, I>(0, bond + amount);
+		let provider: T::AccountId = account("registrar", 0, 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(provider), judgement_id::<T, I>(0), amount);
+
+		assert_eq!(ProviderBonds::<T, I>::get(judgement_id::<T, I>(0)), Some(bond));
+	}
+
+	/// Benchmark: set_kyc_hash
+	///
+	/// Constant complexity: the hash is compared with the one stored and replaced.
+	///
+	/// Worst case: the replaced hash was attested, and the attestation is invalidated.
+	#[benchmark]
+	fn set_kyc_hash() {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_identity::<T, I>(&caller, max_field_length::<T, I>());
+		attest_kyc_hash::<T, I>(&caller);
+		let kyc_hash = T::Hashing::hash(b"new kyc document");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), Some(kyc_hash));
+
+		assert_eq!(KycHashes::<T, I>::get(&caller), Some(kyc_hash));
+		assert!(!KycAttestations::<T, I>::contains_key(&caller));
+	}
+
+	/// Benchmark: attest_kyc
+	///
+	/// Constant complexity: the identity and its KYC hash are read, and the attestation written.
+	#[benchmark]
+	fn attest_kyc() {
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_identity::<T, I>(&target, max_field_length::<T, I>());
+		let kyc_hash = attest_kyc_hash::<T, I>(&target);
+		KycAttestations::<T, I>::remove(&target);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, judgement_id::<T, I>(0), T::Lookup::unlookup(target.clone()), kyc_hash);
+
+		assert_eq!(KycAttestations::<T, I>::get(&target), Some(judgement_id::<T, I>(0)));
+	}
+
+	/// Benchmark: rotate_provider_key
+	///
+	/// Constant complexity: the current key is moved aside, overwriting any earlier one.
+	#[benchmark]
+	fn rotate_provider_key() {
+		add_registrar::<T, I>(0);
+		let registrar: T::AccountId = account("registrar", 0, 0);
+		let previous: T::AccountId = account("key", 0, 0);
+		Identity::<T, I>::set_provider_key(
+			RawOrigin::Signed(registrar.clone()).into(),
+			judgement_id::<T, I>(0),
+			Some(previous.clone()),
+		)
+		.expect("Setting the provider key failed.");
+		let key: T::AccountId = account("key", 1, 0);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(registrar), judgement_id::<T, I>(0), key.clone());
+
+		assert_eq!(ProviderKeys::<T, I>::get(judgement_id::<T, I>(0)), Some(key));
+		assert_eq!(
+			PreviousProviderKeys::<T, I>::get(judgement_id::<T, I>(0)).map(|(key, _)| key),
+			Some(previous)
+		);
+	}
+
+	/// Benchmark: provide_judgement_with_proof
+	///
+	/// As `provide_judgement_inline` without a note, plus the signature verification:
+	/// - Linear `O(j)` complexity in the number of existing judgements, which are decoded and
+	///   encoded, and logarithmic in them for the binary search at insertion.
+	/// - Constant complexity in the signature verification, over a fixed size payload. In the worst
+	///   case the judgement is signed by a rotated out key, so it is verified twice.
+	#[benchmark]
+	fn provide_judgement_with_proof(
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		// Existing judgements with IDs 1, 3, 5, ... so the new one is inserted at the front
+		set_up_odd_judgements::<T, I>(&target, 10, j);
+		// The judgement was requested, so its fee is paid out
+		add_judgement_request::<T, I>(&target, 0);
+		let signature = signed_judgement::<T, I>(&target);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			judgement_id::<T, I>(0),
+			T::Lookup::unlookup(target.clone()),
+			Judgement::KnownGood,
+			signature,
+		);
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.judgements.len(), (j + 1) as usize);
+		assert_eq!(
+			registration.judgements[0],
+			(judgement_id::<T, I>(0), dated::<T, I>(Judgement::KnownGood))
+		);
+		assert_eq!(ProviderNonces::<T, I>::get(judgement_id::<T, I>(0)), 1);
+		assert!(!JudgementRequests::<T, I>::contains_key(&target, judgement_id::<T, I>(0)));
+
+		Ok(())
+	}
+
+	/// Benchmark: provide_judgement_with_reason
+	///
+	/// As `provide_judgement_inline` at its worst case, a note of `n` bytes inserted in front of
+	/// `j` judgements, plus the write of the reason code.
+	#[benchmark]
+	fn provide_judgement_with_reason(
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+		n: Linear<0, { T::MaxNoteLength::get() }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		// Existing judgements with IDs 1, 3, 5, ... so the new one is inserted at the front
+		set_up_odd_judgements::<T, I>(&target, max_field_length::<T, I>(), j);
+		// The judgement was requested, so its fee is paid out
+		add_judgement_request::<T, I>(&target, 0);
+		let note = create_note::<T, I>(n);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Root,
+			judgement_id::<T, I>(0),
+			T::Lookup::unlookup(target.clone()),
+			Judgement::LowQuality,
+			ReasonCode::DataMismatch,
+			Some(note),
+		);
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(
+			registration.judgements[0],
+			(judgement_id::<T, I>(0), dated::<T, I>(Judgement::LowQuality))
+		);
+		assert_eq!(
+			JudgementReasons::<T, I>::get(&target, judgement_id::<T, I>(0)),
+			Some(ReasonCode::DataMismatch)
+		);
+
+		Ok(())
+	}
+
+	/// Benchmark: pause
+	///
+	/// Constant complexity: a single flag is read and written.
+	#[benchmark]
+	fn pause() {
+		#[extrinsic_call]
+		_(RawOrigin::Root);
+
+		assert!(Paused::<T, I>::get());
+	}
+
+	/// Benchmark: unpause
+	///
+	/// Constant complexity: a single flag is read and removed.
+	#[benchmark]
+	fn unpause() {
+		Identity::<T, I>::pause(RawOrigin::Root.into()).expect("Pausing failed.");
+
+		#[extrinsic_call]
+		_(RawOrigin::Root);
+
+		assert!(!Paused::<T, I>::get());
+	}
+
+	/// Benchmark: set_verified_fields
+	///
+	/// Constant complexity: a read and a write of the registration, whose identity information is
+	/// at its largest, to check that each verified field is set.
+	#[benchmark]
+	fn set_verified_fields() {
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_identity::<T, I>(&target, max_field_length::<T, I>());
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, T::Lookup::unlookup(target.clone()), IdentityFields::all());
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.verified_fields, IdentityFields::all());
+	}
+
+	/// Benchmark: provide_field_judgement
+	///
+	/// Linear `O(j)` complexity in the field judgements already on the identity, which are decoded,
+	/// searched, shifted to insert the new one and encoded again.
+	///
+	/// Worst case: the new judgement goes in front of `j` existing ones.
+	#[benchmark]
+	fn provide_field_judgement(
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_odd_field_judgements::<T, I>(&target, j);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Root,
+			judgement_id::<T, I>(0),
+			T::Lookup::unlookup(target.clone()),
+			IdentityField::Email,
+			Judgement::KnownGood,
+		);
+
+		let judgements = FieldJudgements::<T, I>::get(&target);
+		assert_eq!(judgements.len(), (j + 1) as usize);
+		assert_eq!(
+			judgements[0],
+			(judgement_id::<T, I>(0), IdentityField::Email, Judgement::KnownGood)
+		);
+
+		Ok(())
+	}
+
+	/// Benchmark: mirror_remote_identity
+	///
+	/// Linear `O(p)` complexity in the encoded size of the proof, whose nodes are all hashed into
+	/// a database before the identity is looked up in it.
+	///
+	/// Worst case: the proven registration is the largest there can be, and the proof is padded
+	/// with an unrelated node to `p` bytes.
+	#[benchmark]
+	fn mirror_remote_identity(p: Linear<0, { T::MaxRemoteProofSize::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let who: T::AccountId = account("target", 0, 0);
+		// The registration on the foreign chain, taken from a local one
+		let (root, proof) = remote_identity_proof::<T, I>(&who, p);
+		assert!(proof.encoded_size() as u32 <= T::MaxRemoteProofSize::get());
+		T::ForeignStateRoot::set_state_root(root);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), T::Lookup::unlookup(who.clone()), proof);
+
+		let identity = RemoteIdentities::<T, I>::get(&who).unwrap();
+		assert_eq!(identity.state_root, root);
+		assert_eq!(identity.verified_fields, IdentityFields::all());
+	}
+
+	/// Benchmark: receive_remote_judgement
+	///
+	/// Logarithmic `O(log j)` search and linear `O(j)` insertion, as for
+	/// `provide_judgement_remote`, through [`ReceiveRemoteJudgement`] rather than a call.
+	///
+	/// Worst case: `MaxJudgements - 1` local judgements already given.
+	#[benchmark]
+	fn receive_remote_judgement(
+		j: Linear<0, { T::MaxJudgements::get().saturating_sub(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_identity::<T, I>(&target, max_field_length::<T, I>());
+		push_inline_judgements::<T, I>(&target, 0..j, Judgement::Reasonable);
+		let (origin, bridged_id) = bridged_judgement::<T, I>();
+
+		#[block]
+		{
+			Identity::<T, I>::receive_remote_judgement(
+				origin,
+				bridged_id.clone(),
+				target.clone(),
+				Judgement::KnownGood,
+			)
+			.expect("Receiving the bridged judgement failed.");
+		}
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.judgements.len(), (j + 1) as usize);
+		assert!(RemoteJudgementSources::<T, I>::contains_key(&target, &bridged_id));
+
+		Ok(())
+	}
+
+	/// Benchmark: request_audit
+	///
+	/// Linear `O(r)` complexity in the number of registrars the auditor is drawn from, all read
+	/// before drawing.
+	///
+	/// Worst case: `MaxAuditCandidates` registrars, the identity holder preferring none of them.
+	#[benchmark]
+	fn request_audit(r: Linear<1, { T::MaxAuditCandidates::get() }>) {
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_identity::<T, I>(&target, max_field_length::<T, I>());
+		for i in 0..r {
+			add_registrar::<T, I>(i);
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, T::Lookup::unlookup(target.clone()));
+
+		let judgement_id = PendingAudits::<T, I>::get(&target).unwrap();
+		assert!(Registrars::<T, I>::contains_key(&judgement_id));
+	}
+
+	/// Benchmark: set_preferred_registrars
+	///
+	/// Linear `O(p)` complexity in the number of registrars listed, each looked up in
+	/// `Registrars`. Checking them for duplicates is quadratic, but in no more than
+	/// `MaxPreferredRegistrars` of them.
+	///
+	/// Worst case: the registration has maximum length fields, decoded and re-encoded with the
+	/// list.
+	#[benchmark]
+	fn set_preferred_registrars(p: Linear<0, { T::MaxPreferredRegistrars::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_identity::<T, I>(&caller, max_field_length::<T, I>());
+		for i in 0..p {
+			add_registrar::<T, I>(i);
+		}
+		let registrars =
+			BoundedVec::truncate_from((0..p).map(judgement_id::<T, I>).collect::<Vec<_>>());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), registrars.clone());
+
+		assert_eq!(IdentityOf::<T, I>::get(&caller).unwrap().preferred_registrars, registrars);
+	}
+
+	/// Benchmark: set_deposit_exemption
+	///
+	/// Constant complexity: the exemption is written, and an identity the account holds
+	/// re-priced, which is independent of the size of its fields.
+	///
+	/// Worst case: revoking the exemption of an identity with maximum length fields, whose
+	/// deposit is reserved again.
+	#[benchmark]
+	fn set_deposit_exemption() {
+		let who: T::AccountId = account("target", 0, 0);
+		set_up_exempt_identity::<T, I>(&who);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, T::Lookup::unlookup(who.clone()), false);
+
+		assert!(!ExemptAccounts::<T, I>::contains_key(&who));
+		let registration = IdentityOf::<T, I>::get(&who).unwrap();
+		assert!(!registration.deposit.is_zero());
+		assert_eq!(T::Currency::reserved_balance(&who), registration.deposit);
+	}
+
+	/// Benchmark: keep_tombstone
+	///
+	/// Linear `O(j)` complexity: the double map judgements are iterated for the sticky ones, which
+	/// are merged by ID with the sticky inline judgements into the tombstone. Charged by
+	/// `clear_identity` on top of clearing while `T::KeepTombstones` is set.
+	///
+	/// Worst case: `j` sticky judgements in each storage pattern, all under distinct IDs, so that
+	/// every one of them is kept.
+	#[benchmark]
+	fn keep_tombstone(j: Linear<0, { T::MaxJudgements::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_sticky_judgements::<T, I>(&caller, j);
+		let reg = IdentityOf::<T, I>::get(&caller).unwrap();
+
+		#[block]
+		{
+			Identity::<T, I>::keep_tombstone(&caller, &reg);
+		}
+
+		let tombstone = Tombstones::<T, I>::get(&caller).unwrap();
+		assert_eq!(tombstone.judgements.len() as u32, j * 2);
+	}
+
+	/// Benchmark: reap_tombstone
+	///
+	/// Constant complexity: the tombstone is read for the block it was left in and removed.
+	///
+	/// Worst case: a tombstone holding `T::MaxJudgements` sticky judgements of each storage
+	/// pattern, the most it decodes to.
+	#[benchmark]
+	fn reap_tombstone() {
+		let caller: T::AccountId = whitelisted_caller();
+		let who: T::AccountId = account("target", 0, 0);
+		set_up_expired_tombstone::<T, I>(&who);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), T::Lookup::unlookup(who.clone()));
+
+		assert!(!Tombstones::<T, I>::contains_key(&who));
+	}
+
+	/// Benchmark: binary_search_judgements
+	///
+	/// Logarithmic `O(log j)` complexity: only the binary search over `j` inline judgements that
+	/// every inline judgement performs, without reading, decoding or writing the registration.
+	/// Its curve backs the logarithmic claim, which `provide_judgement_inline` hides under the
+	/// linear cost of encoding and decoding the judgements.
+	///
+	/// Worst case: the searched ID is missing, so the search runs until the range is empty.
+	#[benchmark]
+	fn binary_search_judgements(j: Linear<0, { T::MaxJudgements::get() }>) {
+		// Judgements with IDs 1, 3, 5, 7, ... (odd numbers), searched for an even ID after them
+		let judgements = odd_judgements::<T, I>(j);
+		let missing = judgement_id::<T, I>(j * 2);
+		let result;
+
+		#[block]
+		{
+			result = core::hint::black_box(&judgements).binary_search_by(|x| x.0.cmp(&missing));
+		}
+
+		assert_eq!(result, Err(j as usize));
+	}
+
+	/// Benchmark: read_identity
+	///
+	/// Constant complexity: a single read of the caller's registration, with every field at its
+	/// bound. The caller's `System::Account` is whitelisted by `whitelisted_caller`, but its
+	/// `IdentityOf` key is deliberately not: nothing earlier in the block reads it, so the read
+	/// shows up as one DB read. The baseline for `read_identity_whitelisted`.
+	#[benchmark]
+	fn read_identity() {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_identity::<T, I>(&caller, max_field_length::<T, I>());
+		let registration;
+
+		#[block]
+		{
+			registration = IdentityOf::<T, I>::get(&caller);
+		}
+
+		assert!(registration.is_some());
+	}
+
+	/// Benchmark: read_identity_whitelisted
+	///
+	/// Constant complexity: the same read as `read_identity`, with the caller's `IdentityOf` key
+	/// whitelisted. The time is the same, but the read is no longer counted, so the generated
+	/// weight has no DB read: only whitelist a key that something else in the block has already
+	/// paid for.
+	#[benchmark]
+	fn read_identity_whitelisted() {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_identity::<T, I>(&caller, max_field_length::<T, I>());
+		whitelist_identity::<T, I>(&caller);
+		let registration;
+
+		#[block]
+		{
+			registration = IdentityOf::<T, I>::get(&caller);
+		}
+
+		assert!(registration.is_some());
+	}
+
+	/// Benchmark: audit_identity
+	///
+	/// Linear `O(j)` complexity: the `j` double map judgements are counted, and the `j` inline
+	/// judgements checked for order and, on repair, sorted, deduplicated and recounted together
+	/// with the double map ones.
+	///
+	/// Worst case: a repair of an identity whose counter has drifted and whose inline
+	/// judgements are in reverse order, all `KnownGood` so that the providers are recounted too.
+	#[benchmark]
+	fn audit_identity(j: Linear<0, { T::MaxJudgements::get() }>) {
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_inconsistent_judgements::<T, I>(&target, j);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, T::Lookup::unlookup(target.clone()), true);
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(CounterForJudgements::<T, I>::get(&target), j);
+		assert!(registration.judgements.windows(2).all(|pair| pair[0].0 < pair[1].0));
+		// A single inline judgement is in order, and not recounted
+		if j > 1 {
+			assert_eq!(registration.known_good, j);
+		}
+	}
+
+	/// Benchmark: populate_identities
+	///
+	/// Linear `O(n)` complexity: each of the `n` identities mints its account's funds, reserves
+	/// the deposit and pays the registration fee, as a first `set_identity` would.
+	///
+	/// Worst case: none of the accounts exists yet, so that each one is created.
+	#[benchmark]
+	fn populate_identities(n: Linear<0, 100>) {
+		#[extrinsic_call]
+		_(RawOrigin::Root, 0, n);
+
+		for index in 0..n {
+			let who = Pallet::<T, I>::populated_account(index);
+			let registration = IdentityOf::<T, I>::get(&who).unwrap();
+			let display = alloc::format!("identity #{index}");
+			assert!(display.as_bytes().starts_with(&registration.info.display));
+			assert_eq!(T::Currency::reserved_balance(&who), registration.deposit);
+		}
+	}
+
+	/// Benchmark: poke_deposit
+	///
+	/// Constant complexity: the registration is read, its deposit repriced from the field sizes
+	/// and the block the identity was last changed in, and the difference unreserved.
+	///
+	/// Worst case: the deposit of an identity with maximum length fields has decayed as far as
+	/// it goes, so that the most is released.
+	#[benchmark]
+	fn poke_deposit() {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_decayed_identity::<T, I>(&caller);
+		let deposit = IdentityOf::<T, I>::get(&caller).unwrap().deposit;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		let registration = IdentityOf::<T, I>::get(&caller).unwrap();
+		assert!(registration.deposit <= deposit);
+		assert_eq!(T::Currency::reserved_balance(&caller), registration.total_deposit());
+	}
+
+	/// Benchmark: replace_judgement_double_map
+	///
+	/// Constant complexity: `provide_judgement_double_map` replacing a judgement with neither an
+	/// old nor a new note, and not with `KnownGood`, only checks that the registration exists
+	/// without decoding it. Compared with `provide_judgement_double_map`, this is the write of
+	/// `IdentityOf` saved.
+	///
+	/// Worst case: the identity has maximum length fields, and a pending request under the
+	/// judgement ID is settled.
+	#[benchmark]
+	fn replace_judgement_double_map() -> Result<(), BenchmarkError> {
+		if T::MaxJudgements::get() == 0 {
+			// Only refunded to by `provide_judgement_double_map`, which gives no judgement then
+			return Err(BenchmarkError::Weightless);
+		}
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_identity::<T, I>(&target, max_field_length::<T, I>());
+		give_double_map_judgements::<T, I>(&target, [0], None);
+		add_legacy_judgement_request::<T, I>(&target, 0);
+		let registration = IdentityOf::<T, I>::get(&target);
+
+		#[extrinsic_call]
+		provide_judgement_double_map(
+			RawOrigin::Root,
+			judgement_id::<T, I>(0),
+			T::Lookup::unlookup(target.clone()),
+			Judgement::LowQuality,
+			None,
+		);
+
+		assert_eq!(
+			JudgementsDoubleMap::<T, I>::get(&target, judgement_id::<T, I>(0)),
+			Some(dated::<T, I>(Judgement::LowQuality))
+		);
+		assert_eq!(IdentityOf::<T, I>::get(&target), registration);
+		assert!(!JudgementRequests::<T, I>::contains_key(&target, judgement_id::<T, I>(0)));
+
+		Ok(())
+	}
+
+	/// Benchmark: reconfirm_judgement
+	///
+	/// Logarithmic `O(log j)` complexity: the judgement is looked up by binary search among the
+	/// `j` inline judgements, and in the double map.
+	///
+	/// Worst case: the judgement is held in both storage patterns, each redated, and expires in
+	/// this block, reconfirmed before `on_initialize` downgrades it.
+	#[benchmark]
+	fn reconfirm_judgement(
+		j: Linear<1, { T::MaxJudgements::get().max(1) }>,
+	) -> Result<(), BenchmarkError> {
+		ensure_room_for_judgements::<T, I>()?;
+		let target: T::AccountId = account("target", 0, 0);
+		set_up_expiring_judgement::<T, I>(&target, j);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, judgement_id::<T, I>(0), T::Lookup::unlookup(target.clone()));
+
+		let registration = IdentityOf::<T, I>::get(&target).unwrap();
+		assert_eq!(registration.known_good, 1);
+		assert_eq!(registration.judgements[0].1, dated::<T, I>(Judgement::KnownGood));
+		assert_eq!(
+			JudgementsDoubleMap::<T, I>::get(&target, judgement_id::<T, I>(0)),
+			Some(dated::<T, I>(Judgement::KnownGood))
+		);
+		Ok(())
+	}
+
+	/// Benchmark: reset_judgements_in_block
+	///
+	/// Linear `O(p)` complexity in the number of providers which judged in the previous block:
+	/// `on_initialize` removes the `JudgementsInBlock` entry of each.
+	#[benchmark]
+	fn reset_judgements_in_block(p: Linear<0, 100>) {
+		fill_judgements_in_block::<T, I>(p);
+
+		#[block]
+		{
+			Identity::<T, I>::on_initialize(frame_system::Pallet::<T>::block_number());
+		}
+
+		assert_eq!(JudgementsInBlock::<T, I>::iter_keys().count(), 0);
+	}
+
+	/// Benchmark: downgrade_expired_judgements
+	///
+	/// Linear `O(n)` complexity in the number of `KnownGood` judgements `on_initialize` checks
+	/// for expiry, up to `T::MaxExpiryChecks`.
+	///
+	/// Worst case: every judgement checked has expired and is downgraded to `Reasonable` in both
+	/// storage patterns, taking the verification of its identity with it and repricing its
+	/// deposit.
+	#[benchmark]
+	fn downgrade_expired_judgements(
+		n: Linear<0, { T::MaxExpiryChecks::get() }>,
+	) -> Result<(), BenchmarkError> {
+		if T::RevalidationPeriod::get().is_zero() {
+			// Nothing is checked, nor charged for, without a revalidation period
+			return Err(BenchmarkError::Weightless);
+		}
+		set_up_due_revalidations::<T, I>(n);
+
+		#[block]
+		{
+			Identity::<T, I>::on_initialize(frame_system::Pallet::<T>::block_number());
+		}
+
+		assert_eq!(RevalidationDue::<T, I>::iter().count(), 0);
+		if T::MaxJudgements::get() > 0 {
+			for i in 0..n {
+				let target: T::AccountId = account("target", i, 0);
+				assert_eq!(IdentityOf::<T, I>::get(&target).unwrap().known_good, 0);
+			}
+		}
+		Ok(())
+	}
+
+	/// Benchmark: renew_identity
+	///
+	/// Constant complexity: the registration is checked to exist without decoding it, and its
+	/// expiry written.
+	///
+	/// Worst case: the identity has expired, and is renewed before it is reaped.
+	#[benchmark]
+	fn renew_identity() {
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_expired_identity::<T, I>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!matches!(
+			IdentityExpiries::<T, I>::get(&caller),
+			Some(IdentityExpiry::Expired(_))
+		));
+	}
+
+	/// Benchmark: expire_identity
+	///
+	/// Constant complexity: the expiry of the identity is read, and written back as expired.
+	#[benchmark]
+	fn expire_identity() {
+		let caller: T::AccountId = whitelisted_caller();
+		let who: T::AccountId = account("target", 0, 0);
+		set_up_identity::<T, I>(&who, max_field_length::<T, I>());
+		let now = T::BlockNumberProvider::current_block_number();
+		IdentityExpiries::<T, I>::insert(&who, IdentityExpiry::Expires(now));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), T::Lookup::unlookup(who.clone()));
+
+		assert!(matches!(IdentityExpiries::<T, I>::get(&who), Some(IdentityExpiry::Expired(_))));
+	}
+
+	/// Benchmark: pay_rent
+	///
+	/// Constant complexity: the rent owed is withdrawn in one go however many periods it covers,
+	/// and the next collection rescheduled.
+	///
+	/// Worst case: the rent fell due but hasn't been collected yet, so that its scheduled
+	/// collection is removed as well.
+	#[benchmark]
+	fn pay_rent() -> Result<(), BenchmarkError> {
+		ensure_rent::<T, I>()?;
+		let caller: T::AccountId = whitelisted_caller();
+		set_up_rent_due::<T, I>(&caller);
+		let now = T::BlockNumberProvider::current_block_number();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		let paid_until = now + T::RentPeriod::get();
+		assert_eq!(RentStates::<T, I>::get(&caller), Some(RentState::PaidUntil(paid_until)));
+		assert_eq!(RentDue::<T, I>::iter_prefix(now).count(), 0);
+		Ok(())
+	}
+
+	/// Benchmark: collect_due_rent
+	///
+	/// Linear `O(n)` complexity in the number of identities `on_initialize` collects rent from,
+	/// up to `T::MaxRentCollections`.
+	///
+	/// Worst case: every identity pays, so that the rent is withdrawn and the next collection
+	/// scheduled, rather than the identity just marked delinquent.
+	#[benchmark]
+	fn collect_due_rent(
+		n: Linear<0, { T::MaxRentCollections::get() }>,
+	) -> Result<(), BenchmarkError> {
+		if T::IdentityRent::get().is_zero() {
+			// Nothing is collected, nor charged for, without rent
+			return Err(BenchmarkError::Weightless);
+		}
+		for i in 0..n {
+			set_up_rent_due::<T, I>(&account("registrant", i, 0));
+		}
+		let due = T::BlockNumberProvider::current_block_number();
+
+		#[block]
+		{
+			Identity::<T, I>::on_initialize(frame_system::Pallet::<T>::block_number());
+		}
+
+		for i in 0..n {
+			let who: T::AccountId = account("registrant", i, 0);
+			assert_eq!(
+				RentStates::<T, I>::get(&who),
+				Some(RentState::PaidUntil(due + T::RentPeriod::get()))
+			);
+		}
+		Ok(())
+	}
+
+	/// Benchmark: retain_sticky_judgements
+	///
+	/// Linear `O(j)` complexity: only the filter `set_identity` runs over the `j` inline judgements
+	/// of an identity it updates, keeping the sticky and disputed ones, and the re-encoding of
+	/// what is kept. Each non-sticky judgement costs a read of `Disputes`. Its curve is the part
+	/// of the `j` slope of `set_identity_update` that isn't spent on notes and remote sources.
+	///
+	/// Worst case: half the judgements are non-sticky, as in `set_identity_update`, so that the
+	/// filter both keeps and drops.
+	#[benchmark]
+	fn retain_sticky_judgements(j: Linear<0, { T::MaxJudgements::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let mut judgements = alternating_judgements::<T, I>(j);
+		let encoded;
+
+		#[block]
+		{
+			judgements.retain(|item| Identity::<T, I>::is_frozen(&caller, item));
+			encoded = judgements.encode();
+		}
+
+		assert_eq!(judgements.len() as u32, j.div_ceil(2));
+		assert!(judgements.iter().all(|(_, (judgement, _))| judgement.is_sticky()));
+		assert_eq!(encoded.len(), judgements.encoded_size());
+	}
+
+	/// Benchmark: price_identity_by_encoding
+	///
+	/// Linear complexity in the bytes of the fields: pricing the deposit of an identity by
+	/// encoding it whole, as `set_identity` used to. The baseline for
+	/// `price_identity_by_lengths`.
+	///
+	/// Worst case: every field `b` bytes long, up to its own bound.
+	#[benchmark]
+	fn price_identity_by_encoding(b: Linear<0, { max_field_length::<T, I>() }>) {
+		let info = create_uniform_identity_info::<T, I>(b);
+		let deposit;
+
+		#[block]
+		{
+			let bytes = core::hint::black_box(&info).encoded_size() as u32;
+			deposit = T::BasicDeposit::get() + T::ByteDeposit::get() * bytes.into();
+		}
+
+		assert_eq!(deposit, Identity::<T, I>::calculate_identity_deposit(&info.field_sizes()));
+	}
+
+	/// Benchmark: price_identity_by_lengths
+	///
+	/// Constant complexity: pricing the deposit of an identity from the byte length of each
+	/// field, as `set_identity` and `clear_field` do, without encoding the fields. Against
+	/// `price_identity_by_encoding`, it shows what the encode cost large identities.
+	///
+	/// Worst case: every field `b` bytes long, up to its own bound, so that `b` shows no effect.
+	#[benchmark]
+	fn price_identity_by_lengths(b: Linear<0, { max_field_length::<T, I>() }>) {
+		let info = create_uniform_identity_info::<T, I>(b);
+		let deposit;
+
+		#[block]
+		{
+			let field_bytes = core::hint::black_box(&info).field_sizes();
+			deposit = Identity::<T, I>::calculate_identity_deposit(&field_bytes);
+		}
+
+		let bytes = info.encoded_size() as u32;
+		assert_eq!(deposit, T::BasicDeposit::get() + T::ByteDeposit::get() * bytes.into());
+	}
+
+	/// Benchmark: update_score
+	///
+	/// Constant complexity: the incremental update of the cached score that comes with every
+	/// judgement given or removed, a read and a write of `Scores`. Measured on its own to show
+	/// what caching costs each judgement, instead of recomputing the score from all judgements
+	/// on read.
+	#[benchmark]
+	fn update_score() {
+		let target: T::AccountId = account("target", 0, 0);
+		Scores::<T, I>::insert(&target, 1);
+
+		#[block]
+		{
+			Identity::<T, I>::update_score(
+				&target,
+				Some(Judgement::Reasonable),
+				Some(Judgement::KnownGood),
+			);
+		}
+
+		let expected = 1 - T::JudgementScore::convert(Judgement::Reasonable) +
+			T::JudgementScore::convert(Judgement::KnownGood);
+		assert_eq!(Scores::<T, I>::get(&target), expected);
+	}
+
+	impl_benchmark_test_suite!(Identity, crate::mock::new_test_ext(), crate::mock::Test);
+
+	/// The suite again under other bounds than the mock's defaults, so that a benchmark which
+	/// only holds up for particular values of `MaxJudgements` or the field lengths fails a test.
+	#[cfg(test)]
+	mod tiny_bounds {
+		use super::*;
+
+		impl_benchmark_test_suite!(
+			Identity,
+			crate::mock::new_test_ext_with(crate::mock::Bounds::TINY),
+			crate::mock::Test,
+			benchmarks_path = benchmarks
+		);
+	}
+
+	#[cfg(test)]
+	mod production_bounds {
+		use super::*;
+
+		impl_benchmark_test_suite!(
+			Identity,
+			crate::mock::new_test_ext_with(crate::mock::Bounds::PRODUCTION),
+			crate::mock::Test,
+			benchmarks_path = benchmarks
+		);
+	}
+
+	#[cfg(test)]
+	mod no_judgements {
+		use super::*;
+
+		impl_benchmark_test_suite!(
+			Identity,
+			crate::mock::new_test_ext_with(crate::mock::Bounds::NO_JUDGEMENTS),
+			crate::mock::Test,
+			benchmarks_path = benchmarks
+		);
+	}
+
+	/// The rent benchmarks, which the suites above skip as the mock runtime charges no rent by
+	/// default. The rest of the suite expects deposits, which rent replaces.
+	#[cfg(test)]
+	mod rent {
+		use super::*;
+		use crate::mock::{new_test_ext, IdentityRent, Test};
+		use frame_support::assert_ok;
+
+		#[test]
+		fn rent_benchmarks_run_with_rent() {
+			IdentityRent::set(2);
+			new_test_ext().execute_with(|| {
+				assert_ok!(Identity::<Test>::test_benchmark_pay_rent());
+			});
+			new_test_ext().execute_with(|| {
+				assert_ok!(Identity::<Test>::test_benchmark_collect_due_rent());
+			});
+		}
+	}
+}
@@ -0,0 +1,27 @@
+//! Worst-case setup for the benchmarks of rent: paying it, and collecting it as it falls due.
+
+use super::*;
+
+/// Skip a benchmark of rent under a runtime charging none, whose identities hold deposits
+/// instead: its call can only fail, so it is weighed at a whole block to never be included
+pub(super) fn ensure_rent<T: Config<I>, I: 'static>() -> Result<(), BenchmarkError> {
+	if T::IdentityRent::get().is_zero() {
+		let max_block = <T as frame_system::Config>::BlockWeights::get().max_block;
+		return Err(BenchmarkError::Override(BenchmarkResult::from_weight(max_block)));
+	}
+	Ok(())
+}
+
+/// Set up an identity for `who` with maximum length fields whose rent falls due in the current
+/// block, funded to pay it, and have `on_initialize` collect from the current block on
+pub(super) fn set_up_rent_due<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	set_up_identity::<T, I>(who, max_field_length::<T, I>());
+	let now = T::BlockNumberProvider::current_block_number();
+	if let Some(RentState::PaidUntil(at)) = RentStates::<T, I>::get(who) {
+		RentDue::<T, I>::remove(at, who);
+	}
+	RentStates::<T, I>::insert(who, RentState::PaidUntil(now));
+	RentDue::<T, I>::insert(now, who, ());
+	RentCursor::<T, I>::put(now);
+	let _ = T::Currency::deposit_creating(who, T::IdentityRent::get());
+}
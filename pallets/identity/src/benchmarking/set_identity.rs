@@ -0,0 +1,195 @@
+//! Worst-case setup for the benchmarks setting, repricing, claiming and mirroring identities.
+
+use super::*;
+use sp_trie::TrieMut;
+
+/// Fund `caller` and set an identity at half the given field lengths, with `j` judgements for an
+/// update to filter: sticky inline ones alternating with non-sticky remote ones, which carry a
+/// maximum length note to be removed with them. Its KYC hash is attested, for the update to
+/// invalidate
+pub(super) fn set_up_identity_to_update<T: Config<I>, I: 'static>(
+	caller: &T::AccountId,
+	(d, l, w, e, a): (u32, u32, u32, u32, u32),
+	j: u32,
+) {
+	fund_account::<T, I>(caller);
+	let initial_info = create_identity_info::<T, I>(d / 2, l / 2, w / 2, e / 2, a / 2);
+	Identity::<T, I>::set_identity(RawOrigin::Signed(caller.clone()).into(), initial_info)
+		.expect("Setting the identity failed.");
+
+	// Alternate between sticky (KnownGood/Erroneous) and non-sticky (Reasonable/LowQuality)
+	let max_note = create_note::<T, I>(T::MaxNoteLength::get());
+	for i in 0..j {
+		if i % 2 == 0 {
+			give_inline_judgements::<T, I>(caller, [i], Judgement::KnownGood, None);
+		} else {
+			give_remote_judgements::<T, I>(
+				caller,
+				[i],
+				Judgement::Reasonable,
+				Some(max_note.clone()),
+			);
+		}
+	}
+	attest_kyc_hash::<T, I>(caller);
+}
+
+/// Give `who`'s identity a KYC hash attested by the registrar under judgement ID 0, returning
+/// the hash
+pub(super) fn attest_kyc_hash<T: Config<I>, I: 'static>(who: &T::AccountId) -> T::Hash {
+	let kyc_hash = T::Hashing::hash(b"kyc document");
+	KycHashes::<T, I>::insert(who, kyc_hash);
+	KycAttestations::<T, I>::insert(who, judgement_id::<T, I>(0));
+	kyc_hash
+}
+
+/// `j` inline judgements alternating between sticky and non-sticky ones like those of
+/// [`set_up_identity_to_update`], without notes or remote sources
+pub(super) fn alternating_judgements<T: Config<I>, I: 'static>(j: u32) -> DatedJudgementsOf<T, I> {
+	(0..j)
+		.map(|i| {
+			let judgement = if i % 2 == 0 { Judgement::KnownGood } else { Judgement::Reasonable };
+			(judgement_id::<T, I>(i), dated::<T, I>(judgement))
+		})
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("At most `MaxJudgements` judgements.")
+}
+
+/// Store `j` field judgements on `who`, going round the fields and alternating between sticky
+/// and non-sticky ones like the whole-identity judgements of [`set_up_identity_to_update`]
+pub(super) fn set_up_field_judgements_to_update<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	j: u32,
+) {
+	let field_judgements: FieldJudgementsOf<T, I> = (0..j)
+		.map(|i| {
+			let judgement = if i % 2 == 0 { Judgement::KnownGood } else { Judgement::Reasonable };
+			let field = IdentityField::ALL[i as usize % IdentityField::ALL.len()];
+			(judgement_id::<T, I>(i), field, judgement)
+		})
+		.collect::<Vec<_>>()
+		.try_into()
+		.expect("At most `MaxJudgements` judgements.");
+	FieldJudgements::<T, I>::insert(who, field_judgements);
+}
+
+/// Set up a registration for `who` as costly to reprice as there can be: maximum length fields,
+/// a maximum length note on every possible judgement ID of both storage patterns, and a stale
+/// deposit which has to be reserved
+pub(super) fn set_up_stale_registration<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	set_up_identity::<T, I>(who, max_field_length::<T, I>());
+	for i in 0..2 * T::MaxJudgements::get() {
+		JudgementNotes::<T, I>::insert(
+			who,
+			judgement_id::<T, I>(i),
+			create_note::<T, I>(T::MaxNoteLength::get()),
+		);
+	}
+	// Pretend the deposit was priced lower than it is now
+	IdentityOf::<T, I>::mutate(who, |maybe_reg| {
+		if let Some(ref mut reg) = maybe_reg {
+			let _ = T::Currency::unreserve(who, reg.total_deposit());
+			reg.deposit = Zero::zero();
+			reg.notes_deposit = Zero::zero();
+		}
+	});
+}
+
+/// Set up an identity for `who` with maximum length fields, and exempt `who` from its deposit
+pub(super) fn set_up_exempt_identity<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	set_up_identity::<T, I>(who, max_field_length::<T, I>());
+	Identity::<T, I>::set_deposit_exemption(
+		RawOrigin::Root.into(),
+		T::Lookup::unlookup(who.clone()),
+		true,
+	)
+	.expect("Exempting the account failed.");
+}
+
+/// Set up an identity for `who` with maximum length fields, and leave it unchanged for as long
+/// as it takes its deposit to decay as far as it goes
+pub(super) fn set_up_decayed_identity<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	set_up_identity::<T, I>(who, max_field_length::<T, I>());
+	let period = T::DepositDecayPeriod::get();
+	T::BlockNumberProvider::set_block_number(
+		T::BlockNumberProvider::current_block_number() + period + period,
+	);
+}
+
+/// Set up an identity for `who` with maximum length fields, which expired in the current block
+pub(super) fn set_up_expired_identity<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	set_up_identity::<T, I>(who, max_field_length::<T, I>());
+	let now = T::BlockNumberProvider::current_block_number();
+	IdentityExpiries::<T, I>::insert(who, IdentityExpiry::Expired(now));
+}
+
+/// Set up the identity of `original` to be claimed by `beneficiary`, with `j` judgement IDs
+/// each holding an entry in every map that moves along: a remote judgement, a double map
+/// judgement with a maximum length note, a pending request and a dispute, besides an attested
+/// KYC hash. `original` has been inactive for `T::InactivityPeriod` since nominating
+/// `beneficiary`.
+pub(super) fn set_up_claimable_identity<T: Config<I>, I: 'static>(
+	original: &T::AccountId,
+	beneficiary: &T::AccountId,
+	j: u32,
+) {
+	set_up_identity::<T, I>(original, 10);
+	give_remote_judgements::<T, I>(original, 0..j, Judgement::Reasonable, None);
+	give_double_map_judgements::<T, I>(
+		original,
+		0..j,
+		Some(create_note::<T, I>(T::MaxNoteLength::get())),
+	);
+	for i in 0..j {
+		add_legacy_judgement_request::<T, I>(original, i);
+	}
+	dispute_judgements::<T, I>(original, 0..j);
+	attest_kyc_hash::<T, I>(original);
+
+	fund_account::<T, I>(beneficiary);
+	Identity::<T, I>::set_beneficiary(
+		RawOrigin::Signed(original.clone()).into(),
+		T::Lookup::unlookup(beneficiary.clone()),
+	)
+	.expect("Setting the beneficiary failed.");
+	T::BlockNumberProvider::set_block_number(
+		T::BlockNumberProvider::current_block_number() + T::InactivityPeriod::get(),
+	);
+}
+
+/// A read proof of the `IdentityOf` entry of `who`, padded to `p` bytes where it is shorter,
+/// and the state root it is proven against.
+///
+/// The entry is taken from a local identity of `who`, as large as a registration gets: maximum
+/// length fields, `T::MaxJudgements` judgements and every field verified. It is the only entry
+/// in the trie, so that every node is part of its proof.
+pub(super) fn remote_identity_proof<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	p: u32,
+) -> (T::Hash, StorageProof) {
+	set_up_identity::<T, I>(who, max_field_length::<T, I>());
+	push_inline_judgements::<T, I>(who, 0..T::MaxJudgements::get(), Judgement::KnownGood);
+	let value = IdentityOf::<T, I>::mutate(who, |maybe_reg| {
+		let reg = maybe_reg.as_mut().expect("The identity was just set.");
+		reg.verified_fields = IdentityFields::all();
+		reg.encode()
+	});
+
+	let key = Identity::<T, I>::identity_storage_key(who.clone());
+	let mut db = sp_trie::MemoryDB::<T::Hashing>::default();
+	let mut root = T::Hash::default();
+	{
+		let mut trie =
+			sp_trie::TrieDBMutBuilder::<LayoutV1<T::Hashing>>::new(&mut db, &mut root).build();
+		trie.insert(&key, &value).expect("Inserting into an in-memory trie can't fail.");
+	}
+	let mut nodes: Vec<Vec<u8>> = db.drain().into_values().map(|(node, _)| node).collect();
+	let size = StorageProof::new(nodes.clone()).encoded_size() as u32;
+	// Allow for the length prefix of the padding
+	let padding = p.saturating_sub(size).saturating_sub(4);
+	if padding > 0 {
+		nodes.push(vec![0; padding as usize]);
+	}
+	(root, StorageProof::new(nodes))
+}
@@ -0,0 +1,280 @@
+//! Helpers shared by the benchmarks of every area: origins, identity information, funding,
+//! registrars, and the judgements most worst cases are built from.
+
+use super::*;
+
+/// An origin passing `T::RemoteJudgementOrigin`
+pub(super) fn remote_origin<T: Config<I>, I: 'static>() -> T::RuntimeOrigin {
+	T::RemoteJudgementOrigin::try_successful_origin()
+		.expect("`RemoteJudgementOrigin` must have a successful origin for benchmarking")
+}
+
+/// Create a reasonable identity info for benchmarking
+/// This helper demonstrates how to set up test data for benchmarks
+/// The avatar is set even at zero bytes, which costs more than leaving it unset
+pub(super) fn create_identity_info<T: Config<I>, I: 'static>(
+	display: u32,
+	legal: u32,
+	web: u32,
+	email: u32,
+	avatar: u32,
+) -> IdentityInfoOf<T, I> {
+	let data = |bytes: u32| vec![b'X'; bytes as usize];
+
+	IdentityInfo {
+		display: BoundedVec::try_from(data(display)).expect("Display input too long."),
+		legal: BoundedVec::try_from(data(legal)).expect("Legal input too long."),
+		web: BoundedVec::try_from(data(web)).expect("Web input too long."),
+		email: BoundedVec::try_from(data(email)).expect("Email input too long."),
+		avatar: Some(BoundedVec::try_from(data(avatar)).expect("Avatar input too long.")),
+	}
+}
+
+/// Create an identity info with every field `bytes` long, capped at the field's own bound
+pub(super) fn create_uniform_identity_info<T: Config<I>, I: 'static>(
+	bytes: u32,
+) -> IdentityInfoOf<T, I> {
+	create_identity_info::<T, I>(
+		bytes.min(T::MaxDisplayLength::get()),
+		bytes.min(T::MaxLegalLength::get()),
+		bytes.min(T::MaxWebLength::get()),
+		bytes.min(T::MaxEmailLength::get()),
+		bytes.min(T::MaxCidLength::get()),
+	)
+}
+
+/// The largest of the per-field length bounds
+pub(super) fn max_field_length<T: Config<I>, I: 'static>() -> u32 {
+	T::MaxDisplayLength::get()
+		.max(T::MaxLegalLength::get())
+		.max(T::MaxWebLength::get())
+		.max(T::MaxEmailLength::get())
+		.max(T::MaxCidLength::get())
+}
+
+/// Give up on a benchmark of a call which can't succeed without room for a judgement, as under a
+/// `MaxJudgements` of zero, weighing the call at a whole block so that it's never included
+pub(super) fn ensure_room_for_judgements<T: Config<I>, I: 'static>() -> Result<(), BenchmarkError> {
+	if T::MaxJudgements::get() == 0 {
+		let max_block = <T as frame_system::Config>::BlockWeights::get().max_block;
+		return Err(BenchmarkError::Override(BenchmarkResult::from_weight(max_block)));
+	}
+	Ok(())
+}
+
+/// The largest batch `provide_judgements_inline` can give in one go: `T::MaxBatch`, unless an
+/// identity can't hold that many judgements
+pub(super) fn max_batch<T: Config<I>, I: 'static>() -> u32 {
+	T::MaxBatch::get().min(T::MaxJudgements::get())
+}
+
+/// Create a judgement note of the given length for benchmarking
+pub(super) fn create_note<T: Config<I>, I: 'static>(bytes: u32) -> JudgementNote<T, I> {
+	BoundedVec::try_from(vec![b'N'; bytes as usize]).expect("Note input too long.")
+}
+
+/// Fund an account with enough balance for benchmarking operations
+/// This helper ensures accounts have sufficient funds for deposits
+pub(super) fn fund_account<T: Config<I>, I: 'static>(account: &T::AccountId) {
+	let min_balance = T::Currency::minimum_balance();
+	// Identity bytes plus a maximum length note on every judgement of both storage patterns
+	let note_bytes = 2 * T::MaxJudgements::get() * T::MaxNoteLength::get();
+	let deposit_required =
+		T::BasicDeposit::get() + T::ByteDeposit::get() * (1000u32 + note_bytes).into();
+	// Plus the fee of a judgement request to every registrar and a dispute of every judgement
+	let fees = min_balance * T::MaxJudgements::get().into();
+	let challenges = T::ChallengeDeposit::get() * T::MaxJudgements::get().into();
+	// Plus a period's rent, paid when setting the identity under a runtime charging rent
+	let total = min_balance + deposit_required + fees + challenges + T::IdentityRent::get();
+	T::Currency::make_free_balance_be(account, total);
+}
+
+/// Fund a registrar account with its bond and enough to stay alive
+pub(super) fn fund_registrar<T: Config<I>, I: 'static>(registrar: &T::AccountId) {
+	T::Currency::make_free_balance_be(
+		registrar,
+		T::Currency::minimum_balance() + T::RegistrarBond::get(),
+	);
+}
+
+/// The judgement ID to benchmark with for `index`
+pub(super) fn judgement_id<T: Config<I>, I: 'static>(index: u32) -> T::JudgementId {
+	T::BenchmarkHelper::judgement_id(index)
+}
+
+/// Set a registrar for the judgement ID of `index`, with an existing account to be paid into
+pub(super) fn add_registrar<T: Config<I>, I: 'static>(index: u32) -> BalanceOf<T, I> {
+	let registrar: T::AccountId = account("registrar", index, 0);
+	let fee = T::Currency::minimum_balance();
+	fund_registrar::<T, I>(&registrar);
+	Identity::<T, I>::set_registrar(
+		RawOrigin::Root.into(),
+		judgement_id::<T, I>(index),
+		T::Lookup::unlookup(registrar),
+		fee,
+	)
+	.expect("Setting the registrar failed.");
+	fee
+}
+
+/// Fund a provider account with `bond` and enough to stay alive
+pub(super) fn fund_provider<T: Config<I>, I: 'static>(
+	provider: &T::AccountId,
+	bond: BalanceOf<T, I>,
+) {
+	T::Currency::make_free_balance_be(provider, T::Currency::minimum_balance() + bond);
+}
+
+/// Register the registrar account of `index` as the provider for the judgement ID of `index`
+/// with `register_provider`, holding `bond`
+pub(super) fn add_provider<T: Config<I>, I: 'static>(index: u32, bond: BalanceOf<T, I>) {
+	let provider: T::AccountId = account("registrar", index, 0);
+	fund_provider::<T, I>(&provider, bond);
+	Identity::<T, I>::register_provider(
+		RawOrigin::Signed(provider).into(),
+		judgement_id::<T, I>(index),
+		T::Currency::minimum_balance(),
+		bond,
+	)
+	.expect("Registering the provider failed.");
+}
+
+/// Set a registrar for the judgement ID of `index` and have `who` request a judgement from it
+pub(super) fn add_judgement_request<T: Config<I>, I: 'static>(who: &T::AccountId, index: u32) {
+	let fee = add_registrar::<T, I>(index);
+	Identity::<T, I>::request_judgement(
+		RawOrigin::Signed(who.clone()).into(),
+		judgement_id::<T, I>(index),
+		fee,
+	)
+	.expect("Judgement request failed.");
+}
+
+/// Set a registrar for the judgement ID of `index` and insert a pending request of `who` for it
+/// alongside the judgement `who` already holds there. Such requests predate the `Requested` state
+/// and can no longer be made through `request_judgement`, but may still be found on chain.
+pub(super) fn add_legacy_judgement_request<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	index: u32,
+) {
+	let fee = add_registrar::<T, I>(index);
+	T::Currency::reserve(who, fee).expect("Reserving the request fee failed.");
+	TotalDeposits::<T, I>::mutate(|total| *total += fee);
+	JudgementRequests::<T, I>::insert(who, judgement_id::<T, I>(index), fee);
+}
+
+/// Fund `who` and set an identity with every field `bytes` long, capped at the field's own bound
+pub(super) fn set_up_identity<T: Config<I>, I: 'static>(who: &T::AccountId, bytes: u32) {
+	fund_account::<T, I>(who);
+	Identity::<T, I>::set_identity(
+		RawOrigin::Signed(who.clone()).into(),
+		create_uniform_identity_info::<T, I>(bytes),
+	)
+	.expect("Setting the identity failed.");
+}
+
+/// Add the `IdentityOf` key of `who` to the benchmarking whitelist, so that reading and writing
+/// the registration is no longer counted as a DB access. Only right where the key is already
+/// accessed earlier in the block, e.g. by a transaction extension, which pays for it instead.
+pub(super) fn whitelist_identity<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	frame_benchmarking::benchmarking::add_to_whitelist(
+		IdentityOf::<T, I>::hashed_key_for(who).into(),
+	);
+}
+
+/// `judgement`, dated to the current block as the pallet stores it
+pub(super) fn dated<T: Config<I>, I: 'static>(judgement: Judgement) -> DatedJudgementOf<T, I> {
+	(judgement, T::BlockNumberProvider::current_block_number())
+}
+
+/// Push `judgement` under the judgement IDs of `indices` straight into the inline judgements of
+/// `who`, indexing them in `JudgedBy` but without paying for notes or touching anything else.
+/// `indices` must be ascending, to keep the judgements sorted.
+pub(super) fn push_inline_judgements<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	indices: impl IntoIterator<Item = u32>,
+	judgement: Judgement,
+) {
+	IdentityOf::<T, I>::mutate(who, |maybe_reg| {
+		let reg = maybe_reg.as_mut().expect("The identity must be set first.");
+		for i in indices {
+			let item = (judgement_id::<T, I>(i), dated::<T, I>(judgement));
+			if reg.judgements.try_push(item).is_ok() {
+				JudgedBy::<T, I>::insert(judgement_id::<T, I>(i), who, ());
+			}
+		}
+	});
+}
+
+/// Give `judgement` on `who` under the judgement IDs of `indices` through
+/// `provide_judgement_inline`, each with `note`
+pub(super) fn give_inline_judgements<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	indices: impl IntoIterator<Item = u32>,
+	judgement: Judgement,
+	note: Option<JudgementNote<T, I>>,
+) {
+	for i in indices {
+		Identity::<T, I>::provide_judgement_inline(
+			RawOrigin::Root.into(),
+			judgement_id::<T, I>(i),
+			T::Lookup::unlookup(who.clone()),
+			judgement,
+			note.clone(),
+		)
+		.expect("Giving the inline judgement failed.");
+	}
+}
+
+/// Give `Reasonable` judgements on `who` under the judgement IDs of `indices` through
+/// `provide_judgement_double_map`, each with `note`
+pub(super) fn give_double_map_judgements<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	indices: impl IntoIterator<Item = u32>,
+	note: Option<JudgementNote<T, I>>,
+) {
+	for i in indices {
+		Identity::<T, I>::provide_judgement_double_map(
+			RawOrigin::Root.into(),
+			judgement_id::<T, I>(i),
+			T::Lookup::unlookup(who.clone()),
+			Judgement::Reasonable,
+			note.clone(),
+		)
+		.expect("Giving the double map judgement failed.");
+	}
+}
+
+/// Give `judgement` on `who` under the judgement IDs of `indices` through
+/// `provide_judgement_remote`, each with `note`, so that each records its remote source
+pub(super) fn give_remote_judgements<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	indices: impl IntoIterator<Item = u32>,
+	judgement: Judgement,
+	note: Option<JudgementNote<T, I>>,
+) {
+	for i in indices {
+		Identity::<T, I>::provide_judgement_remote(
+			remote_origin::<T, I>(),
+			judgement_id::<T, I>(i),
+			T::Lookup::unlookup(who.clone()),
+			judgement,
+			note.clone(),
+		)
+		.expect("Giving the remote judgement failed.");
+	}
+}
+
+/// Have `who` dispute its judgements under the judgement IDs of `indices`
+pub(super) fn dispute_judgements<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	indices: impl IntoIterator<Item = u32>,
+) {
+	for i in indices {
+		Identity::<T, I>::dispute_judgement(
+			RawOrigin::Signed(who.clone()).into(),
+			judgement_id::<T, I>(i),
+		)
+		.expect("Dispute failed.");
+	}
+}
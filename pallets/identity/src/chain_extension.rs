@@ -0,0 +1,88 @@
+//! Chain extension exposing identity lookups to smart contracts
+//!
+//! Runtimes using `pallet-contracts` can set `type ChainExtension = IdentityExtension;` (or add it
+//! to a tuple of registered extensions) so contracts can gate functionality on verified identities.
+//! `IdentityExtension<I>` reads from instance `I` of the pallet.
+//!
+//! All functions take the SCALE encoded `AccountId` to look up as input and write their SCALE
+//! encoded result to the output buffer:
+//!
+//! | Function ID | Function      | Output                       |
+//! |-------------|---------------|------------------------------|
+//! | `0`         | `identity_of` | `Option<IdentityInfo<..>>`   |
+//! | `1`         | `is_verified` | `bool`                       |
+//! | `2`         | `display_of`  | `Option<Vec<u8>>`            |
+
+use crate::{Config, IdentityOf, Pallet, Registration};
+use alloc::vec::Vec;
+use codec::Encode;
+use core::marker::PhantomData;
+use frame_support::{pallet_prelude::*, traits::Get, DefaultNoBound};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal,
+};
+
+/// Function ID of `identity_of`.
+pub const IDENTITY_OF: u16 = 0;
+/// Function ID of `is_verified`.
+pub const IS_VERIFIED: u16 = 1;
+/// Function ID of `display_of`.
+pub const DISPLAY_OF: u16 = 2;
+
+/// Chain extension giving contracts read access to the identity pallet.
+#[derive(DefaultNoBound)]
+pub struct IdentityExtension<I = ()>(PhantomData<I>);
+
+impl<I: 'static> IdentityExtension<I> {
+	/// The weight of any function: a single read of the registration, counted at its maximum
+	/// encoded size in the proof.
+	pub fn weight<T: Config<I>>() -> Weight {
+		T::DbWeight::get()
+			.reads(1)
+			.saturating_add(Weight::from_parts(0, Registration::<T, I>::max_encoded_len() as u64))
+	}
+
+	/// The SCALE encoded output of function `func_id` for `who`.
+	pub fn output<T: Config<I>>(
+		func_id: u16,
+		who: &T::AccountId,
+	) -> Result<Vec<u8>, DispatchError> {
+		Ok(match func_id {
+			IDENTITY_OF => IdentityOf::<T, I>::get(who).map(|reg| reg.info).encode(),
+			IS_VERIFIED => Pallet::<T, I>::is_verified(who).encode(),
+			DISPLAY_OF => Pallet::<T, I>::display_of(who).encode(),
+			_ => return Err(DispatchError::Other("Unknown identity chain extension function")),
+		})
+	}
+}
+
+impl<T, I> ChainExtension<T> for IdentityExtension<I>
+where
+	T: pallet_contracts::Config + Config<I>,
+	I: 'static,
+{
+	fn call<E: Ext<T = T>>(
+		&mut self,
+		env: Environment<E, InitState>,
+	) -> Result<RetVal, DispatchError> {
+		let func_id = env.func_id();
+		let mut env = env.buf_in_buf_out();
+		let who: T::AccountId = env.read_as()?;
+		env.charge_weight(Self::weight::<T>())?;
+
+		let output = Self::output::<T>(func_id, &who)?;
+		env.write(&output, false, None)?;
+
+		Ok(RetVal::Converging(0))
+	}
+}
+
+impl<T, I> RegisteredChainExtension<T> for IdentityExtension<I>
+where
+	T: pallet_contracts::Config + Config<I>,
+	I: 'static,
+{
+	/// Arbitrary, but unique among the extensions registered with a runtime. Only one instance of
+	/// the pallet can be exposed to contracts this way.
+	const ID: u16 = 0x1D;
+}
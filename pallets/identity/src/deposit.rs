@@ -0,0 +1,108 @@
+//! Where deposits are held
+//!
+//! Identity and note deposits, challenge deposits, registrar bonds and the fees of pending
+//! judgement requests are all held through `Config::DepositBackend`, so that a runtime can back
+//! them with whatever suits it: reserves with [`ReserveDeposits`], fungible holds with
+//! [`HoldDeposits`], or its own implementation, e.g. staking-backed bonds or an off-chain escrow.
+
+use core::marker::PhantomData;
+use frame_support::traits::{
+	fungible::{BalancedHold, Credit, MutateHold},
+	tokens::{Fortitude, Precision, Restriction},
+	BalanceStatus, Get, OnUnbalanced, ReservableCurrency,
+};
+use sp_runtime::{traits::Saturating, DispatchError, DispatchResult};
+
+/// Holds, releases and slashes the deposits of the pallet.
+pub trait DepositBackend<AccountId, Balance> {
+	/// Hold `amount` of `who`'s free balance as a deposit, failing if it can't be held whole.
+	fn hold(who: &AccountId, amount: Balance) -> DispatchResult;
+
+	/// Release up to `amount` of `who`'s deposits, returning what could not be released.
+	fn release(who: &AccountId, amount: Balance) -> Balance;
+
+	/// Slash up to `amount` of `who`'s deposits, returning what could not be slashed.
+	fn slash(who: &AccountId, amount: Balance) -> Balance;
+
+	/// Move up to `amount` of `from`'s deposits to `to`, where they stay held, returning what
+	/// could not be moved.
+	fn transfer(
+		from: &AccountId,
+		to: &AccountId,
+		amount: Balance,
+	) -> Result<Balance, DispatchError>;
+}
+
+/// Deposits held as reserves of `C`, with slashed funds handed to `S`.
+pub struct ReserveDeposits<C, S = ()>(PhantomData<(C, S)>);
+
+impl<AccountId, C, S> DepositBackend<AccountId, C::Balance> for ReserveDeposits<C, S>
+where
+	C: ReservableCurrency<AccountId>,
+	S: OnUnbalanced<C::NegativeImbalance>,
+{
+	fn hold(who: &AccountId, amount: C::Balance) -> DispatchResult {
+		C::reserve(who, amount)
+	}
+
+	fn release(who: &AccountId, amount: C::Balance) -> C::Balance {
+		C::unreserve(who, amount)
+	}
+
+	fn slash(who: &AccountId, amount: C::Balance) -> C::Balance {
+		let (imbalance, remaining) = C::slash_reserved(who, amount);
+		S::on_unbalanced(imbalance);
+		remaining
+	}
+
+	fn transfer(
+		from: &AccountId,
+		to: &AccountId,
+		amount: C::Balance,
+	) -> Result<C::Balance, DispatchError> {
+		C::repatriate_reserved(from, to, amount, BalanceStatus::Reserved)
+	}
+}
+
+/// Deposits held as holds of `F` for the reason `R`, with slashed funds handed to `S`.
+pub struct HoldDeposits<F, R, S = ()>(PhantomData<(F, R, S)>);
+
+impl<AccountId, F, R, S> DepositBackend<AccountId, F::Balance> for HoldDeposits<F, R, S>
+where
+	F: MutateHold<AccountId> + BalancedHold<AccountId>,
+	R: Get<F::Reason>,
+	S: OnUnbalanced<Credit<AccountId, F>>,
+{
+	fn hold(who: &AccountId, amount: F::Balance) -> DispatchResult {
+		F::hold(&R::get(), who, amount)
+	}
+
+	fn release(who: &AccountId, amount: F::Balance) -> F::Balance {
+		let released =
+			F::release(&R::get(), who, amount, Precision::BestEffort).unwrap_or_default();
+		amount.saturating_sub(released)
+	}
+
+	fn slash(who: &AccountId, amount: F::Balance) -> F::Balance {
+		let (credit, remaining) = <F as BalancedHold<AccountId>>::slash(&R::get(), who, amount);
+		S::on_unbalanced(credit);
+		remaining
+	}
+
+	fn transfer(
+		from: &AccountId,
+		to: &AccountId,
+		amount: F::Balance,
+	) -> Result<F::Balance, DispatchError> {
+		let moved = F::transfer_on_hold(
+			&R::get(),
+			from,
+			to,
+			amount,
+			Precision::BestEffort,
+			Restriction::OnHold,
+			Fortitude::Polite,
+		)?;
+		Ok(amount.saturating_sub(moved))
+	}
+}
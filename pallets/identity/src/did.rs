@@ -0,0 +1,77 @@
+//! Export of identities as DID documents
+//!
+//! [`Pallet::did_document`] renders an account's registration as a minimal [`DidDocument`]: the
+//! DID method set by `T::DidMethod`, the account controlling the identity, and a service for each
+//! of its web and email fields. Off-chain resolvers turn it into a W3C DID document for
+//! `did:<method>:<account>`, bridging on-chain identities into DID ecosystems without parsing the
+//! pallet's storage.
+//!
+//! Only what the registration itself holds is exported; judgements and deposits are left out,
+//! apart from which services a registrar has verified.
+
+use crate::{Config, IdentityField, IdentityOf, Pallet};
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use frame_support::traits::Get;
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// What a [`DidService`] links the identity to.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum DidServiceKind {
+	/// A web site of the identity, from its `web` field. A `LinkedDomains` service in DID terms.
+	LinkedDomains,
+	/// An email address of the identity, from its `email` field. The endpoint is a `mailto:`
+	/// URI.
+	Email,
+}
+
+/// A service endpoint of a [`DidDocument`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DidService {
+	/// What the endpoint is.
+	pub kind: DidServiceKind,
+	/// The endpoint, as the URI bytes a DID document lists it under.
+	pub endpoint: Vec<u8>,
+	/// Whether a registrar has verified the field the endpoint is taken from.
+	pub verified: bool,
+}
+
+/// A registration rendered in the shape of a DID document.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DidDocument<AccountId> {
+	/// The DID method name, the `<method>` of `did:<method>:<account>`.
+	pub method: Vec<u8>,
+	/// The account controlling the identity, which is also the subject of the DID.
+	pub controller: AccountId,
+	/// Services derived from the identity's web and email fields, in that order. A field that
+	/// is empty has no service.
+	pub services: Vec<DidService>,
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// `who`'s identity as a DID document, `None` if it has none.
+	pub fn did_document_of(who: T::AccountId) -> Option<DidDocument<T::AccountId>> {
+		let reg = IdentityOf::<T, I>::get(&who)?;
+		let services = [
+			(IdentityField::Web, DidServiceKind::LinkedDomains),
+			(IdentityField::Email, DidServiceKind::Email),
+		]
+		.into_iter()
+		.filter(|(field, _)| !reg.info.field(*field).is_empty())
+		.map(|(field, kind)| {
+			let value = reg.info.field(field);
+			let endpoint = match kind {
+				DidServiceKind::LinkedDomains => value.to_vec(),
+				DidServiceKind::Email => [&b"mailto:"[..], value].concat(),
+			};
+			DidService { kind, endpoint, verified: reg.verified_fields.contains(field) }
+		})
+		.collect();
+		Some(DidDocument {
+			method: T::DidMethod::get().as_bytes().to_vec(),
+			controller: who,
+			services,
+		})
+	}
+}
@@ -0,0 +1,135 @@
+//! Transaction fee discount for verified identities
+//!
+//! [`DiscountVerified`] wraps the `OnChargeTransaction` of `pallet-transaction-payment`, e.g. its
+//! `FungibleAdapter`, and takes `Discount` off the fee of every transaction signed by an account
+//! whose identity holds a `KnownGood` judgement, as told by the pallet's [`InspectIdentity`]. Tips
+//! are paid in full. A runtime sets:
+//!
+//! ```ignore
+//! type OnChargeTransaction = DiscountVerified<FungibleAdapter<Balances, ()>, Identity, Discount>;
+//! ```
+//!
+//! Whether an account gets the discount is decided when the fee is withdrawn, so that a
+//! transaction changing the verification of its own signer is corrected at the rate it was
+//! charged at.
+//!
+//! Looking up the signer's identity isn't covered by the weight of `ChargeTransactionPayment`,
+//! which [`DiscountVerifiedWeight`] adds it to:
+//!
+//! ```ignore
+//! type WeightInfo = DiscountVerifiedWeight<Runtime, (), Identity>;
+//! ```
+
+use crate::{InspectIdentity, VerificationStatus};
+use core::marker::PhantomData;
+use frame_support::{
+	traits::{tokens::Balance, Get},
+	weights::Weight,
+};
+use pallet_transaction_payment::{Config, OnChargeTransaction, WeightInfo};
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf},
+	transaction_validity::TransactionValidityError,
+	Perbill,
+};
+
+/// Charges transaction fees through `C`, less `Discount` for the accounts `Inspect` reports to
+/// hold a `KnownGood` judgement.
+pub struct DiscountVerified<C, Inspect, Discount>(PhantomData<(C, Inspect, Discount)>);
+
+impl<C, Inspect, Discount: Get<Perbill>> DiscountVerified<C, Inspect, Discount> {
+	/// Whether `who` gets the discount: its identity holds a `KnownGood` judgement.
+	pub fn is_discounted<AccountId>(who: &AccountId) -> bool
+	where
+		Inspect: InspectIdentity<AccountId>,
+	{
+		Inspect::verification_status(who) != VerificationStatus::Unverified
+	}
+
+	/// `fee`, which includes `tip`, with `Discount` taken off all but the tip if `discounted`.
+	pub fn discounted_fee<B: Balance>(fee: B, tip: B, discounted: bool) -> B {
+		if !discounted {
+			return fee
+		}
+		fee.saturating_sub(Discount::get().mul_floor(fee.saturating_sub(tip)))
+	}
+}
+
+impl<T, C, Inspect, Discount> OnChargeTransaction<T> for DiscountVerified<C, Inspect, Discount>
+where
+	T: Config,
+	C: OnChargeTransaction<T>,
+	Inspect: InspectIdentity<T::AccountId>,
+	Discount: Get<Perbill>,
+{
+	type Balance = C::Balance;
+	/// Whether the discount was granted, and what `C` withdrew.
+	type LiquidityInfo = (bool, C::LiquidityInfo);
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let discounted = Self::is_discounted(who);
+		let fee = Self::discounted_fee(fee, tip, discounted);
+		C::withdraw_fee(who, call, dispatch_info, fee, tip).map(|withdrawn| (discounted, withdrawn))
+	}
+
+	fn can_withdraw_fee(
+		who: &T::AccountId,
+		call: &T::RuntimeCall,
+		dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+		fee: Self::Balance,
+		tip: Self::Balance,
+	) -> Result<(), TransactionValidityError> {
+		let fee = Self::discounted_fee(fee, tip, Self::is_discounted(who));
+		C::can_withdraw_fee(who, call, dispatch_info, fee, tip)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		dispatch_info: &DispatchInfoOf<T::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<T::RuntimeCall>,
+		corrected_fee: Self::Balance,
+		tip: Self::Balance,
+		(discounted, already_withdrawn): Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		let corrected_fee = Self::discounted_fee(corrected_fee, tip, discounted);
+		C::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			corrected_fee,
+			tip,
+			already_withdrawn,
+		)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn endow_account(who: &T::AccountId, amount: Self::Balance) {
+		C::endow_account(who, amount)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn minimum_balance() -> Self::Balance {
+		C::minimum_balance()
+	}
+}
+
+/// The `pallet-transaction-payment` weights `W`, plus the lookup of the signer's identity through
+/// `Inspect` for [`DiscountVerified`].
+pub struct DiscountVerifiedWeight<T, W, Inspect>(PhantomData<(T, W, Inspect)>);
+
+impl<T, W, Inspect> WeightInfo for DiscountVerifiedWeight<T, W, Inspect>
+where
+	T: frame_system::Config,
+	W: WeightInfo,
+	Inspect: InspectIdentity<T::AccountId>,
+{
+	fn charge_transaction_payment() -> Weight {
+		W::charge_transaction_payment().saturating_add(Inspect::lookup_weight())
+	}
+}
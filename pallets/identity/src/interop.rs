@@ -0,0 +1,151 @@
+//! Conversions to and from the types of the polkadot-sdk `pallet-identity`
+//!
+//! Lets identities and judgements be moved between this pallet and the production one, e.g. to
+//! benchmark both on the same data or to migrate the storage of one into the other. Only what both
+//! can hold converts:
+//!
+//! - [`IdentityInfo`] fields become `Data::Raw` of at most 32 bytes, empty ones `Data::None`, and
+//!   the avatar the upstream `image`. The fields of the upstream `IdentityInfo` this pallet lacks
+//!   (`riot`, `twitter`, `pgp_fingerprint` and `additional`) have to be empty, and hashed data
+//!   can't be converted back.
+//! - A pending upstream `FeePaid` judgement becomes [`Judgement::Requested`], whose fee is held in
+//!   `JudgementRequests` rather than the judgement, so it doesn't convert back. `OutOfDate` has no
+//!   counterpart here.
+
+use crate::{IdentityInfo, Judgement};
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::fmt::Debug;
+use frame_support::{traits::Get, BoundedVec};
+use sp_runtime::RuntimeDebug;
+use upstream_identity::{legacy, Data};
+
+/// Why a value can't be converted to or from its upstream counterpart.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum InteropError {
+	/// A field is longer than the other side allows.
+	FieldTooLong,
+	/// An upstream field only holds the hash of its data.
+	HashedField,
+	/// An upstream field this pallet lacks is set.
+	ExtraField,
+	/// The judgement has no counterpart on the other side.
+	UnmatchedJudgement,
+}
+
+fn to_data<S: Get<u32>>(field: BoundedVec<u8, S>) -> Result<Data, InteropError> {
+	if field.is_empty() {
+		return Ok(Data::None)
+	}
+	field
+		.into_inner()
+		.try_into()
+		.map(Data::Raw)
+		.map_err(|_| InteropError::FieldTooLong)
+}
+
+fn from_data<S: Get<u32>>(data: Data) -> Result<BoundedVec<u8, S>, InteropError> {
+	match data {
+		Data::None => Ok(BoundedVec::default()),
+		Data::Raw(bytes) => bytes.into_inner().try_into().map_err(|_| InteropError::FieldTooLong),
+		_ => Err(InteropError::HashedField),
+	}
+}
+
+impl<D, L, W, E, A, FieldLimit> TryFrom<IdentityInfo<D, L, W, E, A>>
+	for legacy::IdentityInfo<FieldLimit>
+where
+	D: Get<u32>,
+	L: Get<u32>,
+	W: Get<u32>,
+	E: Get<u32>,
+	A: Get<u32>,
+	FieldLimit: Get<u32>,
+{
+	type Error = InteropError;
+
+	fn try_from(info: IdentityInfo<D, L, W, E, A>) -> Result<Self, Self::Error> {
+		Ok(legacy::IdentityInfo {
+			additional: BoundedVec::default(),
+			display: to_data(info.display)?,
+			legal: to_data(info.legal)?,
+			web: to_data(info.web)?,
+			riot: Data::None,
+			email: to_data(info.email)?,
+			pgp_fingerprint: None,
+			image: info.avatar.map_or(Ok(Data::None), to_data)?,
+			twitter: Data::None,
+		})
+	}
+}
+
+impl<D, L, W, E, A, FieldLimit> TryFrom<legacy::IdentityInfo<FieldLimit>>
+	for IdentityInfo<D, L, W, E, A>
+where
+	D: Get<u32>,
+	L: Get<u32>,
+	W: Get<u32>,
+	E: Get<u32>,
+	A: Get<u32>,
+	FieldLimit: Get<u32>,
+{
+	type Error = InteropError;
+
+	fn try_from(info: legacy::IdentityInfo<FieldLimit>) -> Result<Self, Self::Error> {
+		let extra = !info.additional.is_empty() ||
+			!info.riot.is_none() ||
+			info.pgp_fingerprint.is_some() ||
+			!info.twitter.is_none();
+		if extra {
+			return Err(InteropError::ExtraField)
+		}
+		Ok(IdentityInfo {
+			display: from_data(info.display)?,
+			legal: from_data(info.legal)?,
+			web: from_data(info.web)?,
+			email: from_data(info.email)?,
+			avatar: match info.image {
+				Data::None => None,
+				image => Some(from_data(image)?),
+			},
+		})
+	}
+}
+
+impl<Balance> TryFrom<Judgement> for upstream_identity::Judgement<Balance>
+where
+	Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + PartialEq,
+{
+	type Error = InteropError;
+
+	fn try_from(judgement: Judgement) -> Result<Self, Self::Error> {
+		use upstream_identity::Judgement as Upstream;
+		match judgement {
+			Judgement::Unknown => Ok(Upstream::Unknown),
+			Judgement::Reasonable => Ok(Upstream::Reasonable),
+			Judgement::KnownGood => Ok(Upstream::KnownGood),
+			Judgement::Erroneous => Ok(Upstream::Erroneous),
+			Judgement::LowQuality => Ok(Upstream::LowQuality),
+			Judgement::Requested => Err(InteropError::UnmatchedJudgement),
+		}
+	}
+}
+
+impl<Balance> TryFrom<upstream_identity::Judgement<Balance>> for Judgement
+where
+	Balance: Encode + Decode + MaxEncodedLen + Copy + Clone + Debug + Eq + PartialEq,
+{
+	type Error = InteropError;
+
+	fn try_from(judgement: upstream_identity::Judgement<Balance>) -> Result<Self, Self::Error> {
+		use upstream_identity::Judgement as Upstream;
+		match judgement {
+			Upstream::Unknown => Ok(Judgement::Unknown),
+			Upstream::FeePaid(_) => Ok(Judgement::Requested),
+			Upstream::Reasonable => Ok(Judgement::Reasonable),
+			Upstream::KnownGood => Ok(Judgement::KnownGood),
+			Upstream::OutOfDate => Err(InteropError::UnmatchedJudgement),
+			Upstream::LowQuality => Ok(Judgement::LowQuality),
+			Upstream::Erroneous => Ok(Judgement::Erroneous),
+		}
+	}
+}
@@ -18,7 +18,7 @@
 //! - **Identity Information**: Users can set display name, legal name, web, email etc.
 //! - **Judgement System**: Configurable origin can verify identity information
 //! - **Deposits**: Economic mechanism to prevent spam and ensure data quality
-//! - **Judgements**: Verification opinions on identity validity
+//! - **Judgements**: Verification opinions on identity validity, optionally with a short note
 //!
 //! ## Benchmarking Focus
 //!
@@ -43,6 +43,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 pub use pallet::*;
 
 #[cfg(test)]
@@ -54,54 +56,331 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
-mod weights;
+#[cfg(feature = "chain-extension")]
+pub mod chain_extension;
+
+#[cfg(feature = "transaction-payment")]
+pub mod fee_discount;
+
+#[cfg(feature = "interop")]
+pub mod interop;
+
+pub mod deposit;
+
+pub mod did;
+
+pub mod migrations;
+
+pub mod priority;
+
+pub mod remote;
 
-use codec::{Decode, Encode, MaxEncodedLen};
+// Generated weight files refer to the pallet by its crate name
+extern crate self as pallet_identity;
+
+pub mod weights;
+
+use codec::{Compact, Decode, DecodeAll, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use core::ops::RangeInclusive;
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, Get, ReservableCurrency},
-	BoundedVec, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	traits::{
+		fungible, Currency, ExistenceRequirement, Get, OnKilledAccount, OnUnbalanced, Randomness,
+		ReservableCurrency, WithdrawReasons,
+	},
+	BoundedVec, CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
 };
 use frame_system::pallet_prelude::*;
 use scale_info::TypeInfo;
-use sp_runtime::traits::{Saturating, Zero};
-use sp_std::vec;
+use serde::{Deserialize, Serialize};
+use sp_runtime::{
+	traits::{
+		BlockNumberProvider, CheckedDiv, Convert, Hash, IdentifyAccount, Saturating, StaticLookup,
+		TrailingZeroInput, UniqueSaturatedInto, Verify, Zero,
+	},
+	Perbill,
+};
+use sp_std::{vec, vec::Vec};
+use sp_trie::{LayoutV1, StorageProof};
 
+pub use deposit::{DepositBackend, HoldDeposits, ReserveDeposits};
+pub use did::{DidDocument, DidService, DidServiceKind};
+pub use remote::{ForeignStateRoot, RemoteIdentity};
 pub use weights::WeightInfo;
 
 /// Identity information that can be set by users
 #[derive(
 	Encode,
 	Decode,
-	Default,
+	DecodeWithMemTracking,
+	DefaultNoBound,
 	CloneNoBound,
 	PartialEqNoBound,
-	Eq,
+	EqNoBound,
 	RuntimeDebugNoBound,
 	TypeInfo,
 	MaxEncodedLen,
+	Serialize,
+	Deserialize,
 )]
-#[scale_info(skip_type_params(MaxFieldLength))]
-pub struct IdentityInfo<MaxFieldLength: Get<u32>> {
+#[scale_info(skip_type_params(
+	MaxDisplayLength,
+	MaxLegalLength,
+	MaxWebLength,
+	MaxEmailLength,
+	MaxCidLength
+))]
+#[serde(bound = "")]
+pub struct IdentityInfo<
+	MaxDisplayLength: Get<u32>,
+	MaxLegalLength: Get<u32>,
+	MaxWebLength: Get<u32>,
+	MaxEmailLength: Get<u32>,
+	MaxCidLength: Get<u32>,
+> {
 	/// A reasonable display name for the controller of the account.
-	pub display: BoundedVec<u8, MaxFieldLength>,
+	pub display: BoundedVec<u8, MaxDisplayLength>,
 	/// The full legal name in the local jurisdiction of the entity.
-	pub legal: BoundedVec<u8, MaxFieldLength>,
+	pub legal: BoundedVec<u8, MaxLegalLength>,
 	/// A representative website field.
-	pub web: BoundedVec<u8, MaxFieldLength>,
+	pub web: BoundedVec<u8, MaxWebLength>,
 	/// An email address.
-	pub email: BoundedVec<u8, MaxFieldLength>,
+	pub email: BoundedVec<u8, MaxEmailLength>,
+	/// The content identifier, e.g. an IPFS CID, of an avatar image stored off-chain. It is
+	/// opaque to the pallet, which doesn't check its format.
+	pub avatar: Option<BoundedVec<u8, MaxCidLength>>,
 }
 
-impl<MaxFieldLength: Get<u32>> IdentityInfo<MaxFieldLength> {
+impl<
+		MaxDisplayLength: Get<u32>,
+		MaxLegalLength: Get<u32>,
+		MaxWebLength: Get<u32>,
+		MaxEmailLength: Get<u32>,
+		MaxCidLength: Get<u32>,
+	> IdentityInfo<MaxDisplayLength, MaxLegalLength, MaxWebLength, MaxEmailLength, MaxCidLength>
+{
 	/// Get the encoded size of this identity info
 	pub fn encoded_size(&self) -> u32 {
 		self.encode().len() as u32
 	}
+
+	/// Encoded size of each field, in declaration order. They add up to `encoded_size`, less the
+	/// byte of an unset avatar, without encoding any of the fields.
+	pub fn field_sizes(&self) -> [u32; 5] {
+		IdentityField::ALL.map(|field| self.field_size(field))
+	}
+
+	/// Encoded size of `field`, as counted by `field_sizes`: none for an unset avatar, which is
+	/// left out of the deposit.
+	pub fn field_size(&self, field: IdentityField) -> u32 {
+		match (field, &self.avatar) {
+			(IdentityField::Avatar, None) => 0,
+			(IdentityField::Avatar, Some(cid)) => Self::encoded_avatar_size(cid.len() as u32),
+			_ => Self::encoded_field_size(self.field(field).len() as u32),
+		}
+	}
+
+	/// Encoded size of a field `byte_len` bytes long: its compact length prefix and its bytes.
+	pub fn encoded_field_size(byte_len: u32) -> u32 {
+		(Compact(byte_len).encoded_size() as u32).saturating_add(byte_len)
+	}
+
+	/// Encoded size of an avatar set to a content identifier `byte_len` bytes long: the byte
+	/// telling that it's set, then the identifier like any other field.
+	pub fn encoded_avatar_size(byte_len: u32) -> u32 {
+		Self::encoded_field_size(byte_len).saturating_add(1)
+	}
+
+	/// Byte length of each field, in declaration order, without the length prefixes counted by
+	/// `field_sizes`.
+	pub fn byte_len_per_field(&self) -> [u32; 5] {
+		IdentityField::ALL.map(|field| self.field(field).len() as u32)
+	}
+
+	/// The contents of `field`, empty for an unset avatar.
+	pub fn field(&self, field: IdentityField) -> &[u8] {
+		match field {
+			IdentityField::Display => &self.display,
+			IdentityField::Legal => &self.legal,
+			IdentityField::Web => &self.web,
+			IdentityField::Email => &self.email,
+			IdentityField::Avatar =>
+				self.avatar.as_ref().map(|cid| cid.as_slice()).unwrap_or_default(),
+		}
+	}
+
+	/// Move `fields` of `from` into `self`, leaving the other fields as they are.
+	pub fn update_fields(&mut self, fields: IdentityFields, from: Self) {
+		let IdentityInfo { display, legal, web, email, avatar } = from;
+		if fields.contains(IdentityField::Display) {
+			self.display = display;
+		}
+		if fields.contains(IdentityField::Legal) {
+			self.legal = legal;
+		}
+		if fields.contains(IdentityField::Web) {
+			self.web = web;
+		}
+		if fields.contains(IdentityField::Email) {
+			self.email = email;
+		}
+		if fields.contains(IdentityField::Avatar) {
+			self.avatar = avatar;
+		}
+	}
+
+	/// The fields which aren't empty.
+	pub fn populated_fields(&self) -> IdentityFields {
+		IdentityField::ALL
+			.into_iter()
+			.filter(|field| !self.field(*field).is_empty())
+			.fold(IdentityFields::empty(), |fields, field| fields | field)
+	}
+
+	/// Number of fields which aren't empty.
+	pub fn field_count(&self) -> u32 {
+		self.populated_fields().iter().count() as u32
+	}
+
+	/// Whether every field is empty.
+	pub fn is_empty(&self) -> bool {
+		self.populated_fields().is_empty()
+	}
+}
+
+/// A field of [`IdentityInfo`].
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum IdentityField {
+	/// The display name.
+	Display,
+	/// The legal name.
+	Legal,
+	/// The web address.
+	Web,
+	/// The email address.
+	Email,
+	/// The avatar content identifier.
+	Avatar,
+}
+
+impl IdentityField {
+	/// Every field, in declaration order.
+	pub const ALL: [IdentityField; 5] =
+		[Self::Display, Self::Legal, Self::Web, Self::Email, Self::Avatar];
+
+	/// Position of the field in declaration order, e.g. in [`IdentityInfo::field_sizes`].
+	pub fn index(self) -> usize {
+		self as usize
+	}
+}
+
+/// A set of [`IdentityField`]s, as a bit mask with bit `i` for the field of index `i`.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Copy,
+	Clone,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct IdentityFields(u8);
+
+impl IdentityFields {
+	/// The set of no fields.
+	pub const fn empty() -> Self {
+		Self(0)
+	}
+
+	/// The set of every field.
+	pub const fn all() -> Self {
+		Self((1 << IdentityField::ALL.len()) - 1)
+	}
+
+	/// The underlying bit mask.
+	pub fn bits(self) -> u8 {
+		self.0
+	}
+
+	/// Whether the set has no fields.
+	pub fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+
+	/// Whether no bits beyond those of the fields are set, which decoding doesn't check.
+	pub fn is_valid(self) -> bool {
+		self.0 & !Self::all().0 == 0
+	}
+
+	/// Whether `field` is in the set.
+	pub fn contains(self, field: IdentityField) -> bool {
+		self.0 & Self::from(field).0 != 0
+	}
+
+	/// Add `field` to the set.
+	pub fn insert(&mut self, field: IdentityField) {
+		self.0 |= Self::from(field).0;
+	}
+
+	/// Remove `field` from the set.
+	pub fn remove(&mut self, field: IdentityField) {
+		self.0 &= !Self::from(field).0;
+	}
+
+	/// The fields in the set, in declaration order.
+	pub fn iter(self) -> impl Iterator<Item = IdentityField> {
+		IdentityField::ALL.into_iter().filter(move |field| self.contains(*field))
+	}
+}
+
+impl From<IdentityField> for IdentityFields {
+	fn from(field: IdentityField) -> Self {
+		Self(1 << field.index())
+	}
+}
+
+impl core::ops::BitOr<IdentityField> for IdentityFields {
+	type Output = Self;
+
+	fn bitor(mut self, field: IdentityField) -> Self {
+		self.insert(field);
+		self
+	}
 }
 
 /// Judgement provided by verifiers
-#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+	Serialize,
+	Deserialize,
+)]
+#[serde(into = "&'static str", try_from = "alloc::string::String")]
 pub enum Judgement {
 	/// The default value; no opinion is held.
 	Unknown,
@@ -113,6 +392,14 @@ pub enum Judgement {
 	Erroneous,
 	/// An erroneous identity may be corrected.
 	LowQuality,
+	/// A judgement was asked for with `request_judgement`, and no verdict given yet. Never
+	/// given by a provider, nor stored with the verdicts: a judgement ID without a verdict is in
+	/// this state while `JudgementRequests` holds a request under it.
+	///
+	/// A judgement ID goes from no judgement to `Requested`, and from either to any verdict.
+	/// Verdicts are only replaced or removed by the privileged calls of providers, governance
+	/// and the identity holder's own clearing, and never go back to `Requested`.
+	Requested,
 }
 
 impl Judgement {
@@ -121,12 +408,517 @@ impl Judgement {
 	pub fn is_sticky(&self) -> bool {
 		matches!(self, Judgement::KnownGood | Judgement::Erroneous)
 	}
+
+	/// Whether this is a verdict a provider may give, rather than `Requested`.
+	pub fn is_verdict(&self) -> bool {
+		!matches!(self, Judgement::Requested)
+	}
+
+	/// Whether this verdict finds fault with the identity, and may come with a [`ReasonCode`].
+	pub fn is_negative(&self) -> bool {
+		matches!(self, Judgement::Erroneous | Judgement::LowQuality)
+	}
+
+	/// The name of the judgement, as in `Judgement::KnownGood` <-> `"KnownGood"`.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Judgement::Unknown => "Unknown",
+			Judgement::Reasonable => "Reasonable",
+			Judgement::KnownGood => "KnownGood",
+			Judgement::Erroneous => "Erroneous",
+			Judgement::LowQuality => "LowQuality",
+			Judgement::Requested => "Requested",
+		}
+	}
+}
+
+/// Why a provider found fault with an identity, given with an `Erroneous` or `LowQuality` verdict
+/// so that the identity holder knows what to fix.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Copy,
+	Clone,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum ReasonCode {
+	/// The fields don't match the documents or accounts they refer to.
+	DataMismatch,
+	/// The web site or email address couldn't be reached.
+	UnreachableContact,
+	/// The identity appears to impersonate someone else.
+	SuspectedImpersonation,
+	/// The fields are too incomplete to verify.
+	InsufficientInformation,
+	/// The fields are out of date.
+	OutdatedInformation,
+	/// Any other reason, which the note given with the verdict may explain.
+	Other,
+}
+
+/// Scores judgements for an identity's reputation: verdicts of good standing add to it, bad ones
+/// take away from it, the harsher the verdict the more.
+pub struct DefaultJudgementScore;
+
+impl Convert<Judgement, i32> for DefaultJudgementScore {
+	fn convert(judgement: Judgement) -> i32 {
+		match judgement {
+			Judgement::Unknown => 0,
+			Judgement::Reasonable => 1,
+			Judgement::KnownGood => 3,
+			Judgement::LowQuality => -1,
+			Judgement::Requested => 0,
+			Judgement::Erroneous => -3,
+		}
+	}
+}
+
+/// A `u8` that does not stand for any `Judgement`.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct InvalidJudgement(pub u8);
+
+/// The compact numbering used by interfaces that can't carry the enum, e.g.
+/// 0=Unknown, 1=Reasonable, 2=KnownGood, 3=Erroneous, 4=LowQuality, 5=Requested.
+impl TryFrom<u8> for Judgement {
+	type Error = InvalidJudgement;
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Judgement::Unknown),
+			1 => Ok(Judgement::Reasonable),
+			2 => Ok(Judgement::KnownGood),
+			3 => Ok(Judgement::Erroneous),
+			4 => Ok(Judgement::LowQuality),
+			5 => Ok(Judgement::Requested),
+			_ => Err(InvalidJudgement(value)),
+		}
+	}
+}
+
+/// A string that does not name any `Judgement`.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct InvalidJudgementName;
+
+impl core::fmt::Display for InvalidJudgementName {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		f.write_str(
+			"expected one of Unknown, Reasonable, KnownGood, Erroneous, LowQuality, Requested",
+		)
+	}
+}
+
+/// Judgements by name, for CLI tools, genesis configs and tests. Serde goes through these as
+/// well.
+impl core::str::FromStr for Judgement {
+	type Err = InvalidJudgementName;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		match name {
+			"Unknown" => Ok(Judgement::Unknown),
+			"Reasonable" => Ok(Judgement::Reasonable),
+			"KnownGood" => Ok(Judgement::KnownGood),
+			"Erroneous" => Ok(Judgement::Erroneous),
+			"LowQuality" => Ok(Judgement::LowQuality),
+			"Requested" => Ok(Judgement::Requested),
+			_ => Err(InvalidJudgementName),
+		}
+	}
+}
+
+impl TryFrom<alloc::string::String> for Judgement {
+	type Error = InvalidJudgementName;
+
+	fn try_from(name: alloc::string::String) -> Result<Self, Self::Error> {
+		name.parse()
+	}
+}
+
+impl core::fmt::Display for Judgement {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl From<Judgement> for &'static str {
+	fn from(judgement: Judgement) -> Self {
+		judgement.as_str()
+	}
+}
+
+impl From<Judgement> for u8 {
+	fn from(judgement: Judgement) -> Self {
+		match judgement {
+			Judgement::Unknown => 0,
+			Judgement::Reasonable => 1,
+			Judgement::KnownGood => 2,
+			Judgement::Erroneous => 3,
+			Judgement::LowQuality => 4,
+			Judgement::Requested => 5,
+		}
+	}
+}
+
+/// How close an identity is to verification, derived from the number of distinct judgement
+/// providers that gave it a `KnownGood` judgement.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Copy,
+	Clone,
+	Default,
+	PartialEq,
+	Eq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum VerificationStatus {
+	/// No `KnownGood` judgement.
+	#[default]
+	Unverified,
+	/// `KnownGood` judgements, but from fewer providers than required.
+	PartiallyVerified,
+	/// `KnownGood` judgements from at least the required number of providers.
+	Verified,
+}
+
+impl VerificationStatus {
+	/// The status of an identity with `KnownGood` judgements from `providers` distinct providers,
+	/// when `threshold` of them are required. A threshold of zero counts as one.
+	pub fn from_providers(providers: u32, threshold: u32) -> Self {
+		match providers {
+			0 => VerificationStatus::Unverified,
+			providers if providers >= threshold => VerificationStatus::Verified,
+			_ => VerificationStatus::PartiallyVerified,
+		}
+	}
+}
+
+/// How well an identity is verified, derived from the distinct providers of its sticky
+/// judgements, for other pallets to gate on. Levels are ordered, so that e.g. `>= Silver` admits
+/// `Gold` too.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Copy,
+	Clone,
+	Default,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum VerificationLevel {
+	/// No identity, or one judged `Erroneous`.
+	#[default]
+	None,
+	/// An identity without `KnownGood` or `Erroneous` judgements.
+	Bronze,
+	/// `KnownGood` judgements from one provider, and no `Erroneous` ones.
+	Silver,
+	/// `KnownGood` judgements from at least two providers, and no `Erroneous` ones.
+	Gold,
+}
+
+impl VerificationLevel {
+	/// The level of an identity with `KnownGood` judgements from `known_good` distinct providers
+	/// and `Erroneous` ones from `erroneous`.
+	pub fn from_judgements(known_good: u32, erroneous: u32) -> Self {
+		match (known_good, erroneous) {
+			(_, 1..) => VerificationLevel::None,
+			(0, _) => VerificationLevel::Bronze,
+			(1, _) => VerificationLevel::Silver,
+			_ => VerificationLevel::Gold,
+		}
+	}
+}
+
+/// What a call of the pallet is for, see [`Pallet::categorize`]. Lets a runtime's
+/// `InstanceFilter` for `pallet-proxy` allow, e.g., identity manager proxies without listing
+/// every call.
+#[derive(Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum CallCategory {
+	/// Managing one's own identity: setting, clearing and claiming it, requesting and disputing
+	/// judgements, and releasing its deposits.
+	IdentityManagement,
+	/// What registrars and other judgement providers do: giving judgements and vouches,
+	/// managing their keys and claiming their fees.
+	Judgement,
+	/// Governance and maintenance, mostly behind privileged origins.
+	Admin,
+}
+
+/// Handler notified when an account becomes, or stops being, verified.
+///
+/// An account is verified while its identity holds `KnownGood` judgements from at least
+/// `Config::VerificationThreshold` distinct providers, in either storage pattern. Runtimes can use
+/// this to mint and burn a soulbound NFT or a membership credential. The handler's weight is not
+/// accounted for by this pallet, so it must be cheap.
+pub trait OnVerified<AccountId> {
+	/// `who` reached `VerificationStatus::Verified`.
+	fn on_verified(who: &AccountId);
+
+	/// `who` cleared an identity that was verified, or otherwise lost its verified status.
+	fn on_revoked(who: &AccountId);
+}
+
+impl<AccountId> OnVerified<AccountId> for () {
+	fn on_verified(_who: &AccountId) {}
+	fn on_revoked(_who: &AccountId) {}
+}
+
+/// A judgement provider which identity holders can ask for a judgement, for a fee.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RegistrarInfo<AccountId, Balance> {
+	/// The account receiving the fees.
+	pub account: AccountId,
+	/// The fee for a judgement, paid when the judgement is given.
+	pub fee: Balance,
+	/// The amount reserved from `account`, slashed when one of its judgements is overturned.
+	pub bond: Balance,
+}
+
+/// Deposit of a cleared identity held until it can be withdrawn.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingRelease<Balance, BlockNumber> {
+	/// The amount still reserved.
+	pub amount: Balance,
+	/// The block from which it can be withdrawn.
+	pub at: BlockNumber,
+}
+
+/// A judgement given, as recorded in `RecentJudgements`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct JudgementActivity<AccountId, JudgementId, BlockNumber> {
+	/// The account whose identity was judged.
+	pub target: AccountId,
+	/// The ID the judgement was given under.
+	pub judgement_id: JudgementId,
+	/// The judgement given.
+	pub judgement: Judgement,
+	/// The block it was given in.
+	pub at: BlockNumber,
+}
+
+/// What a judgement provider has given, as recorded in `ProviderStats`. Judgements on single
+/// fields are counted along with those on whole identities.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ProviderStatistics<BlockNumber> {
+	/// Number of judgements given, of every kind.
+	pub total: u32,
+	/// Number of `Unknown` judgements given.
+	pub unknown: u32,
+	/// Number of `Reasonable` judgements given.
+	pub reasonable: u32,
+	/// Number of `KnownGood` judgements given.
+	pub known_good: u32,
+	/// Number of `Erroneous` judgements given.
+	pub erroneous: u32,
+	/// Number of `LowQuality` judgements given.
+	pub low_quality: u32,
+	/// The block the last judgement was given in.
+	pub last_active: BlockNumber,
+}
+
+impl<BlockNumber> ProviderStatistics<BlockNumber> {
+	/// Count `judgement`, given in block `at`. `Requested` isn't given, so isn't counted.
+	pub fn record(&mut self, judgement: Judgement, at: BlockNumber) {
+		let count = match judgement {
+			Judgement::Requested => return,
+			Judgement::Unknown => &mut self.unknown,
+			Judgement::Reasonable => &mut self.reasonable,
+			Judgement::KnownGood => &mut self.known_good,
+			Judgement::Erroneous => &mut self.erroneous,
+			Judgement::LowQuality => &mut self.low_quality,
+		};
+		count.saturating_inc();
+		self.total.saturating_inc();
+		self.last_active = at;
+	}
+}
+
+/// When an identity expires, as recorded in `IdentityExpiries`. It expires in two phases: once
+/// `Expires` has passed, anyone may mark it `Expired`, and once the grace period has passed since,
+/// anyone may reap it. Until then its holder may renew it.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum IdentityExpiry<BlockNumber> {
+	/// The identity expires in the block, unless renewed.
+	Expires(BlockNumber),
+	/// The identity expired in the block, and is kept for the grace period, unless renewed.
+	Expired(BlockNumber),
+}
+
+/// Where an identity stands with its rent, as recorded in `RentStates`. Once the rent due has
+/// gone unpaid, the identity is delinquent, and once the grace period has passed since, anyone
+/// may reap it. Until then its holder may pay what it owes.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RentState<BlockNumber> {
+	/// The rent is paid up to the block, in which the next period's falls due.
+	PaidUntil(BlockNumber),
+	/// The rent due in the block couldn't be collected, and is still owed along with that of
+	/// every period since.
+	Delinquent(BlockNumber),
+}
+
+/// Account nominated to take over an identity whose holder is gone.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Beneficiary<AccountId, BlockNumber> {
+	/// The account which may claim the identity.
+	pub account: AccountId,
+	/// The block of the identity holder's last activity: nominating it, or since then acting on
+	/// its identity, e.g. setting or renewing it or requesting a judgement.
+	pub since: BlockNumber,
+}
+
+/// Handler notified of every judgement given, in either storage pattern, e.g. to build up a
+/// reputation from them. The handler's weight is not accounted for by this pallet, so it must be
+/// cheap.
+pub trait OnJudgementGiven<AccountId, JudgementId> {
+	/// `judgement` was given on `who`'s identity under `judgement_id`.
+	fn on_judgement_given(who: &AccountId, judgement_id: &JudgementId, judgement: Judgement);
+}
+
+impl<AccountId, JudgementId> OnJudgementGiven<AccountId, JudgementId> for () {
+	fn on_judgement_given(_who: &AccountId, _judgement_id: &JudgementId, _judgement: Judgement) {}
+}
+
+/// Intake of judgements attested on another chain, for a bridge pallet to feed in once it has
+/// checked the attestation. They are stored like those given through `provide_judgement_remote`,
+/// under the judgement IDs reserved for them, and announced apart from local judgements.
+pub trait ReceiveRemoteJudgement<Origin, AccountId, JudgementId> {
+	/// Store `judgement` on `target`'s identity under `judgement_id`, on behalf of the remote
+	/// registrar `origin` stands for.
+	fn receive_remote_judgement(
+		origin: Origin,
+		judgement_id: JudgementId,
+		target: AccountId,
+		judgement: Judgement,
+	) -> DispatchResult;
+
+	/// The largest weight `receive_remote_judgement` can take, for the bridge to account for.
+	fn receive_remote_judgement_weight() -> Weight;
+}
+
+/// Creates judgement IDs for the benchmarks, and signs judgement payloads for benchmarking
+/// `provide_judgement_with_proof`.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<JudgementId, Public, Signature> {
+	/// The judgement ID to benchmark with for `index`. Distinct indices must give distinct IDs,
+	/// ordered like the indices.
+	fn judgement_id(index: u32) -> JudgementId;
+
+	/// Sign `message` with a key of the helper's choosing, returning its public key and the
+	/// signature.
+	fn sign_message(message: &[u8]) -> (Public, Signature);
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl<JudgementId: From<u32>>
+	BenchmarkHelper<JudgementId, sp_runtime::MultiSigner, sp_runtime::MultiSignature> for ()
+{
+	fn judgement_id(index: u32) -> JudgementId {
+		index.into()
+	}
+
+	fn sign_message(message: &[u8]) -> (sp_runtime::MultiSigner, sp_runtime::MultiSignature) {
+		let public = sp_io::crypto::sr25519_generate(0.into(), None);
+		let signature = sp_io::crypto::sr25519_sign(0.into(), &public, message)
+			.expect("The key was just generated.");
+		(public.into(), signature.into())
+	}
+}
+
+/// Read access to the identities held by the pallet, for other pallets to build on.
+pub trait InspectIdentity<AccountId> {
+	/// Whether `who` has a registered identity.
+	fn has_identity(who: &AccountId) -> bool;
+
+	/// The verification status of `who`'s identity, `Unverified` if it has none.
+	fn verification_status(who: &AccountId) -> VerificationStatus;
+
+	/// The verification level of `who`'s identity, `None` if it has none.
+	fn verification_level(who: &AccountId) -> VerificationLevel;
+
+	/// The weight of looking up an identity through any of the above, proof size included.
+	fn lookup_weight() -> Weight;
+}
+
+/// Reasons a storage proof of an identity fails to verify.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum IdentityProofError {
+	/// The proof lacks the trie nodes to look up the identity under the given state root.
+	InvalidProof,
+	/// The proven value does not decode as a registration.
+	Undecodable,
 }
 
-pub type BalanceOf<T> =
-	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-pub type JudgementId = u32;
+pub type BalanceOf<T, I = ()> =
+	<<T as Config<I>>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T, I = ()> = <<T as Config<I>>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Block number of the [`Config::BlockNumberProvider`] that delays and expiries are measured in.
+pub type BlockNumberOf<T, I = ()> =
+	<<T as Config<I>>::BlockNumberProvider as BlockNumberProvider>::BlockNumber;
+/// A verdict and the block it was given in, as held in either storage pattern.
+pub type DatedJudgementOf<T, I = ()> = (Judgement, BlockNumberOf<T, I>);
+/// The dated judgements on an identity, keyed by judgement id.
+pub type DatedJudgementsOf<T, I = ()> = BoundedVec<
+	(<T as Config<I>>::JudgementId, DatedJudgementOf<T, I>),
+	<T as Config<I>>::MaxJudgements,
+>;
+/// The identity information type with the field bounds configured for `T`.
+pub type IdentityInfoOf<T, I = ()> = IdentityInfo<
+	<T as Config<I>>::MaxDisplayLength,
+	<T as Config<I>>::MaxLegalLength,
+	<T as Config<I>>::MaxWebLength,
+	<T as Config<I>>::MaxEmailLength,
+	<T as Config<I>>::MaxCidLength,
+>;
+/// The single bound on every field of the identity information as first deployed, the
+/// `MaxFieldLength` the arguments of `set_identity_legacy` keep decoding with.
+pub type LegacyFieldLength = frame_support::traits::ConstU32<64>;
+/// The source of an account, e.g. a `MultiAddress`, resolved by `T::Lookup`.
+pub type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+/// Free-text note a judging origin may attach to a judgement.
+pub type JudgementNote<T, I = ()> = BoundedVec<u8, <T as Config<I>>::MaxNoteLength>;
+/// A judgement given, as recorded in `RecentJudgements` for `T`.
+pub type JudgementActivityOf<T, I = ()> = JudgementActivity<
+	<T as frame_system::Config>::AccountId,
+	<T as Config<I>>::JudgementId,
+	BlockNumberOf<T, I>,
+>;
+/// What a judgement provider has given, as recorded in `ProviderStats` for `T`.
+pub type ProviderStatisticsOf<T, I = ()> = ProviderStatistics<BlockNumberOf<T, I>>;
+/// The leading bytes of a display name that `DisplayIndex` is keyed by.
+pub type DisplayPrefixOf<T, I = ()> = BoundedVec<u8, <T as Config<I>>::MaxDisplayLength>;
+/// Judgements on single fields of an identity, as (judgement_id, field, judgement).
+pub type FieldJudgementsOf<T, I = ()> = BoundedVec<
+	(<T as Config<I>>::JudgementId, IdentityField, Judgement),
+	<T as Config<I>>::MaxJudgements,
+>;
+/// Where the bonds of self-registered providers are held: holds of `T::ProviderBondCurrency` for
+/// [`HoldReason::ProviderBond`], slashed to `T::ProviderBondSlash`.
+pub type ProviderBondHolds<T, I = ()> = HoldDeposits<
+	<T as Config<I>>::ProviderBondCurrency,
+	ProviderBondReason<T, I>,
+	<T as Config<I>>::ProviderBondSlash,
+>;
 
+// The `pallet::call` expansion converts `DispatchResultWithPostInfo` into itself and dispatches to
+// deprecated calls without silencing the deprecation warning.
+#[allow(clippy::useless_conversion, deprecated)]
 #[frame_support::pallet(dev_mode)]
 pub mod pallet {
 	use super::*;
@@ -142,413 +934,5494 @@ pub mod pallet {
 		MaxEncodedLen,
 		TypeInfo,
 	)]
-	#[scale_info(skip_type_params(T))]
-	pub struct Registration<T: Config> {
+	#[scale_info(skip_type_params(T, I))]
+	pub struct Registration<T: Config<I>, I: 'static = ()> {
 		/// Information about the identity.
-		pub info: IdentityInfo<T::MaxFieldLength>,
-		/// Judgements on this identity. Stored as (judgement_id, judgement) pairs, ordered by ID.
-		pub judgements: BoundedVec<(u32, Judgement), T::MaxJudgements>,
-		/// Count of judgements stored in the double map (for educational comparison).
-		pub judgements_count_double_map: u32,
+		pub info: IdentityInfoOf<T, I>,
+		/// Judgements on this identity. Stored as (judgement_id, (judgement, given_at)) pairs,
+		/// ordered by ID.
+		pub judgements: DatedJudgementsOf<T, I>,
 		/// Amount reserved for the identity information.
-		pub deposit: BalanceOf<T>,
+		pub deposit: BalanceOf<T, I>,
+		/// Encoded size of each field of `info`, in declaration order, which `deposit` is priced
+		/// on. Lets a change to one field be priced without re-encoding the others.
+		pub field_bytes: [u32; 5],
+		/// Amount reserved for the notes attached to judgements on this identity.
+		pub notes_deposit: BalanceOf<T, I>,
+		/// Number of distinct judgement IDs under which a `KnownGood` judgement is held, in either
+		/// storage pattern. Being sticky, they stay until the identity is cleared, or they expire
+		/// and are downgraded to `Reasonable`.
+		pub known_good: u32,
+		/// Number of distinct judgement IDs under which an `Erroneous` judgement is held, in
+		/// either storage pattern. Being sticky, they stay until the identity is cleared.
+		pub erroneous: u32,
+		/// Verification status derived from `known_good` and `T::VerificationThreshold`,
+		/// recomputed whenever the judgements change.
+		pub status: VerificationStatus,
+		/// Verification level derived from `known_good` and `erroneous`, recomputed along with
+		/// `status`.
+		pub level: VerificationLevel,
+		/// Fields of `info` verified by a registrar. A field drops out as soon as it changes.
+		pub verified_fields: IdentityFields,
+		/// Judgement IDs of the registrars the holder prefers to be judged by, most preferred
+		/// first, as set with `set_preferred_registrars`. Audits are assigned to one of them, if
+		/// any.
+		pub preferred_registrars: BoundedVec<T::JudgementId, T::MaxPreferredRegistrars>,
 	}
 
-	impl<T: Config> Registration<T> {
+	impl<T: Config<I>, I: 'static> Registration<T, I> {
 		/// Calculate the total deposit for this registration
-		pub fn total_deposit(&self) -> BalanceOf<T>
+		pub fn total_deposit(&self) -> BalanceOf<T, I>
 		where
-			BalanceOf<T>: Zero + Saturating + Copy,
+			BalanceOf<T, I>: Zero + Saturating + Copy,
 		{
-			self.deposit
+			self.deposit.saturating_add(self.notes_deposit)
+		}
+	}
+
+	/// What is left of an identity cleared while `T::KeepTombstones` is set, so that auditors can
+	/// prove what it was and which sticky judgements it held.
+	#[derive(
+		Encode,
+		Decode,
+		CloneNoBound,
+		PartialEqNoBound,
+		Eq,
+		RuntimeDebugNoBound,
+		MaxEncodedLen,
+		TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct Tombstone<T: Config<I>, I: 'static = ()> {
+		/// Hash of the encoded identity information.
+		pub info_hash: T::Hash,
+		/// The block the identity was cleared in.
+		pub cleared_at: BlockNumberOf<T, I>,
+		/// The sticky judgements held on the identity, ordered by ID. An inline judgement is kept
+		/// over a double map one under the same ID.
+		pub judgements: BoundedVec<(T::JudgementId, Judgement), MaxTombstoneJudgements<T, I>>,
+	}
+
+	/// The bound of [`Tombstone::judgements`]: `T::MaxJudgements` in each storage pattern.
+	pub struct MaxTombstoneJudgements<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> Get<u32> for MaxTombstoneJudgements<T, I> {
+		fn get() -> u32 {
+			T::MaxJudgements::get().saturating_mul(2)
+		}
+	}
+
+	/// Reasons the pallet holds funds for, apart from what it holds through `T::DepositBackend`.
+	#[pallet::composite_enum]
+	pub enum HoldReason<I: 'static = ()> {
+		/// The bond of a judgement provider registered with `register_provider`.
+		ProviderBond,
+	}
+
+	/// [`HoldReason::ProviderBond`] as the runtime's hold reason.
+	pub struct ProviderBondReason<T, I = ()>(PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> Get<T::RuntimeHoldReason> for ProviderBondReason<T, I> {
+		fn get() -> T::RuntimeHoldReason {
+			HoldReason::<I>::ProviderBond.into()
 		}
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(10);
+
 	#[pallet::pallet]
-	pub struct Pallet<T>(_);
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T, I = ()>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config<I: 'static = ()>: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
-		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		/// The currency trait.
 		type Currency: ReservableCurrency<Self::AccountId>;
 
+		/// Where deposits are held, usually [`ReserveDeposits`] of `Self::Currency`: identity and
+		/// note deposits, challenge deposits, registrar bonds and the fees of pending requests.
+		/// Challenge deposits of upheld disputes and bond shares of overturned judgements are
+		/// slashed through it too, and end up wherever it sends slashed funds.
+		type DepositBackend: DepositBackend<Self::AccountId, BalanceOf<Self, I>>;
+
 		/// The amount held on deposit for a registered identity.
 		#[pallet::constant]
-		type BasicDeposit: Get<BalanceOf<Self>>;
+		type BasicDeposit: Get<BalanceOf<Self, I>>;
 
 		/// The amount held on deposit per encoded byte for a registered identity.
 		#[pallet::constant]
-		type ByteDeposit: Get<BalanceOf<Self>>;
+		type ByteDeposit: Get<BalanceOf<Self, I>>;
 
-		/// Maximum number of judgements allowed for a single identity.
+		/// The share of the identity deposit returned to an identity for as long as it holds a
+		/// `KnownGood` judgement, which is sticky. Zero gives no discount.
 		#[pallet::constant]
-		type MaxJudgements: Get<u32>;
+		type VerifiedDepositDiscount: Get<Perbill>;
 
-		/// The origin which may provide judgements on identities. Root can always do this.
-		type JudgementOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Number of blocks an identity has to stay unchanged before its deposit starts to decay,
+		/// and over which it then decays linearly down to `T::DepositDecayFloor` of itself, to be
+		/// released with `poke_deposit`. Setting or editing the identity restarts the clock. Zero
+		/// disables the decay.
+		#[pallet::constant]
+		type DepositDecayPeriod: Get<BlockNumberOf<Self, I>>;
 
-		/// Maximum length for identity field data.
+		/// The share of the identity deposit which never decays.
 		#[pallet::constant]
-		type MaxFieldLength: Get<u32>;
+		type DepositDecayFloor: Get<Perbill>;
 
-		/// Weight information for extrinsics in this pallet.
-		type WeightInfo: WeightInfo;
-	}
+		/// A fee charged every time an identity is set, apart from the deposit and never refunded,
+		/// for runtimes to price churn. Zero charges nothing.
+		#[pallet::constant]
+		type RegistrationFee: Get<BalanceOf<Self, I>>;
 
-	/// Information that is pertinent to identify the entity behind an account.
+		/// What to do with registration fees, e.g. burn them with `()` or pay them to a treasury.
+		type RegistrationFeeDestination: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
+
+		/// The rent an identity pays every `Self::RentPeriod` blocks in place of holding an
+		/// identity deposit, never refunded. Note deposits and the like are held as usual. Zero
+		/// keeps identities on deposits.
+		#[pallet::constant]
+		type IdentityRent: Get<BalanceOf<Self, I>>;
+
+		/// Number of blocks each payment of `Self::IdentityRent` covers. The first is paid when
+		/// the identity is set.
+		#[pallet::constant]
+		type RentPeriod: Get<BlockNumberOf<Self, I>>;
+
+		/// Number of blocks a delinquent identity is kept for, and may still pay its rent in,
+		/// before anyone may reap it with `reap_delinquent_identity`.
+		#[pallet::constant]
+		type RentGracePeriod: Get<BlockNumberOf<Self, I>>;
+
+		/// Maximum number of identities whose rent is collected at the start of each block. Those
+		/// not reached are collected in the following blocks.
+		#[pallet::constant]
+		type MaxRentCollections: Get<u32>;
+
+		/// What to do with rent, e.g. burn it with `()` or pay it to a treasury.
+		type RentDestination: OnUnbalanced<NegativeImbalanceOf<Self, I>>;
+
+		/// Provides the block number that delays and expiries are measured in, e.g. the relay
+		/// chain's on a parachain. Usually `frame_system::Pallet<Self>`.
+		type BlockNumberProvider: BlockNumberProvider;
+
+		/// Number of blocks the deposit of a cleared identity stays reserved before it can be
+		/// withdrawn with `withdraw_deposit`, discouraging rapid set and clear cycles. Zero
+		/// returns it right away.
+		#[pallet::constant]
+		type DepositReleaseDelay: Get<BlockNumberOf<Self, I>>;
+
+		/// Whether `clear_identity` leaves a [`Tombstone`] behind, e.g. for auditors to prove that
+		/// an account was once `KnownGood`.
+		#[pallet::constant]
+		type KeepTombstones: Get<bool>;
+
+		/// Number of blocks a tombstone is kept for before anyone may reap it with
+		/// `reap_tombstone`.
+		#[pallet::constant]
+		type TombstoneLifetime: Get<BlockNumberOf<Self, I>>;
+
+		/// Number of blocks after which an identity holder who has neither renewed their
+		/// beneficiary nor acted on their identity counts as inactive, and the beneficiary may
+		/// claim the identity.
+		#[pallet::constant]
+		type InactivityPeriod: Get<BlockNumberOf<Self, I>>;
+
+		/// Number of blocks an identity lasts after it was last set or renewed, before anyone may
+		/// mark it expired with `expire_identity`. Zero lets the identities set from then on last
+		/// indefinitely.
+		#[pallet::constant]
+		type IdentityLifetime: Get<BlockNumberOf<Self, I>>;
+
+		/// Number of blocks an expired identity is kept for, and may still be renewed in, before
+		/// anyone may reap it with `reap_identity`.
+		#[pallet::constant]
+		type ExpiryGracePeriod: Get<BlockNumberOf<Self, I>>;
+
+		/// Number of blocks a `KnownGood` judgement counts towards verification after it was given
+		/// or last reconfirmed with `reconfirm_judgement`. It then expires, and is downgraded to
+		/// `Reasonable`. Zero keeps them from expiring. Judgements given by the pallet itself or
+		/// received from a bridge never expire, and `Erroneous` ones stay as they are.
+		#[pallet::constant]
+		type RevalidationPeriod: Get<BlockNumberOf<Self, I>>;
+
+		/// Maximum number of `KnownGood` judgements checked for expiry at the start of each block.
+		/// Those not reached are checked in the following blocks.
+		#[pallet::constant]
+		type MaxExpiryChecks: Get<u32>;
+
+		/// The amount held on deposit by an identity holder for each judgement they dispute.
+		#[pallet::constant]
+		type ChallengeDeposit: Get<BalanceOf<Self, I>>;
+
+		/// Identifier of a judgement provider, under which its judgements are held, e.g. a
+		/// registrar index or an account.
+		type JudgementId: Parameter + Member + Ord + MaxEncodedLen + MaybeSerializeDeserialize;
+
+		/// Maximum number of judgements allowed for a single identity.
+		#[pallet::constant]
+		type MaxJudgements: Get<u32>;
+
+		/// Maximum number of judgements given in one `provide_judgements_inline` call.
+		#[pallet::constant]
+		type MaxBatch: Get<u32>;
+
+		/// Number of judgements kept in `RecentJudgements`, across all identities. Zero keeps
+		/// none.
+		#[pallet::constant]
+		type MaxRecentJudgements: Get<u32>;
+
+		/// Maximum number of judgements written under one judgement ID in a block, so that a
+		/// single provider can't fill a block with judgement writes. Field judgements count too,
+		/// vouches under `Self::SystemJudgementId` and judgements under `Self::BridgedJudgementIds`
+		/// don't. Zero sets no limit.
+		#[pallet::constant]
+		type MaxJudgementsPerBlock: Get<u32>;
+
+		/// Maximum number of times an account can set its identity in a window of
+		/// `Self::IdentitySetPeriod` blocks, so that registrars aren't made to verify an identity
+		/// that keeps changing. Zero sets no limit.
+		#[pallet::constant]
+		type MaxSetsPerPeriod: Get<u32>;
+
+		/// Number of blocks in the windows `Self::MaxSetsPerPeriod` counts over. An account's
+		/// window starts when it first sets its identity after its previous window ended.
+		#[pallet::constant]
+		type IdentitySetPeriod: Get<BlockNumberOf<Self, I>>;
+
+		/// Whether judgements are also written to the storage pattern they weren't given in, as
+		/// shadow copies, e.g. on a test network gathering the weights of both patterns under
+		/// real use. The pattern a judgement is given in stays authoritative: the shadow copies
+		/// are never read. Turning it off leaves the copies behind, no longer kept up to date.
+		#[pallet::constant]
+		type ShadowWrites: Get<bool>;
+
+		/// The origin which may provide judgements on identities. Root can always do this.
+		type JudgementOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Identifier of a registrar judging from another chain, e.g. derived from the
+		/// multilocation of a sibling parachain.
+		type RemoteRegistrarId: Parameter + Member + MaxEncodedLen;
+
+		/// The origin which may provide judgements on behalf of a remote registrar, e.g. an XCM
+		/// origin converter. Yields the ID of that registrar.
+		type RemoteJudgementOrigin: EnsureOrigin<
+			Self::RuntimeOrigin,
+			Success = Self::RemoteRegistrarId,
+		>;
+
+		/// Signature registrars sign judgements with off-chain, e.g. to have them submitted by
+		/// any account through `provide_judgement_with_proof`.
+		type OffchainSignature: Verify<Signer = Self::SigningPublicKey> + Parameter;
+
+		/// Public key of `Self::OffchainSignature`, corresponding to an on-chain account.
+		type SigningPublicKey: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// Signs judgement payloads for the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<
+			Self::JudgementId,
+			Self::SigningPublicKey,
+			Self::OffchainSignature,
+		>;
+
+		/// Number of blocks a rotated out provider key stays valid for, so that judgements it
+		/// signed before the rotation can still be submitted.
+		#[pallet::constant]
+		type KeyRotationGracePeriod: Get<BlockNumberOf<Self, I>>;
+
+		/// The origin which may adjust the pallet's runtime parameters, e.g. governance.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The origin which may pause and unpause registrations and judgements, e.g. for an
+		/// emergency response.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The origin which may resolve disputes of judgements.
+		type DisputeResolutionOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The origin which may exempt accounts from identity deposits, e.g. governance for
+		/// identities of system or treasury-controlled accounts.
+		type NoDepositOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The amount reserved from a registrar's account while it is set.
+		#[pallet::constant]
+		type RegistrarBond: Get<BalanceOf<Self, I>>;
+
+		/// The share of its bond a registrar loses when one of its judgements is overturned.
+		#[pallet::constant]
+		type OverturnSlash: Get<Perbill>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason<I>>;
+
+		/// The currency the bonds of providers registered with `register_provider` are held in,
+		/// for [`HoldReason::ProviderBond`]. Usually the same balances as `Self::Currency`.
+		type ProviderBondCurrency: fungible::MutateHold<
+				Self::AccountId,
+				Reason = Self::RuntimeHoldReason,
+				Balance = BalanceOf<Self, I>,
+			> + fungible::BalancedHold<Self::AccountId>;
+
+		/// What to do with slashed provider bonds, e.g. burn them with `()` or pay them to a
+		/// treasury.
+		type ProviderBondSlash: OnUnbalanced<
+			fungible::Credit<Self::AccountId, Self::ProviderBondCurrency>,
+		>;
+
+		/// The least bond a provider registered with `register_provider` must keep held.
+		#[pallet::constant]
+		type MinProviderBond: Get<BalanceOf<Self, I>>;
+
+		/// Maximum length of the display name.
+		#[pallet::constant]
+		type MaxDisplayLength: Get<u32>;
+
+		/// Number of leading bytes of the display name, lowercased, that identities are indexed
+		/// by in `DisplayIndex` for `search_display`. Zero keeps no index. Changing it leaves the
+		/// identities indexed under the old length until they are set again.
+		#[pallet::constant]
+		type DisplayIndexLength: Get<u32>;
+
+		/// Maximum length of the legal name.
+		#[pallet::constant]
+		type MaxLegalLength: Get<u32>;
+
+		/// Maximum length of the web address.
+		#[pallet::constant]
+		type MaxWebLength: Get<u32>;
+
+		/// Maximum length of the email address.
+		#[pallet::constant]
+		type MaxEmailLength: Get<u32>;
+
+		/// Maximum length of the content identifier of an avatar.
+		#[pallet::constant]
+		type MaxCidLength: Get<u32>;
+
+		/// Maximum length of a note attached to a judgement.
+		#[pallet::constant]
+		type MaxNoteLength: Get<u32>;
+
+		/// The proof size budget of an identity: the largest encoded size of a [`Registration`]
+		/// the bounds above may add up to. Checked by `integrity_test`, so that bounds too large
+		/// for the chain's proof of validity fail the runtime's tests rather than its blocks.
+		#[pallet::constant]
+		type MaxRegistrationSize: Get<u32>;
+
+		/// The contribution of each judgement to an identity's reputation score, e.g.
+		/// [`DefaultJudgementScore`].
+		type JudgementScore: Convert<Judgement, i32>;
+
+		/// Number of distinct judgement providers that must give an identity a `KnownGood`
+		/// judgement for it to be verified.
+		#[pallet::constant]
+		type VerificationThreshold: Get<u32>;
+
+		/// The maximum number of vouches recorded for an identity.
+		#[pallet::constant]
+		type MaxVouches: Get<u32>;
+
+		/// The number of vouches after which an identity is given a `Reasonable` judgement under
+		/// `T::SystemJudgementId`.
+		#[pallet::constant]
+		type VouchThreshold: Get<u32>;
+
+		/// The judgement ID reserved for judgements given by the pallet itself. Registrars can't
+		/// be set for it, and the judgement origins can't give judgements under it.
+		#[pallet::constant]
+		type SystemJudgementId: Get<Self::JudgementId>;
+
+		/// Handler for identities becoming verified and for verified identities being cleared.
+		type OnVerified: OnVerified<Self::AccountId>;
+
+		/// Handler for judgements being given.
+		type OnJudgementGiven: OnJudgementGiven<Self::AccountId, Self::JudgementId>;
+
+		/// The origin which may feed in judgements attested on another chain through
+		/// [`ReceiveRemoteJudgement`], e.g. that of a bridge pallet. Yields the ID of the remote
+		/// registrar that attested them.
+		type BridgeOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::RemoteRegistrarId>;
+
+		/// The judgement IDs reserved for judgements received through [`ReceiveRemoteJudgement`].
+		/// Judgements can't be given locally under them, nor registrars set for them.
+		type BridgedJudgementIds: Get<RangeInclusive<Self::JudgementId>>;
+
+		/// The trusted state root of the foreign chain identities are mirrored from by
+		/// `mirror_remote_identity`.
+		type ForeignStateRoot: ForeignStateRoot<Self::Hash>;
+
+		/// Maximum encoded size of a proof given to `mirror_remote_identity`, in bytes.
+		#[pallet::constant]
+		type MaxRemoteProofSize: Get<u32>;
+
+		/// Source of the randomness registrars are drawn with for `request_audit`.
+		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+
+		/// Maximum number of registrars an auditor is drawn from, in storage order. Registrars
+		/// beyond it are never assigned an audit.
+		#[pallet::constant]
+		type MaxAuditCandidates: Get<u32>;
+
+		/// Maximum number of registrars an identity holder can list with
+		/// `set_preferred_registrars`.
+		#[pallet::constant]
+		type MaxPreferredRegistrars: Get<u32>;
+
+		/// The DID method identities are exported under by `did_document`, e.g. `"example"` for
+		/// `did:example:<account>`.
+		type DidMethod: Get<&'static str>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Information that is pertinent to identify the entity behind an account.
 	#[pallet::storage]
-	pub type IdentityOf<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::AccountId, Registration<T>, OptionQuery>;
+	pub type IdentityOf<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Registration<T, I>, OptionQuery>;
 
 	/// Alternative judgement storage using a double map for educational purposes.
 	/// This demonstrates different storage patterns and their performance implications.
-	/// Key1: AccountId (identity holder), Key2: JudgementId, Value: Judgement
+	/// Key1: AccountId (identity holder), Key2: JudgementId, Value: (Judgement, given_at)
 	#[pallet::storage]
-	pub type JudgementsDoubleMap<T: Config> = StorageDoubleMap<
+	pub type JudgementsDoubleMap<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
 		Blake2_128Concat,
-		JudgementId,
-		Judgement,
+		T::JudgementId,
+		DatedJudgementOf<T, I>,
 		OptionQuery,
 	>;
 
-	/// Pallets use events to inform users when important changes are made.
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// A name was set or reset (which will remove judgement).
-		IdentitySet { who: T::AccountId },
-		/// A name was cleared, and the given balance returned.
-		IdentityCleared { who: T::AccountId, deposit: BalanceOf<T> },
-		/// A judgement was given.
-		JudgementGiven { target: T::AccountId },
-	}
+	/// Number of judgements each account holds in `JudgementsDoubleMap`. Kept out of
+	/// `Registration` so that a double map judgement can be counted without decoding and
+	/// re-encoding the whole registration.
+	#[pallet::storage]
+	pub type CounterForJudgements<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
-	/// Errors inform users that something went wrong.
-	#[pallet::error]
-	pub enum Error<T> {
-		/// Account isn't found.
-		NotFound,
-		/// No identity found.
-		NoIdentity,
-		/// Sticky judgement.
-		StickyJudgement,
-		/// Judgement given.
-		JudgementGiven,
-		/// Invalid judgement.
-		InvalidJudgement,
-		/// The target is invalid.
-		InvalidTarget,
-		/// Too many judgements for this identity.
-		TooManyJudgements,
-	}
+	/// Reverse index of the judgements in both storage patterns: the accounts holding a judgement
+	/// given under each judgement ID, so that a registrar can enumerate everything it attested.
+	/// See `judged_by`.
+	/// Key1: JudgementId, Key2: AccountId (identity holder)
+	#[pallet::storage]
+	pub type JudgedBy<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::JudgementId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Shadow copies of the inline judgements in the double map layout, written while
+	/// `T::ShadowWrites` is set.
+	/// Key1: AccountId (identity holder), Key2: JudgementId, Value: (Judgement, given_at)
+	#[pallet::storage]
+	pub type ShadowJudgementsDoubleMap<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::JudgementId,
+		DatedJudgementOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Shadow copies of the double map judgements in the inline layout, ordered by ID, written
+	/// while `T::ShadowWrites` is set.
+	#[pallet::storage]
+	pub type ShadowJudgementsInline<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, DatedJudgementsOf<T, I>, ValueQuery>;
+
+	/// Notes attached to judgements, e.g. "web domain mismatch" for an `Erroneous` verdict.
+	/// A note lives as long as the judgement it was given with, whichever storage pattern holds
+	/// that judgement. Its bytes are paid for by the identity holder at `T::ByteDeposit` each.
+	/// Key1: AccountId (identity holder), Key2: JudgementId, Value: note
+	#[pallet::storage]
+	pub type JudgementNotes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::JudgementId,
+		JudgementNote<T, I>,
+		OptionQuery,
+	>;
+
+	/// Reason codes given with negative verdicts through `provide_judgement_with_reason`. Like a
+	/// note, a reason code lives as long as the judgement it was given with, and is dropped when
+	/// a judgement is given under the same ID without one.
+	/// Key1: AccountId (identity holder), Key2: JudgementId, Value: reason code
+	#[pallet::storage]
+	pub type JudgementReasons<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::JudgementId,
+		ReasonCode,
+		OptionQuery,
+	>;
+
+	/// The remote registrar behind each inline judgement given with `provide_judgement_remote`.
+	/// Removed when the judgement is replaced by a local one or dropped.
+	/// Key1: AccountId (identity holder), Key2: JudgementId, Value: remote registrar ID
+	#[pallet::storage]
+	pub type RemoteJudgementSources<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::JudgementId,
+		T::RemoteRegistrarId,
+		OptionQuery,
+	>;
+
+	/// Reputation score of each identity: the sum of `T::JudgementScore` over its judgements, in
+	/// both storage patterns. Kept up to date as judgements are given and removed, so reading it
+	/// doesn't need the judgements.
+	#[pallet::storage]
+	pub type Scores<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, i32, ValueQuery>;
+
+	/// Judgements on single fields of an identity, ordered by judgement ID and field.
+	/// Whole-identity judgements are kept apart, in `IdentityOf` and `JudgementsDoubleMap`.
+	#[pallet::storage]
+	pub type FieldJudgements<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, FieldJudgementsOf<T, I>, ValueQuery>;
+
+	/// Hashes of the KYC documents behind identities, set by their holders with `set_kyc_hash`.
+	#[pallet::storage]
+	pub type KycHashes<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::Hash, OptionQuery>;
+
+	/// The judgement ID of the registrar which attested the KYC hash of an identity with
+	/// `attest_kyc`. Dropped as soon as the identity or its KYC hash changes.
+	#[pallet::storage]
+	pub type KycAttestations<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::JudgementId, OptionQuery>;
+
+	/// Read-only mirrors of identities registered on the foreign chain, see
+	/// `mirror_remote_identity`.
+	#[pallet::storage]
+	pub type RemoteIdentities<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RemoteIdentity<T, I>, OptionQuery>;
+
+	/// Audits assigned by `request_audit`, by identity holder, with the judgement ID of the
+	/// registrar to re-verify the identity. Settled once that registrar gives a judgement on it.
+	#[pallet::storage]
+	pub type PendingAudits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::JudgementId, OptionQuery>;
+
+	/// Accounts exempted by `set_deposit_exemption` from reserving an identity deposit. Their
+	/// judgement notes are paid for as usual.
+	#[pallet::storage]
+	pub type ExemptAccounts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// The block each identity was last set or edited in, which its deposit decays from after
+	/// `T::DepositDecayPeriod`. Identities set before it was introduced have none, and don't
+	/// decay until they are set again.
+	#[pallet::storage]
+	pub type UnchangedSince<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberOf<T, I>, OptionQuery>;
+
+	/// When each identity expires, or expired. Identities set while `T::IdentityLifetime` was
+	/// zero have none, and don't expire until they are set or renewed again.
+	#[pallet::storage]
+	pub type IdentityExpiries<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		IdentityExpiry<BlockNumberOf<T, I>>,
+		OptionQuery,
+	>;
+
+	/// Where each identity paying rent stands with it. Identities set while `T::IdentityRent`
+	/// was zero have none until they are set again or their deposit is reconciled.
+	#[pallet::storage]
+	pub type RentStates<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RentState<BlockNumberOf<T, I>>, OptionQuery>;
+
+	/// The identities whose rent falls due in each block, to be collected by `on_initialize`. An
+	/// entry whose rent was paid or stopped since is skipped.
+	/// Key1: block number, Key2: AccountId (identity holder)
+	#[pallet::storage]
+	pub type RentDue<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberOf<T, I>,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// The first block whose `RentDue` entries haven't all been collected yet. `None` until rent
+	/// is first scheduled, when it starts at the block that is due.
+	#[pallet::storage]
+	pub type RentCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumberOf<T, I>, OptionQuery>;
+
+	/// Everything held through `T::DepositBackend`: identity and note deposits, challenge
+	/// deposits, registrar bonds, the fees of pending requests and those accrued by registrars,
+	/// and the deposits awaiting release.
+	#[pallet::storage]
+	pub type TotalDeposits<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BalanceOf<T, I>, ValueQuery>;
+
+	/// Identities by the first `T::DisplayIndexLength` bytes of their display name, lowercased.
+	/// See `search_display`. Identities set before the index was kept are only indexed once they
+	/// are set again.
+	/// Key1: display name prefix, Key2: AccountId (identity holder)
+	#[pallet::storage]
+	pub type DisplayIndex<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		DisplayPrefixOf<T, I>,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Judgement providers accepting requests, by the ID they give judgements under.
+	#[pallet::storage]
+	pub type Registrars<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::JudgementId,
+		RegistrarInfo<T::AccountId, BalanceOf<T, I>>,
+		OptionQuery,
+	>;
+
+	/// Bonds of the providers registered with `register_provider`, by the ID they give judgements
+	/// under. Held through [`ProviderBondHolds`] rather than `T::DepositBackend`, so they are not
+	/// counted in `TotalDeposits`. Their `Registrars` entries have no bond of their own.
+	#[pallet::storage]
+	pub type ProviderBonds<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::JudgementId, BalanceOf<T, I>, OptionQuery>;
+
+	/// Whether registrations and judgements are paused by `pause`.
+	#[pallet::storage]
+	pub type Paused<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	/// Keys registrars sign judgements with off-chain, by the ID they give judgements under.
+	#[pallet::storage]
+	pub type ProviderKeys<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::JudgementId, T::AccountId, OptionQuery>;
+
+	/// Keys rotated out by `rotate_provider_key`, with the block they stop being valid at.
+	#[pallet::storage]
+	pub type PreviousProviderKeys<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::JudgementId,
+		(T::AccountId, BlockNumberOf<T, I>),
+		OptionQuery,
+	>;
+
+	/// The nonce the next signed judgement under a judgement ID must carry, so that every
+	/// signature can be submitted only once.
+	#[pallet::storage]
+	pub type ProviderNonces<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::JudgementId, u64, ValueQuery>;
+
+	/// Beneficiaries nominated by identity holders to take over their identity.
+	#[pallet::storage]
+	pub type Beneficiaries<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Beneficiary<T::AccountId, BlockNumberOf<T, I>>,
+		OptionQuery,
+	>;
+
+	/// Accounts with a `KnownGood` judgement vouching for an identity.
+	#[pallet::storage]
+	pub type Vouches<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxVouches>,
+		ValueQuery,
+	>;
+
+	/// Fees earned by registrar accounts for requested judgements, kept reserved on the account
+	/// until claimed with `claim_fees`.
+	#[pallet::storage]
+	pub type AccruedFees<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T, I>, ValueQuery>;
+
+	/// Pending judgement requests, holding the fee reserved from the identity holder until the
+	/// judgement is given.
+	/// Key1: AccountId (identity holder), Key2: JudgementId, Value: reserved fee
+	#[pallet::storage]
+	pub type JudgementRequests<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::JudgementId,
+		BalanceOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Open disputes of judgements, holding the challenge deposit reserved from the identity
+	/// holder. A disputed judgement is frozen until the dispute is resolved.
+	/// Key1: AccountId (identity holder), Key2: JudgementId, Value: reserved challenge deposit
+	#[pallet::storage]
+	pub type Disputes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::JudgementId,
+		BalanceOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Deposits of cleared identities waiting for `T::DepositReleaseDelay` to pass. Clearing
+	/// again adds to the amount and restarts the delay.
+	#[pallet::storage]
+	pub type PendingReleases<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		PendingRelease<BalanceOf<T, I>, BlockNumberOf<T, I>>,
+		OptionQuery,
+	>;
+
+	/// How much an identity's deposit falls short of its price, for lack of funds when it lost
+	/// the discount of its last `KnownGood` judgement. Paid off when the deposit is next repriced,
+	/// e.g. by `poke_deposit` or `reconcile_deposits`.
+	#[pallet::storage]
+	pub type DepositDebts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T, I>, OptionQuery>;
+
+	/// Tombstones of identities cleared while `T::KeepTombstones` is set. Clearing again replaces
+	/// the tombstone; `reap_tombstone` removes it once `T::TombstoneLifetime` has passed.
+	#[pallet::storage]
+	pub type Tombstones<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Tombstone<T, I>, OptionQuery>;
+
+	/// Ring buffer of the last `T::MaxRecentJudgements` judgements given, in either storage
+	/// pattern, by slot. See `recent_judgements`.
+	#[pallet::storage]
+	pub type RecentJudgements<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u32, JudgementActivityOf<T, I>, OptionQuery>;
+
+	/// The slot of `RecentJudgements` the next judgement is recorded in, overwriting the oldest.
+	#[pallet::storage]
+	pub type RecentJudgementsHead<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// What has been given under each judgement ID, for reviewing its provider. See
+	/// `provider_stats`.
+	#[pallet::storage]
+	pub type ProviderStats<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::JudgementId, ProviderStatisticsOf<T, I>, ValueQuery>;
+
+	/// Number of judgements written under each judgement ID in the current block, against
+	/// `T::MaxJudgementsPerBlock`. Cleared at the start of every block.
+	#[pallet::storage]
+	pub type JudgementsInBlock<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::JudgementId, u32, ValueQuery>;
+
+	/// The block each account's current window of `T::IdentitySetPeriod` blocks started at, and
+	/// the number of times it set its identity in it, against `T::MaxSetsPerPeriod`. Kept when
+	/// the identity is cleared, so that clearing it doesn't reset the count.
+	#[pallet::storage]
+	pub type IdentitySets<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (BlockNumberOf<T, I>, u32), OptionQuery>;
+
+	/// Default for [`JudgementLimit`]: the hard `T::MaxJudgements` bound.
+	#[pallet::type_value]
+	pub fn DefaultJudgementLimit<T: Config<I>, I: 'static>() -> u32 {
+		T::MaxJudgements::get()
+	}
+
+	/// The effective maximum number of judgements per identity, in each storage pattern.
+	/// Can be tightened by `T::ForceOrigin` without a runtime upgrade but never exceeds
+	/// `T::MaxJudgements`. Identities already above a lowered limit keep their judgements.
+	#[pallet::storage]
+	pub type JudgementLimit<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery, DefaultJudgementLimit<T, I>>;
+
+	/// The last account whose deposit `reconcile_deposits` re-priced. The next call resumes after
+	/// it; `None` when no sweep is in progress.
+	#[pallet::storage]
+	pub type ReconcileCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::AccountId, OptionQuery>;
+
+	/// The `KnownGood` judgements to check for expiry in each block, those given or
+	/// reconfirmed `T::RevalidationPeriod` blocks before. An entry whose judgement was dropped or
+	/// reconfirmed since is skipped.
+	/// Key1: block number, Key2: (AccountId (identity holder), JudgementId)
+	#[pallet::storage]
+	pub type RevalidationDue<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberOf<T, I>,
+		Blake2_128Concat,
+		(T::AccountId, T::JudgementId),
+		(),
+		OptionQuery,
+	>;
+
+	/// The first block whose `RevalidationDue` entries haven't all been checked yet. `None` until
+	/// a judgement is first scheduled for revalidation, when it starts at the block that one is due.
+	#[pallet::storage]
+	pub type RevalidationCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumberOf<T, I>, OptionQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		/// Initial judgement limit. Defaults to `T::MaxJudgements` when `None`.
+		pub judgement_limit: Option<u32>,
+		/// Initial identities, each reserving its deposit from the account it is set for and
+		/// paying the registration fee.
+		pub identities: Vec<(T::AccountId, IdentityInfoOf<T, I>)>,
+		/// Initial judgements on the identities above, stored inline, with judgements given by
+		/// name, e.g. `"KnownGood"`. Handlers of `OnVerified` and `OnJudgementGiven` aren't told.
+		pub judgements: Vec<(T::AccountId, T::JudgementId, Judgement)>,
+		#[serde(skip)]
+		pub _config: core::marker::PhantomData<(T, I)>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
+		fn build(&self) {
+			if let Some(limit) = self.judgement_limit {
+				assert!(
+					limit <= T::MaxJudgements::get(),
+					"Judgement limit must not exceed `MaxJudgements`"
+				);
+				JudgementLimit::<T, I>::put(limit);
+			}
+			for (who, info) in &self.identities {
+				Pallet::<T, I>::do_set_identity(who.clone(), info.clone())
+					.expect("Genesis identities must be set once, on funded accounts");
+			}
+			for (who, judgement_id, judgement) in &self.judgements {
+				Pallet::<T, I>::ensure_local_judgement_id(judgement_id)
+					.and_then(|_| Pallet::<T, I>::ensure_verdict(*judgement))
+					.and_then(|_| {
+						Pallet::<T, I>::add_judgement_inline(who, judgement_id, *judgement, None)
+					})
+					.expect(
+						"Genesis judgements must be verdicts given under local IDs, on genesis \
+						 identities",
+					);
+			}
+		}
+	}
+
+	/// Pallets use events to inform users when important changes are made.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// A name was set or reset (which will remove judgement), with its display name and the
+		/// total byte length of its fields, so that a directory of names can be built from events.
+		IdentitySet { who: T::AccountId, display: BoundedVec<u8, T::MaxDisplayLength>, bytes: u32 },
+		/// A name was cleared, and the given balance returned, or held until
+		/// `T::DepositReleaseDelay` has passed.
+		IdentityCleared { who: T::AccountId, deposit: BalanceOf<T, I> },
+		/// A judgement was given.
+		JudgementGiven { target: T::AccountId },
+		/// A judgement was given by a remote registrar.
+		RemoteJudgementGiven { target: T::AccountId, registrar: T::RemoteRegistrarId },
+		/// The effective judgement limit was changed.
+		JudgementLimitSet { limit: u32 },
+		/// An identity's deposit was re-priced by `reconcile_deposits`.
+		DepositReconciled {
+			who: T::AccountId,
+			old_deposit: BalanceOf<T, I>,
+			new_deposit: BalanceOf<T, I>,
+		},
+		/// An identity's deposit could not be raised to its current price, e.g. for lack of funds,
+		/// and was left as is.
+		DepositReconciliationFailed { who: T::AccountId },
+		/// `reconcile_deposits` reached the last registration; the next call starts over.
+		DepositReconciliationCompleted,
+		/// The double map judgements of an identity were moved into its inline storage.
+		JudgementsConsolidated { who: T::AccountId, moved: u32 },
+		/// The double map judgement counter of an identity was corrected.
+		CounterRepaired { who: T::AccountId, old_count: u32, new_count: u32 },
+		/// A registrar was set for a judgement ID.
+		RegistrarSet { judgement_id: T::JudgementId, account: T::AccountId, fee: BalanceOf<T, I> },
+		/// A judgement was requested and its fee reserved.
+		JudgementRequested { who: T::AccountId, judgement_id: T::JudgementId, fee: BalanceOf<T, I> },
+		/// The verification status of an identity changed.
+		VerificationStatusChanged { who: T::AccountId, status: VerificationStatus },
+		/// The verification level of an identity changed with its judgements.
+		VerificationLevelChanged { who: T::AccountId, level: VerificationLevel },
+		/// The non-sticky judgements of an identity were removed.
+		JudgementsCleared { who: T::AccountId, inline: u32, double_map: u32 },
+		/// A judgement was disputed and the challenge deposit reserved.
+		JudgementDisputed {
+			who: T::AccountId,
+			judgement_id: T::JudgementId,
+			deposit: BalanceOf<T, I>,
+		},
+		/// A dispute was resolved. The judgement stands if it was upheld; otherwise it was removed
+		/// and `slashed` taken from the registrar's bonds.
+		DisputeResolved {
+			who: T::AccountId,
+			judgement_id: T::JudgementId,
+			upheld: bool,
+			slashed: BalanceOf<T, I>,
+		},
+		/// The held deposit of a cleared identity was withdrawn.
+		DepositWithdrawn { who: T::AccountId, amount: BalanceOf<T, I> },
+		/// A field of an identity was cleared and its deposit returned.
+		IdentityFieldCleared { who: T::AccountId, field: IdentityField },
+		/// A registrar claimed the fees it earned.
+		FeesClaimed { who: T::AccountId, amount: BalanceOf<T, I> },
+		/// An identity holder nominated a beneficiary.
+		BeneficiarySet { who: T::AccountId, beneficiary: T::AccountId },
+		/// A beneficiary took over the identity of an inactive or reaped account.
+		IdentityClaimed { original: T::AccountId, beneficiary: T::AccountId },
+		/// An account vouched for an identity.
+		Vouched { voucher: T::AccountId, target: T::AccountId },
+		/// A registrar set or removed the key it signs judgements with.
+		ProviderKeySet { judgement_id: T::JudgementId, key: Option<T::AccountId> },
+		/// Registrations and judgements were paused.
+		Paused,
+		/// Registrations and judgements were unpaused.
+		Unpaused,
+		/// A registrar rotated its signing key. The previous key stays valid until
+		/// `previous_valid_until`.
+		ProviderKeyRotated {
+			judgement_id: T::JudgementId,
+			key: T::AccountId,
+			previous_valid_until: BlockNumberOf<T, I>,
+		},
+		/// A registrar set the verified fields of an identity.
+		VerifiedFieldsSet { who: T::AccountId, fields: IdentityFields },
+		/// A judgement was given on a single field of an identity.
+		FieldJudgementGiven { target: T::AccountId, field: IdentityField },
+		/// A judgement attested on another chain was received through a bridge.
+		BridgedJudgementReceived {
+			target: T::AccountId,
+			judgement_id: T::JudgementId,
+			registrar: T::RemoteRegistrarId,
+		},
+		/// An identity on the foreign chain was mirrored, as proven against `state_root`.
+		RemoteIdentityMirrored { who: T::AccountId, state_root: T::Hash },
+		/// The mirror of an identity no longer on the foreign chain was removed.
+		RemoteIdentityRemoved { who: T::AccountId },
+		/// The registrar under `judgement_id` was drawn to re-verify the identity of `target`.
+		AuditRequested { target: T::AccountId, judgement_id: T::JudgementId },
+		/// The registrar under `judgement_id` gave its judgement on the audited identity.
+		AuditCompleted { target: T::AccountId, judgement_id: T::JudgementId },
+		/// `who` was exempted from identity deposits, or had its exemption revoked.
+		DepositExemptionSet { who: T::AccountId, exempt: bool },
+		/// `who` was given its first `KnownGood` judgement, and `T::VerifiedDepositDiscount` of
+		/// its identity deposit was returned, leaving `deposit` reserved.
+		DepositDiscountGranted { who: T::AccountId, deposit: BalanceOf<T, I> },
+		/// `who` lost its last `KnownGood` judgement, and the discount on its identity deposit
+		/// was reserved again, leaving `deposit` reserved.
+		DepositDiscountRevoked { who: T::AccountId, deposit: BalanceOf<T, I> },
+		/// The deposit of `who`'s identity was brought in line with how much of it has decayed.
+		DepositPoked {
+			who: T::AccountId,
+			old_deposit: BalanceOf<T, I>,
+			new_deposit: BalanceOf<T, I>,
+		},
+		/// `who` paid the registration fee for setting its identity.
+		RegistrationFeePaid { who: T::AccountId, fee: BalanceOf<T, I> },
+		/// The tombstone of `who`'s cleared identity was reaped.
+		TombstoneReaped { who: T::AccountId },
+		/// `who` renewed its identity, which now expires in `expires_at`, or never.
+		IdentityRenewed { who: T::AccountId, expires_at: Option<BlockNumberOf<T, I>> },
+		/// `who`'s identity expired. It can be reaped once `T::ExpiryGracePeriod` has passed,
+		/// unless renewed before.
+		IdentityExpired { who: T::AccountId },
+		/// `who`'s expired or delinquent identity was reaped, and `deposit` returned to it.
+		IdentityReaped { who: T::AccountId, deposit: BalanceOf<T, I> },
+		/// The judgements of `who` were audited: `recorded_double_map` is the double map
+		/// judgement counter, `actual_double_map` the number of entries, and `inline_sorted`
+		/// whether the inline judgements were ordered by unique IDs. `repaired` if any of it was
+		/// corrected.
+		AuditResult {
+			who: T::AccountId,
+			recorded_double_map: u32,
+			actual_double_map: u32,
+			inline_sorted: bool,
+			repaired: bool,
+		},
+		/// `who`'s account was reaped, and its identity removed with it. The `deposit` it held,
+		/// including any pending release, was lost with the account.
+		IdentityKilled { who: T::AccountId, deposit: BalanceOf<T, I> },
+		/// `account` registered itself as the provider under `judgement_id`, holding `bond`.
+		ProviderRegistered {
+			judgement_id: T::JudgementId,
+			account: T::AccountId,
+			fee: BalanceOf<T, I>,
+			bond: BalanceOf<T, I>,
+		},
+		/// The bond of the provider under `judgement_id` was topped up or withdrawn from, and is
+		/// now `bond`.
+		ProviderBondChanged { judgement_id: T::JudgementId, bond: BalanceOf<T, I> },
+		/// `who` set the hash of the KYC document behind its identity, or removed it if `None`.
+		KycHashSet { who: T::AccountId, kyc_hash: Option<T::Hash> },
+		/// The registrar under `judgement_id` attested the KYC hash of `who`'s identity.
+		KycAttested { who: T::AccountId, judgement_id: T::JudgementId },
+		/// The attestation of `who`'s KYC hash was dropped, its identity or the hash having
+		/// changed.
+		KycAttestationInvalidated { who: T::AccountId },
+		/// `fields` of `who`'s identity were redacted by `T::ForceOrigin`, releasing `refunded`
+		/// of its deposit.
+		FieldsRedacted { who: T::AccountId, fields: IdentityFields, refunded: BalanceOf<T, I> },
+		/// The negative judgement given on `target` under `judgement_id` came with `reason`.
+		JudgementReasonGiven {
+			target: T::AccountId,
+			judgement_id: T::JudgementId,
+			reason: ReasonCode,
+		},
+		/// The `KnownGood` judgement on `who` under `judgement_id` expired without being
+		/// reconfirmed, and was downgraded to `Reasonable`.
+		JudgementDowngraded { who: T::AccountId, judgement_id: T::JudgementId },
+		/// The `KnownGood` judgement on `who` under `judgement_id` was reconfirmed.
+		JudgementReconfirmed { who: T::AccountId, judgement_id: T::JudgementId },
+		/// `who` set the registrars it prefers to be judged by.
+		PreferredRegistrarsSet { who: T::AccountId },
+		/// `who` paid `amount` of rent for its identity, which is now paid up to `paid_until`.
+		RentPaid { who: T::AccountId, amount: BalanceOf<T, I>, paid_until: BlockNumberOf<T, I> },
+		/// The rent due for `who`'s identity couldn't be collected. It can be reaped once
+		/// `T::RentGracePeriod` has passed, unless the rent is paid before.
+		IdentityDelinquent { who: T::AccountId },
+		/// `who`'s identity on the upstream pallet couldn't be registered here, for `error`, and
+		/// was dropped with its deposit released.
+		UpstreamIdentityDropped { who: T::AccountId, error: DispatchError },
+		/// `who` lost its last `KnownGood` judgement but couldn't reserve the discount again, and
+		/// owes `debt` of its identity deposit.
+		DepositDebtIncurred { who: T::AccountId, debt: BalanceOf<T, I> },
+	}
+
+	/// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Account isn't found.
+		NotFound,
+		/// No identity found.
+		NoIdentity,
+		/// Sticky judgement.
+		StickyJudgement,
+		/// Judgement given.
+		JudgementGiven,
+		/// Invalid judgement.
+		#[deprecated = "No longer returned: judgements are taken as a `Judgement`, not a `u8`"]
+		InvalidJudgement,
+		/// The target is invalid.
+		InvalidTarget,
+		/// Too many judgements for this identity.
+		TooManyJudgements,
+		/// The judgement limit exceeds `T::MaxJudgements`.
+		JudgementLimitTooHigh,
+		/// No registrar is set for the judgement ID.
+		UnknownRegistrar,
+		/// The registrar's fee is higher than the maximum the caller agreed to.
+		FeeChanged,
+		/// A judgement was already requested from this registrar.
+		AlreadyRequested,
+		/// Too many judgements requested for this identity.
+		TooManyRequests,
+		/// The judgement is already disputed.
+		AlreadyDisputed,
+		/// The judgement is disputed and can't be changed until the dispute is resolved.
+		JudgementDisputed,
+		/// Too many judgements disputed for this identity.
+		TooManyDisputes,
+		/// The judgement is not disputed.
+		NotDisputed,
+		/// No deposit is held for the account.
+		NoPendingRelease,
+		/// The deposit can't be withdrawn before `T::DepositReleaseDelay` has passed.
+		ReleaseNotDue,
+		/// The account has no fees to claim.
+		NoFees,
+		/// The caller is not the beneficiary of the identity.
+		NotBeneficiary,
+		/// The identity holder is still active.
+		StillActive,
+		/// The account already has an identity.
+		IdentityExists,
+		/// The judgement ID is reserved for the pallet's own judgements or for bridged ones.
+		ReservedJudgementId,
+		/// Only accounts with a `KnownGood` judgement can vouch.
+		NotTrusted,
+		/// The account already vouched for this identity.
+		AlreadyVouched,
+		/// The identity has the maximum number of vouches.
+		TooManyVouches,
+		/// The caller is not the registrar for the judgement ID.
+		NotRegistrar,
+		/// No signing key is set for the judgement ID.
+		NoProviderKey,
+		/// The signature doesn't match the judgement payload and the registrar's key.
+		InvalidSignature,
+		/// Registrations and judgements are paused.
+		Paused,
+		/// Registrations and judgements are not paused.
+		NotPaused,
+		/// The set of fields has bits for no field.
+		InvalidFields,
+		/// A field to verify is empty.
+		EmptyField,
+		/// No foreign state root is trusted to check proofs against.
+		NoForeignStateRoot,
+		/// The proof is larger than `T::MaxRemoteProofSize`.
+		ProofTooLarge,
+		/// The proof lacks the trie nodes to look up the identity under the foreign state root.
+		InvalidProof,
+		/// The proven value does not decode as a registration.
+		UndecodableProof,
+		/// The account has no mirrored identity to remove.
+		NoRemoteIdentity,
+		/// The judgement ID is not one reserved for bridged judgements.
+		NotBridgedJudgementId,
+		/// The identity already has an audit pending.
+		AuditPending,
+		/// There is no registrar to assign the audit to.
+		NoRegistrars,
+		/// The account has no tombstone.
+		NoTombstone,
+		/// The tombstone can't be reaped before `T::TombstoneLifetime` has passed.
+		TombstoneNotExpired,
+		/// The identity hasn't expired, or was already marked expired.
+		IdentityNotExpired,
+		/// The identity can't be reaped before it was marked expired and `T::ExpiryGracePeriod`
+		/// has passed since, or became delinquent and `T::RentGracePeriod` has passed since.
+		GracePeriodNotOver,
+		/// `T::MaxJudgementsPerBlock` judgements were already written under the judgement ID in
+		/// this block.
+		TooManyJudgementsInBlock,
+		/// The account already set its identity `T::MaxSetsPerPeriod` times in the current window
+		/// of `T::IdentitySetPeriod` blocks.
+		TooManyIdentitySets,
+		/// The judgement can't go there from where it is: only a judgement ID without a
+		/// judgement can be `Requested`, and `Requested` is no verdict a provider can give.
+		InvalidJudgementTransition,
+		/// A registrar is already set for the judgement ID.
+		RegistrarExists,
+		/// The provider's bond would be below `T::MinProviderBond`.
+		BondTooLow,
+		/// The registrar was set by `T::ForceOrigin` rather than registered with
+		/// `register_provider`, and has no provider bond.
+		NotSelfRegistered,
+		/// The identity has no KYC hash to attest.
+		NoKycHash,
+		/// The KYC hash of the identity isn't the one the registrar checked.
+		KycHashChanged,
+		/// A reason code was given with a verdict other than `Erroneous` or `LowQuality`.
+		ReasonNotApplicable,
+		/// There is no `KnownGood` judgement under the judgement ID to reconfirm.
+		NotKnownGood,
+		/// A registrar is listed more than once among the preferred ones.
+		DuplicatePreferredRegistrar,
+		/// The identity pays no rent, or has none due yet.
+		NoRentDue,
+		/// A local registrar is set for the judgement ID, so no remote registrar may judge under
+		/// it.
+		LocalRegistrarId,
+		/// A field of the identity information exceeds its own bound.
+		FieldTooLong,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// Judgements written in the previous block no longer count against their providers
+			let cleared = JudgementsInBlock::<T, I>::clear(u32::MAX, None).unique;
+			T::WeightInfo::reset_judgements_in_block(cleared)
+				.saturating_add(Self::downgrade_expired_judgements())
+				.saturating_add(Self::collect_due_rent())
+		}
+
+		fn integrity_test() {
+			let size = Registration::<T, I>::max_encoded_len();
+			let budget = T::MaxRegistrationSize::get() as usize;
+			assert!(
+				size <= budget,
+				"A registration encodes to up to {size} bytes, over the {budget} bytes of \
+				 `MaxRegistrationSize`.",
+			);
+			assert!(
+				T::IdentityRent::get().is_zero() || !T::RentPeriod::get().is_zero(),
+				"Rent must be charged for a `RentPeriod` of at least one block.",
+			);
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
+
+	#[pallet::view_functions_experimental]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// The verification level of `who`'s identity, `None` if it has none.
+		pub fn level_of(who: T::AccountId) -> VerificationLevel {
+			Self::verification_level(&who)
+		}
+
+		/// The storage key of `who`'s entry in `IdentityOf`, for requesting a read proof of it from
+		/// a full node. See [`Pallet::verify_identity_proof`].
+		pub fn identity_storage_key(who: T::AccountId) -> Vec<u8> {
+			IdentityOf::<T, I>::hashed_key_for(who)
+		}
+
+		/// What setting an identity of `info` costs `who`: the deposit it holds for the identity,
+		/// and the registration fee it pays on top.
+		pub fn registration_cost(
+			who: T::AccountId,
+			info: IdentityInfoOf<T, I>,
+		) -> (BalanceOf<T, I>, BalanceOf<T, I>) {
+			let verified = IdentityOf::<T, I>::get(&who).is_some_and(|reg| reg.known_good > 0);
+			(Self::identity_deposit(&who, &info.field_sizes(), verified), T::RegistrationFee::get())
+		}
+
+		/// The deposit an identity whose fields are the given number of bytes long holds, for
+		/// quoting it before `set_identity` without building the identity. `avatar_len` is `None`
+		/// for an identity without an avatar. Neither the exemption of any account nor the
+		/// registration fee is accounted for. None while identities pay rent instead.
+		pub fn compute_deposit(
+			display_len: u32,
+			legal_len: u32,
+			web_len: u32,
+			email_len: u32,
+			avatar_len: Option<u32>,
+		) -> BalanceOf<T, I> {
+			if !T::IdentityRent::get().is_zero() {
+				return Zero::zero();
+			}
+			let [display, legal, web, email] = [display_len, legal_len, web_len, email_len]
+				.map(IdentityInfoOf::<T, I>::encoded_field_size);
+			let avatar = avatar_len.map_or(0, IdentityInfoOf::<T, I>::encoded_avatar_size);
+			Self::calculate_identity_deposit(&[display, legal, web, email, avatar])
+		}
+
+		/// What requesting a judgement from the registrar under `judgement_id` costs at most: the
+		/// fee reserved with the request, and the deposit held for a note of `T::MaxNoteLength`
+		/// bytes the registrar may give with the judgement. `None` if there is no such registrar.
+		pub fn estimate_judgement_cost(
+			judgement_id: T::JudgementId,
+		) -> Option<(BalanceOf<T, I>, BalanceOf<T, I>)> {
+			let registrar = Registrars::<T, I>::get(judgement_id)?;
+			let deposit = T::ByteDeposit::get()
+				.saturating_mul(BalanceOf::<T, I>::from(T::MaxNoteLength::get()));
+			Some((registrar.fee, deposit))
+		}
+
+		/// The fields `who` has filled in, none if `who` has no identity.
+		pub fn populated_fields(who: T::AccountId) -> IdentityFields {
+			IdentityOf::<T, I>::get(who)
+				.map(|reg| reg.info.populated_fields())
+				.unwrap_or_default()
+		}
+
+		/// Up to `limit` of the most recently given judgements, newest first.
+		pub fn recent_judgements(limit: u32) -> Vec<JudgementActivityOf<T, I>> {
+			let size = T::MaxRecentJudgements::get();
+			if size == 0 {
+				return Vec::new();
+			}
+			let head = RecentJudgementsHead::<T, I>::get() % size;
+			(1..=size.min(limit))
+				.map(|back| if back <= head { head - back } else { size - (back - head) })
+				.map_while(RecentJudgements::<T, I>::get)
+				.collect()
+		}
+
+		/// Up to `limit` of the accounts holding a judgement given under `judgement_id`, in
+		/// storage order, starting after `start_after`. Passing the last account of a page as
+		/// `start_after` gets the next one.
+		pub fn judged_by(
+			judgement_id: T::JudgementId,
+			start_after: Option<T::AccountId>,
+			limit: u32,
+		) -> Vec<T::AccountId> {
+			let accounts = match start_after {
+				Some(last) => JudgedBy::<T, I>::iter_key_prefix_from(
+					&judgement_id,
+					JudgedBy::<T, I>::hashed_key_for(&judgement_id, last),
+				),
+				None => JudgedBy::<T, I>::iter_key_prefix(&judgement_id),
+			};
+			accounts.take(limit as usize).collect()
+		}
+
+		/// Up to `limit` of the accounts whose display name starts with `prefix`, ignoring ASCII
+		/// case, in storage order, starting after `start_after`. Passing the last account of a page
+		/// as `start_after` gets the next one.
+		///
+		/// Looked up in `DisplayIndex`, so a `prefix` shorter than `T::DisplayIndexLength` only
+		/// finds display names as short as itself, and none are found without the index.
+		pub fn search_display(
+			prefix: Vec<u8>,
+			start_after: Option<T::AccountId>,
+			limit: u32,
+		) -> Vec<T::AccountId> {
+			let Some(key) = Self::display_prefix(&prefix) else { return Vec::new() };
+			let accounts = match start_after {
+				Some(last) => DisplayIndex::<T, I>::iter_key_prefix_from(
+					&key,
+					DisplayIndex::<T, I>::hashed_key_for(&key, last),
+				),
+				None => DisplayIndex::<T, I>::iter_key_prefix(&key),
+			};
+			// Past the indexed bytes, the display names themselves have to be compared
+			let matches = |who: &T::AccountId| {
+				prefix.len() <= key.len() ||
+					Self::display_of(who).is_some_and(|display| {
+						display.len() >= prefix.len() &&
+							display[..prefix.len()].eq_ignore_ascii_case(&prefix)
+					})
+			};
+			accounts.filter(matches).take(limit as usize).collect()
+		}
+
+		/// The judgement under `judgement_id` on `who`'s identity: its verdict, inline before
+		/// double map, else `Requested` while a request is pending, else `None`.
+		pub fn judgement_of(who: T::AccountId, judgement_id: T::JudgementId) -> Option<Judgement> {
+			let reg = IdentityOf::<T, I>::get(&who)?;
+			Self::judgement_state(&who, &reg, &judgement_id)
+		}
+
+		/// The reason code given with the judgement under `judgement_id` on `who`'s identity, if
+		/// any.
+		pub fn judgement_reason_of(
+			who: T::AccountId,
+			judgement_id: T::JudgementId,
+		) -> Option<ReasonCode> {
+			JudgementReasons::<T, I>::get(who, judgement_id)
+		}
+
+		/// What has been given under `judgement_id`: the number of judgements of each kind and
+		/// when the last one was.
+		pub fn provider_stats(judgement_id: T::JudgementId) -> ProviderStatisticsOf<T, I> {
+			ProviderStats::<T, I>::get(judgement_id)
+		}
+
+		/// `who`'s identity rendered as a DID document, for off-chain resolvers to bridge into DID
+		/// ecosystems. `None` if `who` has no identity. See [`DidDocument`].
+		pub fn did_document(who: T::AccountId) -> Option<DidDocument<T::AccountId>> {
+			Self::did_document_of(who)
+		}
+
+		/// The judgement IDs of the registrars `who` prefers to be judged by, most preferred
+		/// first, for UIs to offer first. Empty if `who` has no identity or listed none.
+		pub fn preferred_registrars(who: T::AccountId) -> Vec<T::JudgementId> {
+			IdentityOf::<T, I>::get(who)
+				.map(|reg| reg.preferred_registrars.into_inner())
+				.unwrap_or_default()
+		}
+	}
+
+	/// Dispatchable functions allow users to interact with the pallet and invoke state changes.
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Set an account's identity information and reserve the appropriate deposit.
+		///
+		/// If the account already has identity information, the deposit is taken as part payment
+		/// for the new deposit, and the fields that change are no longer verified.
+		///
+		/// An account can set its identity, with this or any other call, at most
+		/// `T::MaxSetsPerPeriod` times in a window of `T::IdentitySetPeriod` blocks.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `info`: The identity information. Each field is bounded by its own `T::Max*Length`,
+		///   enforced when the call is decoded.
+		///
+		/// Emits `IdentitySet` if successful.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_identity_update(
+			// worst case: fields at max length
+			T::MaxDisplayLength::get(),
+			T::MaxLegalLength::get(),
+			T::MaxWebLength::get(),
+			T::MaxEmailLength::get(),
+			T::MaxCidLength::get(),
+			T::MaxJudgements::get() // worst case: maximum judgements
+		))]
+		pub fn set_identity(
+			origin: OriginFor<T>,
+			info: IdentityInfoOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			Self::count_identity_set(&sender)?;
+
+			let ([d, l, w, e, a], previous_judgements) = Self::do_set_identity(sender, info)?;
+
+			// Return actual weight consumed
+			let actual_weight = match previous_judgements {
+				Some(judgements) => T::WeightInfo::set_identity_update(d, l, w, e, a, judgements),
+				None => T::WeightInfo::set_identity(d, l, w, e, a),
+			};
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Provide a judgement for an account's identity using inline storage (BoundedVec).
+		/// This demonstrates the efficient storage pattern where judgements are stored
+		/// inline within the Registration struct as a BoundedVec.
+		///
+		/// The dispatch origin for this call must be `T::JudgementOrigin`.
+		///
+		/// - `judgement_id`: a unique identifier for this judgement provider.
+		/// - `target`: the account whose identity the judgement is upon. This must be an account
+		///   with a registered identity.
+		/// - `judgement`: the judgement of the target's identity.
+		/// - `note`: an optional note explaining the verdict. It replaces any note previously given
+		///   with this `judgement_id`; `None` keeps it and an empty note removes it. The target
+		///   reserves `T::ByteDeposit` per byte of note.
+		///
+		/// Emits `JudgementGiven` if successful.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::provide_judgement_inline(
+			T::MaxJudgements::get(),
+			T::MaxNoteLength::get()
+		))]
+		pub fn provide_judgement_inline(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: AccountIdLookupOf<T>,
+			judgement: Judgement,
+			note: Option<JudgementNote<T, I>>,
+		) -> DispatchResult {
+			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+			Self::ensure_verdict(judgement)?;
+
+			// Add judgement only to the inline BoundedVec storage
+			let (old_status, new_status) =
+				Self::add_judgement_inline(&target, &judgement_id, judgement, note)?;
+			// A local judgement replaces any remote one given with the same ID
+			RemoteJudgementSources::<T, I>::remove(&target, &judgement_id);
+			Self::settle_judgement_request(&target, &judgement_id)?;
+			Self::status_changed(&target, old_status, new_status);
+			Self::judgement_given(&target, &judgement_id, judgement, None)?;
+
+			Self::deposit_event(Event::JudgementGiven { target });
+
+			Ok(())
+		}
+
+		/// Provide a judgement for an account's identity using double map storage.
+		/// This demonstrates the double map storage pattern where judgements are stored
+		/// in a separate DoubleMap, which can be less efficient for cleanup operations but slightly
+		/// faster for `set_identity` in case of many judgements.
+		///
+		/// Note: This version assumes judgement deposit is not necessary and will create storage
+		/// bloat if `T::JudgementOrigin` is not properly managed.
+		///
+		/// The dispatch origin for this call must be `T::JudgementOrigin`.
+		///
+		/// - `judgement_id`: a unique identifier for this judgement provider.
+		/// - `target`: the account whose identity the judgement is upon. This must be an account
+		///   with a registered identity.
+		/// - `judgement`: the judgement of the target's identity.
+		/// - `note`: an optional note explaining the verdict, with the same semantics as in
+		///   `provide_judgement_inline`.
+		///
+		/// Replacing a judgement without giving or removing a note, other than with a sticky
+		/// `KnownGood` or `Erroneous` one, leaves the registration unwritten and is charged
+		/// `T::WeightInfo::replace_judgement_double_map` instead.
+		///
+		/// Emits `JudgementGiven` if successful.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::provide_judgement_double_map(T::MaxNoteLength::get()))]
+		pub fn provide_judgement_double_map(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: AccountIdLookupOf<T>,
+			judgement: Judgement,
+			note: Option<JudgementNote<T, I>>,
+		) -> DispatchResultWithPostInfo {
+			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+			Self::ensure_verdict(judgement)?;
+
+			// Check that target has an identity and validate sticky judgements
+			ensure!(IdentityOf::<T, I>::contains_key(&target), Error::<T, I>::InvalidTarget);
+			ensure!(
+				!Disputes::<T, I>::contains_key(&target, &judgement_id),
+				Error::<T, I>::JudgementDisputed
+			);
+
+			// Check for existing judgement in double map
+			let existing_judgement = JudgementsDoubleMap::<T, I>::get(&target, &judgement_id)
+				.map(|(judgement, _)| judgement);
+			if let Some(existing_judgement) = existing_judgement {
+				ensure!(!existing_judgement.is_sticky(), Error::<T, I>::StickyJudgement);
+				// Existing judgement being replaced
+			} else {
+				// New judgement being added - increment counter
+				CounterForJudgements::<T, I>::try_mutate(&target, |count| -> DispatchResult {
+					ensure!(
+						*count < JudgementLimit::<T, I>::get(),
+						Error::<T, I>::TooManyJudgements
+					);
+					count.saturating_inc();
+					Ok(())
+				})?;
+			}
+			Self::update_score(&target, existing_judgement, Some(judgement));
+
+			// The registration is only touched for what it records: the deposit of a note given
+			// or replaced, and the verification a sticky judgement may bring or take
+			let touches_registration = note.as_ref().is_some_and(|note| {
+				!note.is_empty() || JudgementNotes::<T, I>::contains_key(&target, &judgement_id)
+			}) || judgement.is_sticky();
+			let statuses = if touches_registration {
+				Some(IdentityOf::<T, I>::try_mutate(
+					&target,
+					|maybe_reg| -> Result<_, DispatchError> {
+						let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::InvalidTarget)?;
+						let old_status = reg.status;
+						Self::update_judgement_note(&target, reg, &judgement_id, note)?;
+						// The same inline verdict under the same ID is the same provider
+						let counted = Self::held_inline(reg, &judgement_id, judgement);
+						Self::count_sticky(&target, reg, judgement, counted);
+						Ok((old_status, reg.status))
+					},
+				)?)
+			} else {
+				None
+			};
+
+			// Add judgement to the double map storage, dated to this block
+			let given = (judgement, T::BlockNumberProvider::current_block_number());
+			JudgementsDoubleMap::<T, I>::insert(&target, &judgement_id, given);
+			Self::shadow_double_map_judgement(&target, &judgement_id, given);
+			JudgedBy::<T, I>::insert(&judgement_id, &target, ());
+			Self::settle_judgement_request(&target, &judgement_id)?;
+			if let Some((old_status, new_status)) = statuses {
+				Self::status_changed(&target, old_status, new_status);
+			}
+			Self::judgement_given(&target, &judgement_id, judgement, None)?;
+
+			Self::deposit_event(Event::JudgementGiven { target });
+
+			if existing_judgement.is_some() && !touches_registration {
+				return Ok(Some(T::WeightInfo::replace_judgement_double_map()).into());
+			}
+			Ok(().into())
+		}
+
+		/// Clear an account's identity info and return all deposits.
+		/// This extrinsic handles both storage patterns - the complexity depends on usage:
+		/// - O(1) if only inline judgements were used (via provide_judgement_inline)
+		/// - O(n) if double map judgements were used, where n = actual number of double map
+		///   judgements
+		///
+		/// Payment: All reserved balances on the account are returned, including the fees of
+		/// pending judgement requests and the challenge deposits of open disputes. With a non-zero
+		/// `T::DepositReleaseDelay` they stay reserved until withdrawn with `withdraw_deposit`.
+		///
+		/// With `T::KeepTombstones` set, a [`Tombstone`] of the identity is left behind.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// Emits `IdentityCleared` if successful.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Pallet::<T, I>::clear_identity_weight())]
+		pub fn clear_identity(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let deposit = Self::do_clear_identity(&sender)?;
+			Self::deposit_event(Event::IdentityCleared { who: sender, deposit });
+			Ok(())
+		}
+
+		/// Set an account's identity information using the original four-argument signature.
+		///
+		/// Kept so that tooling and signed transactions built against the pre-`IdentityInfo` call
+		/// remain decodable across the upgrade: each field is bounded by `LegacyFieldLength`, as
+		/// it was then. Behaves like `set_identity` with the avatar set before, if any, so the
+		/// call that can't set one doesn't clear it either.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `display`: The display name.
+		/// - `legal`: The legal name.
+		/// - `web`: The web address.
+		/// - `email`: The email address.
+		///
+		/// Fails with `FieldTooLong` if a field exceeds its own bound, e.g. `T::MaxDisplayLength`.
+		///
+		/// Emits `IdentitySet` if successful.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::set_identity_legacy(
+			// worst case: fields at max length
+			T::MaxDisplayLength::get(),
+			T::MaxLegalLength::get(),
+			T::MaxWebLength::get(),
+			T::MaxEmailLength::get(),
+			T::MaxCidLength::get(),
+			T::MaxJudgements::get() // worst case: maximum judgements
+		))]
+		#[deprecated = "Use `set_identity` with an `IdentityInfo` instead"]
+		pub fn set_identity_legacy(
+			origin: OriginFor<T>,
+			display: BoundedVec<u8, LegacyFieldLength>,
+			legal: BoundedVec<u8, LegacyFieldLength>,
+			web: BoundedVec<u8, LegacyFieldLength>,
+			email: BoundedVec<u8, LegacyFieldLength>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			let info = IdentityInfo {
+				display: Self::rebound_legacy_field(display)?,
+				legal: Self::rebound_legacy_field(legal)?,
+				web: Self::rebound_legacy_field(web)?,
+				email: Self::rebound_legacy_field(email)?,
+				avatar: IdentityOf::<T, I>::get(&sender).and_then(|reg| reg.info.avatar),
+			};
+			Self::count_identity_set(&sender)?;
+
+			let ([d, l, w, e, a], previous_judgements) = Self::do_set_identity(sender, info)?;
+
+			let actual_weight = T::WeightInfo::set_identity_legacy(
+				d,
+				l,
+				w,
+				e,
+				a,
+				previous_judgements.unwrap_or_default(),
+			);
+			Ok(Some(actual_weight).into())
+		}
+
+		/// Set the effective maximum number of judgements per identity.
+		///
+		/// Applies to both storage patterns. Lowering the limit does not remove existing
+		/// judgements, but no new ones are accepted for an identity at or above it.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		///
+		/// - `limit`: the new limit, at most `T::MaxJudgements`.
+		///
+		/// Emits `JudgementLimitSet` if successful.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::set_judgement_limit())]
+		pub fn set_judgement_limit(origin: OriginFor<T>, limit: u32) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			ensure!(limit <= T::MaxJudgements::get(), Error::<T, I>::JudgementLimitTooHigh);
+
+			JudgementLimit::<T, I>::put(limit);
+			Self::deposit_event(Event::JudgementLimitSet { limit });
+			Ok(())
+		}
+
+		/// Re-price the deposits of up to `limit` registrations, reserving or unreserving the
+		/// difference to the current `T::BasicDeposit` and `T::ByteDeposit`.
+		///
+		/// Meant to be called repeatedly after the deposit constants change: each call resumes
+		/// where the previous one stopped, using `ReconcileCursor`, until every registration has
+		/// been visited. Deposits which cannot be raised for lack of funds are left as they are.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		///
+		/// - `limit`: the maximum number of registrations to visit.
+		///
+		/// Emits `DepositReconciled` for each changed deposit and
+		/// `DepositReconciliationCompleted` once the sweep has visited every registration.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::reconcile_deposits(*limit))]
+		pub fn reconcile_deposits(origin: OriginFor<T>, limit: u32) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let mut registrations = match ReconcileCursor::<T, I>::get() {
+				Some(last) =>
+					IdentityOf::<T, I>::iter_from(IdentityOf::<T, I>::hashed_key_for(last)),
+				None => IdentityOf::<T, I>::iter(),
+			};
+
+			let mut visited = 0;
+			let mut last = None;
+			while visited < limit {
+				let Some((who, registration)) = registrations.next() else { break };
+				Self::reconcile_deposit(&who, registration);
+				last = Some(who);
+				visited += 1;
+			}
+
+			// Only keep the cursor if there is something left to visit
+			match (last, registrations.next()) {
+				(Some(last), Some(_)) => ReconcileCursor::<T, I>::put(last),
+				// Nothing visited with `limit` 0, the sweep stays where it was
+				(None, Some(_)) => {},
+				(_, None) => {
+					ReconcileCursor::<T, I>::kill();
+					Self::deposit_event(Event::DepositReconciliationCompleted);
+				},
+			}
+
+			Ok(Some(T::WeightInfo::reconcile_deposits(visited)).into())
+		}
+
+		/// Provide a judgement on behalf of a registrar on another chain.
+		///
+		/// The judgement is stored inline, like with `provide_judgement_inline`, and the
+		/// registrar's ID is recorded alongside it in `RemoteJudgementSources`. How the ID is
+		/// derived from the cross-chain origin is left to `T::RemoteJudgementOrigin`.
+		///
+		/// Judgement IDs of local registrars are theirs alone: a remote judgement under one would
+		/// stand in for theirs, and be paid the fee requested of them.
+		///
+		/// The dispatch origin for this call must be `T::RemoteJudgementOrigin`.
+		///
+		/// - `judgement_id`: a unique identifier for this judgement provider.
+		/// - `target`: the account whose identity the judgement is upon. This must be an account
+		///   with a registered identity.
+		/// - `judgement`: the judgement of the target's identity.
+		/// - `note`: an optional note explaining the verdict, with the same semantics as in
+		///   `provide_judgement_inline`.
+		///
+		/// Emits `RemoteJudgementGiven` if successful.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::provide_judgement_remote(
+			T::MaxJudgements::get(),
+			T::MaxNoteLength::get()
+		))]
+		pub fn provide_judgement_remote(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: AccountIdLookupOf<T>,
+			judgement: Judgement,
+			note: Option<JudgementNote<T, I>>,
+		) -> DispatchResult {
+			let registrar = T::RemoteJudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+			ensure!(
+				!Registrars::<T, I>::contains_key(&judgement_id),
+				Error::<T, I>::LocalRegistrarId
+			);
+			Self::ensure_verdict(judgement)?;
+
+			let (old_status, new_status) =
+				Self::add_judgement_inline(&target, &judgement_id, judgement, note)?;
+			RemoteJudgementSources::<T, I>::insert(&target, &judgement_id, registrar.clone());
+			Self::status_changed(&target, old_status, new_status);
+			Self::judgement_given(&target, &judgement_id, judgement, None)?;
+
+			Self::deposit_event(Event::RemoteJudgementGiven { target, registrar });
+
+			Ok(())
+		}
+
+		/// Move all of an identity's double map judgements into its inline storage.
+		///
+		/// Afterwards the double map holds little for `target`, so later operations priced on
+		/// double map usage, like `clear_identity`, are cheaper. A double map judgement replaces
+		/// an inline one with the same ID unless the inline one is sticky or disputed, in which
+		/// case both are kept where they are. Moved judgements keep the block they were given in.
+		/// Fails without moving anything if the result would exceed the judgement limit.
+		///
+		/// The dispatch origin for this call must be _Signed_ by `target`, or `T::ForceOrigin`.
+		///
+		/// - `target`: the account whose judgements to consolidate.
+		///
+		/// Emits `JudgementsConsolidated` if successful.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::consolidate_judgements(T::MaxJudgements::get()))]
+		pub fn consolidate_judgements(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let target = T::Lookup::lookup(target)?;
+			if let Err(origin) = T::ForceOrigin::try_origin(origin) {
+				let sender = ensure_signed(origin)?;
+				ensure!(sender == target, DispatchError::BadOrigin);
+			}
+
+			let (read, moved, old_status, new_status) =
+				IdentityOf::<T, I>::try_mutate(&target, |maybe_reg| -> Result<_, DispatchError> {
+					let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::NoIdentity)?;
+					let old_status = reg.status;
+
+					let mut judgements = reg.judgements.to_vec();
+					let double_map: Vec<_> =
+						JudgementsDoubleMap::<T, I>::iter_prefix(&target).collect();
+					let read = double_map.len() as u32;
+					let mut kept = Vec::new();
+					for (judgement_id, judgement) in double_map {
+						let found = judgements.binary_search_by(|x| x.0.cmp(&judgement_id));
+						// Neither is dropped: the double map one stays where it is
+						if found
+							.is_ok_and(|position| Self::is_frozen(&target, &judgements[position]))
+						{
+							kept.push((judgement_id, judgement));
+							continue;
+						}
+						JudgementsDoubleMap::<T, I>::remove(&target, &judgement_id);
+						match found {
+							Ok(position) => {
+								judgements[position].1 = judgement;
+								// The replaced judgement may have come from a remote registrar
+								RemoteJudgementSources::<T, I>::remove(&target, &judgement_id);
+							},
+							Err(position) => judgements.insert(position, (judgement_id, judgement)),
+						}
+					}
+					let moved = read.saturating_sub(kept.len() as u32);
+
+					ensure!(
+						judgements.len() as u32 <= JudgementLimit::<T, I>::get(),
+						Error::<T, I>::TooManyJudgements
+					);
+					reg.judgements =
+						judgements.try_into().map_err(|_| Error::<T, I>::TooManyJudgements)?;
+					Self::set_judgement_count(&target, kept.len() as u32);
+					if T::ShadowWrites::get() {
+						ShadowJudgementsInline::<T, I>::remove(&target);
+						for (judgement_id, judgement) in &kept {
+							Self::shadow_double_map_judgement(&target, judgement_id, *judgement);
+						}
+						Self::reshadow_inline_judgements(&target, reg);
+					}
+					let score =
+						reg.judgements.iter().chain(&kept).fold(0i32, |score, (_, given)| {
+							score.saturating_add(T::JudgementScore::convert(given.0))
+						});
+					Scores::<T, I>::insert(&target, score);
+					let was_verified = reg.known_good > 0;
+					// A kept judgement counts unless the inline one under its ID is the same
+					let count = |verdict| {
+						let holds = |(_, (judgement, _)): &&(_, DatedJudgementOf<T, I>)| {
+							*judgement == verdict
+						};
+						let inline = reg.judgements.iter().filter(holds);
+						let kept = kept.iter().filter(holds).filter(|(judgement_id, _)| {
+							!inline.clone().any(|(inline_id, _)| inline_id == judgement_id)
+						});
+						(kept.count() + inline.count()) as u32
+					};
+					(reg.known_good, reg.erroneous) =
+						(count(Judgement::KnownGood), count(Judgement::Erroneous));
+					Self::refresh_status(&target, reg);
+					if was_verified != (reg.known_good > 0) {
+						Self::verification_repriced(&target, reg);
+					}
+					Ok((read, moved, old_status, reg.status))
+				})?;
+
+			Self::status_changed(&target, old_status, new_status);
+			Self::deposit_event(Event::JudgementsConsolidated { who: target, moved });
+			Ok(Some(T::WeightInfo::consolidate_judgements(read)).into())
+		}
+
+		/// Recount an identity's double map judgements and correct
+		/// `CounterForJudgements` if it has drifted from the actual number of entries.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		///
+		/// - `target`: the account whose counter to repair.
+		///
+		/// Emits `CounterRepaired` if the counter was wrong.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::force_recount_judgements(T::MaxJudgements::get()))]
+		pub fn force_recount_judgements(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let target = T::Lookup::lookup(target)?;
+
+			ensure!(IdentityOf::<T, I>::contains_key(&target), Error::<T, I>::NoIdentity);
+			let count = JudgementsDoubleMap::<T, I>::iter_key_prefix(&target).count() as u32;
+			let old_count = Self::set_judgement_count(&target, count);
+
+			if old_count != count {
+				Self::deposit_event(Event::CounterRepaired {
+					who: target,
+					old_count,
+					new_count: count,
+				});
+			}
+			Ok(Some(T::WeightInfo::force_recount_judgements(count)).into())
+		}
+
+		/// Set the account and fee of the registrar giving judgements under `judgement_id`.
+		///
+		/// Identity holders can then ask it for a judgement with `request_judgement`. Pending
+		/// requests keep the fee they were made with.
+		///
+		/// `T::RegistrarBond` is reserved from `account`, and the bond of the registrar it
+		/// replaces, if any, is returned, be it a provider bond held by `register_provider`.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		///
+		/// - `judgement_id`: the judgement ID the registrar gives judgements under.
+		/// - `account`: the account receiving the fees.
+		/// - `fee`: the fee for a judgement.
+		///
+		/// Emits `RegistrarSet` if successful.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::set_registrar())]
+		pub fn set_registrar(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			account: AccountIdLookupOf<T>,
+			fee: BalanceOf<T, I>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let account = T::Lookup::lookup(account)?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+
+			if let Some(previous) = Registrars::<T, I>::get(&judgement_id) {
+				let err_amount = Self::release_deposit(&previous.account, previous.bond);
+				debug_assert!(err_amount.is_zero());
+				if let Some(bond) = ProviderBonds::<T, I>::take(&judgement_id) {
+					let err_amount = ProviderBondHolds::<T, I>::release(&previous.account, bond);
+					debug_assert!(err_amount.is_zero());
+				}
+				// The keys were the previous registrar's
+				if previous.account != account {
+					ProviderKeys::<T, I>::remove(&judgement_id);
+					PreviousProviderKeys::<T, I>::remove(&judgement_id);
+				}
+			}
+			let bond = T::RegistrarBond::get();
+			Self::hold_deposit(&account, bond)?;
+			Registrars::<T, I>::insert(
+				&judgement_id,
+				RegistrarInfo { account: account.clone(), fee, bond },
+			);
+			Self::deposit_event(Event::RegistrarSet { judgement_id, account, fee });
+			Ok(())
+		}
+
+		/// Request a judgement from the registrar giving judgements under `judgement_id`.
+		///
+		/// The registrar's fee is reserved and paid to it once a judgement is given under
+		/// `judgement_id`, or returned if the identity is cleared first. The registrar collects
+		/// its fees with `claim_fees`.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// - `judgement_id`: the judgement ID of the registrar to ask.
+		/// - `max_fee`: the maximum fee the sender is willing to pay, guarding against the fee
+		///   being raised before the call is included.
+		///
+		/// Emits `JudgementRequested` if successful.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::request_judgement(T::MaxJudgements::get()))]
+		pub fn request_judgement(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			max_fee: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+
+			let pending = Self::do_request_judgement(sender, &judgement_id, max_fee)?;
+			Ok(Some(T::WeightInfo::request_judgement(pending)).into())
+		}
+
+		/// Set an account's identity information and request a judgement on it in one go.
+		///
+		/// Behaves like `set_identity` followed by `request_judgement`, but if either fails
+		/// neither takes effect, so the deposit is never reserved without the request being
+		/// filed.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `info`: The identity information, as in `set_identity`.
+		/// - `judgement_id`: the judgement ID of the registrar to ask.
+		/// - `max_fee`: the maximum fee the sender is willing to pay.
+		///
+		/// Emits `IdentitySet` and `JudgementRequested` if successful.
+		#[pallet::call_index(12)]
+		#[pallet::weight(
+			T::WeightInfo::set_identity_update(
+				T::MaxDisplayLength::get(),
+				T::MaxLegalLength::get(),
+				T::MaxWebLength::get(),
+				T::MaxEmailLength::get(),
+				T::MaxCidLength::get(),
+				T::MaxJudgements::get()
+			)
+			.saturating_add(T::WeightInfo::request_judgement(T::MaxJudgements::get()))
+		)]
+		pub fn set_identity_with_judgement_request(
+			origin: OriginFor<T>,
+			info: IdentityInfoOf<T, I>,
+			judgement_id: T::JudgementId,
+			max_fee: BalanceOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			Self::count_identity_set(&sender)?;
+
+			let ([d, l, w, e, a], previous_judgements) =
+				Self::do_set_identity(sender.clone(), info)?;
+			let pending = Self::do_request_judgement(sender, &judgement_id, max_fee)?;
+
+			let set_weight = match previous_judgements {
+				Some(judgements) => T::WeightInfo::set_identity_update(d, l, w, e, a, judgements),
+				None => T::WeightInfo::set_identity(d, l, w, e, a),
+			};
+			Ok(Some(set_weight.saturating_add(T::WeightInfo::request_judgement(pending))).into())
+		}
+
+		/// Provide several judgements for an account's identity at once, using inline storage.
+		///
+		/// Has the same effect as calling `provide_judgement_inline` for each item in order,
+		/// without notes, but reads and writes the registration only once. Fails without giving
+		/// any judgement if one of them can't be given.
+		///
+		/// The dispatch origin for this call must be `T::JudgementOrigin`.
+		///
+		/// - `target`: the account whose identity the judgements are upon. This must be an account
+		///   with a registered identity.
+		/// - `items`: the judgement IDs and the judgements given under them, at most `T::MaxBatch`.
+		///
+		/// Emits `JudgementGiven` for each item if successful.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::provide_judgements_inline(
+			T::MaxJudgements::get(),
+			items.len() as u32
+		))]
+		pub fn provide_judgements_inline(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			items: BoundedVec<(T::JudgementId, Judgement), T::MaxBatch>,
+		) -> DispatchResultWithPostInfo {
+			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			for (judgement_id, judgement) in &items {
+				Self::ensure_local_judgement_id(judgement_id)?;
+				Self::ensure_verdict(*judgement)?;
+			}
+
+			let (previous_judgements, old_status, new_status) =
+				IdentityOf::<T, I>::try_mutate(&target, |maybe_reg| -> Result<_, DispatchError> {
+					let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::InvalidTarget)?;
+					let previous_judgements = reg.judgements.len() as u32;
+					let old_status = reg.status;
+
+					for (judgement_id, judgement) in items.iter() {
+						Self::insert_judgement_inline(&target, reg, judgement_id, *judgement)?;
+						Self::update_judgement_note(&target, reg, judgement_id, None)?;
+						let counted = Self::held_in_double_map(&target, judgement_id, *judgement);
+						Self::count_sticky(&target, reg, *judgement, counted);
+					}
+					Ok((previous_judgements, old_status, reg.status))
+				})?;
+
+			for (judgement_id, judgement) in items.iter() {
+				RemoteJudgementSources::<T, I>::remove(&target, judgement_id);
+				Self::settle_judgement_request(&target, judgement_id)?;
+				Self::judgement_given(&target, judgement_id, *judgement, None)?;
+				Self::deposit_event(Event::JudgementGiven { target: target.clone() });
+			}
+			Self::status_changed(&target, old_status, new_status);
+
+			Ok(Some(T::WeightInfo::provide_judgements_inline(
+				previous_judgements,
+				items.len() as u32,
+			))
+			.into())
+		}
+
+		/// Remove all non-sticky judgements from the caller's identity, in both storage patterns
+		/// and on single fields, keeping the identity information and its deposit.
+		///
+		/// Notes go with their judgements and their deposit is returned. Sticky and disputed
+		/// judgements can't be cleared and stay, with their notes. Like `clear_identity`, the
+		/// complexity depends on usage: O(j) in the inline judgements and O(n) in the double map
+		/// judgements.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// Emits `JudgementsCleared` if successful.
+		#[pallet::call_index(14)]
+		#[pallet::weight(
+			T::WeightInfo::clear_judgements_double_map_usage(T::MaxJudgements::get())
+				.saturating_add(T::WeightInfo::clear_judgements_inline_usage(
+					T::MaxJudgements::get()
+				))
+		)]
+		pub fn clear_judgements(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (inline_before, double_map_before, inline, double_map) =
+				IdentityOf::<T, I>::try_mutate(
+					&sender,
+					|maybe_reg| -> Result<(u32, u32, u32, u32), DispatchError> {
+						let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::NoIdentity)?;
+						let inline_before = reg.judgements.len() as u32;
+						let double_map_before = CounterForJudgements::<T, I>::get(&sender);
+
+						let mut cleared: Vec<(T::JudgementId, DatedJudgementOf<T, I>)> = reg
+							.judgements
+							.iter()
+							.filter(|item| !Self::is_frozen(&sender, item))
+							.cloned()
+							.collect();
+						reg.judgements.retain(|item| Self::is_frozen(&sender, item));
+						for (judgement_id, _) in &cleared {
+							RemoteJudgementSources::<T, I>::remove(&sender, judgement_id);
+							Self::drop_shadow_judgements(&sender, judgement_id, true, false);
+						}
+						let inline = cleared.len() as u32;
+
+						let double_map_cleared: Vec<(T::JudgementId, DatedJudgementOf<T, I>)> =
+							JudgementsDoubleMap::<T, I>::iter_prefix(&sender)
+								.filter(|item| !Self::is_frozen(&sender, item))
+								.collect();
+						for (judgement_id, _) in &double_map_cleared {
+							JudgementsDoubleMap::<T, I>::remove(&sender, judgement_id);
+							Self::drop_shadow_judgements(&sender, judgement_id, false, true);
+						}
+						let double_map = double_map_cleared.len() as u32;
+						Self::set_judgement_count(
+							&sender,
+							double_map_before.saturating_sub(double_map),
+						);
+						cleared.extend(double_map_cleared);
+
+						// A note stays as long as a kept judgement under its ID does
+						let mut refunded = BalanceOf::<T, I>::zero();
+						for (judgement_id, (judgement, _)) in cleared {
+							Self::update_score(&sender, Some(judgement), None);
+							if reg.judgements.binary_search_by(|x| x.0.cmp(&judgement_id)).is_err()
+							{
+								refunded = refunded.saturating_add(Self::drop_orphaned_note(
+									&sender,
+									&judgement_id,
+								));
+								Self::forget_judged(&sender, &judgement_id);
+							}
+						}
+						reg.notes_deposit = reg.notes_deposit.saturating_sub(refunded);
+						Self::drop_field_judgements(&sender, IdentityFields::all());
+
+						Ok((inline_before, double_map_before, inline, double_map))
+					},
+				)?;
+
+			Self::note_activity(&sender);
+			Self::deposit_event(Event::JudgementsCleared { who: sender, inline, double_map });
+			Ok(Some(
+				T::WeightInfo::clear_judgements_double_map_usage(double_map_before)
+					.saturating_add(T::WeightInfo::clear_judgements_inline_usage(inline_before)),
+			)
+			.into())
+		}
+
+		/// Dispute the judgement given on the caller's identity under `judgement_id`.
+		///
+		/// `T::ChallengeDeposit` is reserved from the caller. Until the dispute is resolved the
+		/// judgement is frozen: no judgement can be given under `judgement_id`, in either storage
+		/// pattern, and the caller can't drop it with `set_identity` or `clear_judgements`.
+		/// Clearing the identity withdraws the dispute and returns the deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// - `judgement_id`: the ID the disputed judgement was given under.
+		///
+		/// Emits `JudgementDisputed` if successful.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::dispute_judgement(T::MaxJudgements::get()))]
+		pub fn dispute_judgement(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let reg = IdentityOf::<T, I>::get(&sender).ok_or(Error::<T, I>::NoIdentity)?;
+			ensure!(
+				reg.judgements.binary_search_by(|x| x.0.cmp(&judgement_id)).is_ok() ||
+					JudgementsDoubleMap::<T, I>::contains_key(&sender, &judgement_id),
+				Error::<T, I>::NotFound
+			);
+			ensure!(
+				!Disputes::<T, I>::contains_key(&sender, &judgement_id),
+				Error::<T, I>::AlreadyDisputed
+			);
+			let open = Disputes::<T, I>::iter_key_prefix(&sender).count() as u32;
+			ensure!(open < JudgementLimit::<T, I>::get(), Error::<T, I>::TooManyDisputes);
+
+			let deposit = T::ChallengeDeposit::get();
+			Self::hold_deposit(&sender, deposit)?;
+			Disputes::<T, I>::insert(&sender, &judgement_id, deposit);
+			Self::note_activity(&sender);
+
+			Self::deposit_event(Event::JudgementDisputed { who: sender, judgement_id, deposit });
+			Ok(Some(T::WeightInfo::dispute_judgement(open)).into())
+		}
+
+		/// Resolve the dispute of the judgement given on `target`'s identity under
+		/// `judgement_id`, unfreezing it.
+		///
+		/// Upholding the judgement slashes the challenge deposit of `target`. Overturning it
+		/// returns the challenge deposit, removes the judgement from both storage patterns, even
+		/// if it is sticky, together with its note, and slashes `T::OverturnSlash` of the bond of
+		/// the registrar giving judgements under `judgement_id`, if one is set, and of its
+		/// provider bond if it registered with `register_provider`.
+		///
+		/// The dispatch origin for this call must be `T::DisputeResolutionOrigin`.
+		///
+		/// - `target`: the account whose identity the disputed judgement is upon.
+		/// - `judgement_id`: the ID the disputed judgement was given under.
+		/// - `uphold`: whether the judgement stands.
+		///
+		/// Emits `DisputeResolved` if successful.
+		#[pallet::call_index(16)]
+		#[pallet::weight(
+			T::WeightInfo::resolve_dispute_overturned(T::MaxJudgements::get())
+				.max(T::WeightInfo::resolve_dispute_upheld())
+		)]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			judgement_id: T::JudgementId,
+			uphold: bool,
+		) -> DispatchResultWithPostInfo {
+			T::DisputeResolutionOrigin::ensure_origin(origin)?;
+			let target = T::Lookup::lookup(target)?;
+
+			let deposit =
+				Disputes::<T, I>::take(&target, &judgement_id).ok_or(Error::<T, I>::NotDisputed)?;
+
+			if uphold {
+				Self::slash_deposit(&target, deposit);
+				Self::deposit_event(Event::DisputeResolved {
+					who: target,
+					judgement_id,
+					upheld: true,
+					slashed: Zero::zero(),
+				});
+				return Ok(Some(T::WeightInfo::resolve_dispute_upheld()).into());
+			}
+
+			let err_amount = Self::release_deposit(&target, deposit);
+			debug_assert!(err_amount.is_zero());
+
+			let (judgements, old_status, new_status) =
+				IdentityOf::<T, I>::try_mutate(&target, |maybe_reg| -> Result<_, DispatchError> {
+					let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::NoIdentity)?;
+					let judgements = reg.judgements.len() as u32;
+					let old_status = reg.status;
+					Self::remove_judgement(&target, reg, &judgement_id);
+					Ok((judgements, old_status, reg.status))
+				})?;
+			Self::status_changed(&target, old_status, new_status);
+
+			let slashed = Registrars::<T, I>::mutate(&judgement_id, |maybe_registrar| {
+				let Some(registrar) = maybe_registrar else { return Zero::zero() };
+				let amount = T::OverturnSlash::get().mul_floor(registrar.bond);
+				let slashed =
+					amount.saturating_sub(Self::slash_deposit(&registrar.account, amount));
+				registrar.bond = registrar.bond.saturating_sub(slashed);
+
+				// Self-registered providers lose a share of their provider bond too
+				let provider_slashed = ProviderBonds::<T, I>::mutate(&judgement_id, |maybe_bond| {
+					let Some(bond) = maybe_bond else { return Zero::zero() };
+					let amount = T::OverturnSlash::get().mul_floor(*bond);
+					let remaining = ProviderBondHolds::<T, I>::slash(&registrar.account, amount);
+					*bond = bond.saturating_sub(amount.saturating_sub(remaining));
+					amount.saturating_sub(remaining)
+				});
+				slashed.saturating_add(provider_slashed)
+			});
+
+			Self::deposit_event(Event::DisputeResolved {
+				who: target,
+				judgement_id,
+				upheld: false,
+				slashed,
+			});
+			Ok(Some(T::WeightInfo::resolve_dispute_overturned(judgements)).into())
+		}
+
+		/// Return the deposit held since the caller cleared their identity, once
+		/// `T::DepositReleaseDelay` has passed.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Emits `DepositWithdrawn` if successful.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::withdraw_deposit())]
+		pub fn withdraw_deposit(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let pending =
+				PendingReleases::<T, I>::get(&sender).ok_or(Error::<T, I>::NoPendingRelease)?;
+			ensure!(
+				T::BlockNumberProvider::current_block_number() >= pending.at,
+				Error::<T, I>::ReleaseNotDue
+			);
+
+			PendingReleases::<T, I>::remove(&sender);
+			let err_amount = Self::release_deposit(&sender, pending.amount);
+			debug_assert!(err_amount.is_zero());
+
+			Self::deposit_event(Event::DepositWithdrawn { who: sender, amount: pending.amount });
+			Ok(())
+		}
+
+		/// Clear one field of the caller's identity information, returning the deposit for its
+		/// bytes.
+		///
+		/// Whole-identity judgements are kept: removing information leaves nothing new for them to
+		/// vouch for. The field is no longer verified and loses its non-sticky field judgements.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// - `field`: the field to clear.
+		///
+		/// Emits `IdentityFieldCleared` if successful.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::clear_field())]
+		pub fn clear_field(origin: OriginFor<T>, field: IdentityField) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			Self::clear_fields(&sender, field.into())?;
+			Self::note_activity(&sender);
+			Self::deposit_event(Event::IdentityFieldCleared { who: sender, field });
+			Ok(())
+		}
+
+		/// Claim the fees the caller earned as a registrar, making them free to spend.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Emits `FeesClaimed` if successful.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::claim_fees())]
+		pub fn claim_fees(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let amount = AccruedFees::<T, I>::take(&sender);
+			ensure!(!amount.is_zero(), Error::<T, I>::NoFees);
+			let err_amount = Self::release_deposit(&sender, amount);
+			debug_assert!(err_amount.is_zero());
+
+			Self::deposit_event(Event::FeesClaimed { who: sender, amount });
+			Ok(())
+		}
+
+		/// Nominate the account which may take over the caller's identity once the caller is
+		/// inactive for `T::InactivityPeriod` blocks, or its account is reaped.
+		///
+		/// Calling it again, even with the same beneficiary, restarts the inactivity period.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// - `beneficiary`: the account to nominate, other than the caller.
+		///
+		/// Emits `BeneficiarySet` if successful.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::set_beneficiary())]
+		pub fn set_beneficiary(
+			origin: OriginFor<T>,
+			beneficiary: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(IdentityOf::<T, I>::contains_key(&sender), Error::<T, I>::NoIdentity);
+			ensure!(beneficiary != sender, Error::<T, I>::InvalidTarget);
+
+			let since = T::BlockNumberProvider::current_block_number();
+			Beneficiaries::<T, I>::insert(
+				&sender,
+				Beneficiary { account: beneficiary.clone(), since },
+			);
+			Self::deposit_event(Event::BeneficiarySet { who: sender, beneficiary });
+			Ok(())
+		}
+
+		/// Take over the identity of `original`, as its beneficiary.
+		///
+		/// Possible once `original` has neither renewed the nomination nor acted on its identity,
+		/// e.g. set or renewed it or requested a judgement, for `T::InactivityPeriod` blocks, or
+		/// its account was reaped. The registration moves to the caller with everything attached
+		/// to it: judgements in both storage patterns, field judgements, notes, score, KYC hash
+		/// and attestation, pending requests and open disputes. So do the reserved deposits and
+		/// fees backing them; what `original` no longer holds is reserved from the caller
+		/// instead. The identity deposit is then repriced for the caller, as an exemption from it
+		/// stays with the account it was granted to.
+		///
+		/// Taking over an identity counts as setting one against `T::MaxSetsPerPeriod`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the beneficiary of `original`,
+		/// which must not have an identity itself.
+		///
+		/// - `original`: the account whose identity to claim.
+		///
+		/// Emits `IdentityClaimed` if successful.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::claim_identity(T::MaxJudgements::get()))]
+		pub fn claim_identity(
+			origin: OriginFor<T>,
+			original: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			let original = T::Lookup::lookup(original)?;
+
+			let beneficiary =
+				Beneficiaries::<T, I>::get(&original).ok_or(Error::<T, I>::NotBeneficiary)?;
+			ensure!(beneficiary.account == sender, Error::<T, I>::NotBeneficiary);
+			let inactive_since = beneficiary.since.saturating_add(T::InactivityPeriod::get());
+			ensure!(
+				!frame_system::Pallet::<T>::account_exists(&original) ||
+					T::BlockNumberProvider::current_block_number() >= inactive_since,
+				Error::<T, I>::StillActive
+			);
+			ensure!(!IdentityOf::<T, I>::contains_key(&sender), Error::<T, I>::IdentityExists);
+			Self::count_identity_set(&sender)?;
+
+			let mut reg = IdentityOf::<T, I>::take(&original).ok_or(Error::<T, I>::NoIdentity)?;
+			Beneficiaries::<T, I>::remove(&original);
+
+			// Everything keyed by the identity holder moves along. Each map holds at most one
+			// entry per judgement ID, so the largest of them is what the weight is charged for.
+			let mut reserved = reg.total_deposit();
+			let mut moved = [0u32; 6];
+			for (judgement_id, given) in &reg.judgements {
+				JudgedBy::<T, I>::remove(judgement_id, &original);
+				JudgedBy::<T, I>::insert(judgement_id, &sender, ());
+				Self::move_revalidation(&original, &sender, judgement_id, *given);
+			}
+			for (judgement_id, judgement) in JudgementsDoubleMap::<T, I>::drain_prefix(&original) {
+				JudgementsDoubleMap::<T, I>::insert(&sender, &judgement_id, judgement);
+				Self::move_revalidation(&original, &sender, &judgement_id, judgement);
+				JudgedBy::<T, I>::remove(&judgement_id, &original);
+				JudgedBy::<T, I>::insert(&judgement_id, &sender, ());
+				moved[0].saturating_inc();
+			}
+			Self::set_judgement_count(&sender, CounterForJudgements::<T, I>::take(&original));
+			for (judgement_id, note) in JudgementNotes::<T, I>::drain_prefix(&original) {
+				JudgementNotes::<T, I>::insert(&sender, &judgement_id, note);
+				moved[1].saturating_inc();
+			}
+			for (judgement_id, registrar) in RemoteJudgementSources::<T, I>::drain_prefix(&original)
+			{
+				RemoteJudgementSources::<T, I>::insert(&sender, &judgement_id, registrar);
+				moved[2].saturating_inc();
+			}
+			for (judgement_id, fee) in JudgementRequests::<T, I>::drain_prefix(&original) {
+				JudgementRequests::<T, I>::insert(&sender, &judgement_id, fee);
+				reserved = reserved.saturating_add(fee);
+				moved[3].saturating_inc();
+			}
+			for (judgement_id, deposit) in Disputes::<T, I>::drain_prefix(&original) {
+				Disputes::<T, I>::insert(&sender, &judgement_id, deposit);
+				reserved = reserved.saturating_add(deposit);
+				moved[4].saturating_inc();
+			}
+			for (judgement_id, reason) in JudgementReasons::<T, I>::drain_prefix(&original) {
+				JudgementReasons::<T, I>::insert(&sender, &judgement_id, reason);
+				moved[5].saturating_inc();
+			}
+			if T::ShadowWrites::get() {
+				for (judgement_id, judgement) in
+					ShadowJudgementsDoubleMap::<T, I>::drain_prefix(&original)
+				{
+					ShadowJudgementsDoubleMap::<T, I>::insert(&sender, &judgement_id, judgement);
+				}
+				let shadow = ShadowJudgementsInline::<T, I>::take(&original);
+				if !shadow.is_empty() {
+					ShadowJudgementsInline::<T, I>::insert(&sender, shadow);
+				}
+			}
+			let field_judgements = FieldJudgements::<T, I>::take(&original);
+			if !field_judgements.is_empty() {
+				FieldJudgements::<T, I>::insert(&sender, field_judgements);
+			}
+			if let Some(kyc_hash) = KycHashes::<T, I>::take(&original) {
+				KycHashes::<T, I>::insert(&sender, kyc_hash);
+			}
+			if let Some(judgement_id) = KycAttestations::<T, I>::take(&original) {
+				KycAttestations::<T, I>::insert(&sender, judgement_id);
+			}
+			if let Some(judgement_id) = PendingAudits::<T, I>::take(&original) {
+				PendingAudits::<T, I>::insert(&sender, judgement_id);
+			}
+			let score = Scores::<T, I>::take(&original);
+			if score != 0 {
+				Scores::<T, I>::insert(&sender, score);
+			}
+			let mut vouches = Vouches::<T, I>::take(&original);
+			vouches.retain(|voucher| voucher != &sender);
+			if !vouches.is_empty() {
+				Vouches::<T, I>::insert(&sender, vouches);
+			}
+			IdentityExpiries::<T, I>::remove(&original);
+			Self::renew_expiry(&sender);
+			if let Some(since) = UnchangedSince::<T, I>::take(&original) {
+				UnchangedSince::<T, I>::insert(&sender, since);
+			}
+			match RentStates::<T, I>::take(&original) {
+				Some(RentState::PaidUntil(at)) => {
+					RentDue::<T, I>::remove(at, &original);
+					Self::schedule_rent(&sender, at);
+				},
+				Some(delinquent) => RentStates::<T, I>::insert(&sender, delinquent),
+				None => {},
+			}
+
+			let missing = T::DepositBackend::transfer(&original, &sender, reserved)?;
+			// What couldn't be moved is no longer held by `original`, so holding it again leaves
+			// `TotalDeposits` as it was
+			T::DepositBackend::hold(&sender, missing)?;
+			let deposit = Self::decay_deposit(
+				&sender,
+				Self::identity_deposit(&sender, &reg.field_bytes, reg.known_good > 0),
+			);
+			Self::rejig_deposit(&sender, reg.deposit, deposit)?;
+			Self::deposit_repriced(&sender, &mut reg, deposit);
+			DepositDebts::<T, I>::remove(&original);
+			Self::reconcile_rent(&sender);
+			frame_system::Pallet::<T>::inc_consumers(&sender)?;
+			if frame_system::Pallet::<T>::account_exists(&original) {
+				frame_system::Pallet::<T>::dec_consumers(&original);
+			}
+
+			if reg.status == VerificationStatus::Verified {
+				T::OnVerified::on_revoked(&original);
+				T::OnVerified::on_verified(&sender);
+			}
+			Self::unindex_display(&original, &reg.info.display);
+			Self::index_display(&sender, &reg.info.display);
+			IdentityOf::<T, I>::insert(&sender, reg);
+
+			Self::deposit_event(Event::IdentityClaimed { original, beneficiary: sender });
+			let moved = moved.into_iter().max().unwrap_or_default();
+			Ok(Some(T::WeightInfo::claim_identity(moved)).into())
+		}
+
+		/// Vouch for the identity of `target`.
+		///
+		/// Once `T::VouchThreshold` accounts vouched for it, the identity is given a `Reasonable`
+		/// judgement under `T::SystemJudgementId`, unless it already has a judgement there.
+		///
+		/// The dispatch origin for this call must be _Signed_ by an account whose identity has a
+		/// `KnownGood` judgement.
+		///
+		/// - `target`: the account to vouch for. This must be an account with a registered
+		///   identity, other than the caller.
+		///
+		/// Emits `Vouched`, and `JudgementGiven` if the threshold is reached.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::vouch_for())]
+		pub fn vouch_for(origin: OriginFor<T>, target: AccountIdLookupOf<T>) -> DispatchResult {
+			let voucher = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			ensure!(voucher != target, Error::<T, I>::InvalidTarget);
+			ensure!(
+				IdentityOf::<T, I>::get(&voucher).is_some_and(|reg| reg.known_good > 0),
+				Error::<T, I>::NotTrusted
+			);
+			let reg = IdentityOf::<T, I>::get(&target).ok_or(Error::<T, I>::InvalidTarget)?;
+
+			let vouches =
+				Vouches::<T, I>::try_mutate(&target, |vouches| -> Result<_, DispatchError> {
+					ensure!(!vouches.contains(&voucher), Error::<T, I>::AlreadyVouched);
+					vouches.try_push(voucher.clone()).map_err(|_| Error::<T, I>::TooManyVouches)?;
+					Ok(vouches.len() as u32)
+				})?;
+			Self::deposit_event(Event::Vouched { voucher, target: target.clone() });
+
+			let judgement_id = T::SystemJudgementId::get();
+			let judged = reg.judgements.binary_search_by(|x| x.0.cmp(&judgement_id)).is_ok();
+			if vouches >= T::VouchThreshold::get() && !judged {
+				let judgement = Judgement::Reasonable;
+				let (old_status, new_status) =
+					Self::add_judgement_inline(&target, &judgement_id, judgement, None)?;
+				Self::status_changed(&target, old_status, new_status);
+				Self::judgement_given(&target, &judgement_id, judgement, None)?;
+				Self::deposit_event(Event::JudgementGiven { target });
+			}
+			Ok(())
+		}
+
+		/// Set or remove the key the caller signs judgements under `judgement_id` with.
+		///
+		/// Any key rotated out by `rotate_provider_key` is revoked at once, e.g. because it was
+		/// compromised.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the registrar for
+		/// `judgement_id`.
+		///
+		/// - `judgement_id`: the ID the caller gives judgements under.
+		/// - `key`: the account of the signing key, or `None` to stop accepting signed judgements.
+		///
+		/// Emits `ProviderKeySet` if successful.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::set_provider_key())]
+		pub fn set_provider_key(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			key: Option<T::AccountId>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let registrar =
+				Registrars::<T, I>::get(&judgement_id).ok_or(Error::<T, I>::UnknownRegistrar)?;
+			ensure!(registrar.account == sender, Error::<T, I>::NotRegistrar);
+
+			match &key {
+				Some(key) => ProviderKeys::<T, I>::insert(&judgement_id, key),
+				None => ProviderKeys::<T, I>::remove(&judgement_id),
+			}
+			PreviousProviderKeys::<T, I>::remove(&judgement_id);
+			Self::deposit_event(Event::ProviderKeySet { judgement_id, key });
+			Ok(())
+		}
+
+		/// Replace the key the caller signs judgements under `judgement_id` with, keeping the
+		/// current one valid for `T::KeyRotationGracePeriod` blocks.
+		///
+		/// Judgements signed before the rotation can be submitted during the grace period;
+		/// judgements already given are unaffected either way. A key rotated out earlier is
+		/// dropped.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the registrar for
+		/// `judgement_id`, which must have a key set.
+		///
+		/// - `judgement_id`: the ID the caller gives judgements under.
+		/// - `key`: the account of the new signing key.
+		///
+		/// Emits `ProviderKeyRotated` if successful.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::rotate_provider_key())]
+		pub fn rotate_provider_key(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			key: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let registrar =
+				Registrars::<T, I>::get(&judgement_id).ok_or(Error::<T, I>::UnknownRegistrar)?;
+			ensure!(registrar.account == sender, Error::<T, I>::NotRegistrar);
+			let previous =
+				ProviderKeys::<T, I>::get(&judgement_id).ok_or(Error::<T, I>::NoProviderKey)?;
+
+			let previous_valid_until = T::BlockNumberProvider::current_block_number()
+				.saturating_add(T::KeyRotationGracePeriod::get());
+			PreviousProviderKeys::<T, I>::insert(&judgement_id, (previous, previous_valid_until));
+			ProviderKeys::<T, I>::insert(&judgement_id, &key);
+			Self::deposit_event(Event::ProviderKeyRotated {
+				judgement_id,
+				key,
+				previous_valid_until,
+			});
+			Ok(())
+		}
+
+		/// Provide a judgement signed off-chain by the registrar for `judgement_id`, using inline
+		/// storage.
+		///
+		/// The signature is over [`Pallet::judgement_payload`], which includes the registrar's
+		/// current nonce. The nonce is bumped on success, so each signature is accepted once. It
+		/// must be by the registrar's key, or by the key it rotated out during the grace period.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any account.
+		///
+		/// - `judgement_id`: the ID of the registrar which signed the judgement.
+		/// - `target`: the account whose identity the judgement is upon. This must be an account
+		///   with a registered identity.
+		/// - `judgement`: the judgement of the target's identity.
+		/// - `signature`: the registrar's signature of the payload, by its key in `ProviderKeys`.
+		///
+		/// Emits `JudgementGiven` if successful.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::provide_judgement_with_proof(T::MaxJudgements::get()))]
+		pub fn provide_judgement_with_proof(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: AccountIdLookupOf<T>,
+			judgement: Judgement,
+			signature: T::OffchainSignature,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			Self::ensure_verdict(judgement)?;
+			let key =
+				ProviderKeys::<T, I>::get(&judgement_id).ok_or(Error::<T, I>::NoProviderKey)?;
+			let nonce = ProviderNonces::<T, I>::get(&judgement_id);
+			let payload = Self::judgement_payload(&judgement_id, &target, judgement, nonce);
+			let valid = signature.verify(&payload[..], &key) ||
+				PreviousProviderKeys::<T, I>::get(&judgement_id).is_some_and(
+					|(previous, until)| {
+						T::BlockNumberProvider::current_block_number() < until &&
+							signature.verify(&payload[..], &previous)
+					},
+				);
+			ensure!(valid, Error::<T, I>::InvalidSignature);
+			ProviderNonces::<T, I>::insert(&judgement_id, nonce.saturating_add(1));
+
+			let (old_status, new_status) =
+				Self::add_judgement_inline(&target, &judgement_id, judgement, None)?;
+			RemoteJudgementSources::<T, I>::remove(&target, &judgement_id);
+			Self::settle_judgement_request(&target, &judgement_id)?;
+			Self::status_changed(&target, old_status, new_status);
+			Self::judgement_given(&target, &judgement_id, judgement, None)?;
+
+			Self::deposit_event(Event::JudgementGiven { target });
+			Ok(())
+		}
+
+		/// Pause registrations and judgements, e.g. in an emergency.
+		///
+		/// While paused, no identity can be set or claimed and no judgement requested or given.
+		/// Identities can still be cleared, and deposits, fees and disputes settled.
+		///
+		/// The dispatch origin for this call must be `T::PauseOrigin`.
+		///
+		/// Emits `Paused` if successful.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::pause())]
+		pub fn pause(origin: OriginFor<T>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			Paused::<T, I>::put(true);
+			Self::deposit_event(Event::Paused);
+			Ok(())
+		}
+
+		/// Lift a pause by `pause`.
+		///
+		/// The dispatch origin for this call must be `T::PauseOrigin`.
+		///
+		/// Emits `Unpaused` if successful.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::unpause())]
+		pub fn unpause(origin: OriginFor<T>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			ensure!(Paused::<T, I>::get(), Error::<T, I>::NotPaused);
+			Paused::<T, I>::kill();
+			Self::deposit_event(Event::Unpaused);
+			Ok(())
+		}
+
+		/// Set the fields of `target`'s identity information a registrar verified, e.g. the email
+		/// but not the web address, replacing the fields set before.
+		///
+		/// A field drops out of the set as soon as the identity holder changes or clears it.
+		///
+		/// The dispatch origin for this call must be `T::JudgementOrigin`.
+		///
+		/// - `target`: the account whose identity the fields belong to. This must be an account
+		///   with a registered identity.
+		/// - `fields`: the verified fields, none of which may be empty.
+		///
+		/// Emits `VerifiedFieldsSet` if successful.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::set_verified_fields())]
+		pub fn set_verified_fields(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			fields: IdentityFields,
+		) -> DispatchResult {
+			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			ensure!(fields.is_valid(), Error::<T, I>::InvalidFields);
+
+			IdentityOf::<T, I>::try_mutate(&target, |maybe_reg| -> DispatchResult {
+				let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::NoIdentity)?;
+				let populated = reg.info.populated_fields();
+				ensure!(
+					fields.iter().all(|field| populated.contains(field)),
+					Error::<T, I>::EmptyField
+				);
+				reg.verified_fields = fields;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::VerifiedFieldsSet { who: target, fields });
+			Ok(())
+		}
+
+		/// Provide a judgement on a single field of an account's identity, e.g. its email, apart
+		/// from the judgements on the identity as a whole.
+		///
+		/// A sticky field judgement can't be replaced. Editing or clearing a field drops the
+		/// non-sticky judgements on that field only, where any edit drops the non-sticky
+		/// whole-identity judgements.
+		///
+		/// The dispatch origin for this call must be `T::JudgementOrigin`.
+		///
+		/// - `judgement_id`: a unique identifier for this judgement provider.
+		/// - `target`: the account whose identity the judgement is upon. This must be an account
+		///   with a registered identity.
+		/// - `field`: the judged field, which must not be empty.
+		/// - `judgement`: the judgement of the field.
+		///
+		/// Emits `FieldJudgementGiven` if successful.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::provide_field_judgement(T::MaxJudgements::get()))]
+		pub fn provide_field_judgement(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: AccountIdLookupOf<T>,
+			field: IdentityField,
+			judgement: Judgement,
+		) -> DispatchResult {
+			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+			Self::ensure_verdict(judgement)?;
+			let reg = IdentityOf::<T, I>::get(&target).ok_or(Error::<T, I>::InvalidTarget)?;
+			ensure!(reg.info.populated_fields().contains(field), Error::<T, I>::EmptyField);
+
+			FieldJudgements::<T, I>::try_mutate(&target, |judgements| -> DispatchResult {
+				let item = (judgement_id.clone(), field, judgement);
+				match judgements.binary_search_by(|x| (&x.0, x.1).cmp(&(&judgement_id, field))) {
+					Ok(position) => {
+						ensure!(
+							!judgements[position].2.is_sticky(),
+							Error::<T, I>::StickyJudgement
+						);
+						judgements[position] = item;
+					},
+					Err(position) => judgements
+						.try_insert(position, item)
+						.map_err(|_| Error::<T, I>::TooManyJudgements)?,
+				}
+				Ok(())
+			})?;
+
+			Self::count_judgement_in_block(&judgement_id)?;
+			Self::record_provider_stats(&judgement_id, judgement);
+
+			Self::deposit_event(Event::FieldJudgementGiven { target, field });
+			Ok(())
+		}
+
+		/// Mirror the identity of `who` on the foreign chain, proven by a read proof of its
+		/// `IdentityOf` entry there against `T::ForeignStateRoot`, replacing any older mirror.
+		///
+		/// A proof that `who` has no identity on the foreign chain removes the mirror instead.
+		/// Anyone can submit a proof: it can only show what is on the foreign chain, where the
+		/// identity's deposit is held. See [`crate::remote`] for what the foreign chain must run.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `who`: the account whose identity to mirror.
+		/// - `proof`: the read proof, at most `T::MaxRemoteProofSize` bytes encoded.
+		///
+		/// Emits `RemoteIdentityMirrored` or `RemoteIdentityRemoved` if successful.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::mirror_remote_identity(
+			(proof.encoded_size() as u32).min(T::MaxRemoteProofSize::get())
+		))]
+		pub fn mirror_remote_identity(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+			proof: StorageProof,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				proof.encoded_size() as u32 <= T::MaxRemoteProofSize::get(),
+				Error::<T, I>::ProofTooLarge
+			);
+
+			match Self::verify_remote_identity(proof, &who)? {
+				Some(identity) => {
+					let state_root = identity.state_root;
+					RemoteIdentities::<T, I>::insert(&who, identity);
+					Self::deposit_event(Event::RemoteIdentityMirrored { who, state_root });
+				},
+				None => {
+					ensure!(
+						RemoteIdentities::<T, I>::contains_key(&who),
+						Error::<T, I>::NoRemoteIdentity
+					);
+					RemoteIdentities::<T, I>::remove(&who);
+					Self::deposit_event(Event::RemoteIdentityRemoved { who });
+				},
+			}
+			Ok(())
+		}
+
+		/// Have a registrar drawn at random re-verify an identity.
+		///
+		/// The registrar is drawn with `T::Randomness` from those the identity holder listed with
+		/// `set_preferred_registrars`, or if it listed none, from the first
+		/// `T::MaxAuditCandidates` registrars. The audit stays pending until it gives a judgement
+		/// on the identity, which it is paid for as usual only if the judgement was also
+		/// requested.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		///
+		/// - `target`: the account whose identity to audit.
+		///
+		/// Emits `AuditRequested` if successful.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::request_audit(T::MaxAuditCandidates::get()))]
+		pub fn request_audit(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			let reg = IdentityOf::<T, I>::get(&target).ok_or(Error::<T, I>::InvalidTarget)?;
+			ensure!(!PendingAudits::<T, I>::contains_key(&target), Error::<T, I>::AuditPending);
+
+			let candidates: Vec<T::JudgementId> = if reg.preferred_registrars.is_empty() {
+				Registrars::<T, I>::iter_keys()
+					.take(T::MaxAuditCandidates::get() as usize)
+					.collect()
+			} else {
+				reg.preferred_registrars.into_inner()
+			};
+			ensure!(!candidates.is_empty(), Error::<T, I>::NoRegistrars);
+			let (random, _) = T::Randomness::random(&(b"identity/audit", &target).encode());
+			let seed = u32::decode(&mut TrailingZeroInput::new(random.as_ref()))
+				.expect("input is padded with zeroes; qed");
+			let judgement_id = candidates[seed as usize % candidates.len()].clone();
+
+			PendingAudits::<T, I>::insert(&target, &judgement_id);
+			Self::deposit_event(Event::AuditRequested { target, judgement_id });
+			Ok(Some(T::WeightInfo::request_audit(candidates.len() as u32)).into())
+		}
+
+		/// Exempt an account from the identity deposit, or revoke its exemption.
+		///
+		/// An identity the account already holds is re-priced straight away: an exemption
+		/// releases its deposit, and a revocation reserves it again, failing if the account
+		/// can't afford it.
+		///
+		/// The dispatch origin for this call must be `T::NoDepositOrigin`.
+		///
+		/// - `who`: the account to exempt, or whose exemption to revoke.
+		/// - `exempt`: whether `who` is exempt from now on.
+		///
+		/// Emits `DepositExemptionSet` if successful.
+		#[pallet::call_index(32)]
+		#[pallet::weight(T::WeightInfo::set_deposit_exemption())]
+		pub fn set_deposit_exemption(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+			exempt: bool,
+		) -> DispatchResult {
+			T::NoDepositOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			if exempt {
+				ExemptAccounts::<T, I>::insert(&who, ());
+			} else {
+				ExemptAccounts::<T, I>::remove(&who);
+			}
+			IdentityOf::<T, I>::try_mutate(&who, |maybe_reg| -> DispatchResult {
+				if let Some(reg) = maybe_reg {
+					let new_deposit = Self::decay_deposit(
+						&who,
+						Self::identity_deposit(&who, &reg.field_bytes, reg.known_good > 0),
+					);
+					Self::rejig_deposit(&who, reg.deposit, new_deposit)?;
+					Self::deposit_repriced(&who, reg, new_deposit);
+				}
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::DepositExemptionSet { who, exempt });
+			Ok(())
+		}
+
+		/// Remove the tombstone of an identity cleared at least `T::TombstoneLifetime` blocks ago.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any account.
+		///
+		/// - `who`: the account whose tombstone to reap.
+		///
+		/// Emits `TombstoneReaped` if successful.
+		#[pallet::call_index(33)]
+		#[pallet::weight(T::WeightInfo::reap_tombstone())]
+		pub fn reap_tombstone(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let tombstone = Tombstones::<T, I>::get(&who).ok_or(Error::<T, I>::NoTombstone)?;
+			ensure!(
+				T::BlockNumberProvider::current_block_number() >=
+					tombstone.cleared_at.saturating_add(T::TombstoneLifetime::get()),
+				Error::<T, I>::TombstoneNotExpired
+			);
+
+			Tombstones::<T, I>::remove(&who);
+			Self::deposit_event(Event::TombstoneReaped { who });
+			Ok(())
+		}
+
+		/// Check the judgements of an identity for consistency, and optionally repair them.
+		///
+		/// The double map judgement counter is compared against the actual number of entries, and
+		/// the inline judgements are checked to be ordered by unique IDs, as the binary searches
+		/// over them assume. A repair sets the counter to the number of entries and sorts the
+		/// inline judgements, keeping the first of those sharing an ID, then recomputes the score
+		/// and verification status from what is left.
+		///
+		/// The dispatch origin for this call must be _Signed_ to audit, or `T::ForceOrigin` to also
+		/// repair.
+		///
+		/// - `target`: the account whose identity to audit.
+		/// - `repair`: whether to correct any inconsistency found.
+		///
+		/// Emits `AuditResult` if successful.
+		#[pallet::call_index(34)]
+		#[pallet::weight(T::WeightInfo::audit_identity(T::MaxJudgements::get()))]
+		pub fn audit_identity(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			repair: bool,
+		) -> DispatchResultWithPostInfo {
+			if let Err(origin) = T::ForceOrigin::try_origin(origin) {
+				ensure_signed(origin)?;
+				ensure!(!repair, DispatchError::BadOrigin);
+			}
+			let target = T::Lookup::lookup(target)?;
+
+			let mut reg = IdentityOf::<T, I>::get(&target).ok_or(Error::<T, I>::NoIdentity)?;
+			let double_map: Vec<_> = JudgementsDoubleMap::<T, I>::iter_prefix(&target).collect();
+			let recorded_double_map = CounterForJudgements::<T, I>::get(&target);
+			let actual_double_map = double_map.len() as u32;
+			let inline_sorted = reg.judgements.windows(2).all(|pair| pair[0].0 < pair[1].0);
+			let inline = reg.judgements.len() as u32;
+
+			let repaired = repair && (recorded_double_map != actual_double_map || !inline_sorted);
+			if repaired {
+				Self::set_judgement_count(&target, actual_double_map);
+				if !inline_sorted {
+					let mut judgements = reg.judgements.to_vec();
+					judgements.sort_by(|a, b| a.0.cmp(&b.0));
+					judgements.dedup_by(|later, first| later.0 == first.0);
+					reg.judgements =
+						judgements.try_into().expect("Deduplicating doesn't add judgements; qed");
+					if T::ShadowWrites::get() {
+						Self::reshadow_inline_judgements(&target, &reg);
+					}
+					let old_status = reg.status;
+					Self::recount_judgements(&target, &mut reg, &double_map);
+					Self::status_changed(&target, old_status, reg.status);
+				}
+				IdentityOf::<T, I>::insert(&target, reg);
+			}
+
+			Self::deposit_event(Event::AuditResult {
+				who: target,
+				recorded_double_map,
+				actual_double_map,
+				inline_sorted,
+				repaired,
+			});
+			Ok(Some(T::WeightInfo::audit_identity(inline.max(actual_double_map))).into())
+		}
+
+		/// Create `count` identities with deterministic information in one dispatch, so that
+		/// external benchmarking tools and load tests can fill state without submitting a
+		/// transaction per identity. Only available with the `runtime-benchmarks` feature.
+		///
+		/// The identity of index `i` belongs to `populated_account(i)` and displays
+		/// `identity #i`, truncated to `T::MaxDisplayLength`. Each account is minted what its
+		/// deposit and the registration fee take. An account which already has an identity gets
+		/// it updated.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		///
+		/// - `start`: the index of the first identity to create.
+		/// - `count`: the number of identities to create.
+		///
+		/// Emits `IdentitySet` for each identity if successful.
+		#[cfg(feature = "runtime-benchmarks")]
+		#[pallet::call_index(35)]
+		#[pallet::weight(T::WeightInfo::populate_identities(*count))]
+		pub fn populate_identities(origin: OriginFor<T>, start: u32, count: u32) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			for index in start..start.saturating_add(count) {
+				let who = Self::populated_account(index);
+				let mut display = alloc::format!("identity #{index}").into_bytes();
+				display.truncate(T::MaxDisplayLength::get() as usize);
+				let info = IdentityInfo {
+					display: display.try_into().expect("Truncated to the bound; qed"),
+					..Default::default()
+				};
+
+				let deposit = Self::identity_deposit(&who, &info.field_sizes(), false);
+				let _ = T::Currency::deposit_creating(
+					&who,
+					T::Currency::minimum_balance()
+						.saturating_add(deposit)
+						.saturating_add(T::RegistrationFee::get()),
+				);
+				Self::do_set_identity(who, info)?;
+			}
+			Ok(())
+		}
+
+		/// Release what has decayed of the caller's identity deposit, once the identity has
+		/// stayed unchanged for `T::DepositDecayPeriod` blocks. The deposit keeps decaying, down to
+		/// `T::DepositDecayFloor` of it, and can be poked again later.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// Emits `DepositPoked` if the deposit changed.
+		#[pallet::call_index(36)]
+		#[pallet::weight(T::WeightInfo::poke_deposit())]
+		pub fn poke_deposit(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			IdentityOf::<T, I>::try_mutate(&sender, |maybe_reg| -> DispatchResult {
+				let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::NoIdentity)?;
+				let deposit = Self::decay_deposit(
+					&sender,
+					Self::identity_deposit(&sender, &reg.field_bytes, reg.known_good > 0),
+				);
+				if deposit == reg.deposit {
+					return Ok(());
+				}
+				Self::rejig_deposit(&sender, reg.deposit, deposit)?;
+				Self::deposit_event(Event::DepositPoked {
+					who: sender.clone(),
+					old_deposit: reg.deposit,
+					new_deposit: deposit,
+				});
+				Self::deposit_repriced(&sender, reg, deposit);
+				Ok(())
+			})
+		}
+
+		/// Renew the caller's identity for `T::IdentityLifetime` blocks, also if it has expired
+		/// but not yet been reaped.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// Emits `IdentityRenewed` if successful.
+		#[pallet::call_index(37)]
+		#[pallet::weight(T::WeightInfo::renew_identity())]
+		pub fn renew_identity(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(IdentityOf::<T, I>::contains_key(&sender), Error::<T, I>::NoIdentity);
+
+			let expires_at = Self::renew_expiry(&sender);
+			Self::note_activity(&sender);
+			Self::deposit_event(Event::IdentityRenewed { who: sender, expires_at });
+			Ok(())
+		}
+
+		/// Mark the identity of `who` expired, once it has gone unrenewed for
+		/// `T::IdentityLifetime` blocks. It can be reaped once `T::ExpiryGracePeriod` has passed
+		/// since, unless renewed before.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any account.
+		///
+		/// - `who`: the account whose identity to mark expired.
+		///
+		/// Emits `IdentityExpired` if successful.
+		#[pallet::call_index(38)]
+		#[pallet::weight(T::WeightInfo::expire_identity())]
+		pub fn expire_identity(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let now = T::BlockNumberProvider::current_block_number();
+			IdentityExpiries::<T, I>::try_mutate(&who, |expiry| -> DispatchResult {
+				match expiry {
+					Some(IdentityExpiry::Expires(at)) if now >= *at => {
+						*expiry = Some(IdentityExpiry::Expired(now));
+						Ok(())
+					},
+					_ => Err(Error::<T, I>::IdentityNotExpired.into()),
+				}
+			})?;
+
+			Self::deposit_event(Event::IdentityExpired { who });
+			Ok(())
+		}
+
+		/// Clear the identity of `who` once it was marked expired and `T::ExpiryGracePeriod` has
+		/// passed since, releasing everything it held to `who` as `clear_identity` does.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any account.
+		///
+		/// - `who`: the account whose identity to reap.
+		///
+		/// Emits `IdentityReaped` if successful.
+		#[pallet::call_index(39)]
+		#[pallet::weight(
+			T::WeightInfo::expire_identity().saturating_add(Pallet::<T, I>::clear_identity_weight())
+		)]
+		pub fn reap_identity(origin: OriginFor<T>, who: AccountIdLookupOf<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let Some(IdentityExpiry::Expired(expired_at)) = IdentityExpiries::<T, I>::get(&who)
+			else {
+				return Err(Error::<T, I>::GracePeriodNotOver.into());
+			};
+			ensure!(
+				T::BlockNumberProvider::current_block_number() >=
+					expired_at.saturating_add(T::ExpiryGracePeriod::get()),
+				Error::<T, I>::GracePeriodNotOver
+			);
+
+			let deposit = Self::do_clear_identity(&who)?;
+			Self::deposit_event(Event::IdentityReaped { who, deposit });
+			Ok(())
+		}
+
+		/// Replace some fields of the caller's identity, leaving the others as they are.
+		///
+		/// Behaves like `set_identity` with the other fields kept, but only the replaced fields
+		/// are compared and measured, so a small edit costs less than replacing the whole identity.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// - `fields`: the fields to replace.
+		/// - `info`: the new contents of `fields`. Its other fields are ignored, and are best left
+		///   empty to keep the call small.
+		///
+		/// Emits `IdentitySet` if successful.
+		#[pallet::call_index(40)]
+		#[pallet::weight({
+			// Worst case: the replaced fields at max length
+			let max = |field, length: u32| if fields.contains(field) { length } else { 0 };
+			T::WeightInfo::update_identity_fields(
+				max(IdentityField::Display, T::MaxDisplayLength::get()),
+				max(IdentityField::Legal, T::MaxLegalLength::get()),
+				max(IdentityField::Web, T::MaxWebLength::get()),
+				max(IdentityField::Email, T::MaxEmailLength::get()),
+				max(IdentityField::Avatar, T::MaxCidLength::get()),
+				T::MaxJudgements::get(),
+			)
+		})]
+		pub fn update_identity_fields(
+			origin: OriginFor<T>,
+			fields: IdentityFields,
+			info: IdentityInfoOf<T, I>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			ensure!(fields.is_valid(), Error::<T, I>::InvalidFields);
+			Self::count_identity_set(&sender)?;
+
+			let ([d, l, w, e, a], judgements) =
+				Self::do_update_identity_fields(sender, fields, info)?;
+			Ok(Some(T::WeightInfo::update_identity_fields(d, l, w, e, a, judgements)).into())
+		}
+
+		/// Register the caller as the judgement provider under `judgement_id`, without
+		/// `T::ForceOrigin`, by holding a bond for [`HoldReason::ProviderBond`].
+		///
+		/// The caller then gives judgements like any registrar, e.g. by setting a key with
+		/// `set_provider_key`. `T::OverturnSlash` of the bond is slashed whenever one of its
+		/// judgements is overturned by `resolve_dispute`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `judgement_id`: the judgement ID to give judgements under. No registrar may be set for
+		///   it yet.
+		/// - `fee`: the fee for a judgement.
+		/// - `bond`: the bond to hold, at least `T::MinProviderBond`.
+		///
+		/// Emits `ProviderRegistered` if successful.
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::register_provider())]
+		pub fn register_provider(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			fee: BalanceOf<T, I>,
+			bond: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+			ensure!(
+				!Registrars::<T, I>::contains_key(&judgement_id),
+				Error::<T, I>::RegistrarExists
+			);
+			ensure!(bond >= T::MinProviderBond::get(), Error::<T, I>::BondTooLow);
+
+			ProviderBondHolds::<T, I>::hold(&sender, bond)?;
+			ProviderBonds::<T, I>::insert(&judgement_id, bond);
+			Registrars::<T, I>::insert(
+				&judgement_id,
+				RegistrarInfo { account: sender.clone(), fee, bond: Zero::zero() },
+			);
+			Self::deposit_event(Event::ProviderRegistered {
+				judgement_id,
+				account: sender,
+				fee,
+				bond,
+			});
+			Ok(())
+		}
+
+		/// Add `amount` to the caller's provider bond, e.g. to restore it after a slash.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the provider registered with
+		/// `register_provider` for `judgement_id`.
+		///
+		/// Emits `ProviderBondChanged` if successful.
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::top_up_provider_bond())]
+		pub fn top_up_provider_bond(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			amount: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let bond = Self::provider_bond(&sender, &judgement_id)?;
+
+			ProviderBondHolds::<T, I>::hold(&sender, amount)?;
+			let bond = bond.saturating_add(amount);
+			ProviderBonds::<T, I>::insert(&judgement_id, bond);
+			Self::deposit_event(Event::ProviderBondChanged { judgement_id, bond });
+			Ok(())
+		}
+
+		/// Release `amount` of the caller's provider bond, keeping at least `T::MinProviderBond`
+		/// held.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the provider registered with
+		/// `register_provider` for `judgement_id`.
+		///
+		/// Emits `ProviderBondChanged` if successful.
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::withdraw_provider_bond())]
+		pub fn withdraw_provider_bond(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			amount: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let bond = Self::provider_bond(&sender, &judgement_id)?;
+			ensure!(amount <= bond, Error::<T, I>::BondTooLow);
+			let bond = bond.saturating_sub(amount);
+			ensure!(bond >= T::MinProviderBond::get(), Error::<T, I>::BondTooLow);
+
+			let err_amount = ProviderBondHolds::<T, I>::release(&sender, amount);
+			debug_assert!(err_amount.is_zero());
+			ProviderBonds::<T, I>::insert(&judgement_id, bond);
+			Self::deposit_event(Event::ProviderBondChanged { judgement_id, bond });
+			Ok(())
+		}
+
+		/// Set the hash of the KYC document behind the caller's identity, or remove it with
+		/// `None`. The document itself stays off-chain.
+		///
+		/// A changed hash is no longer attested.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// Emits `KycHashSet` if successful, and `KycAttestationInvalidated` if the previous hash
+		/// was attested.
+		#[pallet::call_index(44)]
+		#[pallet::weight(T::WeightInfo::set_kyc_hash())]
+		pub fn set_kyc_hash(origin: OriginFor<T>, kyc_hash: Option<T::Hash>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			ensure!(IdentityOf::<T, I>::contains_key(&sender), Error::<T, I>::NoIdentity);
+
+			if KycHashes::<T, I>::get(&sender) != kyc_hash {
+				Self::invalidate_kyc_attestation(&sender);
+				KycHashes::<T, I>::set(&sender, kyc_hash);
+			}
+			Self::note_activity(&sender);
+			Self::deposit_event(Event::KycHashSet { who: sender, kyc_hash });
+			Ok(())
+		}
+
+		/// Attest that the KYC document behind `target`'s identity, checked off-chain by the
+		/// registrar under `judgement_id`, matches its KYC hash.
+		///
+		/// The attestation holds until `target` changes its identity or its KYC hash.
+		///
+		/// The dispatch origin for this call must be `T::JudgementOrigin`.
+		///
+		/// - `kyc_hash`: the hash of the document checked, which must still be `target`'s.
+		///
+		/// Emits `KycAttested` if successful.
+		#[pallet::call_index(45)]
+		#[pallet::weight(T::WeightInfo::attest_kyc())]
+		pub fn attest_kyc(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: AccountIdLookupOf<T>,
+			kyc_hash: T::Hash,
+		) -> DispatchResult {
+			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+			ensure!(IdentityOf::<T, I>::contains_key(&target), Error::<T, I>::InvalidTarget);
+			let current = KycHashes::<T, I>::get(&target).ok_or(Error::<T, I>::NoKycHash)?;
+			ensure!(current == kyc_hash, Error::<T, I>::KycHashChanged);
+
+			KycAttestations::<T, I>::insert(&target, &judgement_id);
+			Self::deposit_event(Event::KycAttested { who: target, judgement_id });
+			Ok(())
+		}
+
+		/// Blank `fields` of `target`'s identity, e.g. to honour a request to remove a legal
+		/// name, refunding the deposit for their bytes.
+		///
+		/// The registration is kept with its whole-identity judgements, as `clear_field` keeps
+		/// them. The fields are no longer verified and lose their non-sticky field judgements.
+		///
+		/// The dispatch origin for this call must be `T::ForceOrigin`.
+		///
+		/// - `target`: the account whose identity to redact.
+		/// - `fields`: the fields to blank. Those already empty are left as they are.
+		///
+		/// Emits `FieldsRedacted` if successful.
+		#[pallet::call_index(46)]
+		#[pallet::weight(T::WeightInfo::redact_fields())]
+		pub fn redact_fields(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			fields: IdentityFields,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			ensure!(fields.is_valid(), Error::<T, I>::InvalidFields);
+
+			let refunded = Self::clear_fields(&target, fields)?;
+			Self::deposit_event(Event::FieldsRedacted { who: target, fields, refunded });
+			Ok(())
+		}
+
+		/// Provide a negative judgement for an account's identity with the reason for it, using
+		/// inline storage like `provide_judgement_inline`.
+		///
+		/// The dispatch origin for this call must be `T::JudgementOrigin`.
+		///
+		/// - `judgement_id`: a unique identifier for this judgement provider.
+		/// - `target`: the account whose identity the judgement is upon. This must be an account
+		///   with a registered identity.
+		/// - `judgement`: the judgement of the target's identity, `Erroneous` or `LowQuality`.
+		/// - `reason`: what the target has to fix. It is kept in `JudgementReasons` for as long as
+		///   the judgement.
+		/// - `note`: an optional note explaining the verdict, with the same semantics as in
+		///   `provide_judgement_inline`.
+		///
+		/// Emits `JudgementGiven` and `JudgementReasonGiven` if successful.
+		#[pallet::call_index(47)]
+		#[pallet::weight(T::WeightInfo::provide_judgement_with_reason(
+			T::MaxJudgements::get(),
+			T::MaxNoteLength::get()
+		))]
+		pub fn provide_judgement_with_reason(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: AccountIdLookupOf<T>,
+			judgement: Judgement,
+			reason: ReasonCode,
+			note: Option<JudgementNote<T, I>>,
+		) -> DispatchResult {
+			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+			ensure!(judgement.is_negative(), Error::<T, I>::ReasonNotApplicable);
+
+			let (old_status, new_status) =
+				Self::add_judgement_inline(&target, &judgement_id, judgement, note)?;
+			RemoteJudgementSources::<T, I>::remove(&target, &judgement_id);
+			Self::settle_judgement_request(&target, &judgement_id)?;
+			Self::status_changed(&target, old_status, new_status);
+			Self::judgement_given(&target, &judgement_id, judgement, Some(reason))?;
+
+			Self::deposit_event(Event::JudgementGiven { target: target.clone() });
+			Self::deposit_event(Event::JudgementReasonGiven { target, judgement_id, reason });
+			Ok(())
+		}
+
+		/// Reconfirm the `KnownGood` judgement on an account's identity, so that it counts towards
+		/// verification for another `T::RevalidationPeriod` blocks before it expires. An expired
+		/// judgement was downgraded, and has to be given again instead.
+		///
+		/// The dispatch origin for this call must be `T::JudgementOrigin`.
+		///
+		/// - `judgement_id`: the judgement provider which gave the judgement.
+		/// - `target`: the account whose identity holds it, in either storage pattern.
+		///
+		/// Emits `JudgementReconfirmed` if successful.
+		#[pallet::call_index(48)]
+		#[pallet::weight(T::WeightInfo::reconfirm_judgement(T::MaxJudgements::get()))]
+		pub fn reconfirm_judgement(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			let target = T::Lookup::lookup(target)?;
+			Self::ensure_local_judgement_id(&judgement_id)?;
+
+			IdentityOf::<T, I>::try_mutate(&target, |maybe_reg| -> DispatchResult {
+				let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::NoIdentity)?;
+				let given_at = Self::known_good_given_at(&target, reg, &judgement_id)
+					.ok_or(Error::<T, I>::NotKnownGood)?;
+				Self::redate_known_good(&target, reg, &judgement_id);
+				RevalidationDue::<T, I>::remove(
+					given_at.saturating_add(T::RevalidationPeriod::get()),
+					(&target, &judgement_id),
+				);
+				Ok(())
+			})?;
+			Self::schedule_revalidation(&target, &judgement_id);
+
+			Self::deposit_event(Event::JudgementReconfirmed { who: target, judgement_id });
+			Ok(())
+		}
+
+		/// List the registrars the caller prefers to be judged by, most preferred first, replacing
+		/// the previous list. An empty list clears it.
+		///
+		/// UIs offer the preferred registrars first, and `request_audit` draws the auditor from
+		/// them. The list is bounded by `T::MaxPreferredRegistrars` and isn't charged a deposit
+		/// for.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
+		/// identity.
+		///
+		/// - `registrars`: the judgement IDs of the registrars, each currently a registrar and
+		///   listed once.
+		///
+		/// Emits `PreferredRegistrarsSet` if successful.
+		#[pallet::call_index(49)]
+		#[pallet::weight(T::WeightInfo::set_preferred_registrars(registrars.len() as u32))]
+		pub fn set_preferred_registrars(
+			origin: OriginFor<T>,
+			registrars: BoundedVec<T::JudgementId, T::MaxPreferredRegistrars>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_not_paused()?;
+			for (position, judgement_id) in registrars.iter().enumerate() {
+				ensure!(
+					!registrars[..position].contains(judgement_id),
+					Error::<T, I>::DuplicatePreferredRegistrar
+				);
+				ensure!(
+					Registrars::<T, I>::contains_key(judgement_id),
+					Error::<T, I>::UnknownRegistrar
+				);
+			}
+
+			IdentityOf::<T, I>::try_mutate(&sender, |maybe_reg| -> DispatchResult {
+				let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::NoIdentity)?;
+				reg.preferred_registrars = registrars;
+				Ok(())
+			})?;
+			Self::note_activity(&sender);
+			Self::deposit_event(Event::PreferredRegistrarsSet { who: sender });
+			Ok(())
+		}
+
+		/// Pay the rent the caller's identity owes by now, for every period up to the current
+		/// one. A delinquent identity is no longer delinquent once paid up.
+		///
+		/// Rent is otherwise collected when it falls due, or when the identity is set again.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender's identity must have
+		/// rent due.
+		///
+		/// Emits `RentPaid` if successful.
+		#[pallet::call_index(50)]
+		#[pallet::weight(T::WeightInfo::pay_rent())]
+		pub fn pay_rent(origin: OriginFor<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			// Identities yet to start paying rent start when their deposit is reconciled
+			ensure!(RentStates::<T, I>::contains_key(&sender), Error::<T, I>::NoRentDue);
+
+			ensure!(Self::pay_due_rent(&sender)?, Error::<T, I>::NoRentDue);
+			Self::note_activity(&sender);
+			Ok(())
+		}
+
+		/// Clear the identity of `who` once it became delinquent and `T::RentGracePeriod` has
+		/// passed since, releasing everything it held to `who` as `clear_identity` does. The rent
+		/// it owes is never collected.
+		///
+		/// The dispatch origin for this call must be _Signed_, by any account.
+		///
+		/// - `who`: the account whose identity to reap.
+		///
+		/// Emits `IdentityReaped` if successful.
+		#[pallet::call_index(51)]
+		#[pallet::weight(
+			T::WeightInfo::expire_identity().saturating_add(Pallet::<T, I>::clear_identity_weight())
+		)]
+		pub fn reap_delinquent_identity(
+			origin: OriginFor<T>,
+			who: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			let Some(RentState::Delinquent(due)) = RentStates::<T, I>::get(&who) else {
+				return Err(Error::<T, I>::GracePeriodNotOver.into());
+			};
+			ensure!(
+				T::BlockNumberProvider::current_block_number() >=
+					due.saturating_add(T::RentGracePeriod::get()),
+				Error::<T, I>::GracePeriodNotOver
+			);
+
+			let deposit = Self::do_clear_identity(&who)?;
+			Self::deposit_event(Event::IdentityReaped { who, deposit });
+			Ok(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Fail with `ReservedJudgementId` unless judgements under `judgement_id` may be given on
+		/// this chain: it is neither `T::SystemJudgementId` nor in `T::BridgedJudgementIds`.
+		fn ensure_local_judgement_id(judgement_id: &T::JudgementId) -> DispatchResult {
+			ensure!(
+				*judgement_id != T::SystemJudgementId::get() &&
+					!T::BridgedJudgementIds::get().contains(judgement_id),
+				Error::<T, I>::ReservedJudgementId
+			);
+			Ok(())
+		}
+
+		/// The bond of the provider `who` registered under `judgement_id` with `register_provider`.
+		/// Fails unless `who` is the registrar for `judgement_id` and registered itself.
+		fn provider_bond(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+		) -> Result<BalanceOf<T, I>, DispatchError> {
+			let registrar =
+				Registrars::<T, I>::get(judgement_id).ok_or(Error::<T, I>::UnknownRegistrar)?;
+			ensure!(registrar.account == *who, Error::<T, I>::NotRegistrar);
+			ProviderBonds::<T, I>::get(judgement_id).ok_or(Error::<T, I>::NotSelfRegistered.into())
+		}
+
+		/// Fail with `InvalidJudgementTransition` unless `judgement` is a verdict: judgement IDs
+		/// only become `Requested` through `request_judgement`.
+		fn ensure_verdict(judgement: Judgement) -> DispatchResult {
+			ensure!(judgement.is_verdict(), Error::<T, I>::InvalidJudgementTransition);
+			Ok(())
+		}
+
+		/// The judgement under `judgement_id` on `who`'s identity `reg`: its verdict, inline
+		/// before double map, else `Requested` while a request is pending.
+		fn judgement_state(
+			who: &T::AccountId,
+			reg: &Registration<T, I>,
+			judgement_id: &T::JudgementId,
+		) -> Option<Judgement> {
+			reg.judgements
+				.binary_search_by(|x| x.0.cmp(judgement_id))
+				.ok()
+				.map(|position| reg.judgements[position].1)
+				.or_else(|| JudgementsDoubleMap::<T, I>::get(who, judgement_id))
+				.map(|(judgement, _)| judgement)
+				.or_else(|| {
+					JudgementRequests::<T, I>::contains_key(who, judgement_id)
+						.then_some(Judgement::Requested)
+				})
+		}
+
+		/// The account `populate_identities` creates the identity of `index` for.
+		#[cfg(feature = "runtime-benchmarks")]
+		pub fn populated_account(index: u32) -> T::AccountId {
+			frame_benchmarking::account("populated", index, 0)
+		}
+
+		/// Fail with `Paused` while registrations and judgements are paused.
+		fn ensure_not_paused() -> DispatchResult {
+			ensure!(!Paused::<T, I>::get(), Error::<T, I>::Paused);
+			Ok(())
+		}
+
+		/// The payload a registrar signs to give `judgement` on `target`'s identity under
+		/// `judgement_id` through `provide_judgement_with_proof`. It is bound to this chain by
+		/// its genesis hash and to a single use by `nonce`.
+		pub fn judgement_payload(
+			judgement_id: &T::JudgementId,
+			target: &T::AccountId,
+			judgement: Judgement,
+			nonce: u64,
+		) -> Vec<u8> {
+			let genesis_hash = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+			(b"identity/judgement", genesis_hash, judgement_id, target, judgement, nonce).encode()
+		}
+
+		/// What `call` is for. Every call has exactly one category.
+		pub fn categorize(call: &Call<T, I>) -> CallCategory {
+			match call {
+				Call::set_identity { .. } |
+				Call::set_identity_legacy { .. } |
+				Call::set_identity_with_judgement_request { .. } |
+				Call::update_identity_fields { .. } |
+				Call::clear_identity { .. } |
+				Call::clear_judgements { .. } |
+				Call::clear_field { .. } |
+				Call::set_kyc_hash { .. } |
+				Call::set_preferred_registrars { .. } |
+				Call::consolidate_judgements { .. } |
+				Call::request_judgement { .. } |
+				Call::dispute_judgement { .. } |
+				Call::withdraw_deposit { .. } |
+				Call::poke_deposit { .. } |
+				Call::set_beneficiary { .. } |
+				Call::claim_identity { .. } |
+				Call::mirror_remote_identity { .. } |
+				Call::reap_tombstone { .. } |
+				Call::renew_identity { .. } |
+				Call::expire_identity { .. } |
+				Call::reap_identity { .. } |
+				Call::pay_rent { .. } |
+				Call::reap_delinquent_identity { .. } => CallCategory::IdentityManagement,
+				Call::provide_judgement_inline { .. } |
+				Call::provide_judgement_double_map { .. } |
+				Call::provide_judgement_remote { .. } |
+				Call::provide_judgements_inline { .. } |
+				Call::provide_judgement_with_proof { .. } |
+				Call::provide_judgement_with_reason { .. } |
+				Call::reconfirm_judgement { .. } |
+				Call::provide_field_judgement { .. } |
+				Call::set_verified_fields { .. } |
+				Call::attest_kyc { .. } |
+				Call::vouch_for { .. } |
+				Call::set_provider_key { .. } |
+				Call::rotate_provider_key { .. } |
+				Call::claim_fees { .. } |
+				Call::register_provider { .. } |
+				Call::top_up_provider_bond { .. } |
+				Call::withdraw_provider_bond { .. } => CallCategory::Judgement,
+				Call::set_judgement_limit { .. } |
+				Call::reconcile_deposits { .. } |
+				Call::force_recount_judgements { .. } |
+				Call::set_registrar { .. } |
+				Call::redact_fields { .. } |
+				Call::resolve_dispute { .. } |
+				Call::pause { .. } |
+				Call::unpause { .. } |
+				Call::request_audit { .. } |
+				Call::audit_identity { .. } |
+				Call::set_deposit_exemption { .. } => CallCategory::Admin,
+				#[cfg(feature = "runtime-benchmarks")]
+				Call::populate_identities { .. } => CallCategory::Admin,
+				Call::__Ignore(..) => unreachable!("Never constructed; qed"),
+			}
+		}
+
+		/// Get the identity information for an account.
+		pub fn identity_of(who: &T::AccountId) -> Option<Registration<T, I>> {
+			IdentityOf::<T, I>::get(who)
+		}
+
+		/// Whether the account has an identity with `KnownGood` judgements from at least
+		/// `T::VerificationThreshold` providers.
+		pub fn is_verified(who: &T::AccountId) -> bool {
+			Self::verification_status(who) == VerificationStatus::Verified
+		}
+
+		/// The reputation score of the account's identity, zero if it has none.
+		pub fn score_of(who: &T::AccountId) -> i32 {
+			Scores::<T, I>::get(who)
+		}
+
+		/// The verification status of the account's identity, `Unverified` if it has none.
+		pub fn verification_status(who: &T::AccountId) -> VerificationStatus {
+			IdentityOf::<T, I>::get(who).map(|reg| reg.status).unwrap_or_default()
+		}
+
+		/// The verification level of the account's identity, `None` if it has none.
+		pub fn verification_level(who: &T::AccountId) -> VerificationLevel {
+			IdentityOf::<T, I>::get(who).map(|reg| reg.level).unwrap_or_default()
+		}
+
+		/// Get the display name of an account's identity.
+		pub fn display_of(who: &T::AccountId) -> Option<BoundedVec<u8, T::MaxDisplayLength>> {
+			IdentityOf::<T, I>::get(who).map(|reg| reg.info.display)
+		}
+
+		/// The weight of clearing an identity in the worst case: both storage patterns fully used,
+		/// every judgement carrying a note and a reason, and a tombstone kept of the sticky ones.
+		fn clear_identity_weight() -> Weight {
+			// Reasons and shadow copies, which the benchmarks don't give
+			let max = T::MaxJudgements::get() as u64;
+			let shadows = if T::ShadowWrites::get() { max.saturating_add(1) } else { 0 };
+			let unbenchmarked = max.saturating_mul(2).saturating_add(shadows);
+			T::WeightInfo::clear_identity_double_map_usage(T::MaxJudgements::get())
+				.saturating_add(T::WeightInfo::clear_identity_inline_usage(T::MaxJudgements::get()))
+				.saturating_add(if T::KeepTombstones::get() {
+					T::WeightInfo::keep_tombstone(T::MaxJudgements::get())
+				} else {
+					Weight::zero()
+				})
+				.saturating_add(T::DbWeight::get().reads_writes(unbenchmarked, unbenchmarked))
+		}
+
+		/// Remove `who`'s identity with everything held on it, and release what it held.
+		///
+		/// Returns the deposit, fees and challenge deposits released, or held for
+		/// `T::DepositReleaseDelay`.
+		fn do_clear_identity(who: &T::AccountId) -> Result<BalanceOf<T, I>, DispatchError> {
+			let id = IdentityOf::<T, I>::take(who).ok_or(Error::<T, I>::NoIdentity)?;
+			Self::unindex_display(who, &id.info.display);
+			if T::KeepTombstones::get() {
+				// Before the double map judgements are cleared below
+				Self::keep_tombstone(who, &id);
+			}
+			let deposit = Self::remove_identity_state(who, &id);
+
+			let delay = T::DepositReleaseDelay::get();
+			if delay.is_zero() {
+				let err_amount = Self::release_deposit(who, deposit);
+				debug_assert!(err_amount.is_zero());
+			} else {
+				let at = T::BlockNumberProvider::current_block_number().saturating_add(delay);
+				PendingReleases::<T, I>::mutate(who, |pending| {
+					let amount = pending
+						.as_ref()
+						.map_or(deposit, |pending| pending.amount.saturating_add(deposit));
+					*pending = Some(PendingRelease { amount, at });
+				});
+			}
+			frame_system::Pallet::<T>::dec_consumers(who);
+
+			if id.status == VerificationStatus::Verified {
+				T::OnVerified::on_revoked(who);
+			}
+
+			Ok(deposit)
+		}
+
+		/// Remove everything kept of `who`'s identity `id` besides its registration and display
+		/// index, returning all it held: the deposit of the identity and its notes, and those of
+		/// its pending requests and open disputes.
+		fn remove_identity_state(who: &T::AccountId, id: &Registration<T, I>) -> BalanceOf<T, I> {
+			// Pending requests are withdrawn, refunding their fees
+			let fees = JudgementRequests::<T, I>::drain_prefix(who)
+				.fold(BalanceOf::<T, I>::zero(), |total, (_, fee)| total.saturating_add(fee));
+			// Open disputes are withdrawn, refunding their challenge deposits
+			let challenges = Disputes::<T, I>::drain_prefix(who)
+				.fold(BalanceOf::<T, I>::zero(), |total, (_, deposit)| {
+					total.saturating_add(deposit)
+				});
+			let deposit = id.total_deposit().saturating_add(fees).saturating_add(challenges);
+
+			// Always cleanup double map judgements (this is O(n) where n = actual judgements)
+			// This operation uses drain_prefix and will be fast if no double map judgements exist
+			let cleared = Self::clear_judgements_double_map(who);
+			debug_assert_eq!(cleared, CounterForJudgements::<T, I>::take(who));
+			for (judgement_id, _) in &id.judgements {
+				JudgedBy::<T, I>::remove(judgement_id, who);
+			}
+
+			// Notes are removed with the judgements they belong to; their deposit is part of
+			// `total_deposit`. Notes and reasons are kept for judgements in either storage
+			// pattern, remote sources and shadow copies only for inline ones.
+			let inline = T::MaxJudgements::get();
+			let either = inline.saturating_mul(2);
+			let _ = JudgementNotes::<T, I>::clear_prefix(who, either, None);
+			let _ = JudgementReasons::<T, I>::clear_prefix(who, either, None);
+			let _ = RemoteJudgementSources::<T, I>::clear_prefix(who, inline, None);
+			if T::ShadowWrites::get() {
+				let _ = ShadowJudgementsDoubleMap::<T, I>::clear_prefix(who, inline, None);
+				ShadowJudgementsInline::<T, I>::remove(who);
+			}
+			FieldJudgements::<T, I>::remove(who);
+			KycHashes::<T, I>::remove(who);
+			KycAttestations::<T, I>::remove(who);
+			PendingAudits::<T, I>::remove(who);
+			Scores::<T, I>::remove(who);
+			DepositDebts::<T, I>::remove(who);
+			Beneficiaries::<T, I>::remove(who);
+			Vouches::<T, I>::remove(who);
+			UnchangedSince::<T, I>::remove(who);
+			IdentityExpiries::<T, I>::remove(who);
+			Self::stop_rent(who);
+
+			// The inline judgements are dropped with the Registration struct, only their
+			// `JudgedBy` entries are removed one by one
+			deposit
+		}
+
+		/// Store `info` as the identity of `who`, keeping sticky judgements and adjusting the
+		/// reserved deposit.
+		///
+		/// Returns the per-field lengths `[display, legal, web, email, avatar]` involved (the longer
+		/// of old and new) and, if the identity already existed, the number of inline judgements it held
+		/// before the update. Both feed the actual weight of the calling extrinsic.
+		fn do_set_identity(
+			who: T::AccountId,
+			info: IdentityInfoOf<T, I>,
+		) -> Result<([u32; 5], Option<u32>), DispatchError> {
+			// Calculate the length of each field for weight calculation
+			let mut field_lengths = info.byte_len_per_field();
+
+			let (mut registration, previous_judgements) = match IdentityOf::<T, I>::take(&who) {
+				Some(mut id) => {
+					// Calculate expected weight based on previous state
+					let expected_judgements_count = id.judgements.len() as u32;
+
+					// Make sure we account for long existing fields in storage
+					for (length, old_length) in
+						field_lengths.iter_mut().zip(id.info.byte_len_per_field())
+					{
+						*length = (*length).max(old_length);
+					}
+					Self::retain_frozen_judgements(&who, &mut id);
+					// A changed field is no longer verified, and keeps only its sticky field
+					// judgements
+					let mut changed = IdentityFields::empty();
+					for field in IdentityField::ALL {
+						if id.info.field(field) != info.field(field) {
+							id.verified_fields.remove(field);
+							changed.insert(field);
+						}
+					}
+					Self::drop_field_judgements(&who, changed);
+					Self::unindex_display(&who, &id.info.display);
+					id.info = info;
+
+					(id, Some(expected_judgements_count))
+				},
+				None => {
+					// Keep the account alive for as long as it has an identity. It already has a
+					// provider, the balance paying the deposit.
+					frame_system::Pallet::<T>::inc_consumers(&who)?;
+					let reg = Registration {
+						info,
+						judgements: BoundedVec::default(),
+						deposit: Zero::zero(),
+						field_bytes: [0; 5],
+						notes_deposit: Zero::zero(),
+						known_good: 0,
+						erroneous: 0,
+						status: VerificationStatus::Unverified,
+						level: VerificationLevel::Bronze,
+						verified_fields: IdentityFields::empty(),
+						preferred_registrars: BoundedVec::default(),
+					};
+
+					(reg, None)
+				},
+			};
+
+			registration.field_bytes = registration.info.field_sizes();
+			Self::store_identity(who, registration)?;
+
+			Ok((field_lengths, previous_judgements))
+		}
+
+		/// Register `info` as the identity of `who`, who has none, as brought over from the state
+		/// of another chain: its deposit is held but no registration fee charged. The verdicts of
+		/// `judgements` are given inline, but those under IDs reserved for system or bridged
+		/// judgements are left out, and the fees of `requests` are held as pending requests.
+		#[cfg(feature = "interop")]
+		pub(crate) fn import_identity(
+			who: T::AccountId,
+			info: IdentityInfoOf<T, I>,
+			judgements: Vec<(T::JudgementId, Judgement)>,
+			requests: Vec<(T::JudgementId, BalanceOf<T, I>)>,
+		) -> DispatchResult {
+			ensure!(!IdentityOf::<T, I>::contains_key(&who), Error::<T, I>::IdentityExists);
+			frame_system::Pallet::<T>::inc_consumers(&who)?;
+			let field_bytes = info.field_sizes();
+			let deposit = Self::identity_deposit(&who, &field_bytes, false);
+			Self::hold_deposit(&who, deposit)?;
+			UnchangedSince::<T, I>::insert(&who, T::BlockNumberProvider::current_block_number());
+			Self::renew_expiry(&who);
+			Self::index_display(&who, &info.display);
+			IdentityOf::<T, I>::insert(
+				&who,
+				Registration {
+					info,
+					judgements: BoundedVec::default(),
+					deposit,
+					field_bytes,
+					notes_deposit: Zero::zero(),
+					known_good: 0,
+					erroneous: 0,
+					status: VerificationStatus::Unverified,
+					level: VerificationLevel::Bronze,
+					verified_fields: IdentityFields::empty(),
+					preferred_registrars: BoundedVec::default(),
+				},
+			);
+
+			for (judgement_id, judgement) in judgements {
+				if Self::ensure_local_judgement_id(&judgement_id).is_err() {
+					continue;
+				}
+				Self::ensure_verdict(judgement)?;
+				Self::add_judgement_inline(&who, &judgement_id, judgement, None)?;
+			}
+			for (judgement_id, fee) in requests {
+				Self::hold_deposit(&who, fee)?;
+				JudgementRequests::<T, I>::insert(&who, judgement_id, fee);
+			}
+			Ok(())
+		}
+
+		/// Replace `fields` of `who`'s identity with those of `info`, keeping sticky judgements
+		/// and adjusting the reserved deposit. Only the replaced fields are compared, and
+		/// measured for the deposit.
+		///
+		/// Returns the per-field lengths `[display, legal, web, email, avatar]` involved (the longer
+		/// of old and new for a replaced field, zero for the others) and the number of inline judgements
+		/// the identity held before. Both feed the actual weight of the calling extrinsic.
+		fn do_update_identity_fields(
+			who: T::AccountId,
+			fields: IdentityFields,
+			info: IdentityInfoOf<T, I>,
+		) -> Result<([u32; 5], u32), DispatchError> {
+			let mut id = IdentityOf::<T, I>::take(&who).ok_or(Error::<T, I>::NoIdentity)?;
+			let previous_judgements = id.judgements.len() as u32;
+			Self::retain_frozen_judgements(&who, &mut id);
+
+			let mut field_lengths = [0; 5];
+			let mut changed = IdentityFields::empty();
+			for (index, field) in IdentityField::ALL.into_iter().enumerate() {
+				if !fields.contains(field) {
+					continue;
+				}
+				let (old, new) = (id.info.field(field), info.field(field));
+				field_lengths[index] = old.len().max(new.len()) as u32;
+				if old != new {
+					id.verified_fields.remove(field);
+					changed.insert(field);
+				}
+				id.field_bytes[index] = info.field_size(field);
+			}
+			Self::drop_field_judgements(&who, changed);
+			if changed.contains(IdentityField::Display) {
+				Self::unindex_display(&who, &id.info.display);
+			}
+			id.info.update_fields(fields, info);
+
+			Self::store_identity(who, id)?;
+			Ok((field_lengths, previous_judgements))
+		}
+
+		/// Drop the judgements of `who`'s identity `id` which are neither sticky nor disputed,
+		/// together with their notes, when the identity is set again.
+		fn retain_frozen_judgements(who: &T::AccountId, id: &mut Registration<T, I>) {
+			let mut refunded = BalanceOf::<T, I>::zero();
+			for (judgement_id, (judgement, _)) in
+				id.judgements.iter().filter(|item| !Self::is_frozen(who, item))
+			{
+				refunded = refunded.saturating_add(Self::drop_orphaned_note(who, judgement_id));
+				Self::forget_judged(who, judgement_id);
+				Self::drop_shadow_judgements(who, judgement_id, true, false);
+				RemoteJudgementSources::<T, I>::remove(who, judgement_id);
+				Self::update_score(who, Some(*judgement), None);
+			}
+			id.notes_deposit = id.notes_deposit.saturating_sub(refunded);
+			id.judgements.retain(|item| Self::is_frozen(who, item));
+		}
+
+		/// Store the set or updated `registration` of `who`, whose `field_bytes` are up to date:
+		/// reprice its deposit, charge the registration fee, index its display name and drop the
+		/// attestation of its KYC hash.
+		fn store_identity(
+			who: T::AccountId,
+			mut registration: Registration<T, I>,
+		) -> DispatchResult {
+			UnchangedSince::<T, I>::insert(&who, T::BlockNumberProvider::current_block_number());
+			Self::invalidate_kyc_attestation(&who);
+			Self::renew_expiry(&who);
+			Self::note_activity(&who);
+			Self::pay_due_rent(&who)?;
+			let new_deposit = Self::identity_deposit(
+				&who,
+				&registration.field_bytes,
+				registration.known_good > 0,
+			);
+			let old_deposit = registration.deposit;
+			Self::rejig_deposit(&who, old_deposit, new_deposit)?;
+			Self::charge_registration_fee(&who)?;
+
+			Self::deposit_repriced(&who, &mut registration, new_deposit);
+			Self::index_display(&who, &registration.info.display);
+			let display = registration.info.display.clone();
+			let bytes = registration.info.byte_len_per_field().iter().sum();
+			IdentityOf::<T, I>::insert(&who, registration);
+			Self::deposit_event(Event::IdentitySet { who, display, bytes });
+			Ok(())
+		}
+
+		/// Blank `fields` of `who`'s identity, keeping its whole-identity judgements and
+		/// repricing its deposit. The fields are no longer verified and lose their non-sticky
+		/// field judgements.
+		///
+		/// Returns the deposit released.
+		fn clear_fields(
+			who: &T::AccountId,
+			fields: IdentityFields,
+		) -> Result<BalanceOf<T, I>, DispatchError> {
+			IdentityOf::<T, I>::try_mutate(who, |maybe_reg| {
+				let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::NoIdentity)?;
+				for field in fields.iter() {
+					match field {
+						IdentityField::Display => {
+							Self::unindex_display(who, &reg.info.display);
+							reg.info.display = BoundedVec::default()
+						},
+						IdentityField::Legal => reg.info.legal = BoundedVec::default(),
+						IdentityField::Web => reg.info.web = BoundedVec::default(),
+						IdentityField::Email => reg.info.email = BoundedVec::default(),
+						IdentityField::Avatar => reg.info.avatar = None,
+					}
+					reg.field_bytes[field.index()] = reg.info.field_size(field);
+					reg.verified_fields.remove(field);
+				}
+				Self::drop_field_judgements(who, fields);
+				Self::invalidate_kyc_attestation(who);
+				UnchangedSince::<T, I>::insert(who, T::BlockNumberProvider::current_block_number());
+
+				let new_deposit = Self::identity_deposit(who, &reg.field_bytes, reg.known_good > 0);
+				Self::rejig_deposit(who, reg.deposit, new_deposit)?;
+				let released = reg.deposit.saturating_sub(new_deposit);
+				Self::deposit_repriced(who, reg, new_deposit);
+				Ok(released)
+			})
+		}
+
+		/// Drop the attestation of `who`'s KYC hash, if any, as its identity or the hash changed.
+		fn invalidate_kyc_attestation(who: &T::AccountId) {
+			if KycAttestations::<T, I>::take(who).is_some() {
+				Self::deposit_event(Event::KycAttestationInvalidated { who: who.clone() });
+			}
+		}
+
+		/// Reserve the fee of the registrar under `judgement_id` and record the request of `who`.
+		///
+		/// Returns the number of requests `who` already had pending, which feeds the actual weight
+		/// of the calling extrinsic.
+		fn do_request_judgement(
+			who: T::AccountId,
+			judgement_id: &T::JudgementId,
+			max_fee: BalanceOf<T, I>,
+		) -> Result<u32, DispatchError> {
+			let reg = IdentityOf::<T, I>::get(&who).ok_or(Error::<T, I>::NoIdentity)?;
+			let registrar =
+				Registrars::<T, I>::get(judgement_id).ok_or(Error::<T, I>::UnknownRegistrar)?;
+			ensure!(registrar.fee <= max_fee, Error::<T, I>::FeeChanged);
+
+			// Only a judgement ID without a judgement can become `Requested`. A verdict is revised
+			// by its provider rather than asked for again, and a sticky one can't be revised.
+			match Self::judgement_state(&who, &reg, judgement_id) {
+				None => {},
+				Some(Judgement::Requested) => return Err(Error::<T, I>::AlreadyRequested.into()),
+				Some(judgement) if judgement.is_sticky() =>
+					return Err(Error::<T, I>::StickyJudgement.into()),
+				Some(_) => return Err(Error::<T, I>::InvalidJudgementTransition.into()),
+			}
+
+			let pending = JudgementRequests::<T, I>::iter_key_prefix(&who).count() as u32;
+			ensure!(pending < JudgementLimit::<T, I>::get(), Error::<T, I>::TooManyRequests);
+
+			Self::hold_deposit(&who, registrar.fee)?;
+			JudgementRequests::<T, I>::insert(&who, judgement_id, registrar.fee);
+			Self::note_activity(&who);
+			Self::deposit_event(Event::JudgementRequested {
+				who,
+				judgement_id: judgement_id.clone(),
+				fee: registrar.fee,
+			});
+			Ok(pending)
+		}
+
+		/// Pay the fee of a pending request for the judgement just given under `judgement_id` to
+		/// its registrar, where it stays reserved until claimed, and complete the pending audit if
+		/// it was the registrar's. Pays nothing if no judgement was requested, and only what `who`
+		/// still holds of the fee otherwise.
+		fn settle_judgement_request(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+		) -> DispatchResult {
+			if PendingAudits::<T, I>::get(who).as_ref() == Some(judgement_id) {
+				PendingAudits::<T, I>::remove(who);
+				Self::deposit_event(Event::AuditCompleted {
+					target: who.clone(),
+					judgement_id: judgement_id.clone(),
+				});
+			}
+			let Some(fee) = JudgementRequests::<T, I>::take(who, judgement_id) else {
+				return Ok(());
+			};
+			let registrar =
+				Registrars::<T, I>::get(judgement_id).ok_or(Error::<T, I>::UnknownRegistrar)?;
+			let missing = T::DepositBackend::transfer(who, &registrar.account, fee)?;
+			// What couldn't be moved, e.g. after a slash of `who`, is no longer held by anyone
+			TotalDeposits::<T, I>::mutate(|total| *total = total.saturating_sub(missing));
+			AccruedFees::<T, I>::mutate(&registrar.account, |fees| {
+				*fees = fees.saturating_add(fee.saturating_sub(missing))
+			});
+			Ok(())
+		}
+
+		/// `field` of the identity information taken by `set_identity_legacy`, under its own bound
+		/// `S` instead of `LegacyFieldLength`.
+		fn rebound_legacy_field<S: Get<u32>>(
+			field: BoundedVec<u8, LegacyFieldLength>,
+		) -> Result<BoundedVec<u8, S>, Error<T, I>> {
+			field.into_inner().try_into().map_err(|_| Error::<T, I>::FieldTooLong)
+		}
+
+		/// Calculate the deposit required for an identity from the encoded size of each field.
+		pub(crate) fn calculate_identity_deposit(field_bytes: &[u32; 5]) -> BalanceOf<T, I> {
+			let bytes = field_bytes.iter().fold(0u32, |total, bytes| total.saturating_add(*bytes));
+			let byte_deposit = T::ByteDeposit::get().saturating_mul(BalanceOf::<T, I>::from(bytes));
+			T::BasicDeposit::get().saturating_add(byte_deposit)
+		}
+
+		/// The identity deposit of `who` for fields of `field_bytes` encoded sizes: none if `who`
+		/// is in `ExemptAccounts` or identities pay rent instead, and less
+		/// `T::VerifiedDepositDiscount` if `verified`, i.e. it holds a `KnownGood` judgement.
+		fn identity_deposit(
+			who: &T::AccountId,
+			field_bytes: &[u32; 5],
+			verified: bool,
+		) -> BalanceOf<T, I> {
+			if ExemptAccounts::<T, I>::contains_key(who) || !T::IdentityRent::get().is_zero() {
+				return Zero::zero();
+			}
+			let deposit = Self::calculate_identity_deposit(field_bytes);
+			if verified {
+				deposit.saturating_sub(T::VerifiedDepositDiscount::get().mul_floor(deposit))
+			} else {
+				deposit
+			}
+		}
+
+		/// `deposit` less what of it has decayed since `who`'s identity was last set or edited:
+		/// nothing for `T::DepositDecayPeriod` blocks, then linearly more over as many blocks
+		/// again, down to `T::DepositDecayFloor` of it.
+		fn decay_deposit(who: &T::AccountId, deposit: BalanceOf<T, I>) -> BalanceOf<T, I> {
+			let period = T::DepositDecayPeriod::get();
+			let Some(since) = UnchangedSince::<T, I>::get(who) else { return deposit };
+			if period.is_zero() {
+				return deposit;
+			}
+			let decaying_for = T::BlockNumberProvider::current_block_number()
+				.saturating_sub(since)
+				.saturating_sub(period)
+				.min(period);
+			let decayable = deposit.saturating_sub(T::DepositDecayFloor::get().mul_ceil(deposit));
+			deposit
+				.saturating_sub(Perbill::from_rational(decaying_for, period).mul_floor(decayable))
+		}
+
+		/// Reprice the deposit of `who`'s registration `reg` after it gained its first or lost
+		/// its last `KnownGood` judgement, returning or reserving again the discount. If the
+		/// discount can't be reserved again, e.g. for lack of funds, the deposit is left as it is
+		/// and the difference recorded in `DepositDebts`.
+		fn verification_repriced(who: &T::AccountId, reg: &mut Registration<T, I>) {
+			let verified = reg.known_good > 0;
+			let deposit =
+				Self::decay_deposit(who, Self::identity_deposit(who, &reg.field_bytes, verified));
+			if deposit == reg.deposit {
+				DepositDebts::<T, I>::remove(who);
+				return;
+			}
+			if Self::rejig_deposit(who, reg.deposit, deposit).is_err() {
+				let debt = deposit.saturating_sub(reg.deposit);
+				DepositDebts::<T, I>::insert(who, debt);
+				Self::deposit_event(Event::DepositDebtIncurred { who: who.clone(), debt });
+				return;
+			}
+			Self::deposit_repriced(who, reg, deposit);
+			let who = who.clone();
+			Self::deposit_event(if verified {
+				Event::DepositDiscountGranted { who, deposit }
+			} else {
+				Event::DepositDiscountRevoked { who, deposit }
+			});
+		}
+
+		/// Record `deposit`, just reserved or unreserved in full, as the deposit of `who`'s
+		/// registration `reg`, paying off any debt on it.
+		fn deposit_repriced(
+			who: &T::AccountId,
+			reg: &mut Registration<T, I>,
+			deposit: BalanceOf<T, I>,
+		) {
+			reg.deposit = deposit;
+			DepositDebts::<T, I>::remove(who);
+		}
+
+		/// Charge `who` the registration fee, handing it to `T::RegistrationFeeDestination`.
+		fn charge_registration_fee(who: &T::AccountId) -> DispatchResult {
+			let fee = T::RegistrationFee::get();
+			if fee.is_zero() {
+				return Ok(());
+			}
+			let imbalance = T::Currency::withdraw(
+				who,
+				fee,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			T::RegistrationFeeDestination::on_unbalanced(imbalance);
+			Self::deposit_event(Event::RegistrationFeePaid { who: who.clone(), fee });
+			Ok(())
+		}
+
+		/// Bring the deposit of `who`'s `registration`, and whether it pays rent, in line with the
+		/// current deposit and rent constants.
+		fn reconcile_deposit(who: &T::AccountId, mut registration: Registration<T, I>) {
+			Self::reconcile_rent(who);
+			let old_deposit = registration.total_deposit();
+			let deposit = Self::decay_deposit(
+				who,
+				Self::identity_deposit(who, &registration.field_bytes, registration.known_good > 0),
+			);
+			let notes_deposit = JudgementNotes::<T, I>::iter_prefix_values(who).fold(
+				Zero::zero(),
+				|total: BalanceOf<T, I>, note| {
+					total.saturating_add(Self::calculate_note_deposit(&note))
+				},
+			);
+			let new_deposit = deposit.saturating_add(notes_deposit);
+			if new_deposit == old_deposit {
+				return;
+			}
+
+			if Self::rejig_deposit(who, old_deposit, new_deposit).is_err() {
+				Self::deposit_event(Event::DepositReconciliationFailed { who: who.clone() });
+				return;
+			}
+			Self::deposit_repriced(who, &mut registration, deposit);
+			registration.notes_deposit = notes_deposit;
+			IdentityOf::<T, I>::insert(who, registration);
+			Self::deposit_event(Event::DepositReconciled {
+				who: who.clone(),
+				old_deposit,
+				new_deposit,
+			});
+		}
+
+		/// Calculate the deposit required for a judgement note.
+		pub(crate) fn calculate_note_deposit(note: &JudgementNote<T, I>) -> BalanceOf<T, I> {
+			T::ByteDeposit::get().saturating_mul(BalanceOf::<T, I>::from(note.len() as u32))
+		}
+
+		/// Store the tombstone of `who`'s registration `reg` as it is cleared, with the sticky
+		/// judgements of both storage patterns. The double map judgements must not be cleared yet.
+		pub(crate) fn keep_tombstone(who: &T::AccountId, reg: &Registration<T, I>) {
+			let mut judgements: Vec<_> = reg
+				.judgements
+				.iter()
+				.filter(|(_, (judgement, _))| judgement.is_sticky())
+				.map(|(judgement_id, (judgement, _))| (judgement_id.clone(), *judgement))
+				.collect();
+			for (judgement_id, (judgement, _)) in JudgementsDoubleMap::<T, I>::iter_prefix(who) {
+				if !judgement.is_sticky() {
+					continue;
+				}
+				if let Err(position) = judgements.binary_search_by(|x| x.0.cmp(&judgement_id)) {
+					judgements.insert(position, (judgement_id, judgement));
+				}
+			}
+			let tombstone = Tombstone {
+				info_hash: T::Hashing::hash_of(&reg.info),
+				cleared_at: T::BlockNumberProvider::current_block_number(),
+				// Each storage pattern holds at most `T::MaxJudgements` judgements
+				judgements: BoundedVec::truncate_from(judgements),
+			};
+			Tombstones::<T, I>::insert(who, tombstone);
+		}
+
+		/// Helper function to clear all judgements from the double map for an account.
+		/// This demonstrates efficient cleanup using clear_prefix - O(j) where j is actual
+		/// judgements.
+		fn clear_judgements_double_map(who: &T::AccountId) -> u32 {
+			let mut removed = 0u32;
+			for (judgement_id, _) in JudgementsDoubleMap::<T, I>::drain_prefix(who) {
+				JudgedBy::<T, I>::remove(&judgement_id, who);
+				removed.saturating_inc();
+			}
+			removed
+		}
+
+		/// Set the number of double map judgements `who` holds to `count`, returning the old one.
+		/// The counter is removed rather than stored as zero.
+		fn set_judgement_count(who: &T::AccountId, count: u32) -> u32 {
+			CounterForJudgements::<T, I>::mutate_exists(who, |counter| {
+				core::mem::replace(counter, Some(count).filter(|count| *count > 0)).unwrap_or(0)
+			})
+		}
+
+		/// Helper function to add a judgement to inline storage only (BoundedVec).
+		/// This demonstrates the efficient inline storage pattern.
+		///
+		/// Returns the verification status of the identity before and after.
+		fn add_judgement_inline(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+			judgement: Judgement,
+			note: Option<JudgementNote<T, I>>,
+		) -> Result<(VerificationStatus, VerificationStatus), DispatchError> {
+			IdentityOf::<T, I>::try_mutate(who, |maybe_reg| -> Result<_, DispatchError> {
+				let reg = maybe_reg.as_mut().ok_or(Error::<T, I>::InvalidTarget)?;
+				let old_status = reg.status;
+				Self::insert_judgement_inline(who, reg, judgement_id, judgement)?;
+				Self::update_judgement_note(who, reg, judgement_id, note)?;
+				let counted = Self::held_in_double_map(who, judgement_id, judgement);
+				Self::count_sticky(who, reg, judgement, counted);
+				Ok((old_status, reg.status))
+			})
+		}
+
+		/// Insert or replace the inline judgement under `judgement_id` in `who`'s registration
+		/// `reg`, dated to this block, keeping the judgements ordered by ID.
+		fn insert_judgement_inline(
+			who: &T::AccountId,
+			reg: &mut Registration<T, I>,
+			judgement_id: &T::JudgementId,
+			judgement: Judgement,
+		) -> DispatchResult {
+			ensure!(
+				!Disputes::<T, I>::contains_key(who, judgement_id),
+				Error::<T, I>::JudgementDisputed
+			);
+
+			// Use binary search for the BoundedVec (efficient)
+			let given = (judgement, T::BlockNumberProvider::current_block_number());
+			let item = (judgement_id.clone(), given);
+			match reg.judgements.binary_search_by(|x| x.0.cmp(judgement_id)) {
+				Ok(position) => {
+					// Judgement exists, check if it's sticky
+					let (existing_judgement, _) = reg.judgements[position].1;
+					if existing_judgement.is_sticky() {
+						return Err(Error::<T, I>::StickyJudgement.into());
+					}
+					// Replace the existing judgement
+					reg.judgements[position] = item;
+					Self::update_score(who, Some(existing_judgement), Some(judgement));
+				},
+				Err(position) => {
+					// The `BoundedVec` enforces `T::MaxJudgements`; the stored limit may be tighter
+					ensure!(
+						(reg.judgements.len() as u32) < JudgementLimit::<T, I>::get(),
+						Error::<T, I>::TooManyJudgements
+					);
+					// Insert new judgement at the correct position to maintain ordering
+					reg.judgements
+						.try_insert(position, item)
+						.map_err(|_| Error::<T, I>::TooManyJudgements)?;
+					JudgedBy::<T, I>::insert(judgement_id, who, ());
+					Self::update_score(who, None, Some(judgement));
+				},
+			}
+			if T::ShadowWrites::get() {
+				ShadowJudgementsDoubleMap::<T, I>::insert(who, judgement_id, given);
+			}
+			Ok(())
+		}
+
+		/// Copy a double map judgement given on `who` into `ShadowJudgementsInline` while
+		/// `T::ShadowWrites` is set.
+		fn shadow_double_map_judgement(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+			given: DatedJudgementOf<T, I>,
+		) {
+			if !T::ShadowWrites::get() {
+				return;
+			}
+			ShadowJudgementsInline::<T, I>::mutate(who, |shadow| {
+				match shadow.binary_search_by(|x| x.0.cmp(judgement_id)) {
+					Ok(position) => shadow[position].1 = given,
+					// Holds no more than the double map, which is bounded the same
+					Err(position) => {
+						let _ = shadow.try_insert(position, (judgement_id.clone(), given));
+					},
+				}
+			});
+		}
+
+		/// Drop the shadow copies of `who`'s judgements under `judgement_id` removed from the
+		/// `inline` or the `double_map` storage pattern, while `T::ShadowWrites` is set.
+		fn drop_shadow_judgements(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+			inline: bool,
+			double_map: bool,
+		) {
+			if !T::ShadowWrites::get() {
+				return;
+			}
+			if inline {
+				ShadowJudgementsDoubleMap::<T, I>::remove(who, judgement_id);
+			}
+			if double_map {
+				ShadowJudgementsInline::<T, I>::mutate_exists(who, |maybe_shadow| {
+					if let Some(shadow) = maybe_shadow {
+						shadow.retain(|(id, _)| id != judgement_id);
+						if shadow.is_empty() {
+							*maybe_shadow = None;
+						}
+					}
+				});
+			}
+		}
+
+		/// Rewrite the shadow copies of the inline judgements of `who`'s registration `reg`, after
+		/// they were rearranged as a whole.
+		fn reshadow_inline_judgements(who: &T::AccountId, reg: &Registration<T, I>) {
+			let _ = ShadowJudgementsDoubleMap::<T, I>::clear_prefix(who, u32::MAX, None);
+			for (judgement_id, judgement) in &reg.judgements {
+				ShadowJudgementsDoubleMap::<T, I>::insert(who, judgement_id, judgement);
+			}
+		}
+
+		/// Check that `TotalDeposits` adds up, and that the shadow copies written while
+		/// `T::ShadowWrites` is set agree with the judgements they copy.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub(crate) fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				TotalDeposits::<T, I>::get() == Self::recompute_total_deposits(),
+				"`TotalDeposits` differs from the deposits held"
+			);
+			if !T::ShadowWrites::get() {
+				return Ok(());
+			}
+			for (who, judgement_id, judgement) in ShadowJudgementsDoubleMap::<T, I>::iter() {
+				let reg =
+					IdentityOf::<T, I>::get(&who).ok_or("Shadow judgement without identity")?;
+				let inline = reg
+					.judgements
+					.binary_search_by(|x| x.0.cmp(&judgement_id))
+					.ok()
+					.map(|position| reg.judgements[position].1);
+				ensure!(inline == Some(judgement), "Shadow judgement differs from the inline one");
+			}
+			for (who, shadow) in ShadowJudgementsInline::<T, I>::iter() {
+				ensure!(
+					shadow.windows(2).all(|pair| pair[0].0 < pair[1].0),
+					"Shadow inline judgements are out of order"
+				);
+				for (judgement_id, judgement) in shadow {
+					ensure!(
+						JudgementsDoubleMap::<T, I>::get(&who, &judgement_id) == Some(judgement),
+						"Shadow judgement differs from the double map one"
+					);
+				}
+			}
+			Ok(())
+		}
+
+		/// Replace the contribution of the judgement `old` to `who`'s score with that of `new`.
+		/// `None` stands for no judgement, e.g. when one is added or removed.
+		pub(crate) fn update_score(
+			who: &T::AccountId,
+			old: Option<Judgement>,
+			new: Option<Judgement>,
+		) {
+			let score =
+				|judgement: Option<Judgement>| judgement.map_or(0, T::JudgementScore::convert);
+			let delta = score(new).saturating_sub(score(old));
+			if delta != 0 {
+				Scores::<T, I>::mutate(who, |total| *total = total.saturating_add(delta));
+			}
+		}
+
+		/// Check a read proof of `who`'s `IdentityOf` entry against a block's `state_root`.
+		///
+		/// Returns the proven registration, or `None` if the proof shows `who` has no identity at
+		/// that block. The proof is expected to be taken for [`Self::identity_storage_key`].
+		pub fn verify_identity_proof(
+			state_root: &T::Hash,
+			proof: StorageProof,
+			who: &T::AccountId,
+		) -> Result<Option<Registration<T, I>>, IdentityProofError> {
+			let db = proof.into_memory_db::<T::Hashing>();
+			let key = Self::identity_storage_key(who.clone());
+			let value = sp_trie::read_trie_value::<LayoutV1<T::Hashing>, _>(
+				&db, state_root, &key, None, None,
+			)
+			.map_err(|_| IdentityProofError::InvalidProof)?;
+
+			value
+				.map(|value| {
+					Registration::<T, I>::decode_all(&mut &value[..])
+						.map_err(|_| IdentityProofError::Undecodable)
+				})
+				.transpose()
+		}
+
+		/// Remove the judgements under `judgement_id` from `who`'s registration `reg`, in both
+		/// storage patterns and regardless of stickiness, together with their note and remote
+		/// source. The note deposit is returned.
+		fn remove_judgement(
+			who: &T::AccountId,
+			reg: &mut Registration<T, I>,
+			judgement_id: &T::JudgementId,
+		) {
+			let inline = reg
+				.judgements
+				.binary_search_by(|x| x.0.cmp(judgement_id))
+				.ok()
+				.map(|position| reg.judgements.remove(position).1 .0);
+			let double_map = JudgementsDoubleMap::<T, I>::take(who, judgement_id)
+				.map(|(judgement, _)| judgement);
+			if double_map.is_some() {
+				Self::set_judgement_count(
+					who,
+					CounterForJudgements::<T, I>::get(who).saturating_sub(1),
+				);
+			}
+
+			for judgement in [inline, double_map] {
+				Self::update_score(who, judgement, None);
+			}
+			// Both patterns hold a verdict under the same ID as one provider
+			let held = |verdict| inline == Some(verdict) || double_map == Some(verdict);
+			if held(Judgement::KnownGood) {
+				reg.known_good.saturating_dec();
+				if reg.known_good == 0 {
+					Self::verification_repriced(who, reg);
+				}
+			}
+			if held(Judgement::Erroneous) {
+				reg.erroneous.saturating_dec();
+			}
+			Self::refresh_status(who, reg);
+
+			RemoteJudgementSources::<T, I>::remove(who, judgement_id);
+			JudgedBy::<T, I>::remove(judgement_id, who);
+			Self::drop_shadow_judgements(who, judgement_id, inline.is_some(), double_map.is_some());
+			let refunded = Self::drop_orphaned_note(who, judgement_id);
+			reg.notes_deposit = reg.notes_deposit.saturating_sub(refunded);
+		}
+
+		/// Count a sticky judgement towards `reg`'s verification, unless it is `counted` already
+		/// because the other storage pattern holds the same one under the same ID.
+		fn count_sticky(
+			who: &T::AccountId,
+			reg: &mut Registration<T, I>,
+			judgement: Judgement,
+			counted: bool,
+		) {
+			if counted {
+				return;
+			}
+			match judgement {
+				Judgement::KnownGood => {
+					reg.known_good.saturating_inc();
+					Self::refresh_status(who, reg);
+					if reg.known_good == 1 {
+						Self::verification_repriced(who, reg);
+					}
+				},
+				Judgement::Erroneous => {
+					reg.erroneous.saturating_inc();
+					Self::refresh_status(who, reg);
+				},
+				_ => {},
+			}
+		}
+
+		/// When the `KnownGood` judgement under `judgement_id` on `who`'s identity `reg` was last
+		/// given or reconfirmed, the later of the two storage patterns. `None` if neither holds
+		/// one.
+		pub(crate) fn known_good_given_at(
+			who: &T::AccountId,
+			reg: &Registration<T, I>,
+			judgement_id: &T::JudgementId,
+		) -> Option<BlockNumberOf<T, I>> {
+			let inline = reg
+				.judgements
+				.binary_search_by(|x| x.0.cmp(judgement_id))
+				.ok()
+				.map(|position| reg.judgements[position].1);
+			[inline, JudgementsDoubleMap::<T, I>::get(who, judgement_id)]
+				.into_iter()
+				.flatten()
+				.filter(|(judgement, _)| *judgement == Judgement::KnownGood)
+				.map(|(_, given_at)| given_at)
+				.max()
+		}
+
+		/// Date the `KnownGood` judgements under `judgement_id` on `who`'s identity `reg` to this
+		/// block, in both storage patterns.
+		fn redate_known_good(
+			who: &T::AccountId,
+			reg: &mut Registration<T, I>,
+			judgement_id: &T::JudgementId,
+		) {
+			let given = (Judgement::KnownGood, T::BlockNumberProvider::current_block_number());
+			if Self::held_inline(reg, judgement_id, Judgement::KnownGood) {
+				if let Ok(position) = reg.judgements.binary_search_by(|x| x.0.cmp(judgement_id)) {
+					reg.judgements[position].1 = given;
+				}
+				if T::ShadowWrites::get() {
+					ShadowJudgementsDoubleMap::<T, I>::insert(who, judgement_id, given);
+				}
+			}
+			if Self::held_in_double_map(who, judgement_id, Judgement::KnownGood) {
+				JudgementsDoubleMap::<T, I>::insert(who, judgement_id, given);
+				Self::shadow_double_map_judgement(who, judgement_id, given);
+			}
+		}
 
-	/// Dispatchable functions allow users to interact with the pallet and invoke state changes.
-	#[pallet::call]
-	impl<T: Config> Pallet<T> {
-		/// Set an account's identity information and reserve the appropriate deposit.
-		///
-		/// If the account already has identity information, the deposit is taken as part payment
-		/// for the new deposit.
-		///
-		/// The dispatch origin for this call must be _Signed_.
-		///
-		/// - `display`: The display name.
-		/// - `legal`: The legal name.
-		/// - `web`: The web address.
-		/// - `email`: The email address.
-		///
-		/// Emits `IdentitySet` if successful.
-		#[pallet::weight(T::WeightInfo::set_identity_update(
-			T::MaxFieldLength::get(), // worst case: fields at max length
-			T::MaxJudgements::get() // worst case: maximum judgements
-		))]
-		pub fn set_identity(
-			origin: OriginFor<T>,
-			display: BoundedVec<u8, T::MaxFieldLength>,
-			legal: BoundedVec<u8, T::MaxFieldLength>,
-			web: BoundedVec<u8, T::MaxFieldLength>,
-			email: BoundedVec<u8, T::MaxFieldLength>,
-		) -> DispatchResultWithPostInfo {
-			let sender = ensure_signed(origin)?;
+		/// Have the `KnownGood` judgement on `who` under `judgement_id`, given or reconfirmed in
+		/// this block, checked for expiry `T::RevalidationPeriod` blocks from now. Not while the
+		/// period is zero, nor for the judgement IDs of the pallet's own and bridged judgements.
+		fn schedule_revalidation(who: &T::AccountId, judgement_id: &T::JudgementId) {
+			let period = T::RevalidationPeriod::get();
+			if period.is_zero() || Self::ensure_local_judgement_id(judgement_id).is_err() {
+				return;
+			}
+			let due = T::BlockNumberProvider::current_block_number().saturating_add(period);
+			if !RevalidationCursor::<T, I>::exists() {
+				RevalidationCursor::<T, I>::put(due);
+			}
+			RevalidationDue::<T, I>::insert(due, (who, judgement_id), ());
+		}
 
-			let info = IdentityInfo { display, legal, web, email };
+		/// Check the `KnownGood` judgements due for revalidation by now, downgrading those not
+		/// reconfirmed since, up to `T::MaxExpiryChecks` of them. A block without any due counts as
+		/// one check, so that catching up on a gap in the block numbers is bounded too.
+		fn downgrade_expired_judgements() -> Weight {
+			if T::RevalidationPeriod::get().is_zero() {
+				return Weight::zero();
+			}
+			let Some(mut block) = RevalidationCursor::<T, I>::get() else {
+				return T::WeightInfo::downgrade_expired_judgements(0);
+			};
+			let now = T::BlockNumberProvider::current_block_number();
+			let limit = T::MaxExpiryChecks::get();
+			let mut checked = 0u32;
+			while block <= now && checked < limit {
+				let due: Vec<_> = RevalidationDue::<T, I>::drain_prefix(block)
+					.take(limit.saturating_sub(checked) as usize)
+					.collect();
+				checked = checked.saturating_add((due.len() as u32).max(1));
+				for ((who, judgement_id), ()) in due {
+					Self::downgrade_expired(&who, &judgement_id, now);
+				}
+				if RevalidationDue::<T, I>::iter_prefix(block).next().is_some() {
+					break;
+				}
+				block.saturating_inc();
+			}
+			RevalidationCursor::<T, I>::put(block);
+			T::WeightInfo::downgrade_expired_judgements(checked)
+		}
 
-			// Calculate the length of the longest field for weight calculation
-			let max_field_length = info
-				.display
-				.len()
-				.max(info.legal.len())
-				.max(info.web.len())
-				.max(info.email.len());
+		/// Move the check for expiry of the judgement `given` under `judgement_id` from the
+		/// identity of `from` to that of `to`, if it is a `KnownGood` one still to be checked.
+		fn move_revalidation(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			judgement_id: &T::JudgementId,
+			(judgement, given_at): DatedJudgementOf<T, I>,
+		) {
+			if judgement != Judgement::KnownGood {
+				return;
+			}
+			let due = given_at.saturating_add(T::RevalidationPeriod::get());
+			if RevalidationDue::<T, I>::take(due, (from, judgement_id)).is_some() {
+				RevalidationDue::<T, I>::insert(due, (to, judgement_id), ());
+			}
+		}
 
-			let id = match IdentityOf::<T>::take(&sender) {
-				Some(mut id) => {
-					// Calculate expected weight based on previous state
-					let expected_judgements_count = id.judgements.len() as u32;
+		/// Downgrade the `KnownGood` judgement on `who` under `judgement_id` to `Reasonable` if it
+		/// was last given or reconfirmed `T::RevalidationPeriod` blocks before `now` or earlier.
+		/// The downgrade isn't counted in its provider's `ProviderStats`, nor is
+		/// `T::OnJudgementGiven` told, as the provider didn't give it.
+		fn downgrade_expired(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+			now: BlockNumberOf<T, I>,
+		) {
+			let Some(mut reg) = IdentityOf::<T, I>::get(who) else { return };
+			let due = Self::known_good_given_at(who, &reg, judgement_id)
+				.map(|given_at| given_at.saturating_add(T::RevalidationPeriod::get()));
+			if due.is_none_or(|due| due > now) {
+				return;
+			}
 
-					// Make sure we account for long existing fields in storage
-					let max_field_length = max_field_length
-						.max(id.info.display.len())
-						.max(id.info.legal.len())
-						.max(id.info.web.len())
-						.max(id.info.email.len());
-					// Only keep sticky judgements when setting new identity
-					id.judgements.retain(|(_id, judgement)| judgement.is_sticky());
-					id.info = info;
-					// Note: We preserve judgements_count_double_map to maintain consistency
-					// with double map storage (double map judgements are independent of inline)
+			let old_status = reg.status;
+			Self::downgrade_known_good(who, &mut reg.judgements, judgement_id);
+			reg.known_good.saturating_dec();
+			if reg.known_good == 0 {
+				Self::verification_repriced(who, &mut reg);
+			}
+			Self::refresh_status(who, &mut reg);
+			let new_status = reg.status;
+			IdentityOf::<T, I>::insert(who, reg);
+			Self::status_changed(who, old_status, new_status);
+			Self::deposit_event(Event::JudgementDowngraded {
+				who: who.clone(),
+				judgement_id: judgement_id.clone(),
+			});
+		}
 
-					// Calculate actual weight used
-					let actual_weight = T::WeightInfo::set_identity_update(
-						max_field_length as u32,
-						expected_judgements_count,
-					);
+		/// Replace the `KnownGood` judgements under `judgement_id` on `who`'s identity, inline in
+		/// its `judgements` and in the double map, with `Reasonable` ones dated the same, and update
+		/// its score. The caller accounts for the registration's `known_good`.
+		pub(crate) fn downgrade_known_good(
+			who: &T::AccountId,
+			judgements: &mut DatedJudgementsOf<T, I>,
+			judgement_id: &T::JudgementId,
+		) {
+			if let Ok(position) = judgements.binary_search_by(|x| x.0.cmp(judgement_id)) {
+				let (judgement, given_at) = judgements[position].1;
+				if judgement == Judgement::KnownGood {
+					let downgraded = (Judgement::Reasonable, given_at);
+					judgements[position].1 = downgraded;
+					Self::update_score(who, Some(judgement), Some(Judgement::Reasonable));
+					if T::ShadowWrites::get() {
+						ShadowJudgementsDoubleMap::<T, I>::insert(who, judgement_id, downgraded);
+					}
+				}
+			}
+			if let Some((judgement @ Judgement::KnownGood, given_at)) =
+				JudgementsDoubleMap::<T, I>::get(who, judgement_id)
+			{
+				let downgraded = (Judgement::Reasonable, given_at);
+				JudgementsDoubleMap::<T, I>::insert(who, judgement_id, downgraded);
+				Self::shadow_double_map_judgement(who, judgement_id, downgraded);
+				Self::update_score(who, Some(judgement), Some(Judgement::Reasonable));
+			}
+		}
 
-					(id, actual_weight)
-				},
-				None => {
-					let reg = Registration {
-						info,
-						judgements: BoundedVec::default(),
-						judgements_count_double_map: 0,
-						deposit: Zero::zero(),
-					};
+		/// Whether `reg` holds `judgement` under `judgement_id` inline.
+		fn held_inline(
+			reg: &Registration<T, I>,
+			judgement_id: &T::JudgementId,
+			judgement: Judgement,
+		) -> bool {
+			reg.judgements
+				.binary_search_by(|x| x.0.cmp(judgement_id))
+				.is_ok_and(|position| reg.judgements[position].1 .0 == judgement)
+		}
 
-					// Calculate actual weight for new identity
-					let actual_weight = T::WeightInfo::set_identity(max_field_length as u32);
+		/// Whether the double map holds `judgement` under `judgement_id` for `who`.
+		fn held_in_double_map(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+			judgement: Judgement,
+		) -> bool {
+			JudgementsDoubleMap::<T, I>::get(who, judgement_id)
+				.is_some_and(|(held, _)| held == judgement)
+		}
 
-					(reg, actual_weight)
-				},
-			};
-			let (mut registration, actual_weight) = id;
+		/// Whether the judgement `item` on `who`'s identity has to be kept when the identity holder
+		/// drops judgements: it is sticky or disputed.
+		pub(crate) fn is_frozen(
+			who: &T::AccountId,
+			(judgement_id, (judgement, _)): &(T::JudgementId, DatedJudgementOf<T, I>),
+		) -> bool {
+			judgement.is_sticky() || Disputes::<T, I>::contains_key(who, judgement_id)
+		}
 
-			let new_deposit = Self::calculate_identity_deposit(&registration.info);
-			let old_deposit = registration.deposit;
-			Self::rejig_deposit(&sender, old_deposit, new_deposit)?;
+		/// Drop the non-sticky judgements on `fields` of `who`'s identity.
+		fn drop_field_judgements(who: &T::AccountId, fields: IdentityFields) {
+			if fields.is_empty() {
+				return;
+			}
+			FieldJudgements::<T, I>::mutate_exists(who, |maybe_judgements| {
+				if let Some(judgements) = maybe_judgements {
+					judgements.retain(|(_, field, judgement)| {
+						judgement.is_sticky() || !fields.contains(*field)
+					});
+					if judgements.is_empty() {
+						*maybe_judgements = None;
+					}
+				}
+			});
+		}
 
-			registration.deposit = new_deposit;
-			IdentityOf::<T>::insert(&sender, registration);
-			Self::deposit_event(Event::IdentitySet { who: sender });
+		/// Derive `reg.status` and `reg.level` of `who`'s identity from its counts of sticky
+		/// judgements, announcing a change of level.
+		fn refresh_status(who: &T::AccountId, reg: &mut Registration<T, I>) {
+			reg.status =
+				VerificationStatus::from_providers(reg.known_good, T::VerificationThreshold::get());
+			let level = VerificationLevel::from_judgements(reg.known_good, reg.erroneous);
+			if level != reg.level {
+				reg.level = level;
+				Self::deposit_event(Event::VerificationLevelChanged { who: who.clone(), level });
+			}
+		}
 
-			// Return actual weight consumed
-			Ok(Some(actual_weight).into())
+		/// Recompute the score and the `KnownGood` providers of `who`'s registration `reg` from
+		/// its inline judgements and its `double_map` judgements.
+		fn recount_judgements(
+			who: &T::AccountId,
+			reg: &mut Registration<T, I>,
+			double_map: &[(T::JudgementId, DatedJudgementOf<T, I>)],
+		) {
+			let judgements = || reg.judgements.iter().chain(double_map);
+			Scores::<T, I>::insert(
+				who,
+				judgements().fold(0i32, |score, (_, (judgement, _))| {
+					score.saturating_add(T::JudgementScore::convert(*judgement))
+				}),
+			);
+			// Both patterns hold a verdict under the same ID as one provider
+			let providers = |verdict| {
+				let mut judgement_ids: Vec<_> = judgements()
+					.filter(|(_, (judgement, _))| *judgement == verdict)
+					.map(|(judgement_id, _)| judgement_id)
+					.collect();
+				judgement_ids.sort();
+				judgement_ids.dedup();
+				judgement_ids.len() as u32
+			};
+			let was_verified = reg.known_good > 0;
+			(reg.known_good, reg.erroneous) =
+				(providers(Judgement::KnownGood), providers(Judgement::Erroneous));
+			Self::refresh_status(who, reg);
+			if was_verified != (reg.known_good > 0) {
+				Self::verification_repriced(who, reg);
+			}
 		}
 
-		/// Provide a judgement for an account's identity using inline storage (BoundedVec).
-		/// This demonstrates the efficient storage pattern where judgements are stored
-		/// inline within the Registration struct as a BoundedVec.
-		///
-		/// The dispatch origin for this call must be `T::JudgementOrigin`.
-		///
-		/// - `judgement_id`: a unique identifier for this judgement provider.
-		/// - `target`: the account whose identity the judgement is upon. This must be an account
-		///   with a registered identity.
-		/// - `judgement_type`: the type of judgement (0=Unknown, 1=Reasonable, 2=KnownGood,
-		///   3=Erroneous, 4=LowQuality).
-		///
-		/// Emits `JudgementGiven` if successful.
-		#[pallet::weight(T::WeightInfo::provide_judgement_inline(T::MaxJudgements::get()))]
-		pub fn provide_judgement_inline(
-			origin: OriginFor<T>,
-			judgement_id: JudgementId,
-			target: T::AccountId,
-			judgement_type: u8,
+		/// Count a judgement given on `target` against its provider's `T::MaxJudgementsPerBlock`
+		/// and in its `ProviderStats`, record it in `RecentJudgements` and its `reason` in
+		/// `JudgementReasons`, and notify `T::OnJudgementGiven` of it.
+		fn judgement_given(
+			target: &T::AccountId,
+			judgement_id: &T::JudgementId,
+			judgement: Judgement,
+			reason: Option<ReasonCode>,
 		) -> DispatchResult {
-			T::JudgementOrigin::ensure_origin(origin)?;
+			Self::count_judgement_in_block(judgement_id)?;
+			Self::record_provider_stats(judgement_id, judgement);
+			match reason {
+				Some(reason) => JudgementReasons::<T, I>::insert(target, judgement_id, reason),
+				None => JudgementReasons::<T, I>::remove(target, judgement_id),
+			}
+			let size = T::MaxRecentJudgements::get();
+			if size > 0 {
+				let slot = RecentJudgementsHead::<T, I>::get() % size;
+				let activity = JudgementActivity {
+					target: target.clone(),
+					judgement_id: judgement_id.clone(),
+					judgement,
+					at: T::BlockNumberProvider::current_block_number(),
+				};
+				RecentJudgements::<T, I>::insert(slot, activity);
+				RecentJudgementsHead::<T, I>::put((slot + 1) % size);
+			}
+			if judgement == Judgement::KnownGood {
+				Self::schedule_revalidation(target, judgement_id);
+			}
+			T::OnJudgementGiven::on_judgement_given(target, judgement_id, judgement);
+			Ok(())
+		}
 
-			// Convert u8 to Judgement
-			let judgement = Self::u8_to_judgement(judgement_type)?;
+		/// Hold `amount` of `who`'s free balance through `T::DepositBackend`, counting it in
+		/// `TotalDeposits`.
+		fn hold_deposit(who: &T::AccountId, amount: BalanceOf<T, I>) -> DispatchResult {
+			T::DepositBackend::hold(who, amount)?;
+			TotalDeposits::<T, I>::mutate(|total| *total = total.saturating_add(amount));
+			Ok(())
+		}
 
-			// Add judgement only to the inline BoundedVec storage
-			Self::add_judgement_inline(&target, judgement_id, judgement)?;
+		/// Release up to `amount` of `who`'s deposits through `T::DepositBackend`, returning what
+		/// could not be released.
+		fn release_deposit(who: &T::AccountId, amount: BalanceOf<T, I>) -> BalanceOf<T, I> {
+			let remaining = T::DepositBackend::release(who, amount);
+			TotalDeposits::<T, I>::mutate(|total| {
+				*total = total.saturating_sub(amount.saturating_sub(remaining))
+			});
+			remaining
+		}
 
-			Self::deposit_event(Event::JudgementGiven { target });
+		/// Slash up to `amount` of `who`'s deposits through `T::DepositBackend`, returning what
+		/// could not be slashed.
+		fn slash_deposit(who: &T::AccountId, amount: BalanceOf<T, I>) -> BalanceOf<T, I> {
+			let remaining = T::DepositBackend::slash(who, amount);
+			TotalDeposits::<T, I>::mutate(|total| {
+				*total = total.saturating_sub(amount.saturating_sub(remaining))
+			});
+			remaining
+		}
 
-			Ok(())
+		/// Add up everything held through `T::DepositBackend` from where it is recorded, as
+		/// `TotalDeposits` should hold it. Iterates over every identity, request, dispute,
+		/// registrar, accrued fee and pending release.
+		#[cfg(any(feature = "try-runtime", test))]
+		pub(crate) fn recompute_total_deposits() -> BalanceOf<T, I> {
+			Self::recompute_total_deposits_with(
+				IdentityOf::<T, I>::iter_values().map(|reg| reg.total_deposit()),
+			)
 		}
 
-		/// Provide a judgement for an account's identity using double map storage.
-		/// This demonstrates the double map storage pattern where judgements are stored
-		/// in a separate DoubleMap, which can be less efficient for cleanup operations but slightly
-		/// faster for `set_identity` in case of many judgements.
-		///
-		/// Note: This version assumes judgement deposit is not necessary and will create storage
-		/// bloat if `T::JudgementOrigin` is not properly managed.
-		///
-		/// The dispatch origin for this call must be `T::JudgementOrigin`.
-		///
-		/// - `judgement_id`: a unique identifier for this judgement provider.
-		/// - `target`: the account whose identity the judgement is upon. This must be an account
-		///   with a registered identity.
-		/// - `judgement_type`: the type of judgement (0=Unknown, 1=Reasonable, 2=KnownGood,
-		///   3=Erroneous, 4=LowQuality).
-		///
-		/// Emits `JudgementGiven` if successful.
-		#[pallet::weight(T::WeightInfo::provide_judgement_double_map())]
-		pub fn provide_judgement_double_map(
-			origin: OriginFor<T>,
-			judgement_id: JudgementId,
-			target: T::AccountId,
-			judgement_type: u8,
-		) -> DispatchResult {
-			T::JudgementOrigin::ensure_origin(origin)?;
+		/// [`Self::recompute_total_deposits`], with the total deposit of each identity given, e.g.
+		/// by a migration reading an earlier layout of `IdentityOf`.
+		pub(crate) fn recompute_total_deposits_with(
+			identity_deposits: impl Iterator<Item = BalanceOf<T, I>>,
+		) -> BalanceOf<T, I> {
+			let sum =
+				|total: BalanceOf<T, I>, amount: BalanceOf<T, I>| total.saturating_add(amount);
+			[
+				identity_deposits.fold(Zero::zero(), sum),
+				JudgementRequests::<T, I>::iter_values().fold(Zero::zero(), sum),
+				Disputes::<T, I>::iter_values().fold(Zero::zero(), sum),
+				Registrars::<T, I>::iter_values()
+					.map(|registrar| registrar.bond)
+					.fold(Zero::zero(), sum),
+				AccruedFees::<T, I>::iter_values().fold(Zero::zero(), sum),
+				PendingReleases::<T, I>::iter_values()
+					.map(|pending| pending.amount)
+					.fold(Zero::zero(), sum),
+			]
+			.into_iter()
+			.fold(Zero::zero(), sum)
+		}
 
-			// Convert u8 to Judgement
-			let judgement = Self::u8_to_judgement(judgement_type)?;
+		/// Count this block as the last activity of `who` towards `T::InactivityPeriod`, if it
+		/// nominated a beneficiary.
+		fn note_activity(who: &T::AccountId) {
+			Beneficiaries::<T, I>::mutate_extant(who, |beneficiary| {
+				beneficiary.since = T::BlockNumberProvider::current_block_number()
+			});
+		}
 
-			// Check that target has an identity and validate sticky judgements
-			let _is_new_judgement =
-				IdentityOf::<T>::try_mutate(&target, |maybe_reg| -> Result<bool, DispatchError> {
-					let reg = maybe_reg.as_mut().ok_or(Error::<T>::InvalidTarget)?;
+		/// Let `who`'s identity expire `T::IdentityLifetime` blocks from now, or never if that is
+		/// zero. Returns when it expires.
+		fn renew_expiry(who: &T::AccountId) -> Option<BlockNumberOf<T, I>> {
+			let lifetime = T::IdentityLifetime::get();
+			if lifetime.is_zero() {
+				IdentityExpiries::<T, I>::remove(who);
+				return None;
+			}
+			let expires_at =
+				T::BlockNumberProvider::current_block_number().saturating_add(lifetime);
+			IdentityExpiries::<T, I>::insert(who, IdentityExpiry::Expires(expires_at));
+			Some(expires_at)
+		}
 
-					// Check for existing judgement in double map
-					if let Some(existing_judgement) =
-						JudgementsDoubleMap::<T>::get(&target, judgement_id)
-					{
-						if existing_judgement.is_sticky() {
-							return Err(Error::<T>::StickyJudgement.into());
-						}
-						// Existing judgement being replaced
-						Ok(false)
-					} else {
-						// New judgement being added - increment counter
-						ensure!(
-							reg.judgements_count_double_map < T::MaxJudgements::get(),
-							Error::<T>::TooManyJudgements
-						);
-						reg.judgements_count_double_map =
-							reg.judgements_count_double_map.saturating_add(1);
-						Ok(true)
-					}
-				})?;
+		/// Whether `who`'s identity pays rent rather than holding a deposit: while
+		/// `T::IdentityRent` is non-zero, unless `who` is in `ExemptAccounts`.
+		fn pays_rent(who: &T::AccountId) -> bool {
+			!T::IdentityRent::get().is_zero() && !ExemptAccounts::<T, I>::contains_key(who)
+		}
 
-			// Add judgement to the double map storage
-			JudgementsDoubleMap::<T>::insert(&target, judgement_id, judgement);
+		/// Collect the rent `who`'s identity owes by now, if it pays rent: that of every period
+		/// since the one last paid, or the current one if it paid none yet. Stops the rent of an
+		/// identity which no longer pays any. Returns whether any rent was paid.
+		fn pay_due_rent(who: &T::AccountId) -> Result<bool, DispatchError> {
+			if !Self::pays_rent(who) {
+				Self::stop_rent(who);
+				return Ok(false);
+			}
+			let now = T::BlockNumberProvider::current_block_number();
+			let due = match RentStates::<T, I>::get(who) {
+				Some(RentState::PaidUntil(at)) if at > now => return Ok(false),
+				Some(RentState::PaidUntil(at) | RentState::Delinquent(at)) => at,
+				None => now,
+			};
+			Self::collect_rent(who, due, now)?;
+			Ok(true)
+		}
 
-			Self::deposit_event(Event::JudgementGiven { target });
+		/// Charge `who` the rent of every period from the one falling due in `due` to the one
+		/// `now` is in, handing it to `T::RentDestination`, and schedule the collection of the
+		/// next.
+		fn collect_rent(
+			who: &T::AccountId,
+			due: BlockNumberOf<T, I>,
+			now: BlockNumberOf<T, I>,
+		) -> DispatchResult {
+			let period = T::RentPeriod::get();
+			let mut periods = now.saturating_sub(due).checked_div(&period).unwrap_or_default();
+			periods.saturating_inc();
+			let count: u32 = periods.unique_saturated_into();
+			let amount = T::IdentityRent::get().saturating_mul(BalanceOf::<T, I>::from(count));
+			let imbalance = T::Currency::withdraw(
+				who,
+				amount,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive,
+			)?;
+			T::RentDestination::on_unbalanced(imbalance);
 
+			let paid_until = due.saturating_add(period.saturating_mul(periods));
+			Self::schedule_rent(who, paid_until);
+			Self::deposit_event(Event::RentPaid { who: who.clone(), amount, paid_until });
 			Ok(())
 		}
 
-		/// Clear an account's identity info and return all deposits.
-		/// This extrinsic handles both storage patterns - the complexity depends on usage:
-		/// - O(1) if only inline judgements were used (via provide_judgement_inline)
-		/// - O(n) if double map judgements were used, where n = actual number of double map
-		///   judgements
-		///
-		/// Payment: All reserved balances on the account are returned.
-		///
-		/// The dispatch origin for this call must be _Signed_ and the sender must have a registered
-		/// identity.
-		///
-		/// Emits `IdentityCleared` if successful.
-		#[pallet::weight(T::WeightInfo::clear_identity_double_map_usage(T::MaxJudgements::get()))]
-		pub fn clear_identity(origin: OriginFor<T>) -> DispatchResult {
-			let sender = ensure_signed(origin)?;
+		/// Record `who`'s rent as paid up to `paid_until`, and have the next collected then.
+		fn schedule_rent(who: &T::AccountId, paid_until: BlockNumberOf<T, I>) {
+			if let Some(RentState::PaidUntil(at)) = RentStates::<T, I>::get(who) {
+				RentDue::<T, I>::remove(at, who);
+			}
+			if !RentCursor::<T, I>::exists() {
+				RentCursor::<T, I>::put(paid_until);
+			}
+			RentDue::<T, I>::insert(paid_until, who, ());
+			RentStates::<T, I>::insert(who, RentState::PaidUntil(paid_until));
+		}
 
-			let id = IdentityOf::<T>::take(&sender).ok_or(Error::<T>::NoIdentity)?;
-			let deposit = id.total_deposit();
+		/// Stop charging rent for `who`'s identity, e.g. as it is cleared or exempted. Whatever
+		/// it owes is forgiven.
+		fn stop_rent(who: &T::AccountId) {
+			if let Some(RentState::PaidUntil(at)) = RentStates::<T, I>::take(who) {
+				RentDue::<T, I>::remove(at, who);
+			}
+		}
 
-			// Always cleanup double map judgements (this is O(n) where n = actual judgements)
-			// This operation uses drain_prefix and will be fast if no double map judgements exist
-			let cleared = Self::clear_judgements_double_map(&sender);
-			debug_assert_eq!(cleared, id.judgements_count_double_map);
+		/// Mark `who`'s identity delinquent, as the rent falling due in `due` couldn't be
+		/// collected.
+		fn mark_delinquent(who: &T::AccountId, due: BlockNumberOf<T, I>) {
+			RentStates::<T, I>::insert(who, RentState::Delinquent(due));
+			Self::deposit_event(Event::IdentityDelinquent { who: who.clone() });
+		}
 
-			// The inline judgements are automatically dropped with the Registration struct (O(1))
+		/// Have `who`'s identity start paying rent now if it should and doesn't yet, as it no
+		/// longer holds a deposit, marking it delinquent if it can't. Stops its rent if it
+		/// shouldn't pay any.
+		fn reconcile_rent(who: &T::AccountId) {
+			if !Self::pays_rent(who) {
+				Self::stop_rent(who);
+				return;
+			}
+			if RentStates::<T, I>::contains_key(who) {
+				return;
+			}
+			let now = T::BlockNumberProvider::current_block_number();
+			if Self::collect_rent(who, now, now).is_err() {
+				Self::mark_delinquent(who, now);
+			}
+		}
 
-			let err_amount = T::Currency::unreserve(&sender, deposit);
-			debug_assert!(err_amount.is_zero());
+		/// Collect the rent falling due by now, of up to `T::MaxRentCollections` identities,
+		/// marking those which can't pay delinquent. A block without any due counts as one
+		/// collection, so that catching up on a gap in the block numbers is bounded too.
+		fn collect_due_rent() -> Weight {
+			if T::IdentityRent::get().is_zero() {
+				return Weight::zero();
+			}
+			let Some(mut block) = RentCursor::<T, I>::get() else {
+				return T::WeightInfo::collect_due_rent(0);
+			};
+			let now = T::BlockNumberProvider::current_block_number();
+			let limit = T::MaxRentCollections::get();
+			let mut collected = 0u32;
+			while block <= now && collected < limit {
+				let due: Vec<_> = RentDue::<T, I>::drain_prefix(block)
+					.take(limit.saturating_sub(collected) as usize)
+					.collect();
+				collected = collected.saturating_add((due.len() as u32).max(1));
+				for (who, ()) in due {
+					Self::collect_rent_due_in(&who, block, now);
+				}
+				if RentDue::<T, I>::iter_prefix(block).next().is_some() {
+					break;
+				}
+				block.saturating_inc();
+			}
+			RentCursor::<T, I>::put(block);
+			T::WeightInfo::collect_due_rent(collected)
+		}
 
-			Self::deposit_event(Event::IdentityCleared { who: sender, deposit });
-			Ok(())
+		/// Collect the rent of `who`'s identity falling due in `due`, unless it was paid or
+		/// stopped since, marking the identity delinquent if it can't pay.
+		fn collect_rent_due_in(
+			who: &T::AccountId,
+			due: BlockNumberOf<T, I>,
+			now: BlockNumberOf<T, I>,
+		) {
+			if RentStates::<T, I>::get(who) != Some(RentState::PaidUntil(due)) {
+				return;
+			}
+			if !Self::pays_rent(who) {
+				Self::stop_rent(who);
+			} else if Self::collect_rent(who, due, now).is_err() {
+				Self::mark_delinquent(who, due);
+			}
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		/// Get the identity information for an account.
-		pub fn identity_of(who: &T::AccountId) -> Option<Registration<T>> {
-			IdentityOf::<T>::get(who)
+		/// The key `display` is indexed under in `DisplayIndex`: its first
+		/// `T::DisplayIndexLength` bytes, lowercased. `None` for an empty display or without an
+		/// index.
+		fn display_prefix(display: &[u8]) -> Option<DisplayPrefixOf<T, I>> {
+			let length = (T::DisplayIndexLength::get() as usize).min(display.len());
+			if length == 0 {
+				return None
+			}
+			display[..length].to_ascii_lowercase().try_into().ok()
 		}
 
-		/// Convert u8 to Judgement enum
-		fn u8_to_judgement(judgement_type: u8) -> Result<Judgement, DispatchError> {
-			match judgement_type {
-				0 => Ok(Judgement::Unknown),
-				1 => Ok(Judgement::Reasonable),
-				2 => Ok(Judgement::KnownGood),
-				3 => Ok(Judgement::Erroneous),
-				4 => Ok(Judgement::LowQuality),
-				_ => Err(Error::<T>::InvalidJudgement.into()),
+		/// Index `who` in `DisplayIndex` under its display name `display`.
+		fn index_display(who: &T::AccountId, display: &[u8]) {
+			if let Some(key) = Self::display_prefix(display) {
+				DisplayIndex::<T, I>::insert(key, who, ());
 			}
 		}
 
-		/// Calculate the deposit required for an identity.
-		fn calculate_identity_deposit(info: &IdentityInfo<T::MaxFieldLength>) -> BalanceOf<T> {
-			let bytes = info.encoded_size();
-			let byte_deposit = T::ByteDeposit::get().saturating_mul(BalanceOf::<T>::from(bytes));
-			T::BasicDeposit::get().saturating_add(byte_deposit)
+		/// Remove `who` from `DisplayIndex`, where it was indexed under its display name
+		/// `display`.
+		fn unindex_display(who: &T::AccountId, display: &[u8]) {
+			if let Some(key) = Self::display_prefix(display) {
+				DisplayIndex::<T, I>::remove(key, who);
+			}
 		}
 
-		/// Helper function to clear all judgements from the double map for an account.
-		/// This demonstrates efficient cleanup using clear_prefix - O(j) where j is actual
-		/// judgements.
-		fn clear_judgements_double_map(who: &T::AccountId) -> u32 {
-			let removed = JudgementsDoubleMap::<T>::drain_prefix(who);
-			removed.count() as u32
+		/// Count `judgement` in the statistics of the provider giving it under `judgement_id`.
+		fn record_provider_stats(judgement_id: &T::JudgementId, judgement: Judgement) {
+			let now = T::BlockNumberProvider::current_block_number();
+			ProviderStats::<T, I>::mutate(judgement_id, |stats| stats.record(judgement, now));
 		}
 
-		/// Helper function to add a judgement to inline storage only (BoundedVec).
-		/// This demonstrates the efficient inline storage pattern.
-		fn add_judgement_inline(
-			who: &T::AccountId,
-			judgement_id: JudgementId,
-			judgement: Judgement,
-		) -> Result<(), DispatchError> {
-			IdentityOf::<T>::try_mutate(who, |maybe_reg| -> Result<(), DispatchError> {
-				let reg = maybe_reg.as_mut().ok_or(Error::<T>::InvalidTarget)?;
+		/// Count a judgement written under `judgement_id` in this block, failing once
+		/// `T::MaxJudgementsPerBlock` of them were. Vouches and bridged judgements aren't counted:
+		/// no provider of this chain gives them.
+		fn count_judgement_in_block(judgement_id: &T::JudgementId) -> DispatchResult {
+			let max = T::MaxJudgementsPerBlock::get();
+			if max == 0 || Self::ensure_local_judgement_id(judgement_id).is_err() {
+				return Ok(());
+			}
+			JudgementsInBlock::<T, I>::try_mutate(judgement_id, |count| {
+				ensure!(*count < max, Error::<T, I>::TooManyJudgementsInBlock);
+				count.saturating_inc();
+				Ok(())
+			})
+		}
 
-				// Use binary search for the BoundedVec (efficient)
-				let item = (judgement_id, judgement);
-				match reg.judgements.binary_search_by_key(&judgement_id, |x| x.0) {
-					Ok(position) => {
-						// Judgement exists, check if it's sticky
-						if reg.judgements[position].1.is_sticky() {
-							return Err(Error::<T>::StickyJudgement.into())
-						}
-						// Replace the existing judgement
-						reg.judgements[position] = item;
-					},
-					Err(position) => {
-						// Insert new judgement at the correct position to maintain ordering
-						reg.judgements
-							.try_insert(position, item)
-							.map_err(|_| Error::<T>::TooManyJudgements)?;
-					},
-				}
+		/// Count a set of `who`'s identity in its window of `T::IdentitySetPeriod` blocks, starting
+		/// a new window if the last one is over, failing once `T::MaxSetsPerPeriod` sets were
+		/// counted in it.
+		fn count_identity_set(who: &T::AccountId) -> DispatchResult {
+			let max = T::MaxSetsPerPeriod::get();
+			if max == 0 {
+				return Ok(());
+			}
+			let now = T::BlockNumberProvider::current_block_number();
+			let period = T::IdentitySetPeriod::get();
+			IdentitySets::<T, I>::try_mutate(who, |window| {
+				let (start, sets) = match *window {
+					Some((start, sets)) if now < start.saturating_add(period) => (start, sets),
+					_ => (now, 0),
+				};
+				ensure!(sets < max, Error::<T, I>::TooManyIdentitySets);
+				*window = Some((start, sets.saturating_add(1)));
 				Ok(())
 			})
 		}
 
+		/// Announce a change of `who`'s verification status and notify `T::OnVerified` if it
+		/// became, or stopped being, verified.
+		fn status_changed(who: &T::AccountId, old: VerificationStatus, new: VerificationStatus) {
+			if old == new {
+				return;
+			}
+			if new == VerificationStatus::Verified {
+				T::OnVerified::on_verified(who);
+			} else if old == VerificationStatus::Verified {
+				T::OnVerified::on_revoked(who);
+			}
+			Self::deposit_event(Event::VerificationStatusChanged { who: who.clone(), status: new });
+		}
+
+		/// Replace the note attached to `judgement_id` on `who`'s identity, reserving or
+		/// unreserving the difference in note deposit. An empty note removes it, `None` leaves it
+		/// as it is.
+		fn update_judgement_note(
+			who: &T::AccountId,
+			reg: &mut Registration<T, I>,
+			judgement_id: &T::JudgementId,
+			note: Option<JudgementNote<T, I>>,
+		) -> DispatchResult {
+			let Some(note) = note else { return Ok(()) };
+			let note = Some(note).filter(|note| !note.is_empty());
+			let old_deposit = JudgementNotes::<T, I>::get(who, judgement_id)
+				.map_or_else(Zero::zero, |old| Self::calculate_note_deposit(&old));
+			let new_deposit = note.as_ref().map_or_else(Zero::zero, Self::calculate_note_deposit);
+			Self::rejig_deposit(who, old_deposit, new_deposit)?;
+
+			reg.notes_deposit =
+				reg.notes_deposit.saturating_sub(old_deposit).saturating_add(new_deposit);
+			match note {
+				Some(note) => JudgementNotes::<T, I>::insert(who, judgement_id, note),
+				None => JudgementNotes::<T, I>::remove(who, judgement_id),
+			}
+			Ok(())
+		}
+
+		/// Remove the note and the reason code attached to an inline judgement that is being
+		/// dropped, unless a double map judgement with the same ID still refers to them. Returns
+		/// the refunded deposit.
+		fn drop_orphaned_note(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+		) -> BalanceOf<T, I> {
+			if JudgementsDoubleMap::<T, I>::contains_key(who, judgement_id) {
+				return Zero::zero();
+			}
+			JudgementReasons::<T, I>::remove(who, judgement_id);
+			JudgementNotes::<T, I>::take(who, judgement_id).map_or_else(Zero::zero, |note| {
+				let deposit = Self::calculate_note_deposit(&note);
+				let err_amount = Self::release_deposit(who, deposit);
+				debug_assert!(err_amount.is_zero());
+				deposit
+			})
+		}
+
+		/// Remove `who` from the accounts judged under `judgement_id` as its inline judgement is
+		/// dropped, unless a double map judgement with the same ID remains.
+		fn forget_judged(who: &T::AccountId, judgement_id: &T::JudgementId) {
+			if !JudgementsDoubleMap::<T, I>::contains_key(who, judgement_id) {
+				JudgedBy::<T, I>::remove(judgement_id, who);
+			}
+		}
+
 		/// Take the `current` deposit that `who` is holding, and update it to a `new` one.
 		fn rejig_deposit(
 			who: &T::AccountId,
-			current: BalanceOf<T>,
-			new: BalanceOf<T>,
+			current: BalanceOf<T, I>,
+			new: BalanceOf<T, I>,
 		) -> DispatchResult {
-			if new > current {
-				T::Currency::reserve(who, new - current)?;
-			} else if new < current {
-				let err_amount = T::Currency::unreserve(who, current - new);
-				debug_assert!(err_amount.is_zero());
+			match new.cmp(&current) {
+				core::cmp::Ordering::Greater => Self::hold_deposit(who, new - current)?,
+				core::cmp::Ordering::Less => {
+					let err_amount = Self::release_deposit(who, current - new);
+					debug_assert!(err_amount.is_zero());
+				},
+				core::cmp::Ordering::Equal => {},
 			}
 			Ok(())
 		}
 	}
+
+	/// Removes the identity of a reaped account, which would otherwise be left orphaned: the
+	/// registration, its judgements in both storage patterns and everything kept alongside, as
+	/// `clear_identity` does. Nothing is released to the account, which is gone along with what
+	/// was held of it, and no tombstone is kept. A runtime sets it, or a tuple including it, as
+	/// `frame_system::Config::OnKilledAccount`.
+	///
+	/// An identity with a beneficiary is kept instead, for the beneficiary to take over with
+	/// `claim_identity` without waiting out `T::InactivityPeriod`, holding from its own balance
+	/// what was held of the reaped account.
+	///
+	/// An identity holds a consumer reference on its account, so the account is only reaped
+	/// without clearing it first if its references were forcibly dropped.
+	impl<T: Config<I>, I: 'static> OnKilledAccount<T::AccountId> for Pallet<T, I> {
+		fn on_killed_account(who: &T::AccountId) {
+			IdentitySets::<T, I>::remove(who);
+			let pending = PendingReleases::<T, I>::take(who).map_or(Zero::zero(), |p| p.amount);
+			let identity = if Beneficiaries::<T, I>::contains_key(who) {
+				None
+			} else {
+				IdentityOf::<T, I>::take(who)
+			};
+			let deposit = match identity {
+				Some(id) => {
+					Self::unindex_display(who, &id.info.display);
+					if id.status == VerificationStatus::Verified {
+						T::OnVerified::on_revoked(who);
+					}
+					Self::remove_identity_state(who, &id)
+				},
+				None if pending.is_zero() => return,
+				None => Zero::zero(),
+			}
+			.saturating_add(pending);
+			TotalDeposits::<T, I>::mutate(|total| *total = total.saturating_sub(deposit));
+			Self::deposit_event(Event::IdentityKilled { who: who.clone(), deposit });
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> InspectIdentity<T::AccountId> for Pallet<T, I> {
+		fn has_identity(who: &T::AccountId) -> bool {
+			IdentityOf::<T, I>::contains_key(who)
+		}
+
+		fn verification_status(who: &T::AccountId) -> VerificationStatus {
+			Self::verification_status(who)
+		}
+
+		fn verification_level(who: &T::AccountId) -> VerificationLevel {
+			Self::verification_level(who)
+		}
+
+		fn lookup_weight() -> Weight {
+			T::DbWeight::get().reads(1).saturating_add(Weight::from_parts(
+				0,
+				Registration::<T, I>::max_encoded_len() as u64,
+			))
+		}
+	}
+
+	impl<T: Config<I>, I: 'static>
+		ReceiveRemoteJudgement<OriginFor<T>, T::AccountId, T::JudgementId> for Pallet<T, I>
+	{
+		fn receive_remote_judgement(
+			origin: OriginFor<T>,
+			judgement_id: T::JudgementId,
+			target: T::AccountId,
+			judgement: Judgement,
+		) -> DispatchResult {
+			let registrar = T::BridgeOrigin::ensure_origin(origin)?;
+			Self::ensure_not_paused()?;
+			ensure!(
+				T::BridgedJudgementIds::get().contains(&judgement_id),
+				Error::<T, I>::NotBridgedJudgementId
+			);
+			Self::ensure_verdict(judgement)?;
+
+			// Not dispatched, so not transactional either: nothing is kept of a judgement failing
+			// halfway through
+			frame_support::storage::with_storage_layer(|| {
+				// No request can be pending: registrars can't be set for bridged judgement IDs
+				let (old_status, new_status) =
+					Self::add_judgement_inline(&target, &judgement_id, judgement, None)?;
+				RemoteJudgementSources::<T, I>::insert(&target, &judgement_id, registrar.clone());
+				Self::status_changed(&target, old_status, new_status);
+				Self::judgement_given(&target, &judgement_id, judgement, None)?;
+
+				Self::deposit_event(Event::BridgedJudgementReceived {
+					target,
+					judgement_id,
+					registrar,
+				});
+				Ok(())
+			})
+		}
+
+		fn receive_remote_judgement_weight() -> Weight {
+			T::WeightInfo::receive_remote_judgement(T::MaxJudgements::get())
+		}
+	}
 }
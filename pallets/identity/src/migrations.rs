@@ -0,0 +1,1253 @@
+//! Storage migrations for the Identity pallet.
+
+use super::*;
+
+/// Migrations to storage version 1, which adds `field_bytes` to [`Registration`], along with
+/// the fields added to it before storage versions were kept.
+pub mod v1 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// The storage layouts of version 0.
+	pub mod v0 {
+		use super::*;
+
+		/// [`Registration`] as first deployed, before `notes_deposit`, `known_good`, `status` and
+		/// `field_bytes`. Its identity information was bounded by a single `MaxFieldLength`, which
+		/// each per-field limit must be at least, and judgement IDs were `u32`.
+		#[derive(Encode, Decode)]
+		pub struct Registration<T: Config<I>, I: 'static = ()> {
+			pub info: v7::IdentityInfo<T, I>,
+			pub judgements: BoundedVec<(u32, Judgement), T::MaxJudgements>,
+			pub judgements_count_double_map: u32,
+			pub deposit: BalanceOf<T, I>,
+		}
+	}
+
+	/// [`Registration`] at version 1, before `verified_fields`.
+	#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct Registration<T: Config<I>, I: 'static = ()> {
+		pub info: v7::IdentityInfo<T, I>,
+		pub judgements: BoundedVec<(T::JudgementId, Judgement), T::MaxJudgements>,
+		pub judgements_count_double_map: u32,
+		pub deposit: BalanceOf<T, I>,
+		pub field_bytes: [u32; 4],
+		pub notes_deposit: BalanceOf<T, I>,
+		pub known_good: u32,
+		pub status: VerificationStatus,
+	}
+
+	/// [`IdentityOf`] at version 1.
+	#[frame_support::storage_alias]
+	pub type IdentityOf<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Registration<T, I>,
+		OptionQuery,
+	>;
+
+	/// Fill in `field_bytes` of every registration from its identity information,
+	/// `notes_deposit` from the notes on its judgements, and `known_good` and `status` from the
+	/// providers of `KnownGood` judgements in both storage patterns. Deposits are left as they
+	/// are: they were priced on the same bytes.
+	pub struct InnerMigrateV0ToV1<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T, I>
+	where
+		T::JudgementId: From<u32>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			let (mut translated, mut reads) = (0u64, 0u64);
+			IdentityOf::<T, I>::translate::<v0::Registration<T, I>, _>(|who, old| {
+				translated.saturating_inc();
+				let judgements: BoundedVec<(T::JudgementId, _), _> = BoundedVec::truncate_from(
+					old.judgements
+						.into_iter()
+						.map(|(judgement_id, judgement)| (judgement_id.into(), judgement))
+						.collect(),
+				);
+				let notes_deposit = JudgementNotes::<T, I>::iter_prefix_values(&who).fold(
+					Zero::zero(),
+					|total: BalanceOf<T, I>, note| {
+						reads.saturating_inc();
+						total.saturating_add(Pallet::<T, I>::calculate_note_deposit(&note))
+					},
+				);
+				// Both patterns hold a verdict under the same ID as one provider
+				let mut known_good: Vec<_> = judgements
+					.iter()
+					.filter(|(_, judgement)| *judgement == Judgement::KnownGood)
+					.map(|(judgement_id, _)| judgement_id.clone())
+					.collect();
+				for (judgement_id, judgement) in v6::JudgementsDoubleMap::<T, I>::iter_prefix(&who)
+				{
+					reads.saturating_inc();
+					if judgement == Judgement::KnownGood {
+						known_good.push(judgement_id);
+					}
+				}
+				known_good.sort();
+				known_good.dedup();
+				let known_good = known_good.len() as u32;
+				Some(Registration {
+					field_bytes: old.info.field_sizes(),
+					info: old.info,
+					judgements,
+					judgements_count_double_map: old.judgements_count_double_map,
+					deposit: old.deposit,
+					notes_deposit,
+					known_good,
+					status: VerificationStatus::from_providers(
+						known_good,
+						T::VerificationThreshold::get(),
+					),
+				})
+			});
+			T::DbWeight::get().reads_writes(translated.saturating_add(reads), translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((IdentityOf::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state")?;
+			let mut migrated = 0u32;
+			for registration in IdentityOf::<T, I>::iter_values() {
+				ensure!(
+					registration.field_bytes == registration.info.field_sizes(),
+					"Field sizes don't match the identity information"
+				);
+				ensure!(
+					registration.status ==
+						VerificationStatus::from_providers(
+							registration.known_good,
+							T::VerificationThreshold::get(),
+						),
+					"A verification status doesn't match the judgements"
+				);
+				migrated.saturating_inc();
+			}
+			ensure!(migrated == count, "Registrations were lost in the migration");
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV0ToV1`], run only while the pallet is at storage version 0.
+	pub type MigrateV0ToV1<T, I = ()> = VersionedMigration<
+		0,
+		1,
+		InnerMigrateV0ToV1<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 2, which adds `verified_fields` to [`Registration`].
+pub mod v2 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// [`Registration`] at versions 2 and 3, before `CounterForJudgements`.
+	#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct Registration<T: Config<I>, I: 'static = ()> {
+		pub info: v7::IdentityInfo<T, I>,
+		pub judgements: BoundedVec<(T::JudgementId, Judgement), T::MaxJudgements>,
+		pub judgements_count_double_map: u32,
+		pub deposit: BalanceOf<T, I>,
+		pub field_bytes: [u32; 4],
+		pub notes_deposit: BalanceOf<T, I>,
+		pub known_good: u32,
+		pub status: VerificationStatus,
+		pub verified_fields: IdentityFields,
+	}
+
+	/// [`IdentityOf`] at versions 2 and 3.
+	#[frame_support::storage_alias]
+	pub type IdentityOf<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Registration<T, I>,
+		OptionQuery,
+	>;
+
+	/// Add an empty `verified_fields` to every registration: no field was verified on its own
+	/// before.
+	pub struct InnerMigrateV1ToV2<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV1ToV2<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			IdentityOf::<T, I>::translate::<v1::Registration<T, I>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(Registration {
+					info: old.info,
+					judgements: old.judgements,
+					judgements_count_double_map: old.judgements_count_double_map,
+					deposit: old.deposit,
+					field_bytes: old.field_bytes,
+					notes_deposit: old.notes_deposit,
+					known_good: old.known_good,
+					status: old.status,
+					verified_fields: IdentityFields::empty(),
+				})
+			});
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((v1::IdentityOf::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state")?;
+			let migrated = IdentityOf::<T, I>::iter_values().count() as u32;
+			ensure!(migrated == count, "Registrations were lost in the migration");
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV1ToV2`], run only while the pallet is at storage version 1.
+	pub type MigrateV1ToV2<T, I = ()> = VersionedMigration<
+		1,
+		2,
+		InnerMigrateV1ToV2<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 3, which adds the [`JudgedBy`] index.
+pub mod v3 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// Index the judgements already given, in both storage patterns, in `JudgedBy`.
+	pub struct InnerMigrateV2ToV3<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV2ToV3<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let (mut reads, mut writes) = (0u64, 0u64);
+			for (who, reg) in v2::IdentityOf::<T, I>::iter() {
+				reads.saturating_inc();
+				for (judgement_id, _) in reg.judgements {
+					JudgedBy::<T, I>::insert(&judgement_id, &who, ());
+					writes.saturating_inc();
+				}
+			}
+			for (who, judgement_id) in JudgementsDoubleMap::<T, I>::iter_keys() {
+				reads.saturating_inc();
+				JudgedBy::<T, I>::insert(&judgement_id, &who, ());
+				writes.saturating_inc();
+			}
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			for (who, reg) in v2::IdentityOf::<T, I>::iter() {
+				for (judgement_id, _) in reg.judgements {
+					ensure!(
+						JudgedBy::<T, I>::contains_key(&judgement_id, &who),
+						"An inline judgement was not indexed"
+					);
+				}
+			}
+			for (who, judgement_id) in JudgementsDoubleMap::<T, I>::iter_keys() {
+				ensure!(
+					JudgedBy::<T, I>::contains_key(&judgement_id, &who),
+					"A double map judgement was not indexed"
+				);
+			}
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV2ToV3`], run only while the pallet is at storage version 2.
+	pub type MigrateV2ToV3<T, I = ()> = VersionedMigration<
+		2,
+		3,
+		InnerMigrateV2ToV3<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 4, which moves the double map judgement counter out of
+/// [`Registration`] into [`CounterForJudgements`].
+pub mod v4 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// [`Registration`] at versions 4 and 5, before `erroneous` and `level`.
+	#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct Registration<T: Config<I>, I: 'static = ()> {
+		pub info: v7::IdentityInfo<T, I>,
+		pub judgements: BoundedVec<(T::JudgementId, Judgement), T::MaxJudgements>,
+		pub deposit: BalanceOf<T, I>,
+		pub field_bytes: [u32; 4],
+		pub notes_deposit: BalanceOf<T, I>,
+		pub known_good: u32,
+		pub status: VerificationStatus,
+		pub verified_fields: IdentityFields,
+	}
+
+	/// [`IdentityOf`] at versions 4 and 5.
+	#[frame_support::storage_alias]
+	pub type IdentityOf<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Registration<T, I>,
+		OptionQuery,
+	>;
+
+	/// Drop `judgements_count_double_map` from every registration, recording the non-zero ones in
+	/// `CounterForJudgements`.
+	pub struct InnerMigrateV3ToV4<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV3ToV4<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let (mut translated, mut counted) = (0u64, 0u64);
+			IdentityOf::<T, I>::translate::<v2::Registration<T, I>, _>(|who, old| {
+				translated.saturating_inc();
+				if old.judgements_count_double_map > 0 {
+					CounterForJudgements::<T, I>::insert(&who, old.judgements_count_double_map);
+					counted.saturating_inc();
+				}
+				Some(Registration {
+					info: old.info,
+					judgements: old.judgements,
+					deposit: old.deposit,
+					field_bytes: old.field_bytes,
+					notes_deposit: old.notes_deposit,
+					known_good: old.known_good,
+					status: old.status,
+					verified_fields: old.verified_fields,
+				})
+			});
+			T::DbWeight::get().reads_writes(translated, translated.saturating_add(counted))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let counters: Vec<(T::AccountId, u32)> = v2::IdentityOf::<T, I>::iter()
+				.map(|(who, reg)| (who, reg.judgements_count_double_map))
+				.collect();
+			Ok(counters.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let counters = Vec::<(T::AccountId, u32)>::decode(&mut &state[..])
+				.map_err(|_| "Invalid pre-upgrade state")?;
+			let migrated = IdentityOf::<T, I>::iter_values().count();
+			ensure!(migrated == counters.len(), "Registrations were lost in the migration");
+			for (who, count) in counters {
+				ensure!(
+					CounterForJudgements::<T, I>::get(&who) == count,
+					"A double map judgement counter was lost in the migration"
+				);
+			}
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV3ToV4`], run only while the pallet is at storage version 3.
+	pub type MigrateV3ToV4<T, I = ()> = VersionedMigration<
+		3,
+		4,
+		InnerMigrateV3ToV4<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 5, which adds `TotalDeposits`.
+pub mod v5 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// Set `TotalDeposits` to everything held through `DepositBackend` so far.
+	pub struct InnerMigrateV4ToV5<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV4ToV5<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let reads = v4::IdentityOf::<T, I>::iter_keys().count() +
+				JudgementRequests::<T, I>::iter_keys().count() +
+				Disputes::<T, I>::iter_keys().count() +
+				Registrars::<T, I>::iter_keys().count() +
+				AccruedFees::<T, I>::iter_keys().count() +
+				PendingReleases::<T, I>::iter_keys().count();
+			TotalDeposits::<T, I>::put(total_deposits::<T, I>());
+			T::DbWeight::get().reads_writes(2 * reads as u64, 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				TotalDeposits::<T, I>::get() == total_deposits::<T, I>(),
+				"`TotalDeposits` differs from the deposits held"
+			);
+			Ok(())
+		}
+	}
+
+	/// Everything held through `DepositBackend`, with the registrations in their version 5
+	/// layout.
+	fn total_deposits<T: Config<I>, I: 'static>() -> BalanceOf<T, I> {
+		Pallet::<T, I>::recompute_total_deposits_with(
+			v4::IdentityOf::<T, I>::iter_values()
+				.map(|reg| reg.deposit.saturating_add(reg.notes_deposit)),
+		)
+	}
+
+	/// [`InnerMigrateV4ToV5`], run only while the pallet is at storage version 4.
+	pub type MigrateV4ToV5<T, I = ()> = VersionedMigration<
+		4,
+		5,
+		InnerMigrateV4ToV5<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 6, which adds `erroneous` and `level` to [`Registration`].
+pub mod v6 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// [`Registration`] at version 6, before judgements were dated.
+	#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct Registration<T: Config<I>, I: 'static = ()> {
+		pub info: v7::IdentityInfo<T, I>,
+		pub judgements: BoundedVec<(T::JudgementId, Judgement), T::MaxJudgements>,
+		pub deposit: BalanceOf<T, I>,
+		pub field_bytes: [u32; 4],
+		pub notes_deposit: BalanceOf<T, I>,
+		pub known_good: u32,
+		pub erroneous: u32,
+		pub status: VerificationStatus,
+		pub level: VerificationLevel,
+		pub verified_fields: IdentityFields,
+	}
+
+	/// [`IdentityOf`] at version 6.
+	#[frame_support::storage_alias]
+	pub type IdentityOf<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Registration<T, I>,
+		OptionQuery,
+	>;
+
+	/// [`JudgementsDoubleMap`] up to version 6, of undated judgements.
+	#[frame_support::storage_alias]
+	pub type JudgementsDoubleMap<T: Config<I>, I: 'static> = StorageDoubleMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		<T as Config<I>>::JudgementId,
+		Judgement,
+		OptionQuery,
+	>;
+
+	/// Count the providers of `Erroneous` judgements of every registration, in both storage
+	/// patterns, and derive its verification level.
+	pub struct InnerMigrateV5ToV6<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV5ToV6<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let (mut translated, mut reads) = (0u64, 0u64);
+			IdentityOf::<T, I>::translate::<v4::Registration<T, I>, _>(|who, old| {
+				translated.saturating_inc();
+				// Both patterns hold a verdict under the same ID as one provider
+				let mut erroneous: Vec<_> = old
+					.judgements
+					.iter()
+					.filter(|(_, judgement)| *judgement == Judgement::Erroneous)
+					.map(|(judgement_id, _)| judgement_id.clone())
+					.collect();
+				for (judgement_id, judgement) in JudgementsDoubleMap::<T, I>::iter_prefix(&who) {
+					reads.saturating_inc();
+					if judgement == Judgement::Erroneous {
+						erroneous.push(judgement_id);
+					}
+				}
+				erroneous.sort();
+				erroneous.dedup();
+				let erroneous = erroneous.len() as u32;
+				Some(Registration {
+					info: old.info,
+					judgements: old.judgements,
+					deposit: old.deposit,
+					field_bytes: old.field_bytes,
+					notes_deposit: old.notes_deposit,
+					known_good: old.known_good,
+					erroneous,
+					status: old.status,
+					level: VerificationLevel::from_judgements(old.known_good, erroneous),
+					verified_fields: old.verified_fields,
+				})
+			});
+			T::DbWeight::get().reads_writes(translated.saturating_add(reads), translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((v4::IdentityOf::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let count = u32::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state")?;
+			let mut migrated = 0u32;
+			for registration in IdentityOf::<T, I>::iter_values() {
+				let level = VerificationLevel::from_judgements(
+					registration.known_good,
+					registration.erroneous,
+				);
+				ensure!(
+					registration.level == level,
+					"A verification level doesn't match the judgements"
+				);
+				migrated.saturating_inc();
+			}
+			ensure!(migrated == count, "Registrations were lost in the migration");
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV5ToV6`], run only while the pallet is at storage version 5.
+	pub type MigrateV5ToV6<T, I = ()> = VersionedMigration<
+		5,
+		6,
+		InnerMigrateV5ToV6<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 7, which dates every judgement with the block it was given in.
+pub mod v7 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// [`IdentityInfo`] up to version 7, before `avatar`.
+	#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct IdentityInfo<T: Config<I>, I: 'static = ()> {
+		pub display: BoundedVec<u8, T::MaxDisplayLength>,
+		pub legal: BoundedVec<u8, T::MaxLegalLength>,
+		pub web: BoundedVec<u8, T::MaxWebLength>,
+		pub email: BoundedVec<u8, T::MaxEmailLength>,
+	}
+
+	impl<T: Config<I>, I: 'static> IdentityInfo<T, I> {
+		/// Encoded size of each field, in declaration order.
+		pub fn field_sizes(&self) -> [u32; 4] {
+			[self.display.len(), self.legal.len(), self.web.len(), self.email.len()]
+				.map(|len| IdentityInfoOf::<T, I>::encoded_field_size(len as u32))
+		}
+	}
+
+	/// [`Registration`] at version 7, before `avatar`.
+	#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct Registration<T: Config<I>, I: 'static = ()> {
+		pub info: IdentityInfo<T, I>,
+		pub judgements: DatedJudgementsOf<T, I>,
+		pub deposit: BalanceOf<T, I>,
+		pub field_bytes: [u32; 4],
+		pub notes_deposit: BalanceOf<T, I>,
+		pub known_good: u32,
+		pub erroneous: u32,
+		pub status: VerificationStatus,
+		pub level: VerificationLevel,
+		pub verified_fields: IdentityFields,
+	}
+
+	/// [`IdentityOf`] at version 7.
+	#[frame_support::storage_alias]
+	pub type IdentityOf<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Registration<T, I>,
+		OptionQuery,
+	>;
+
+	/// [`RemoteIdentity`] up to version 7, before `avatar`.
+	#[derive(Encode, Decode)]
+	pub struct RemoteIdentity<T: Config<I>, I: 'static = ()> {
+		pub info: IdentityInfo<T, I>,
+		pub status: VerificationStatus,
+		pub verified_fields: IdentityFields,
+		pub state_root: T::Hash,
+	}
+
+	/// The inline judgements of every registration before the upgrade, by account.
+	#[cfg(feature = "try-runtime")]
+	type UndatedJudgements<T, I> = Vec<(
+		<T as frame_system::Config>::AccountId,
+		Vec<(<T as Config<I>>::JudgementId, Judgement)>,
+	)>;
+
+	/// Date every judgement, in both storage patterns and their shadow copies, with the block of
+	/// the upgrade: when they were given was never recorded.
+	pub struct InnerMigrateV6ToV7<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV6ToV7<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let now = T::BlockNumberProvider::current_block_number();
+			let date = |judgements: BoundedVec<(T::JudgementId, Judgement), T::MaxJudgements>| {
+				BoundedVec::truncate_from(
+					judgements
+						.into_iter()
+						.map(|(judgement_id, judgement)| (judgement_id, (judgement, now)))
+						.collect(),
+				)
+			};
+			let mut translated = 0u64;
+			IdentityOf::<T, I>::translate::<v6::Registration<T, I>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(Registration {
+					info: old.info,
+					judgements: date(old.judgements),
+					deposit: old.deposit,
+					field_bytes: old.field_bytes,
+					notes_deposit: old.notes_deposit,
+					known_good: old.known_good,
+					erroneous: old.erroneous,
+					status: old.status,
+					level: old.level,
+					verified_fields: old.verified_fields,
+				})
+			});
+			JudgementsDoubleMap::<T, I>::translate::<Judgement, _>(|_, _, judgement| {
+				translated.saturating_inc();
+				Some((judgement, now))
+			});
+			ShadowJudgementsDoubleMap::<T, I>::translate::<Judgement, _>(|_, _, judgement| {
+				translated.saturating_inc();
+				Some((judgement, now))
+			});
+			ShadowJudgementsInline::<T, I>::translate::<
+				BoundedVec<(T::JudgementId, Judgement), T::MaxJudgements>,
+				_,
+			>(|_, shadow| {
+				translated.saturating_inc();
+				Some(date(shadow))
+			});
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let judgements: UndatedJudgements<T, I> = v6::IdentityOf::<T, I>::iter()
+				.map(|(who, reg)| (who, reg.judgements.into_inner()))
+				.collect();
+			let double_map = v6::JudgementsDoubleMap::<T, I>::iter_keys().count() as u32;
+			Ok((judgements, double_map).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let (judgements, double_map) =
+				<(UndatedJudgements<T, I>, u32)>::decode(&mut &state[..])
+					.map_err(|_| "Invalid pre-upgrade state")?;
+			ensure!(
+				IdentityOf::<T, I>::iter_keys().count() == judgements.len(),
+				"Registrations were lost in the migration"
+			);
+			for (who, old) in judgements {
+				let reg = IdentityOf::<T, I>::get(&who).ok_or("A registration was lost")?;
+				ensure!(
+					reg.judgements
+						.iter()
+						.map(|(judgement_id, (judgement, _))| (judgement_id.clone(), *judgement))
+						.eq(old),
+					"Inline judgements changed in the migration"
+				);
+			}
+			ensure!(
+				JudgementsDoubleMap::<T, I>::iter_values().count() as u32 == double_map,
+				"Double map judgements were lost in the migration"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV6ToV7`], run only while the pallet is at storage version 6.
+	pub type MigrateV6ToV7<T, I = ()> = VersionedMigration<
+		6,
+		7,
+		InnerMigrateV6ToV7<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 8, which adds `avatar` to [`IdentityInfo`].
+pub mod v8 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// `info` with an unset avatar.
+	fn without_avatar<T: Config<I>, I: 'static>(
+		info: v7::IdentityInfo<T, I>,
+	) -> IdentityInfoOf<T, I> {
+		let v7::IdentityInfo { display, legal, web, email } = info;
+		IdentityInfo { display, legal, web, email, avatar: None }
+	}
+
+	/// Add an unset `avatar` to the identity information of every registration and mirrored
+	/// remote identity. Deposits are left as they are: an unset avatar adds nothing to them.
+	pub struct InnerMigrateV7ToV8<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV7ToV8<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			v9::IdentityOf::<T, I>::translate::<v7::Registration<T, I>, _>(|_, old| {
+				translated.saturating_inc();
+				let [display, legal, web, email] = old.field_bytes;
+				Some(v9::Registration {
+					info: without_avatar(old.info),
+					judgements: old.judgements,
+					deposit: old.deposit,
+					field_bytes: [display, legal, web, email, 0],
+					notes_deposit: old.notes_deposit,
+					known_good: old.known_good,
+					erroneous: old.erroneous,
+					status: old.status,
+					level: old.level,
+					verified_fields: old.verified_fields,
+				})
+			});
+			RemoteIdentities::<T, I>::translate::<v7::RemoteIdentity<T, I>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(RemoteIdentity {
+					info: without_avatar(old.info),
+					status: old.status,
+					verified_fields: old.verified_fields,
+					state_root: old.state_root,
+				})
+			});
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let registrations = v7::IdentityOf::<T, I>::iter_keys().count() as u32;
+			let remote = RemoteIdentities::<T, I>::iter_keys().count() as u32;
+			Ok((registrations, remote).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let (registrations, remote) =
+				<(u32, u32)>::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state")?;
+			let mut migrated = 0u32;
+			for registration in v9::IdentityOf::<T, I>::iter_values() {
+				ensure!(
+					registration.field_bytes == registration.info.field_sizes(),
+					"Field sizes don't match the identity information"
+				);
+				migrated.saturating_inc();
+			}
+			ensure!(migrated == registrations, "Registrations were lost in the migration");
+			ensure!(
+				RemoteIdentities::<T, I>::iter_values().count() as u32 == remote,
+				"Remote identities were lost in the migration"
+			);
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV7ToV8`], run only while the pallet is at storage version 7.
+	pub type MigrateV7ToV8<T, I = ()> = VersionedMigration<
+		7,
+		8,
+		InnerMigrateV7ToV8<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 9, which downgrades expired `KnownGood` judgements instead of
+/// keeping them in `StaleJudgements`.
+pub mod v9 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// [`Registration`] at versions 8 and 9, before `preferred_registrars`.
+	#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound)]
+	pub struct Registration<T: Config<I>, I: 'static = ()> {
+		pub info: IdentityInfoOf<T, I>,
+		pub judgements: DatedJudgementsOf<T, I>,
+		pub deposit: BalanceOf<T, I>,
+		pub field_bytes: [u32; 5],
+		pub notes_deposit: BalanceOf<T, I>,
+		pub known_good: u32,
+		pub erroneous: u32,
+		pub status: VerificationStatus,
+		pub level: VerificationLevel,
+		pub verified_fields: IdentityFields,
+	}
+
+	/// [`IdentityOf`] at versions 8 and 9.
+	#[frame_support::storage_alias]
+	pub type IdentityOf<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Registration<T, I>,
+		OptionQuery,
+	>;
+
+	/// The `KnownGood` judgements gone stale up to version 8, which no longer counted towards
+	/// verification.
+	#[frame_support::storage_alias]
+	pub type StaleJudgements<T: Config<I>, I: 'static> = StorageDoubleMap<
+		Pallet<T, I>,
+		Blake2_128Concat,
+		<T as frame_system::Config>::AccountId,
+		Blake2_128Concat,
+		<T as Config<I>>::JudgementId,
+		(),
+		OptionQuery,
+	>;
+
+	/// Downgrade every stale judgement to `Reasonable`, as it would have been had it expired at
+	/// version 9. Stale judgements weren't counted in `known_good` already, so the verification
+	/// of their identities stays as it is.
+	pub struct InnerMigrateV8ToV9<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV8ToV9<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut downgraded = 0u64;
+			for (who, judgement_id, ()) in StaleJudgements::<T, I>::drain() {
+				downgraded.saturating_inc();
+				IdentityOf::<T, I>::mutate(&who, |maybe_reg| {
+					if let Some(reg) = maybe_reg {
+						Pallet::<T, I>::downgrade_known_good(
+							&who,
+							&mut reg.judgements,
+							&judgement_id,
+						);
+					}
+				});
+			}
+			// Each stale entry is removed, and its registration, double map entry and score read
+			// and written
+			T::DbWeight::get().reads_writes(4 * downgraded, 4 * downgraded)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let stale: Vec<_> = StaleJudgements::<T, I>::iter_keys().collect();
+			Ok(stale.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let stale = <Vec<(T::AccountId, T::JudgementId)>>::decode(&mut &state[..])
+				.map_err(|_| "Invalid pre-upgrade state")?;
+			ensure!(StaleJudgements::<T, I>::iter_keys().next().is_none(), "Stale judgements left");
+			let known_good =
+				|(judgement, _): &DatedJudgementOf<T, I>| *judgement == Judgement::KnownGood;
+			for (who, judgement_id) in stale {
+				let inline = IdentityOf::<T, I>::get(&who).is_some_and(|reg| {
+					reg.judgements
+						.iter()
+						.any(|(id, given)| *id == judgement_id && known_good(given))
+				});
+				let double_map = JudgementsDoubleMap::<T, I>::get(&who, &judgement_id)
+					.is_some_and(|given| known_good(&given));
+				ensure!(!inline && !double_map, "A stale judgement is still KnownGood");
+			}
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV8ToV9`], run only while the pallet is at storage version 8.
+	pub type MigrateV8ToV9<T, I = ()> = VersionedMigration<
+		8,
+		9,
+		InnerMigrateV8ToV9<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Migrations to storage version 10, which adds `preferred_registrars` to [`Registration`].
+pub mod v10 {
+	use super::*;
+	use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+
+	/// Add an empty `preferred_registrars` to every registration.
+	pub struct InnerMigrateV9ToV10<T, I = ()>(core::marker::PhantomData<(T, I)>);
+
+	impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for InnerMigrateV9ToV10<T, I> {
+		fn on_runtime_upgrade() -> Weight {
+			let mut translated = 0u64;
+			IdentityOf::<T, I>::translate::<v9::Registration<T, I>, _>(|_, old| {
+				translated.saturating_inc();
+				Some(Registration {
+					info: old.info,
+					judgements: old.judgements,
+					deposit: old.deposit,
+					field_bytes: old.field_bytes,
+					notes_deposit: old.notes_deposit,
+					known_good: old.known_good,
+					erroneous: old.erroneous,
+					status: old.status,
+					level: old.level,
+					verified_fields: old.verified_fields,
+					preferred_registrars: BoundedVec::default(),
+				})
+			});
+			T::DbWeight::get().reads_writes(translated, translated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((v9::IdentityOf::<T, I>::iter_keys().count() as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let registrations =
+				u32::decode(&mut &state[..]).map_err(|_| "Invalid pre-upgrade state")?;
+			let mut migrated = 0u32;
+			for registration in IdentityOf::<T, I>::iter_values() {
+				ensure!(
+					registration.preferred_registrars.is_empty(),
+					"A registration has preferred registrars"
+				);
+				migrated.saturating_inc();
+			}
+			ensure!(migrated == registrations, "Registrations were lost in the migration");
+			Ok(())
+		}
+	}
+
+	/// [`InnerMigrateV9ToV10`], run only while the pallet is at storage version 9.
+	pub type MigrateV9ToV10<T, I = ()> = VersionedMigration<
+		9,
+		10,
+		InnerMigrateV9ToV10<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
+
+/// Import of the identities of the polkadot-sdk `pallet-identity`, for chains whose state is
+/// forked from one running it.
+#[cfg(feature = "interop")]
+pub mod import {
+	use super::*;
+	use frame_support::{
+		migrations::{MigrationId, SteppedMigration, SteppedMigrationError},
+		storage::with_storage_layer,
+		traits::PalletInfoAccess,
+		weights::WeightMeter,
+	};
+	use upstream_identity::{legacy, Data, RegistrarIndex};
+
+	/// A registration of the upstream pallet: `Data` fields and judgements by registrar index.
+	pub type UpstreamRegistration<T, I, MaxRegistrars, FieldLimit> =
+		upstream_identity::Registration<
+			BalanceOf<T, I>,
+			MaxRegistrars,
+			legacy::IdentityInfo<FieldLimit>,
+		>;
+
+	/// The storage of the upstream pallet, under its pallet name `Prefix`.
+	pub mod upstream {
+		use super::*;
+
+		/// `IdentityOf` of the upstream pallet, of [`UpstreamRegistration`]s by account.
+		#[frame_support::storage_alias(dynamic)]
+		pub type IdentityOf<Prefix: Get<&'static str>, AccountId: FullCodec, Value: FullCodec> =
+			StorageMap<Prefix, Twox64Concat, AccountId, Value, OptionQuery>;
+
+		/// `SubsOf` of the upstream pallet: the deposit held for an account's sub-accounts, and
+		/// the sub-accounts.
+		#[frame_support::storage_alias(dynamic)]
+		pub type SubsOf<Prefix: Get<&'static str>, AccountId: FullCodec, Value: FullCodec> =
+			StorageMap<Prefix, Twox64Concat, AccountId, Value, OptionQuery>;
+
+		/// `SuperOf` of the upstream pallet: the super account of a sub-account, and its name.
+		#[frame_support::storage_alias(dynamic)]
+		pub type SuperOf<Prefix: Get<&'static str>, AccountId: FullCodec, Value: FullCodec> =
+			StorageMap<Prefix, Blake2_128Concat, AccountId, Value, OptionQuery>;
+	}
+
+	/// [`upstream::IdentityOf`] of the pallet named `Prefix` in a runtime of `T`.
+	type UpstreamIdentities<T, I, Prefix, MaxRegistrars, FieldLimit> = upstream::IdentityOf<
+		Prefix,
+		<T as frame_system::Config>::AccountId,
+		UpstreamRegistration<T, I, MaxRegistrars, FieldLimit>,
+	>;
+
+	/// [`upstream::SubsOf`] of the pallet named `Prefix` in a runtime of `T`.
+	type UpstreamSubs<T, I, Prefix, MaxSubAccounts> = upstream::SubsOf<
+		Prefix,
+		<T as frame_system::Config>::AccountId,
+		(BalanceOf<T, I>, BoundedVec<<T as frame_system::Config>::AccountId, MaxSubAccounts>),
+	>;
+
+	/// [`upstream::SuperOf`] of the pallet named `Prefix` in a runtime of `T`.
+	type UpstreamSupers<T, Prefix> = upstream::SuperOf<
+		Prefix,
+		<T as frame_system::Config>::AccountId,
+		(<T as frame_system::Config>::AccountId, Data),
+	>;
+
+	/// Every upstream account before the import, whether it held an identity here already, its
+	/// reserved balance and what of it the upstream pallet reserved.
+	#[cfg(feature = "try-runtime")]
+	type UpstreamAccounts<T, I> =
+		Vec<(<T as frame_system::Config>::AccountId, bool, BalanceOf<T, I>, BalanceOf<T, I>)>;
+
+	/// Move every identity out of the upstream `IdentityOf` of the pallet named `Prefix` into
+	/// this pallet, whose judgement ID of a registrar is its upstream registrar index. Identities
+	/// are moved a few at a time, in as many steps of a multi-block migration as needed, the
+	/// cursor being the account moved last.
+	///
+	/// The deposit and the paid fees the upstream pallet reserved are released, and the identity
+	/// registered anew: its deposit held at this pallet's price, its verdicts given inline and
+	/// its `FeePaid` judgements left pending as requests. `OutOfDate` judgements, which have no
+	/// counterpart, are dropped, and so are `FeePaid` judgements of registrars unknown to this
+	/// pallet, whose fee is refunded. Sub-accounts and usernames are not imported: the
+	/// sub-accounts are removed and their deposit released.
+	///
+	/// An identity that can't be held here, e.g. for a field this pallet lacks, hashed data, more
+	/// judgements than `T::MaxJudgements` or an identity already held here, is dropped with its
+	/// deposit released, as if cleared, and `UpstreamIdentityDropped` emitted.
+	///
+	/// The upstream pallet must have been named differently from this one: their `IdentityOf`
+	/// would share a prefix. Nothing is imported otherwise.
+	pub struct ImportIdentities<T, I, Prefix, MaxRegistrars, FieldLimit, MaxSubAccounts>(
+		core::marker::PhantomData<(T, I, Prefix, MaxRegistrars, FieldLimit, MaxSubAccounts)>,
+	);
+
+	impl<T, I, Prefix, MaxRegistrars, FieldLimit, MaxSubAccounts>
+		ImportIdentities<T, I, Prefix, MaxRegistrars, FieldLimit, MaxSubAccounts>
+	where
+		T: Config<I>,
+		I: 'static,
+		T::JudgementId: From<RegistrarIndex>,
+		Prefix: Get<&'static str>,
+		MaxRegistrars: Get<u32> + 'static,
+		FieldLimit: Get<u32> + 'static,
+		MaxSubAccounts: Get<u32> + 'static,
+	{
+		/// The most weight moving one identity can take: releasing its upstream deposits and
+		/// removing its sub-accounts, then registering it with a judgement or request from every
+		/// upstream registrar.
+		pub fn weight_per_identity() -> Weight {
+			let j = T::MaxJudgements::get();
+			let registrars = MaxRegistrars::get() as u64;
+			T::DbWeight::get()
+				.reads_writes(4, 4)
+				.saturating_add(T::DbWeight::get().writes(MaxSubAccounts::get() as u64))
+				.saturating_add(T::WeightInfo::set_identity(
+					T::MaxDisplayLength::get(),
+					T::MaxLegalLength::get(),
+					T::MaxWebLength::get(),
+					T::MaxEmailLength::get(),
+					T::MaxCidLength::get(),
+				))
+				.saturating_add(
+					T::WeightInfo::provide_judgement_inline(j, 0)
+						.max(T::WeightInfo::request_judgement(j))
+						.saturating_mul(registrars),
+				)
+		}
+
+		/// The upstream identities after the account `cursor`, or all of them without one.
+		/// Imported identities are removed upstream, so the iteration goes on past them.
+		fn identities_after(
+			cursor: Option<&T::AccountId>,
+		) -> impl Iterator<Item = (T::AccountId, UpstreamRegistration<T, I, MaxRegistrars, FieldLimit>)>
+		{
+			type Identities<T, I, Prefix, MaxRegistrars, FieldLimit> =
+				UpstreamIdentities<T, I, Prefix, MaxRegistrars, FieldLimit>;
+			let start =
+				cursor.map(Identities::<T, I, Prefix, MaxRegistrars, FieldLimit>::hashed_key_for);
+			match start {
+				Some(key) => Identities::<T, I, Prefix, MaxRegistrars, FieldLimit>::iter_from(key),
+				None => Identities::<T, I, Prefix, MaxRegistrars, FieldLimit>::iter(),
+			}
+		}
+
+		/// Move `who`'s upstream `registration` into this pallet, releasing what the upstream
+		/// pallet held for it and emitting `UpstreamIdentityDropped` if it can't be registered.
+		fn import(
+			who: T::AccountId,
+			registration: UpstreamRegistration<T, I, MaxRegistrars, FieldLimit>,
+		) {
+			let fees = registration
+				.judgements
+				.iter()
+				.filter_map(|(_, judgement)| match judgement {
+					upstream_identity::Judgement::FeePaid(fee) => Some(*fee),
+					_ => None,
+				})
+				.fold(BalanceOf::<T, I>::zero(), |total, fee| total.saturating_add(fee));
+			T::Currency::unreserve(&who, registration.deposit.saturating_add(fees));
+			if let Some((deposit, subs)) = UpstreamSubs::<T, I, Prefix, MaxSubAccounts>::take(&who)
+			{
+				T::Currency::unreserve(&who, deposit);
+				for sub in subs {
+					UpstreamSupers::<T, Prefix>::remove(sub);
+				}
+			}
+
+			if let Err(error) = with_storage_layer(|| Self::register(&who, registration)) {
+				Pallet::<T, I>::deposit_event(Event::UpstreamIdentityDropped { who, error });
+			}
+		}
+
+		/// Register `who`'s upstream `registration` in this pallet.
+		fn register(
+			who: &T::AccountId,
+			registration: UpstreamRegistration<T, I, MaxRegistrars, FieldLimit>,
+		) -> DispatchResult {
+			let info = IdentityInfoOf::<T, I>::try_from(registration.info)
+				.map_err(|_| Error::<T, I>::InvalidFields)?;
+			let (mut judgements, mut requests) = (Vec::new(), Vec::new());
+			for (index, judgement) in registration.judgements {
+				let judgement_id = T::JudgementId::from(index);
+				match judgement {
+					upstream_identity::Judgement::FeePaid(fee) =>
+						if Registrars::<T, I>::contains_key(&judgement_id) {
+							requests.push((judgement_id, fee));
+						},
+					judgement =>
+						if let Ok(judgement) = Judgement::try_from(judgement) {
+							judgements.push((judgement_id, judgement));
+						},
+				}
+			}
+			Pallet::<T, I>::import_identity(who.clone(), info, judgements, requests)
+		}
+	}
+
+	impl<T, I, Prefix, MaxRegistrars, FieldLimit, MaxSubAccounts> SteppedMigration
+		for ImportIdentities<T, I, Prefix, MaxRegistrars, FieldLimit, MaxSubAccounts>
+	where
+		T: Config<I>,
+		I: 'static,
+		T::JudgementId: From<RegistrarIndex>,
+		Prefix: Get<&'static str>,
+		MaxRegistrars: Get<u32> + 'static,
+		FieldLimit: Get<u32> + 'static,
+		MaxSubAccounts: Get<u32> + 'static,
+	{
+		type Cursor = T::AccountId;
+		type Identifier = MigrationId<15>;
+
+		fn id() -> Self::Identifier {
+			MigrationId { pallet_id: *b"identity-import", version_from: 0, version_to: 1 }
+		}
+
+		fn step(
+			mut cursor: Option<Self::Cursor>,
+			meter: &mut WeightMeter,
+		) -> Result<Option<Self::Cursor>, SteppedMigrationError> {
+			if Prefix::get() == Pallet::<T, I>::name() {
+				return Ok(None);
+			}
+			let required = Self::weight_per_identity();
+			if meter.remaining().any_lt(required) {
+				return Err(SteppedMigrationError::InsufficientWeight { required });
+			}
+
+			let mut identities = Self::identities_after(cursor.as_ref());
+			while meter.try_consume(required).is_ok() {
+				let Some((who, registration)) = identities.next() else {
+					return Ok(None);
+				};
+				UpstreamIdentities::<T, I, Prefix, MaxRegistrars, FieldLimit>::remove(&who);
+				Self::import(who.clone(), registration);
+				cursor = Some(who);
+			}
+			Ok(cursor)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			ensure!(
+				Prefix::get() != Pallet::<T, I>::name(),
+				"The upstream pallet has the name of this one"
+			);
+			let accounts: UpstreamAccounts<T, I> =
+				UpstreamIdentities::<T, I, Prefix, MaxRegistrars, FieldLimit>::iter()
+					.map(|(who, registration)| {
+						let fees = registration.judgements.iter().fold(
+							BalanceOf::<T, I>::zero(),
+							|total, (_, judgement)| match judgement {
+								upstream_identity::Judgement::FeePaid(fee) =>
+									total.saturating_add(*fee),
+								_ => total,
+							},
+						);
+						let subs = UpstreamSubs::<T, I, Prefix, MaxSubAccounts>::get(&who)
+							.map_or(Zero::zero(), |(deposit, _)| deposit);
+						let held = registration.deposit.saturating_add(fees).saturating_add(subs);
+						(
+							who.clone(),
+							IdentityOf::<T, I>::contains_key(&who),
+							T::Currency::reserved_balance(&who),
+							held,
+						)
+					})
+					.collect();
+			let local = IdentityOf::<T, I>::iter_keys().count() as u32;
+			Ok((accounts, local).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let (accounts, local) = <(UpstreamAccounts<T, I>, u32)>::decode(&mut &state[..])
+				.map_err(|_| "Invalid pre-upgrade state")?;
+			ensure!(
+				UpstreamIdentities::<T, I, Prefix, MaxRegistrars, FieldLimit>::iter_keys()
+					.next()
+					.is_none(),
+				"Upstream identities were left behind"
+			);
+			let mut imported = 0u32;
+			for (who, existed, reserved, held) in &accounts {
+				ensure!(
+					UpstreamSubs::<T, I, Prefix, MaxSubAccounts>::get(who).is_none(),
+					"Upstream sub-accounts were left behind"
+				);
+				if !existed && IdentityOf::<T, I>::contains_key(who) {
+					imported.saturating_inc();
+					continue;
+				}
+				// A dropped identity, or one held here already, only had its upstream deposits
+				// released
+				ensure!(
+					T::Currency::reserved_balance(who) == reserved.saturating_sub(*held),
+					"The upstream deposits of a dropped identity weren't released"
+				);
+			}
+			ensure!(
+				IdentityOf::<T, I>::iter_keys().count() as u32 == local.saturating_add(imported),
+				"Identities held here were lost in the import"
+			);
+			Ok(())
+		}
+	}
+}
@@ -1,12 +1,21 @@
 use crate as pallet_identity;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use frame_support::{
-	construct_runtime, derive_impl, parameter_types,
-	traits::{ConstU128, ConstU16, ConstU32, ConstU64},
+	construct_runtime, derive_impl,
+	instances::Instance2,
+	parameter_types,
+	traits::{
+		ConstU128, ConstU16, ConstU32, ConstU64, EnsureOrigin, Imbalance, OnUnbalanced, Randomness,
+		VariantCount,
+	},
 };
 use frame_system as system;
+use scale_info::TypeInfo;
+use sp_core::H256;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	testing::{TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, BlockNumberProvider, IdentityLookup},
+	BuildStorage, Perbill,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -18,6 +27,7 @@ construct_runtime!(
 		System: frame_system,
 		Balances: pallet_balances,
 		Identity: pallet_identity,
+		OrgIdentity: pallet_identity::<Instance2>,
 	}
 );
 
@@ -30,7 +40,7 @@ impl system::Config for Test {
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	type Nonce = u64;
-	type Hash = sp_core::H256;
+	type Hash = H256;
 	type Hashing = BlakeTwo256;
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
@@ -41,7 +51,7 @@ impl system::Config for Test {
 	type PalletInfo = PalletInfo;
 	type AccountData = pallet_balances::AccountData<u128>;
 	type OnNewAccount = ();
-	type OnKilledAccount = ();
+	type OnKilledAccount = (Identity, OrgIdentity);
 	type SystemWeightInfo = ();
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
@@ -60,29 +70,432 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
+	type RuntimeHoldReason = MockHoldReason;
 	type RuntimeFreezeReason = ();
 	type DoneSlashHandler = ();
 }
 
+/// The reasons funds are held for: deposits when backed by `pallet_identity::HoldDeposits`, and
+/// the provider bonds of each registry.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	MaxEncodedLen,
+	TypeInfo,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Debug,
+)]
+pub enum MockHoldReason {
+	IdentityDeposit,
+	ProviderBond,
+	OrgProviderBond,
+}
+
+impl VariantCount for MockHoldReason {
+	const VARIANT_COUNT: u32 = 3;
+}
+
+impl From<pallet_identity::HoldReason> for MockHoldReason {
+	fn from(reason: pallet_identity::HoldReason) -> Self {
+		match reason {
+			pallet_identity::HoldReason::ProviderBond => MockHoldReason::ProviderBond,
+			pallet_identity::HoldReason::__Ignore(..) => unreachable!("Never constructed; qed"),
+		}
+	}
+}
+
+impl From<pallet_identity::HoldReason<Instance2>> for MockHoldReason {
+	fn from(reason: pallet_identity::HoldReason<Instance2>) -> Self {
+		match reason {
+			pallet_identity::HoldReason::ProviderBond => MockHoldReason::OrgProviderBond,
+			pallet_identity::HoldReason::__Ignore(..) => unreachable!("Never constructed; qed"),
+		}
+	}
+}
+
 parameter_types! {
-	pub const BasicDeposit: u128 = 10;
-	pub const ByteDeposit: u128 = 1;
-	pub const MaxJudgements: u32 = 20;
-	pub const MaxFieldLength: u32 = 64;
+	pub const IdentityHoldReason: MockHoldReason = MockHoldReason::IdentityDeposit;
+	pub static BasicDeposit: u128 = 10;
+	pub static ByteDeposit: u128 = 1;
+	pub static VerifiedDepositDiscount: Perbill = Perbill::zero();
+	pub static DepositDecayPeriod: u64 = 0;
+	pub static DepositDecayFloor: Perbill = Perbill::from_percent(50);
+	pub static RegistrationFee: u128 = 0;
+	/// Registration fees and rent received by `MockFeeCollector`.
+	pub static CollectedFees: u128 = 0;
+	pub static IdentityRent: u128 = 0;
+	pub static RentPeriod: u64 = 10;
+	pub static RentGracePeriod: u64 = 10;
+	pub static MaxRentCollections: u32 = 4;
+	pub static ChallengeDeposit: u128 = 20;
+	pub static DepositReleaseDelay: u64 = 0;
+	pub static KeepTombstones: bool = false;
+	pub static TombstoneLifetime: u64 = 50;
+	pub static InactivityPeriod: u64 = 100;
+	pub static IdentityLifetime: u64 = 0;
+	pub static ExpiryGracePeriod: u64 = 10;
+	pub static RevalidationPeriod: u64 = 1_000;
+	pub static MaxExpiryChecks: u32 = 4;
+	pub static KeyRotationGracePeriod: u64 = 10;
+	pub static RegistrarBond: u128 = 100;
+	pub static OverturnSlash: Perbill = Perbill::from_percent(50);
+	pub static MinProviderBond: u128 = 50;
+	pub static MaxJudgements: u32 = 20;
+	pub static MaxRecentJudgements: u32 = 4;
+	pub static MaxJudgementsPerBlock: u32 = 0;
+	pub static MaxSetsPerPeriod: u32 = 0;
+	pub static IdentitySetPeriod: u64 = 10;
+	pub static ShadowWrites: bool = false;
+	pub const MaxBatch: u32 = 5;
+	pub static MaxDisplayLength: u32 = 64;
+	pub static DisplayIndexLength: u32 = 0;
+	pub static MaxLegalLength: u32 = 64;
+	pub static MaxWebLength: u32 = 100;
+	pub static MaxEmailLength: u32 = 64;
+	pub static MaxCidLength: u32 = 64;
+	pub static MaxNoteLength: u32 = 32;
+	pub static MaxRegistrationSize: u32 = 832;
+	pub static VerificationThreshold: u32 = 1;
+	pub static VouchThreshold: u32 = 2;
+	pub const MaxVouches: u32 = 3;
+	pub const SystemJudgementId: u32 = u32::MAX;
+	/// Block number reported by `MockRelayBlockNumber`.
+	pub static RelayBlockNumber: u64 = 0;
+	/// Accounts currently holding a badge from `MockBadges`.
+	pub static Badges: Vec<u64> = vec![];
+	/// Judgements reported to `MockJudgementLog`, in order.
+	pub static JudgementLog: Vec<(u64, u32, pallet_identity::Judgement)> = vec![];
+	/// State root of the foreign chain trusted by `MockForeignStateRoot`.
+	pub static TrustedStateRoot: Option<H256> = None;
+	pub const MaxRemoteProofSize: u32 = 8192;
+	pub const BridgedJudgementIds: core::ops::RangeInclusive<u32> = 1000..=1999;
+	pub const BridgedJudgementIds2: core::ops::RangeInclusive<u64> = 1000..=1999;
+	/// Output of `MockRandomness`, whatever the subject.
+	pub static RandomSeed: H256 = H256::zero();
+	pub const MaxAuditCandidates: u32 = 8;
+	pub const MaxPreferredRegistrars: u32 = 3;
+	pub const DidMethod: &'static str = "bench";
+}
+
+/// Stands in for a treasury, counting the registration fees and rent it receives.
+pub struct MockFeeCollector;
+impl OnUnbalanced<pallet_identity::NegativeImbalanceOf<Test>> for MockFeeCollector {
+	fn on_nonzero_unbalanced(fee: pallet_identity::NegativeImbalanceOf<Test>) {
+		CollectedFees::mutate(|total| *total += fee.peek());
+	}
+}
+
+/// Stands in for a soulbound badge: minted on verification, burned on revocation.
+pub struct MockBadges;
+impl pallet_identity::OnVerified<u64> for MockBadges {
+	fn on_verified(who: &u64) {
+		Badges::mutate(|badges| badges.push(*who));
+	}
+
+	fn on_revoked(who: &u64) {
+		Badges::mutate(|badges| badges.retain(|badge| badge != who));
+	}
+}
+
+/// Records every judgement given, standing in for a consumer such as a reputation pallet.
+pub struct MockJudgementLog;
+impl pallet_identity::OnJudgementGiven<u64, u32> for MockJudgementLog {
+	fn on_judgement_given(who: &u64, judgement_id: &u32, judgement: pallet_identity::Judgement) {
+		JudgementLog::mutate(|log| log.push((*who, *judgement_id, judgement)));
+	}
+}
+
+/// Stands in for an XCM origin converter: signed accounts from 2000 up act as the registrars of
+/// sibling parachains, identified by the account ID as para ID.
+pub struct EnsureSiblingRegistrar;
+impl EnsureOrigin<RuntimeOrigin> for EnsureSiblingRegistrar {
+	type Success = u32;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<u32, RuntimeOrigin> {
+		match o.clone().into() {
+			Ok(system::RawOrigin::Signed(who)) if who >= 2000 => Ok(who as u32),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(2000))
+	}
+}
+
+/// Deterministic randomness: always `RandomSeed`, known since block 0.
+pub struct MockRandomness;
+impl Randomness<H256, u64> for MockRandomness {
+	fn random(_subject: &[u8]) -> (H256, u64) {
+		(RandomSeed::get(), 0)
+	}
+}
+
+/// Stands in for a bridge pallet: signed account 1500 relays the judgements of remote
+/// registrar 1.
+pub struct EnsureBridge;
+impl EnsureOrigin<RuntimeOrigin> for EnsureBridge {
+	type Success = u32;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<u32, RuntimeOrigin> {
+		match o.clone().into() {
+			Ok(system::RawOrigin::Signed(1500)) => Ok(1),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(1500))
+	}
+}
+
+/// Stands in for the relay chain's block number on a parachain, decoupled from `System`.
+pub struct MockRelayBlockNumber;
+impl BlockNumberProvider for MockRelayBlockNumber {
+	type BlockNumber = u64;
+
+	fn current_block_number() -> u64 {
+		RelayBlockNumber::get()
+	}
+
+	fn set_block_number(block: u64) {
+		RelayBlockNumber::set(block);
+	}
+}
+
+/// Stands in for a light client of the foreign chain identities are mirrored from.
+pub struct MockForeignStateRoot;
+impl pallet_identity::ForeignStateRoot<H256> for MockForeignStateRoot {
+	fn state_root() -> Option<H256> {
+		TrustedStateRoot::get()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_state_root(root: H256) {
+		TrustedStateRoot::set(Some(root));
+	}
+}
+
+/// Uses indices as judgement IDs, and signs benchmark payloads with the test key of account 0.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockSigner;
+#[cfg(feature = "runtime-benchmarks")]
+impl<JudgementId: From<u32>>
+	pallet_identity::BenchmarkHelper<JudgementId, UintAuthorityId, TestSignature> for MockSigner
+{
+	fn judgement_id(index: u32) -> JudgementId {
+		index.into()
+	}
+
+	fn sign_message(message: &[u8]) -> (UintAuthorityId, TestSignature) {
+		(UintAuthorityId(0), TestSignature(0, message.to_vec()))
+	}
 }
 
 impl pallet_identity::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	type DepositBackend = pallet_identity::ReserveDeposits<Balances>;
 	type BasicDeposit = BasicDeposit;
 	type ByteDeposit = ByteDeposit;
+	type VerifiedDepositDiscount = VerifiedDepositDiscount;
+	type DepositDecayPeriod = DepositDecayPeriod;
+	type DepositDecayFloor = DepositDecayFloor;
+	type RegistrationFee = RegistrationFee;
+	type RegistrationFeeDestination = MockFeeCollector;
+	type IdentityRent = IdentityRent;
+	type RentPeriod = RentPeriod;
+	type RentGracePeriod = RentGracePeriod;
+	type MaxRentCollections = MaxRentCollections;
+	type RentDestination = MockFeeCollector;
+	type ChallengeDeposit = ChallengeDeposit;
+	type BlockNumberProvider = System;
+	type DepositReleaseDelay = DepositReleaseDelay;
+	type KeepTombstones = KeepTombstones;
+	type TombstoneLifetime = TombstoneLifetime;
+	type InactivityPeriod = InactivityPeriod;
+	type IdentityLifetime = IdentityLifetime;
+	type ExpiryGracePeriod = ExpiryGracePeriod;
+	type RevalidationPeriod = RevalidationPeriod;
+	type MaxExpiryChecks = MaxExpiryChecks;
+	type JudgementId = u32;
 	type MaxJudgements = MaxJudgements;
-	type MaxFieldLength = MaxFieldLength;
+	type MaxBatch = MaxBatch;
+	type MaxRecentJudgements = MaxRecentJudgements;
+	type MaxJudgementsPerBlock = MaxJudgementsPerBlock;
+	type MaxSetsPerPeriod = MaxSetsPerPeriod;
+	type IdentitySetPeriod = IdentitySetPeriod;
+	type ShadowWrites = ShadowWrites;
+	type MaxDisplayLength = MaxDisplayLength;
+	type DisplayIndexLength = DisplayIndexLength;
+	type MaxLegalLength = MaxLegalLength;
+	type MaxWebLength = MaxWebLength;
+	type MaxEmailLength = MaxEmailLength;
+	type MaxCidLength = MaxCidLength;
+	type MaxNoteLength = MaxNoteLength;
+	type MaxRegistrationSize = MaxRegistrationSize;
 	type JudgementOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type RemoteRegistrarId = u32;
+	type RemoteJudgementOrigin = EnsureSiblingRegistrar;
+	type OffchainSignature = TestSignature;
+	type SigningPublicKey = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockSigner;
+	type KeyRotationGracePeriod = KeyRotationGracePeriod;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type PauseOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type DisputeResolutionOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type NoDepositOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type RegistrarBond = RegistrarBond;
+	type OverturnSlash = OverturnSlash;
+	type RuntimeHoldReason = MockHoldReason;
+	type ProviderBondCurrency = Balances;
+	type ProviderBondSlash = ();
+	type MinProviderBond = MinProviderBond;
+	type JudgementScore = pallet_identity::DefaultJudgementScore;
+	type VerificationThreshold = VerificationThreshold;
+	type MaxVouches = MaxVouches;
+	type VouchThreshold = VouchThreshold;
+	type SystemJudgementId = SystemJudgementId;
+	type OnVerified = MockBadges;
+	type OnJudgementGiven = MockJudgementLog;
+	type BridgeOrigin = EnsureBridge;
+	type BridgedJudgementIds = BridgedJudgementIds;
+	type ForeignStateRoot = MockForeignStateRoot;
+	type MaxRemoteProofSize = MaxRemoteProofSize;
+	type Randomness = MockRandomness;
+	type MaxAuditCandidates = MaxAuditCandidates;
+	type MaxPreferredRegistrars = MaxPreferredRegistrars;
+	type DidMethod = DidMethod;
 	type WeightInfo = ();
 }
 
+parameter_types! {
+	pub const OrgBasicDeposit: u128 = 100;
+}
+
+/// A second, independent registry, e.g. for organisations rather than individuals.
+impl pallet_identity::Config<Instance2> for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type DepositBackend = pallet_identity::ReserveDeposits<Balances>;
+	type BasicDeposit = OrgBasicDeposit;
+	type ByteDeposit = ByteDeposit;
+	type VerifiedDepositDiscount = VerifiedDepositDiscount;
+	type DepositDecayPeriod = DepositDecayPeriod;
+	type DepositDecayFloor = DepositDecayFloor;
+	type RegistrationFee = RegistrationFee;
+	type RegistrationFeeDestination = MockFeeCollector;
+	type IdentityRent = IdentityRent;
+	type RentPeriod = RentPeriod;
+	type RentGracePeriod = RentGracePeriod;
+	type MaxRentCollections = MaxRentCollections;
+	type RentDestination = MockFeeCollector;
+	type ChallengeDeposit = ChallengeDeposit;
+	type BlockNumberProvider = MockRelayBlockNumber;
+	type DepositReleaseDelay = DepositReleaseDelay;
+	type KeepTombstones = KeepTombstones;
+	type TombstoneLifetime = TombstoneLifetime;
+	type InactivityPeriod = InactivityPeriod;
+	type IdentityLifetime = IdentityLifetime;
+	type ExpiryGracePeriod = ExpiryGracePeriod;
+	type RevalidationPeriod = RevalidationPeriod;
+	type MaxExpiryChecks = MaxExpiryChecks;
+	type JudgementId = u64;
+	type MaxJudgements = MaxJudgements;
+	type MaxBatch = MaxBatch;
+	type MaxRecentJudgements = MaxRecentJudgements;
+	type MaxJudgementsPerBlock = MaxJudgementsPerBlock;
+	type MaxSetsPerPeriod = MaxSetsPerPeriod;
+	type IdentitySetPeriod = IdentitySetPeriod;
+	type ShadowWrites = ShadowWrites;
+	type MaxDisplayLength = MaxDisplayLength;
+	type DisplayIndexLength = DisplayIndexLength;
+	type MaxLegalLength = MaxLegalLength;
+	type MaxWebLength = MaxWebLength;
+	type MaxEmailLength = MaxEmailLength;
+	type MaxCidLength = MaxCidLength;
+	type MaxNoteLength = MaxNoteLength;
+	type MaxRegistrationSize = MaxRegistrationSize;
+	type JudgementOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type RemoteRegistrarId = u32;
+	type RemoteJudgementOrigin = EnsureSiblingRegistrar;
+	type OffchainSignature = TestSignature;
+	type SigningPublicKey = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockSigner;
+	type KeyRotationGracePeriod = KeyRotationGracePeriod;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type PauseOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type DisputeResolutionOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type NoDepositOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type RegistrarBond = RegistrarBond;
+	type OverturnSlash = OverturnSlash;
+	type RuntimeHoldReason = MockHoldReason;
+	type ProviderBondCurrency = Balances;
+	type ProviderBondSlash = ();
+	type MinProviderBond = MinProviderBond;
+	type JudgementScore = pallet_identity::DefaultJudgementScore;
+	type VerificationThreshold = VerificationThreshold;
+	type MaxVouches = MaxVouches;
+	type VouchThreshold = VouchThreshold;
+	type SystemJudgementId = ConstU64<{ u64::MAX }>;
+	type OnVerified = ();
+	type OnJudgementGiven = ();
+	type BridgeOrigin = EnsureBridge;
+	type BridgedJudgementIds = BridgedJudgementIds2;
+	type ForeignStateRoot = MockForeignStateRoot;
+	type MaxRemoteProofSize = MaxRemoteProofSize;
+	type Randomness = MockRandomness;
+	type MaxAuditCandidates = MaxAuditCandidates;
+	type MaxPreferredRegistrars = MaxPreferredRegistrars;
+	type DidMethod = DidMethod;
+	type WeightInfo = ();
+}
+
+/// Bounds to configure the mock runtime with in place of its defaults, to check that the
+/// benchmarks hold up as the bounds change.
+#[cfg(feature = "runtime-benchmarks")]
+#[derive(Clone, Copy, Debug)]
+pub struct Bounds {
+	/// `MaxJudgements`.
+	pub max_judgements: u32,
+	/// `MaxDisplayLength`, `MaxLegalLength`, `MaxWebLength`, `MaxEmailLength` and `MaxCidLength`
+	/// alike.
+	pub max_field_length: u32,
+	/// `MaxNoteLength`.
+	pub max_note_length: u32,
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl Bounds {
+	/// Bounds small enough for the benchmarks' linear components to have hardly any range.
+	pub const TINY: Self = Self { max_judgements: 2, max_field_length: 4, max_note_length: 1 };
+	/// Bounds of a registry which holds no judgements, whose judgement benchmarks can't run.
+	pub const NO_JUDGEMENTS: Self =
+		Self { max_judgements: 0, max_field_length: 64, max_note_length: 32 };
+	/// Bounds of a production registry, well above the benchmarking runtime's.
+	pub const PRODUCTION: Self =
+		Self { max_judgements: 100, max_field_length: 256, max_note_length: 256 };
+
+	/// Configure the mock runtime with these bounds, for the rest of the current thread.
+	pub fn set(self) {
+		MaxJudgements::set(self.max_judgements);
+		MaxDisplayLength::set(self.max_field_length);
+		MaxLegalLength::set(self.max_field_length);
+		MaxWebLength::set(self.max_field_length);
+		MaxEmailLength::set(self.max_field_length);
+		MaxCidLength::set(self.max_field_length);
+		MaxNoteLength::set(self.max_note_length);
+	}
+}
+
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap();
@@ -96,3 +509,10 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	ext.execute_with(|| System::set_block_number(1));
 	ext
 }
+
+/// Build genesis storage for the mock runtime configured with `bounds`.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_test_ext_with(bounds: Bounds) -> sp_io::TestExternalities {
+	bounds.set();
+	new_test_ext()
+}
@@ -0,0 +1,112 @@
+//! Transaction priority boost for verified identities
+//!
+//! [`PrioritizeVerified`] is a transaction extension adding `Boost` to the priority of every
+//! transaction signed by an account whose identity holds a `KnownGood` judgement, as told by the
+//! pallet's [`InspectIdentity`]. Under load the pool then prefers verified senders over
+//! unverified ones paying the same tip, so that spamming from fresh accounts doesn't crowd them
+//! out. A runtime appends it to its transaction extensions:
+//!
+//! ```ignore
+//! type TxExtension = (
+//!     // ...
+//!     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+//!     PrioritizeVerified<Runtime, Identity, VerifiedPriorityBoost>,
+//! );
+//! ```
+//!
+//! The extension carries no data in the transaction. Its boost is added to the priority of the
+//! other extensions, saturating at `TransactionPriority::MAX`.
+
+use crate::{InspectIdentity, VerificationStatus};
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use core::marker::PhantomData;
+use frame_support::{
+	pallet_prelude::TransactionSource, traits::OriginTrait, CloneNoBound, DefaultNoBound,
+	EqNoBound, PartialEqNoBound,
+};
+use frame_system::Config;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	impl_tx_ext_default,
+	traits::{DispatchInfoOf, Get, TransactionExtension, ValidateResult},
+	transaction_validity::{TransactionPriority, ValidTransaction},
+	Weight,
+};
+
+/// Raises the priority of transactions signed by accounts `Inspect` reports to hold a
+/// `KnownGood` judgement by `Boost`.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	CloneNoBound,
+	DefaultNoBound,
+	EqNoBound,
+	PartialEqNoBound,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(T, Inspect, Boost))]
+pub struct PrioritizeVerified<T, Inspect, Boost>(PhantomData<(T, Inspect, Boost)>);
+
+impl<T, Inspect, Boost> core::fmt::Debug for PrioritizeVerified<T, Inspect, Boost> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "PrioritizeVerified")
+	}
+}
+
+impl<T, Inspect, Boost: Get<TransactionPriority>> PrioritizeVerified<T, Inspect, Boost> {
+	/// Create the extension.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	/// The priority `who` is boosted by: `Boost` if its identity holds a `KnownGood` judgement.
+	pub fn boost_of<AccountId>(who: &AccountId) -> TransactionPriority
+	where
+		Inspect: InspectIdentity<AccountId>,
+	{
+		Self::boost(Inspect::verification_status(who))
+	}
+
+	/// The priority an identity of `status` is boosted by.
+	pub fn boost(status: VerificationStatus) -> TransactionPriority {
+		match status {
+			VerificationStatus::Unverified => 0,
+			VerificationStatus::PartiallyVerified | VerificationStatus::Verified => Boost::get(),
+		}
+	}
+}
+
+impl<T, Inspect, Boost> TransactionExtension<T::RuntimeCall>
+	for PrioritizeVerified<T, Inspect, Boost>
+where
+	T: Config + Send + Sync,
+	Inspect: InspectIdentity<T::AccountId> + Send + Sync + 'static,
+	Boost: Get<TransactionPriority> + Send + Sync + 'static,
+{
+	const IDENTIFIER: &'static str = "PrioritizeVerified";
+	type Implicit = ();
+	type Val = ();
+	type Pre = ();
+
+	fn weight(&self, _: &T::RuntimeCall) -> Weight {
+		// The signer's registration
+		Inspect::lookup_weight()
+	}
+
+	fn validate(
+		&self,
+		origin: T::RuntimeOrigin,
+		_call: &T::RuntimeCall,
+		_info: &DispatchInfoOf<T::RuntimeCall>,
+		_len: usize,
+		_self_implicit: Self::Implicit,
+		_inherited_implication: &impl Encode,
+		_source: TransactionSource,
+	) -> ValidateResult<Self::Val, T::RuntimeCall> {
+		let priority = origin.as_signer().map_or(0, Self::boost_of);
+		Ok((ValidTransaction { priority, ..Default::default() }, (), origin))
+	}
+
+	impl_tx_ext_default!(T::RuntimeCall; prepare);
+}
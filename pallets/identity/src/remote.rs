@@ -0,0 +1,100 @@
+//! Read-only mirrors of identities registered on a foreign chain
+//!
+//! An identity held by this pallet on another chain can be carried over by proving its
+//! `IdentityOf` entry against a state root of that chain which this chain trusts, e.g. one relayed
+//! by a light client or set by governance. The proven registration is mirrored as a
+//! [`RemoteIdentity`]: the information and verification standing as of that state root, without
+//! the deposits and judgements, which stay on the foreign chain.
+//!
+//! The foreign chain is expected to run this pallet under the same name and with the same types,
+//! so that the entry is found under [`Pallet::identity_storage_key`] and decodes as a
+//! [`Registration`].
+
+use crate::{
+	Config, Error, IdentityFields, IdentityInfoOf, IdentityProofError, Pallet, Registration,
+	VerificationStatus,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound};
+use scale_info::TypeInfo;
+use sp_trie::StorageProof;
+
+/// Provides the state root of the foreign chain that identities are mirrored from.
+pub trait ForeignStateRoot<Hash> {
+	/// The state root to check proofs against, or `None` if there is none to trust, which
+	/// disables mirroring.
+	fn state_root() -> Option<Hash>;
+
+	/// Trust `root` from now on, for the benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_state_root(root: Hash);
+}
+
+impl<Hash> ForeignStateRoot<Hash> for () {
+	fn state_root() -> Option<Hash> {
+		None
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_state_root(_root: Hash) {}
+}
+
+/// An identity registered on the foreign chain, as proven against `state_root`.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(T, I))]
+pub struct RemoteIdentity<T: Config<I>, I: 'static = ()> {
+	/// Information about the identity.
+	pub info: IdentityInfoOf<T, I>,
+	/// Verification status of the identity on the foreign chain.
+	pub status: VerificationStatus,
+	/// Fields of `info` verified by a registrar on the foreign chain.
+	pub verified_fields: IdentityFields,
+	/// The state root of the foreign chain the identity was proven against.
+	pub state_root: T::Hash,
+}
+
+impl<T: Config<I>, I: 'static> RemoteIdentity<T, I> {
+	/// The mirror of `registration`, proven against `state_root`.
+	pub fn new(registration: Registration<T, I>, state_root: T::Hash) -> Self {
+		Self {
+			info: registration.info,
+			status: registration.status,
+			verified_fields: registration.verified_fields,
+			state_root,
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> From<IdentityProofError> for Error<T, I> {
+	fn from(error: IdentityProofError) -> Self {
+		match error {
+			IdentityProofError::InvalidProof => Error::<T, I>::InvalidProof,
+			IdentityProofError::Undecodable => Error::<T, I>::UndecodableProof,
+		}
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Check a read proof of `who`'s `IdentityOf` entry on the foreign chain against the trusted
+	/// state root.
+	///
+	/// Returns the proven identity, or `None` if the proof shows `who` has no identity there.
+	pub fn verify_remote_identity(
+		proof: StorageProof,
+		who: &T::AccountId,
+	) -> Result<Option<RemoteIdentity<T, I>>, Error<T, I>> {
+		let state_root =
+			T::ForeignStateRoot::state_root().ok_or(Error::<T, I>::NoForeignStateRoot)?;
+		let registration = Self::verify_identity_proof(&state_root, proof, who)?;
+		Ok(registration.map(|registration| RemoteIdentity::new(registration, state_root)))
+	}
+}
@@ -1,6 +1,36 @@
-use crate::{mock::*, pallet::JudgementsDoubleMap, Error, Event, IdentityInfo, Judgement};
-use frame_support::{assert_noop, assert_ok, BoundedVec};
-use sp_runtime::traits::Zero;
+use crate::{
+	mock::*,
+	pallet::{
+		AccruedFees, Beneficiaries, CounterForJudgements, DepositDebts, Disputes, ExemptAccounts,
+		FieldJudgements, IdentityExpiries, IdentitySets, JudgedBy, JudgementLimit, JudgementNotes,
+		JudgementReasons, JudgementRequests, JudgementsDoubleMap, JudgementsInBlock,
+		KycAttestations, KycHashes, Paused, PendingAudits, PendingReleases, PreviousProviderKeys,
+		ProviderBonds, ProviderKeys, ProviderNonces, ReconcileCursor, Registrars, RemoteIdentities,
+		RemoteJudgementSources, RentDue, RentStates, RevalidationDue, Scores,
+		ShadowJudgementsDoubleMap, ShadowJudgementsInline, Tombstones, TotalDeposits,
+		UnchangedSince, Vouches,
+	},
+	CallCategory, DepositBackend, DidService, DidServiceKind, Error, HoldDeposits, IdentityExpiry,
+	IdentityField, IdentityFields, IdentityInfo, IdentityInfoOf, IdentityProofError,
+	InspectIdentity, InvalidJudgement, InvalidJudgementName, Judgement, LegacyFieldLength,
+	PendingRelease, ReasonCode, ReceiveRemoteJudgement, RentState, VerificationLevel,
+	VerificationStatus,
+};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	assert_noop, assert_ok,
+	instances::Instance2,
+	traits::{
+		fungible::InspectHold, GetCallName, Hooks, ReservableCurrency, UnfilteredDispatchable,
+	},
+	BoundedVec,
+};
+use sp_runtime::{
+	testing::TestSignature, traits::Zero, BuildStorage, DispatchError, Perbill, TokenError,
+};
+use sp_trie::StorageProof;
+
+type Event = crate::Event<Test>;
 
 #[test]
 fn set_identity_works() {
@@ -10,26 +40,23 @@ fn set_identity_works() {
 			legal: b"legal".to_vec().try_into().unwrap(),
 			web: b"web".to_vec().try_into().unwrap(),
 			email: b"email".to_vec().try_into().unwrap(),
+			avatar: None,
 		};
 
 		// Set identity for account 1
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display.clone(),
-			info.legal.clone(),
-			info.web.clone(),
-			info.email.clone(),
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
 
 		// Check storage
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.info, info);
 		assert!(!registration.deposit.is_zero());
 		assert_eq!(registration.judgements.len(), 0);
-		assert_eq!(registration.judgements_count_double_map, 0);
+		assert_eq!(CounterForJudgements::<Test>::get(1), 0);
 
 		// Check event
-		System::assert_last_event(Event::IdentitySet { who: 1 }.into());
+		System::assert_last_event(
+			Event::IdentitySet { who: 1, display: info.display.clone(), bytes: 20 }.into(),
+		);
 	});
 }
 
@@ -41,16 +68,11 @@ fn clear_identity_works() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
 
 		// Set identity first
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 		let deposit = Identity::identity_of(&1).unwrap().deposit;
 
 		// Clear identity
@@ -81,27 +103,23 @@ fn provide_judgement_inline_works() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 
 		// Provide judgement (2 = KnownGood) with judgement_id 0
 		assert_ok!(Identity::provide_judgement_inline(
 			RuntimeOrigin::root(),
 			0, // judgement_id
 			1, // target
-			2  // judgement_type
+			Judgement::KnownGood,
+			None
 		));
 
 		// Check storage
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 1);
-		assert_eq!(registration.judgements[0], (0, Judgement::KnownGood));
+		assert_eq!(registration.judgements[0], (0, (Judgement::KnownGood, 1)));
 
 		// Check event
 		System::assert_last_event(Event::JudgementGiven { target: 1 }.into());
@@ -113,7 +131,13 @@ fn provide_judgement_inline_fails_without_identity() {
 	new_test_ext().execute_with(|| {
 		// Try to provide judgement for non-existent identity
 		assert_noop!(
-			Identity::provide_judgement_inline(RuntimeOrigin::root(), 0, 1, 2),
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::KnownGood,
+				None
+			),
 			Error::<Test>::InvalidTarget
 		);
 	});
@@ -128,21 +152,28 @@ fn provide_judgement_inline_respects_sticky_judgements() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 
 		// Provide sticky judgement (2 = KnownGood) with judgement_id 0
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 0, 1, 2));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
 
 		// Try to override same judgement_id with different judgement - should fail
 		assert_noop!(
-			Identity::provide_judgement_inline(RuntimeOrigin::root(), 0, 1, 1),
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::Reasonable,
+				None
+			),
 			Error::<Test>::StickyJudgement
 		);
 	});
@@ -157,28 +188,26 @@ fn set_identity_clears_non_sticky_judgement() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display.clone(),
-			info.legal.clone(),
-			info.web.clone(),
-			info.email.clone(),
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
 
 		// Provide non-sticky judgement (1 = Reasonable) with judgement_id 0
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 0, 1, 1));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 1);
-		assert_eq!(registration.judgements[0], (0, Judgement::Reasonable));
+		assert_eq!(registration.judgements[0], (0, (Judgement::Reasonable, 1)));
 
 		// Update identity - should clear non-sticky judgement
 		assert_ok!(Identity::set_identity(
 			RuntimeOrigin::signed(1),
-			b"new_display".to_vec().try_into().unwrap(),
-			info.legal,
-			info.web,
-			info.email,
+			IdentityInfo { display: b"new_display".to_vec().try_into().unwrap(), ..info },
 		));
 
 		// Non-sticky judgement should be cleared
@@ -196,34 +225,32 @@ fn set_identity_preserves_sticky_judgement() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display.clone(),
-			info.legal.clone(),
-			info.web.clone(),
-			info.email.clone(),
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
 
 		// Provide sticky judgement (2 = KnownGood) with judgement_id 0
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 0, 1, 2));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 1);
-		assert_eq!(registration.judgements[0], (0, Judgement::KnownGood));
+		assert_eq!(registration.judgements[0], (0, (Judgement::KnownGood, 1)));
 
 		// Update identity - should preserve sticky judgement
 		assert_ok!(Identity::set_identity(
 			RuntimeOrigin::signed(1),
-			b"new_display".to_vec().try_into().unwrap(),
-			info.legal,
-			info.web,
-			info.email,
+			IdentityInfo { display: b"new_display".to_vec().try_into().unwrap(), ..info },
 		));
 
 		// Sticky judgement should be preserved
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 1);
-		assert_eq!(registration.judgements[0], (0, Judgement::KnownGood));
+		assert_eq!(registration.judgements[0], (0, (Judgement::KnownGood, 1)));
 	});
 }
 
@@ -236,6 +263,7 @@ fn deposit_calculation_works() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
 
 		let large_info = IdentityInfo {
@@ -246,27 +274,16 @@ fn deposit_calculation_works() {
 			legal: b"legal_name".to_vec().try_into().unwrap(),
 			web: b"https://example.com".to_vec().try_into().unwrap(),
 			email: b"test@example.com".to_vec().try_into().unwrap(),
+			avatar: None,
 		};
 
 		// Set small identity
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			small_info.display,
-			small_info.legal,
-			small_info.web,
-			small_info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), small_info));
 		let small_deposit = Identity::identity_of(&1).unwrap().deposit;
 
 		// Clear and set large identity
 		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			large_info.display,
-			large_info.legal,
-			large_info.web,
-			large_info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), large_info));
 		let large_deposit = Identity::identity_of(&1).unwrap().deposit;
 
 		// Large deposit should be greater than small deposit due to byte deposit
@@ -283,28 +300,47 @@ fn multiple_judgements_work() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 
 		// Add multiple judgements with different IDs
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 5, 1, 1)); // Reasonable
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 1, 1, 2)); // KnownGood
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 10, 1, 3)); // Erroneous
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 0, 1, 4)); // LowQuality
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			5,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			10,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::LowQuality,
+			None
+		));
 
 		// Check storage - should be sorted by ID
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 4);
-		assert_eq!(registration.judgements[0], (0, Judgement::LowQuality));
-		assert_eq!(registration.judgements[1], (1, Judgement::KnownGood));
-		assert_eq!(registration.judgements[2], (5, Judgement::Reasonable));
-		assert_eq!(registration.judgements[3], (10, Judgement::Erroneous));
+		assert_eq!(registration.judgements[0], (0, (Judgement::LowQuality, 1)));
+		assert_eq!(registration.judgements[1], (1, (Judgement::KnownGood, 1)));
+		assert_eq!(registration.judgements[2], (5, (Judgement::Reasonable, 1)));
+		assert_eq!(registration.judgements[3], (10, (Judgement::Erroneous, 1)));
 	});
 }
 
@@ -317,26 +353,33 @@ fn judgement_update_works() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 
 		// Add initial judgement
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 5, 1, 1)); // Reasonable
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			5,
+			1,
+			Judgement::Reasonable,
+			None
+		));
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 1);
-		assert_eq!(registration.judgements[0], (5, Judgement::Reasonable));
+		assert_eq!(registration.judgements[0], (5, (Judgement::Reasonable, 1)));
 
 		// Update same judgement_id with different judgement
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 5, 1, 4)); // LowQuality
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			5,
+			1,
+			Judgement::LowQuality,
+			None
+		));
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 1);
-		assert_eq!(registration.judgements[0], (5, Judgement::LowQuality));
+		assert_eq!(registration.judgements[0], (5, (Judgement::LowQuality, 1)));
 	});
 }
 
@@ -349,20 +392,39 @@ fn mixed_sticky_non_sticky_judgements() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display.clone(),
-			info.legal.clone(),
-			info.web.clone(),
-			info.email.clone(),
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
 
 		// Add mix of sticky and non-sticky judgements
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 1, 1, 1)); // Reasonable (non-sticky)
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 2, 1, 2)); // KnownGood (sticky)
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 3, 1, 3)); // Erroneous (sticky)
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 4, 1, 4)); // LowQuality (non-sticky)
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		)); // non-sticky
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::KnownGood,
+			None
+		)); // sticky
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			3,
+			1,
+			Judgement::Erroneous,
+			None
+		)); // sticky
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			4,
+			1,
+			Judgement::LowQuality,
+			None
+		)); // non-sticky
 
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 4);
@@ -370,17 +432,14 @@ fn mixed_sticky_non_sticky_judgements() {
 		// Update identity - should only keep sticky judgements
 		assert_ok!(Identity::set_identity(
 			RuntimeOrigin::signed(1),
-			b"new_display".to_vec().try_into().unwrap(),
-			info.legal,
-			info.web,
-			info.email,
+			IdentityInfo { display: b"new_display".to_vec().try_into().unwrap(), ..info },
 		));
 
 		// Only sticky judgements should remain
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 2);
-		assert_eq!(registration.judgements[0], (2, Judgement::KnownGood));
-		assert_eq!(registration.judgements[1], (3, Judgement::Erroneous));
+		assert_eq!(registration.judgements[0], (2, (Judgement::KnownGood, 1)));
+		assert_eq!(registration.judgements[1], (3, (Judgement::Erroneous, 1)));
 	});
 }
 
@@ -393,18 +452,19 @@ fn too_many_judgements_error() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 
 		// Add judgements up to the maximum (20)
 		for i in 0..20 {
-			assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), i, 1, 1));
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				i,
+				1,
+				Judgement::Reasonable,
+				None
+			));
 		}
 
 		// Verify we've reached the limit
@@ -413,7 +473,13 @@ fn too_many_judgements_error() {
 
 		// Try to add one more judgement - should fail
 		assert_noop!(
-			Identity::provide_judgement_inline(RuntimeOrigin::root(), 20, 1, 1),
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				20,
+				1,
+				Judgement::Reasonable,
+				None
+			),
 			Error::<Test>::TooManyJudgements
 		);
 	});
@@ -428,31 +494,44 @@ fn inline_storage_pattern_works() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 
 		// Add judgements using inline storage
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 5, 1, 1)); // Reasonable
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 1, 1, 2)); // KnownGood
-		assert_ok!(Identity::provide_judgement_inline(RuntimeOrigin::root(), 10, 1, 3)); // Erroneous
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			5,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			10,
+			1,
+			Judgement::Erroneous,
+			None
+		));
 
 		// Check inline storage (BoundedVec in Registration)
 		let registration = Identity::identity_of(&1).unwrap();
 		assert_eq!(registration.judgements.len(), 3);
-		assert_eq!(registration.judgements[0], (1, Judgement::KnownGood));
-		assert_eq!(registration.judgements[1], (5, Judgement::Reasonable));
-		assert_eq!(registration.judgements[2], (10, Judgement::Erroneous));
+		assert_eq!(registration.judgements[0], (1, (Judgement::KnownGood, 1)));
+		assert_eq!(registration.judgements[1], (5, (Judgement::Reasonable, 1)));
+		assert_eq!(registration.judgements[2], (10, (Judgement::Erroneous, 1)));
 
 		// Verify double map is still empty (since we only used inline)
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 1), None);
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 5), None);
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 10), None);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 1), None);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 5), None);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 10), None);
 
 		// Clear identity using the unified method
 		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
@@ -471,25 +550,38 @@ fn double_map_storage_pattern_works() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 
 		// Add judgements using double map storage
-		assert_ok!(Identity::provide_judgement_double_map(RuntimeOrigin::root(), 5, 1, 1)); // Reasonable
-		assert_ok!(Identity::provide_judgement_double_map(RuntimeOrigin::root(), 1, 1, 2)); // KnownGood
-		assert_ok!(Identity::provide_judgement_double_map(RuntimeOrigin::root(), 10, 1, 3)); // Erroneous
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			5,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			10,
+			1,
+			Judgement::Erroneous,
+			None
+		));
 
 		// Check double map storage
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 1), Some(Judgement::KnownGood));
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 5), Some(Judgement::Reasonable));
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 10), Some(Judgement::Erroneous));
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 99), None); // Non-existent
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 1), Some((Judgement::KnownGood, 1)));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 5), Some((Judgement::Reasonable, 1)));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 10), Some((Judgement::Erroneous, 1)));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 99), None); // Non-existent
 
 		// Verify inline storage is still empty (since we only used double map)
 		let registration = Identity::identity_of(&1).unwrap();
@@ -500,9 +592,9 @@ fn double_map_storage_pattern_works() {
 
 		// Verify both storages are cleared
 		assert!(Identity::identity_of(&1).is_none());
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 1), None);
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 5), None);
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 10), None);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 1), None);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 5), None);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 10), None);
 	});
 }
 
@@ -515,38 +607,6812 @@ fn double_map_counter_tracks_correctly() {
 			legal: BoundedVec::default(),
 			web: BoundedVec::default(),
 			email: BoundedVec::default(),
+			avatar: None,
 		};
-		assert_ok!(Identity::set_identity(
-			RuntimeOrigin::signed(1),
-			info.display,
-			info.legal,
-			info.web,
-			info.email,
-		));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
 
 		// Initial counter should be 0
-		let registration = Identity::identity_of(&1).unwrap();
-		assert_eq!(registration.judgements_count_double_map, 0);
+		assert_eq!(CounterForJudgements::<Test>::get(1), 0);
 
 		// Add judgements using double map
-		assert_ok!(Identity::provide_judgement_double_map(RuntimeOrigin::root(), 1, 1, 1)); // New
-		assert_ok!(Identity::provide_judgement_double_map(RuntimeOrigin::root(), 2, 1, 2)); // New
-		assert_ok!(Identity::provide_judgement_double_map(RuntimeOrigin::root(), 3, 1, 3)); // New
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		)); // New
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::KnownGood,
+			None
+		)); // New
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			3,
+			1,
+			Judgement::Erroneous,
+			None
+		)); // New
 
 		// Counter should be 3
+		assert_eq!(CounterForJudgements::<Test>::get(1), 3);
 		let registration = Identity::identity_of(&1).unwrap();
-		assert_eq!(registration.judgements_count_double_map, 3);
 
 		// Replace existing judgement (should not increment)
-		assert_ok!(Identity::provide_judgement_double_map(RuntimeOrigin::root(), 1, 1, 4)); // Replace
+		let replaced = Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::LowQuality,
+			None,
+		)
+		.unwrap(); // Replace
 
-		// Counter should still be 3
-		let registration = Identity::identity_of(&1).unwrap();
-		assert_eq!(registration.judgements_count_double_map, 3);
+		// Counter should still be 3, and the registration is left alone and not paid for
+		assert_eq!(CounterForJudgements::<Test>::get(1), 3);
+		assert_eq!(Identity::identity_of(&1), Some(registration));
+		assert_eq!(
+			replaced.actual_weight,
+			Some(<() as crate::WeightInfo>::replace_judgement_double_map())
+		);
+
+		// A sticky replacement is counted in the registration, and paid for in full
+		let replaced = Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			4,
+			1,
+			Judgement::Reasonable,
+			None,
+		)
+		.unwrap();
+		assert_eq!(CounterForJudgements::<Test>::get(1), 4);
+		assert_eq!(replaced.actual_weight, None);
+		let replaced = Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			4,
+			1,
+			Judgement::Erroneous,
+			None,
+		)
+		.unwrap();
+		assert_eq!(CounterForJudgements::<Test>::get(1), 4);
+		assert_eq!(replaced.actual_weight, None);
+		assert_eq!(Identity::identity_of(&1).unwrap().erroneous, 2);
 
 		// Verify double map contents
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 1), Some(Judgement::LowQuality));
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 2), Some(Judgement::KnownGood));
-		assert_eq!(JudgementsDoubleMap::<Test>::get(&1, 3), Some(Judgement::Erroneous));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 1), Some((Judgement::LowQuality, 1)));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 2), Some((Judgement::KnownGood, 1)));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 3), Some((Judgement::Erroneous, 1)));
+	});
+}
+
+#[test]
+fn judgements_record_the_block_they_were_given_in() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+
+		// A replaced verdict is dated anew in either pattern
+		System::set_block_number(5);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().judgements.to_vec(),
+			vec![(0, (Judgement::KnownGood, 5))]
+		);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 1), Some((Judgement::Reasonable, 1)));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 2), Some((Judgement::LowQuality, 5)));
+
+		// Moving a judgement between the patterns keeps its date
+		System::set_block_number(9);
+		assert_ok!(Identity::consolidate_judgements(RuntimeOrigin::signed(1), 1));
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().judgements.to_vec(),
+			vec![
+				(0, (Judgement::KnownGood, 5)),
+				(1, (Judgement::Reasonable, 1)),
+				(2, (Judgement::LowQuality, 5)),
+			]
+		);
+	});
+}
+
+#[test]
+fn identity_info_decoding_enforces_field_bounds() {
+	new_test_ext().execute_with(|| {
+		let empty = Vec::<u8>::new;
+
+		// A display exceeding `MaxDisplayLength` (64) must be rejected while decoding the call
+		let oversized = (vec![b'X'; 65], empty(), empty(), empty());
+		let encoded = oversized.encode();
+		assert!(IdentityInfoOf::<Test>::decode(&mut &encoded[..]).is_err());
+
+		// Each field is checked against its own bound: 100 bytes is too long for a display but
+		// fits `MaxWebLength` (100)
+		let long_display = (vec![b'X'; 100], empty(), empty(), empty());
+		let encoded = long_display.encode();
+		assert!(IdentityInfoOf::<Test>::decode(&mut &encoded[..]).is_err());
+
+		// An avatar is bounded by `MaxCidLength` (64)
+		let long_avatar = (empty(), empty(), empty(), empty(), Some(vec![b'X'; 65]));
+		let encoded = long_avatar.encode();
+		assert!(IdentityInfoOf::<Test>::decode(&mut &encoded[..]).is_err());
+
+		// Fields at exactly their limits decode fine
+		let at_limit =
+			(vec![b'X'; 64], vec![b'X'; 64], vec![b'X'; 100], vec![b'X'; 64], Some(vec![b'X'; 64]));
+		let encoded = at_limit.encode();
+		let info = IdentityInfoOf::<Test>::decode(&mut &encoded[..]).unwrap();
+		assert_eq!(info.display.len(), 64);
+		assert_eq!(info.web.len(), 100);
+		assert_eq!(info.avatar.as_ref().map(|cid| cid.len()), Some(64));
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
+	});
+}
+
+#[test]
+fn identity_info_introspection() {
+	new_test_ext().execute_with(|| {
+		// Nothing filled in
+		let empty = IdentityInfoOf::<Test>::default();
+		assert!(empty.is_empty());
+		assert_eq!(empty.field_count(), 0);
+		assert_eq!(empty.populated_fields(), IdentityFields::empty());
+		assert_eq!(empty.byte_len_per_field(), [0; 5]);
+		// An unset avatar is left out of the deposit
+		assert_eq!(empty.field_sizes(), [1, 1, 1, 1, 0]);
+		assert_eq!(empty.field_sizes().iter().sum::<u32>() + 1, empty.encoded_size());
+
+		// One field, without its length prefix
+		let email_only = IdentityInfoOf::<Test> {
+			email: b"a@b.c".to_vec().try_into().unwrap(),
+			..Default::default()
+		};
+		assert!(!email_only.is_empty());
+		assert_eq!(email_only.field_count(), 1);
+		assert_eq!(email_only.populated_fields(), IdentityFields::from(IdentityField::Email));
+		assert_eq!(email_only.byte_len_per_field(), [0, 0, 0, 5, 0]);
+
+		// Every field at its limit, each taking a two byte length prefix from 64 bytes on
+		let full = IdentityInfoOf::<Test> {
+			display: vec![b'X'; 64].try_into().unwrap(),
+			legal: vec![b'X'; 64].try_into().unwrap(),
+			web: vec![b'X'; 100].try_into().unwrap(),
+			email: vec![b'X'; 64].try_into().unwrap(),
+			avatar: Some(vec![b'X'; 64].try_into().unwrap()),
+		};
+		assert_eq!(full.field_count(), 5);
+		assert_eq!(full.populated_fields(), IdentityFields::all());
+		assert_eq!(full.byte_len_per_field(), [64, 64, 100, 64, 64]);
+		assert_eq!(full.field_sizes(), [66, 66, 102, 66, 67]);
+		assert_eq!(full.field_sizes().iter().sum::<u32>(), full.encoded_size());
+
+		// The view function reports the fields of a stored identity
+		assert_eq!(Identity::populated_fields(1), IdentityFields::empty());
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), email_only));
+		assert_eq!(Identity::populated_fields(1), IdentityFields::from(IdentityField::Email));
+	});
+}
+
+#[test]
+#[allow(deprecated)]
+fn set_identity_legacy_matches_set_identity() {
+	new_test_ext().execute_with(|| {
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: b"legal".to_vec().try_into().unwrap(),
+			web: b"web".to_vec().try_into().unwrap(),
+			email: b"email".to_vec().try_into().unwrap(),
+			avatar: None,
+		};
+
+		// Account 1 uses the struct-based call, account 2 the legacy one
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		assert_ok!(Identity::set_identity_legacy(
+			RuntimeOrigin::signed(2),
+			info.display.to_vec().try_into().unwrap(),
+			info.legal.to_vec().try_into().unwrap(),
+			info.web.to_vec().try_into().unwrap(),
+			info.email.to_vec().try_into().unwrap(),
+		));
+		let bytes = info.byte_len_per_field().iter().sum();
+		System::assert_last_event(
+			Event::IdentitySet { who: 2, display: info.display.clone(), bytes }.into(),
+		);
+
+		let new_style = Identity::identity_of(&1).unwrap();
+		let legacy = Identity::identity_of(&2).unwrap();
+		assert_eq!(legacy.info, new_style.info);
+		assert_eq!(legacy.deposit, new_style.deposit);
+		assert_eq!(Balances::reserved_balance(2), Balances::reserved_balance(1));
+	});
+}
+
+#[test]
+#[allow(deprecated)]
+fn set_identity_legacy_keeps_the_avatar_and_field_bounds() {
+	new_test_ext().execute_with(|| {
+		let avatar = Some(b"bafy".to_vec().try_into().unwrap());
+		let info = IdentityInfo { avatar, ..empty_info() };
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		let deposit = Balances::reserved_balance(1);
+
+		// The call has no avatar to give, so the one set before stays
+		let display: BoundedVec<u8, LegacyFieldLength> = b"display".to_vec().try_into().unwrap();
+		assert_ok!(Identity::set_identity_legacy(
+			RuntimeOrigin::signed(1),
+			display.clone(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.info.avatar, info.avatar);
+		assert_eq!(registration.info.display.to_vec(), display.to_vec());
+		assert_eq!(Balances::reserved_balance(1), deposit + 7);
+
+		// Fields decode up to the original 64 bytes, and must also fit their own bound
+		let too_long = vec![b'X'; 65];
+		assert!(BoundedVec::<u8, LegacyFieldLength>::decode(&mut &too_long.encode()[..]).is_err());
+		MaxDisplayLength::set(6);
+		assert_noop!(
+			Identity::set_identity_legacy(
+				RuntimeOrigin::signed(1),
+				display,
+				Default::default(),
+				Default::default(),
+				Default::default(),
+			),
+			Error::<Test>::FieldTooLong
+		);
+	});
+}
+
+#[test]
+#[allow(deprecated)]
+fn set_identity_legacy_keeps_its_own_call_index() {
+	use crate::Call;
+
+	let display: BoundedVec<u8, MaxDisplayLength> = b"display".to_vec().try_into().unwrap();
+	let legacy = RuntimeCall::Identity(Call::set_identity_legacy {
+		display: display.to_vec().try_into().unwrap(),
+		legal: BoundedVec::default(),
+		web: BoundedVec::default(),
+		email: BoundedVec::default(),
+	});
+	let current = RuntimeCall::Identity(Call::set_identity {
+		info: IdentityInfo {
+			display,
+			legal: BoundedVec::default(),
+			web: BoundedVec::default(),
+			email: BoundedVec::default(),
+			avatar: None,
+		},
+	});
+
+	// Same pallet index, distinct call indices, identical argument encoding but for the unset
+	// avatar the legacy call lacks
+	let legacy = legacy.encode();
+	let current = current.encode();
+	assert_eq!(legacy[0], current[0]);
+	assert_eq!(current[1], 0);
+	assert_eq!(legacy[1], 4);
+	assert_eq!(legacy[2..], current[2..current.len() - 1]);
+
+	// Transactions built against the old signature still decode
+	assert!(matches!(
+		RuntimeCall::decode(&mut &legacy[..]),
+		Ok(RuntimeCall::Identity(Call::set_identity_legacy { .. }))
+	));
+}
+
+fn note(text: &[u8]) -> Option<BoundedVec<u8, MaxNoteLength>> {
+	Some(text.to_vec().try_into().unwrap())
+}
+
+fn empty_info() -> IdentityInfoOf<Test> {
+	IdentityInfo {
+		display: BoundedVec::default(),
+		legal: BoundedVec::default(),
+		web: BoundedVec::default(),
+		email: BoundedVec::default(),
+		avatar: None,
+	}
+}
+
+fn set_display_identity(who: u64) {
+	let info = IdentityInfo {
+		display: b"display".to_vec().try_into().unwrap(),
+		legal: BoundedVec::default(),
+		web: BoundedVec::default(),
+		email: BoundedVec::default(),
+		avatar: None,
+	};
+	assert_ok!(Identity::set_identity(RuntimeOrigin::signed(who), info));
+}
+
+#[test]
+fn judgement_note_is_stored_and_paid_for() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		let identity_deposit = Balances::reserved_balance(1);
+
+		// Erroneous (3) with a 19 byte note
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			note(b"web domain mismatch")
+		));
+
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), note(b"web domain mismatch"));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.notes_deposit, 19);
+		assert_eq!(registration.total_deposit(), identity_deposit + 19);
+		assert_eq!(Balances::reserved_balance(1), identity_deposit + 19);
+	});
+}
+
+#[test]
+fn judgement_note_is_replaced_or_removed_with_next_judgement() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		let identity_deposit = Balances::reserved_balance(1);
+
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::LowQuality,
+			note(b"blurry photo")
+		));
+		assert_eq!(Balances::reserved_balance(1), identity_deposit + 12);
+
+		// A shorter note refunds the difference
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::LowQuality,
+			note(b"typo")
+		));
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), note(b"typo"));
+		assert_eq!(Balances::reserved_balance(1), identity_deposit + 4);
+
+		// No note keeps the previous one, also for the inline judgement under the same ID
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), note(b"typo"));
+		assert_eq!(Balances::reserved_balance(1), identity_deposit + 4);
+
+		// An empty note removes it
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			note(b"")
+		));
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), None);
+		assert_eq!(Identity::identity_of(&1).unwrap().notes_deposit, 0);
+		assert_eq!(Balances::reserved_balance(1), identity_deposit);
+	});
+}
+
+#[test]
+fn judgement_note_requires_funds() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		// Leave less free balance above the existential deposit (500) than the note costs
+		let free = Balances::free_balance(1);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), 2, free - 510));
+		assert_eq!(Balances::free_balance(1), 510);
+
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::Erroneous,
+				note(b"suspected impersonation")
+			),
+			sp_runtime::DispatchError::ConsumerRemaining
+		);
+	});
+}
+
+#[test]
+fn set_identity_drops_notes_of_non_sticky_judgements() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		let identity_deposit = Balances::reserved_balance(1);
+
+		// Reasonable (non-sticky) and Erroneous (sticky), both with notes
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			note(b"ok")
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Erroneous,
+			note(b"wrong email")
+		));
+		// A double map judgement sharing ID 0 keeps that note alive
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::LowQuality,
+			note(b"lq")
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::LowQuality,
+			note(b"lq")
+		));
+
+		set_display_identity(1);
+
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), None);
+		assert_eq!(JudgementNotes::<Test>::get(1, 1), note(b"wrong email"));
+		assert_eq!(JudgementNotes::<Test>::get(1, 2), note(b"lq"));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.notes_deposit, 13);
+		assert_eq!(Balances::reserved_balance(1), identity_deposit + 13);
+	});
+}
+
+#[test]
+fn clear_identity_removes_notes_and_returns_their_deposit() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			note(b"wrong email")
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::LowQuality,
+			note(b"blurry photo")
+		));
+		let deposit = Identity::identity_of(&1).unwrap().total_deposit();
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+
+		assert_eq!(JudgementNotes::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::IdentityCleared { who: 1, deposit }.into());
+	});
+}
+
+#[test]
+fn judgement_reason_is_stored_and_surfaced() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_noop!(
+			Identity::provide_judgement_with_reason(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::Reasonable,
+				ReasonCode::DataMismatch,
+				None
+			),
+			Error::<Test>::ReasonNotApplicable
+		);
+		assert_noop!(
+			Identity::provide_judgement_with_reason(
+				RuntimeOrigin::signed(1),
+				0,
+				1,
+				Judgement::LowQuality,
+				ReasonCode::DataMismatch,
+				None
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(Identity::provide_judgement_with_reason(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::LowQuality,
+			ReasonCode::UnreachableContact,
+			note(b"mail bounced")
+		));
+		System::assert_has_event(Event::JudgementGiven { target: 1 }.into());
+		System::assert_last_event(
+			Event::JudgementReasonGiven {
+				target: 1,
+				judgement_id: 0,
+				reason: ReasonCode::UnreachableContact,
+			}
+			.into(),
+		);
+		assert_eq!(Identity::judgement_of(1, 0), Some(Judgement::LowQuality));
+		assert_eq!(Identity::judgement_reason_of(1, 0), Some(ReasonCode::UnreachableContact));
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), note(b"mail bounced"));
+
+		// A judgement given under the same ID without a reason drops it
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(Identity::judgement_reason_of(1, 0), None);
+	});
+}
+
+#[test]
+fn judgement_reason_is_dropped_with_its_judgement() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		for (judgement_id, judgement, reason) in [
+			(0, Judgement::Erroneous, ReasonCode::SuspectedImpersonation),
+			(1, Judgement::LowQuality, ReasonCode::DataMismatch),
+		] {
+			assert_ok!(Identity::provide_judgement_with_reason(
+				RuntimeOrigin::root(),
+				judgement_id,
+				1,
+				judgement,
+				reason,
+				None
+			));
+		}
+
+		// The sticky judgement stays with its reason
+		assert_ok!(Identity::clear_judgements(RuntimeOrigin::signed(1)));
+		assert_eq!(JudgementReasons::<Test>::get(1, 0), Some(ReasonCode::SuspectedImpersonation));
+		assert!(!JudgementReasons::<Test>::contains_key(1, 1));
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(JudgementReasons::<Test>::iter_prefix(1).count(), 0);
+	});
+}
+
+#[test]
+fn judgement_limit_defaults_to_max_judgements() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(JudgementLimit::<Test>::get(), MaxJudgements::get());
+	});
+}
+
+#[test]
+fn set_judgement_limit_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::set_judgement_limit(RuntimeOrigin::signed(1), 5),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::set_judgement_limit(RuntimeOrigin::root(), MaxJudgements::get() + 1),
+			Error::<Test>::JudgementLimitTooHigh
+		);
+
+		assert_ok!(Identity::set_judgement_limit(RuntimeOrigin::root(), 5));
+		assert_eq!(JudgementLimit::<Test>::get(), 5);
+		System::assert_last_event(Event::JudgementLimitSet { limit: 5 }.into());
+	});
+}
+
+#[test]
+fn judgement_limit_is_enforced_in_both_storage_patterns() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_judgement_limit(RuntimeOrigin::root(), 2));
+
+		for i in 0..2 {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				i,
+				1,
+				Judgement::Reasonable,
+				None
+			));
+			assert_ok!(Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				i,
+				1,
+				Judgement::Reasonable,
+				None
+			));
+		}
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				2,
+				1,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::TooManyJudgements
+		);
+		assert_noop!(
+			Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				2,
+				1,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::TooManyJudgements
+		);
+
+		// Replacing an existing judgement is still allowed at the limit
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+	});
+}
+
+#[test]
+fn lowering_judgement_limit_keeps_existing_judgements() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		for i in 0..3 {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				i,
+				1,
+				Judgement::Reasonable,
+				None
+			));
+		}
+
+		assert_ok!(Identity::set_judgement_limit(RuntimeOrigin::root(), 1));
+
+		assert_eq!(Identity::identity_of(&1).unwrap().judgements.len(), 3);
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				3,
+				1,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::TooManyJudgements
+		);
+	});
+}
+
+#[test]
+fn genesis_judgement_limit_is_applied() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	crate::GenesisConfig::<Test> { judgement_limit: Some(3), ..Default::default() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		assert_eq!(JudgementLimit::<Test>::get(), 3);
+	});
+}
+
+#[test]
+#[should_panic(expected = "Judgement limit must not exceed `MaxJudgements`")]
+fn genesis_judgement_limit_above_max_panics() {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	crate::GenesisConfig::<Test> {
+		judgement_limit: Some(MaxJudgements::get() + 1),
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+}
+
+#[test]
+fn genesis_identities_are_set() {
+	let info = IdentityInfo {
+		display: b"genesis".to_vec().try_into().unwrap(),
+		legal: BoundedVec::default(),
+		web: BoundedVec::default(),
+		email: BoundedVec::default(),
+		avatar: None,
+	};
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 1000)], dev_accounts: None }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	crate::GenesisConfig::<Test> {
+		identities: vec![(1, info.clone())],
+		judgements: vec![(1, 0, Judgement::KnownGood)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	sp_io::TestExternalities::from(t).execute_with(|| {
+		let registration = crate::IdentityOf::<Test>::get(1).unwrap();
+		assert_eq!(registration.info, info);
+		assert_eq!(registration.judgements.to_vec(), vec![(0, (Judgement::KnownGood, 0))]);
+		assert_eq!(registration.status, VerificationStatus::Verified);
+		assert_eq!(Balances::reserved_balance(1), registration.deposit);
+	});
+}
+
+#[test]
+fn genesis_config_round_trips_through_json() {
+	let info = IdentityInfo {
+		display: b"display".to_vec().try_into().unwrap(),
+		legal: b"legal".to_vec().try_into().unwrap(),
+		web: BoundedVec::default(),
+		email: BoundedVec::default(),
+		avatar: None,
+	};
+	let config = crate::GenesisConfig::<Test> {
+		judgement_limit: Some(3),
+		identities: vec![(1, info)],
+		judgements: vec![(1, 0, Judgement::KnownGood)],
+		..Default::default()
+	};
+	let json = serde_json::to_string(&config).unwrap();
+	let decoded: crate::GenesisConfig<Test> = serde_json::from_str(&json).unwrap();
+	assert_eq!(decoded.judgement_limit, config.judgement_limit);
+	assert_eq!(decoded.identities, config.identities);
+	assert_eq!(decoded.judgements, config.judgements);
+	assert!(json.contains(r#""judgements":[[1,0,"KnownGood"]]"#));
+
+	// Field bounds are enforced while deserializing, as while decoding
+	let oversized = json.replace("[100,105,115,112,108,97,121]", &format!("{:?}", vec![b'X'; 65]));
+	assert!(serde_json::from_str::<crate::GenesisConfig<Test>>(&oversized).is_err());
+}
+
+#[test]
+fn first_known_good_judgement_mints_a_badge() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		// Non-sticky judgements don't verify an identity
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert!(Badges::get().is_empty());
+		assert!(!Identity::is_verified(&1));
+
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_eq!(Badges::get(), vec![1]);
+		assert!(Identity::is_verified(&1));
+
+		// Further `KnownGood` judgements, in either storage pattern, don't mint again
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_eq!(Badges::get(), vec![1]);
+
+		// The badge outlives an identity update since `KnownGood` is sticky
+		set_display_identity(1);
+		assert!(Identity::is_verified(&1));
+		assert_eq!(Badges::get(), vec![1]);
+	});
+}
+
+#[test]
+fn known_good_double_map_judgement_mints_a_badge() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_eq!(Badges::get(), vec![1]);
+		assert!(Identity::is_verified(&1));
+	});
+}
+
+#[test]
+fn clearing_a_verified_identity_burns_the_badge() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		set_display_identity(2);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			2,
+			Judgement::KnownGood,
+			None
+		));
+		assert_eq!(Badges::get(), vec![1, 2]);
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(Badges::get(), vec![2]);
+
+		// Re-registering starts unverified
+		set_display_identity(1);
+		assert!(!Identity::is_verified(&1));
+	});
+}
+
+#[test]
+fn clearing_an_unverified_identity_does_not_revoke() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+
+		// Pretend a badge exists to observe that no revocation happens
+		Badges::set(vec![1]);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(Badges::get(), vec![1]);
+	});
+}
+
+#[test]
+fn provide_judgement_remote_records_the_registrar() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		assert_ok!(Identity::provide_judgement_remote(
+			RuntimeOrigin::signed(2001),
+			0,
+			1,
+			Judgement::KnownGood,
+			note(b"checked by sibling")
+		));
+		System::assert_last_event(
+			Event::RemoteJudgementGiven { target: 1, registrar: 2001 }.into(),
+		);
+
+		// Stored inline like a local judgement, with its note and source alongside
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.judgements.to_vec(), vec![(0, (Judgement::KnownGood, 1))]);
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), note(b"checked by sibling"));
+		assert_eq!(RemoteJudgementSources::<Test>::get(1, 0), Some(2001));
+		assert_eq!(Badges::get(), vec![1]);
+	});
+}
+
+#[test]
+fn provide_judgement_remote_requires_remote_origin() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		assert_noop!(
+			Identity::provide_judgement_remote(
+				RuntimeOrigin::signed(2),
+				0,
+				1,
+				Judgement::Reasonable,
+				None
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::provide_judgement_remote(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::Reasonable,
+				None
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::provide_judgement_remote(
+				RuntimeOrigin::signed(2000),
+				0,
+				2,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::InvalidTarget
+		);
+	});
+}
+
+#[test]
+fn provide_judgement_remote_rejects_local_registrar_ids() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+
+		// The fee requested of the local registrar stays theirs
+		assert_noop!(
+			Identity::provide_judgement_remote(
+				RuntimeOrigin::signed(2001),
+				0,
+				1,
+				Judgement::KnownGood,
+				None
+			),
+			Error::<Test>::LocalRegistrarId
+		);
+		assert_eq!(JudgementRequests::<Test>::get(1, 0), Some(50));
+	});
+}
+
+#[test]
+fn receive_remote_judgement_works() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		assert_ok!(Identity::receive_remote_judgement(
+			RuntimeOrigin::signed(1500),
+			1000,
+			1,
+			Judgement::KnownGood
+		));
+		System::assert_last_event(
+			Event::BridgedJudgementReceived { target: 1, judgement_id: 1000, registrar: 1 }.into(),
+		);
+
+		// Stored inline like a remote judgement given through a call
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.judgements.to_vec(), vec![(1000, (Judgement::KnownGood, 1))]);
+		assert_eq!(RemoteJudgementSources::<Test>::get(1, 1000), Some(1));
+		assert_eq!(JudgementLog::get(), vec![(1, 1000, Judgement::KnownGood)]);
+		assert_eq!(Badges::get(), vec![1]);
+	});
+}
+
+#[test]
+fn receive_remote_judgement_checks_origin_and_id() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		assert_noop!(
+			Identity::receive_remote_judgement(
+				RuntimeOrigin::signed(2000),
+				1000,
+				1,
+				Judgement::Reasonable
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::receive_remote_judgement(
+				RuntimeOrigin::signed(1500),
+				999,
+				1,
+				Judgement::Reasonable
+			),
+			Error::<Test>::NotBridgedJudgementId
+		);
+		assert_noop!(
+			Identity::receive_remote_judgement(
+				RuntimeOrigin::signed(1500),
+				1000,
+				2,
+				Judgement::Reasonable
+			),
+			Error::<Test>::InvalidTarget
+		);
+		// Nothing is kept of a judgement rejected halfway through
+		assert_noop!(
+			Identity::receive_remote_judgement(
+				RuntimeOrigin::signed(1500),
+				1000,
+				1,
+				Judgement::Requested
+			),
+			Error::<Test>::InvalidJudgementTransition
+		);
+	});
+}
+
+#[test]
+fn bridged_judgement_ids_are_reserved() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				1000,
+				1,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::ReservedJudgementId
+		);
+		assert_noop!(
+			Identity::provide_judgement_remote(
+				RuntimeOrigin::signed(2000),
+				1999,
+				1,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::ReservedJudgementId
+		);
+		assert_noop!(
+			Identity::set_registrar(RuntimeOrigin::root(), 1500, 1, 10),
+			Error::<Test>::ReservedJudgementId
+		);
+	});
+}
+
+#[test]
+fn remote_judgement_source_follows_its_judgement() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		// A later remote judgement with the same ID records its own registrar
+		assert_ok!(Identity::provide_judgement_remote(
+			RuntimeOrigin::signed(2000),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_remote(
+			RuntimeOrigin::signed(2001),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(RemoteJudgementSources::<Test>::get(1, 0), Some(2001));
+
+		// A local judgement with the same ID replaces the remote one
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(RemoteJudgementSources::<Test>::get(1, 0), None);
+
+		// Dropping a non-sticky remote judgement on identity update drops its source, sticky ones
+		// keep it
+		assert_ok!(Identity::provide_judgement_remote(
+			RuntimeOrigin::signed(2000),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_remote(
+			RuntimeOrigin::signed(2000),
+			2,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		set_display_identity(1);
+		assert_eq!(RemoteJudgementSources::<Test>::get(1, 1), None);
+		assert_eq!(RemoteJudgementSources::<Test>::get(1, 2), Some(2000));
+
+		// Clearing the identity removes all sources
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(RemoteJudgementSources::<Test>::iter_prefix(1).count(), 0);
+	});
+}
+
+#[test]
+fn lookup_helpers_work() {
+	new_test_ext().execute_with(|| {
+		assert!(!Identity::is_verified(&1));
+		assert_eq!(Identity::display_of(&1), None);
+
+		set_display_identity(1);
+		assert!(!Identity::is_verified(&1));
+		assert_eq!(Identity::display_of(&1).unwrap().to_vec(), b"display".to_vec());
+
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert!(Identity::is_verified(&1));
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert!(!Identity::is_verified(&1));
+		assert_eq!(Identity::display_of(&1), None);
+	});
+}
+
+#[test]
+fn identity_proof_is_verified_against_state_root() {
+	let mut ext = new_test_ext();
+	let registration = ext.execute_with(|| {
+		set_display_identity(1);
+		Identity::identity_of(&1).unwrap()
+	});
+	ext.commit_all().unwrap();
+	let root = *ext.backend.root();
+
+	let keys = [Identity::identity_storage_key(1), Identity::identity_storage_key(2)];
+	let proof = sp_state_machine::prove_read(ext.as_backend(), &keys).unwrap();
+
+	// The proof shows both the identity of 1 and the absence of one for 2
+	assert_eq!(Identity::verify_identity_proof(&root, proof.clone(), &1), Ok(Some(registration)));
+	assert_eq!(Identity::verify_identity_proof(&root, proof.clone(), &2), Ok(None));
+
+	// Proofs don't hold against another state root, and an empty proof proves nothing
+	let other_root = sp_core::H256::repeat_byte(1);
+	assert_eq!(
+		Identity::verify_identity_proof(&other_root, proof, &1),
+		Err(IdentityProofError::InvalidProof)
+	);
+	assert_eq!(
+		Identity::verify_identity_proof(&root, StorageProof::empty(), &1),
+		Err(IdentityProofError::InvalidProof)
+	);
+}
+
+/// State root of a foreign chain on which `who` registered a verified display name with
+/// `new_test_ext`, or cleared it again if `cleared`, and a read proof of the identities of `keys`.
+fn foreign_identity_proof(who: u64, cleared: bool, keys: &[u64]) -> (sp_core::H256, StorageProof) {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		set_display_identity(who);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			who,
+			Judgement::KnownGood,
+			None
+		));
+		if cleared {
+			assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(who)));
+		}
+	});
+	ext.commit_all().unwrap();
+	let keys: Vec<_> = keys.iter().map(|who| Identity::identity_storage_key(*who)).collect();
+	(*ext.backend.root(), sp_state_machine::prove_read(ext.as_backend(), &keys).unwrap())
+}
+
+#[test]
+fn mirror_remote_identity_works() {
+	let (root, proof) = foreign_identity_proof(1, false, &[1, 2]);
+	let (cleared_root, cleared_proof) = foreign_identity_proof(1, true, &[1]);
+
+	new_test_ext().execute_with(|| {
+		// Nothing to mirror from until a state root is trusted
+		assert_noop!(
+			Identity::mirror_remote_identity(RuntimeOrigin::signed(3), 1, proof.clone()),
+			Error::<Test>::NoForeignStateRoot
+		);
+		TrustedStateRoot::set(Some(root));
+
+		assert_ok!(Identity::mirror_remote_identity(RuntimeOrigin::signed(3), 1, proof.clone()));
+		System::assert_last_event(
+			Event::RemoteIdentityMirrored { who: 1, state_root: root }.into(),
+		);
+		let identity = RemoteIdentities::<Test>::get(1).unwrap();
+		assert_eq!(identity.info.display.to_vec(), b"display".to_vec());
+		assert_eq!(identity.status, VerificationStatus::Verified);
+		assert_eq!(identity.state_root, root);
+		// The mirror is read-only: it holds no deposit and isn't a local identity
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert!(Identity::identity_of(&1).is_none());
+
+		// The proof also shows 2 has no identity, but there is no mirror to remove
+		assert_noop!(
+			Identity::mirror_remote_identity(RuntimeOrigin::signed(3), 2, proof.clone()),
+			Error::<Test>::NoRemoteIdentity
+		);
+		// A proof against another state root doesn't hold
+		assert_noop!(
+			Identity::mirror_remote_identity(RuntimeOrigin::signed(3), 1, cleared_proof.clone()),
+			Error::<Test>::InvalidProof
+		);
+		let padded = StorageProof::new(proof.clone().into_iter_nodes().chain([vec![
+			0;
+			MaxRemoteProofSize::get()
+				as usize
+		]]));
+		assert_noop!(
+			Identity::mirror_remote_identity(RuntimeOrigin::signed(3), 1, padded),
+			Error::<Test>::ProofTooLarge
+		);
+
+		// Once the identity is cleared on the foreign chain, the mirror goes too
+		TrustedStateRoot::set(Some(cleared_root));
+		assert_ok!(Identity::mirror_remote_identity(RuntimeOrigin::signed(3), 1, cleared_proof));
+		System::assert_last_event(Event::RemoteIdentityRemoved { who: 1 }.into());
+		assert!(!RemoteIdentities::<Test>::contains_key(1));
+	});
+}
+
+/// Output of `MockRandomness` drawing the registrar at `index` in storage order.
+fn random_seed(index: u8) -> sp_core::H256 {
+	let mut seed = [0u8; 32];
+	seed[0] = index;
+	seed.into()
+}
+
+#[test]
+fn request_audit_assigns_a_random_registrar() {
+	new_test_ext().execute_with(|| {
+		for (judgement_id, account) in [(0, 3), (1, 4), (2, 5)] {
+			assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), judgement_id, account, 10));
+		}
+		let registrars: Vec<u32> = Registrars::<Test>::iter_keys().collect();
+		set_display_identity(1);
+		set_display_identity(2);
+
+		RandomSeed::set(random_seed(1));
+		assert_ok!(Identity::request_audit(RuntimeOrigin::root(), 1));
+		System::assert_last_event(
+			Event::AuditRequested { target: 1, judgement_id: registrars[1] }.into(),
+		);
+		assert_eq!(PendingAudits::<Test>::get(1), Some(registrars[1]));
+
+		// Drawn again with other randomness, wrapping around the registrars
+		RandomSeed::set(random_seed(5));
+		assert_ok!(Identity::request_audit(RuntimeOrigin::root(), 2));
+		assert_eq!(PendingAudits::<Test>::get(2), Some(registrars[2]));
+
+		// Judgements by other registrars leave the audit pending
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			registrars[0],
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(PendingAudits::<Test>::get(1), Some(registrars[1]));
+
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			registrars[1],
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		System::assert_has_event(
+			Event::AuditCompleted { target: 1, judgement_id: registrars[1] }.into(),
+		);
+		assert_eq!(PendingAudits::<Test>::get(1), None);
+
+		// Clearing the identity drops its audit
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		assert_eq!(PendingAudits::<Test>::get(2), None);
+	});
+}
+
+#[test]
+fn request_audit_fails_without_identity_or_registrars() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		assert_noop!(
+			Identity::request_audit(RuntimeOrigin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::request_audit(RuntimeOrigin::root(), 1),
+			Error::<Test>::NoRegistrars
+		);
+
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 10));
+		assert_noop!(
+			Identity::request_audit(RuntimeOrigin::root(), 2),
+			Error::<Test>::InvalidTarget
+		);
+		assert_ok!(Identity::request_audit(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Identity::request_audit(RuntimeOrigin::root(), 1),
+			Error::<Test>::AuditPending
+		);
+	});
+}
+
+fn preferred(registrars: &[u32]) -> BoundedVec<u32, MaxPreferredRegistrars> {
+	registrars.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn set_preferred_registrars_lists_registrars() {
+	new_test_ext().execute_with(|| {
+		for (judgement_id, account) in [(0, 3), (1, 4), (2, 5)] {
+			assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), judgement_id, account, 10));
+		}
+		assert_noop!(
+			Identity::set_preferred_registrars(RuntimeOrigin::signed(1), preferred(&[0])),
+			Error::<Test>::NoIdentity
+		);
+		set_display_identity(1);
+
+		assert_ok!(Identity::set_preferred_registrars(
+			RuntimeOrigin::signed(1),
+			preferred(&[2, 0])
+		));
+		System::assert_last_event(Event::PreferredRegistrarsSet { who: 1 }.into());
+		assert_eq!(Identity::preferred_registrars(1), vec![2, 0]);
+
+		assert_noop!(
+			Identity::set_preferred_registrars(RuntimeOrigin::signed(1), preferred(&[1, 1])),
+			Error::<Test>::DuplicatePreferredRegistrar
+		);
+		assert_noop!(
+			Identity::set_preferred_registrars(RuntimeOrigin::signed(1), preferred(&[1, 7])),
+			Error::<Test>::UnknownRegistrar
+		);
+
+		// An empty list goes back to drawing from every registrar
+		assert_ok!(Identity::set_preferred_registrars(RuntimeOrigin::signed(1), preferred(&[])));
+		assert!(Identity::preferred_registrars(1).is_empty());
+		assert!(Identity::preferred_registrars(2).is_empty());
+	});
+}
+
+#[test]
+fn request_audit_draws_from_the_preferred_registrars() {
+	new_test_ext().execute_with(|| {
+		for (judgement_id, account) in [(0, 3), (1, 4), (2, 5)] {
+			assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), judgement_id, account, 10));
+		}
+		set_display_identity(1);
+		assert_ok!(Identity::set_preferred_registrars(
+			RuntimeOrigin::signed(1),
+			preferred(&[2, 0])
+		));
+
+		// Drawn from the preferred registrars in the order listed, not from all of them
+		RandomSeed::set(random_seed(0));
+		assert_ok!(Identity::request_audit(RuntimeOrigin::root(), 1));
+		assert_eq!(PendingAudits::<Test>::get(1), Some(2));
+		PendingAudits::<Test>::remove(1);
+		RandomSeed::set(random_seed(3));
+		assert_ok!(Identity::request_audit(RuntimeOrigin::root(), 1));
+		assert_eq!(PendingAudits::<Test>::get(1), Some(0));
+	});
+}
+
+#[test]
+fn reconcile_deposits_reprices_in_pages() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=3 {
+			set_display_identity(who);
+		}
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			note(b"ok")
+		));
+		let deposit_of = |who| Identity::identity_of(&who).unwrap().total_deposit();
+		let old_deposits: Vec<u128> = (1..=3).map(deposit_of).collect();
+
+		// Every byte now costs twice as much
+		ByteDeposit::set(2);
+
+		// The first page stops part way and remembers where
+		assert_ok!(Identity::reconcile_deposits(RuntimeOrigin::root(), 2));
+		assert!(ReconcileCursor::<Test>::get().is_some());
+		let reconciled = (1..=3).filter(|&who| deposit_of(who) != old_deposits[who as usize - 1]);
+		assert_eq!(reconciled.count(), 2);
+
+		// The second page finishes the sweep
+		assert_ok!(Identity::reconcile_deposits(RuntimeOrigin::root(), 2));
+		assert_eq!(ReconcileCursor::<Test>::get(), None);
+		System::assert_last_event(Event::DepositReconciliationCompleted.into());
+
+		for who in 1..=3 {
+			let registration = Identity::identity_of(&who).unwrap();
+			let bytes = registration.info.field_sizes().iter().sum::<u32>() as u128;
+			assert_eq!(registration.deposit, BasicDeposit::get() + 2 * bytes);
+			assert_eq!(Balances::reserved_balance(who), registration.total_deposit());
+		}
+		assert_eq!(Identity::identity_of(&1).unwrap().notes_deposit, 4);
+	});
+}
+
+#[test]
+fn reconcile_deposits_refunds_and_skips_the_unfunded() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		set_display_identity(2);
+		let deposit = Identity::identity_of(&1).unwrap().deposit;
+
+		// Account 2 can't afford a much higher deposit
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), 3, 400));
+		BasicDeposit::set(200);
+		assert_ok!(Identity::reconcile_deposits(RuntimeOrigin::root(), 10));
+		System::assert_has_event(Event::DepositReconciliationFailed { who: 2 }.into());
+		System::assert_has_event(
+			Event::DepositReconciled { who: 1, old_deposit: deposit, new_deposit: deposit + 190 }
+				.into(),
+		);
+		assert_eq!(Identity::identity_of(&2).unwrap().deposit, deposit);
+		assert_eq!(Balances::reserved_balance(2), deposit);
+
+		// Lowering the price returns the difference
+		BasicDeposit::set(5);
+		assert_ok!(Identity::reconcile_deposits(RuntimeOrigin::root(), 10));
+		assert_eq!(Balances::reserved_balance(1), deposit - 5);
+		assert_eq!(Balances::reserved_balance(2), deposit - 5);
+	});
+}
+
+#[test]
+fn reconcile_deposits_requires_force_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::reconcile_deposits(RuntimeOrigin::signed(1), 10),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		// Zero limit keeps a sweep in progress where it was
+		set_display_identity(1);
+		set_display_identity(2);
+		assert_ok!(Identity::reconcile_deposits(RuntimeOrigin::root(), 1));
+		let cursor = ReconcileCursor::<Test>::get();
+		assert!(cursor.is_some());
+		assert_ok!(Identity::reconcile_deposits(RuntimeOrigin::root(), 0));
+		assert_eq!(ReconcileCursor::<Test>::get(), cursor);
+	});
+}
+
+#[test]
+fn exempt_accounts_set_identities_without_a_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::set_deposit_exemption(RuntimeOrigin::signed(1), 1, true),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Identity::set_deposit_exemption(RuntimeOrigin::root(), 1, true));
+		System::assert_last_event(Event::DepositExemptionSet { who: 1, exempt: true }.into());
+		assert!(ExemptAccounts::<Test>::contains_key(1));
+
+		set_display_identity(1);
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// Clearing a field or re-pricing leaves it at zero
+		assert_ok!(Identity::clear_field(RuntimeOrigin::signed(1), IdentityField::Display));
+		ByteDeposit::set(2);
+		assert_ok!(Identity::reconcile_deposits(RuntimeOrigin::root(), 10));
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 0);
+
+		// Notes are still paid for
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			note(b"ok")
+		));
+		assert_eq!(Balances::reserved_balance(1), 4);
+
+		// Clearing returns only the notes deposit
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::IdentityCleared { who: 1, deposit: 4 }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn registration_fee_is_charged_on_every_set_and_never_refunded() {
+	new_test_ext().execute_with(|| {
+		RegistrationFee::set(3);
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: BoundedVec::default(),
+			web: BoundedVec::default(),
+			email: BoundedVec::default(),
+			avatar: None,
+		};
+		let (deposit, fee) = Identity::registration_cost(1, info.clone());
+		assert_eq!((deposit, fee), (10 + 11, 3));
+		assert_eq!(Identity::compute_deposit(7, 0, 0, 0, None), deposit);
+		// An avatar costs its bytes, their length prefix and the byte telling that it's set
+		assert_eq!(Identity::compute_deposit(7, 0, 0, 0, Some(4)), deposit + 6);
+
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		System::assert_has_event(Event::RegistrationFeePaid { who: 1, fee: 3 }.into());
+		assert_eq!(Balances::reserved_balance(1), deposit);
+		assert_eq!(Balances::free_balance(1), 1000 - deposit - fee);
+		assert_eq!(CollectedFees::get(), 3);
+
+		// An update pays again, and clearing refunds only the deposit
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		assert_eq!(CollectedFees::get(), 6);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 1000 - 2 * fee);
+
+		// Exempt accounts are spared the deposit, not the fee
+		assert_ok!(Identity::set_deposit_exemption(RuntimeOrigin::root(), 2, true));
+		assert_eq!(Identity::registration_cost(2, info.clone()), (0, 3));
+		assert_eq!(Identity::compute_deposit(7, 0, 0, 0, None), deposit);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(2), info.clone()));
+		assert_eq!(Balances::free_balance(2), 1000 - fee);
+
+		// An account that can't pay the fee can't set an identity
+		RegistrationFee::set(2000);
+		assert_noop!(
+			Identity::set_identity(RuntimeOrigin::signed(3), info),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn switching_exemption_reprices_a_held_identity() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		let deposit = Identity::identity_of(&1).unwrap().deposit;
+		assert_eq!(Balances::reserved_balance(1), deposit);
+
+		// An exemption releases the deposit straight away
+		assert_ok!(Identity::set_deposit_exemption(RuntimeOrigin::root(), 1, true));
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// Revoking it reserves the deposit again, at the current price
+		BasicDeposit::set(20);
+		assert_ok!(Identity::set_deposit_exemption(RuntimeOrigin::root(), 1, false));
+		System::assert_last_event(Event::DepositExemptionSet { who: 1, exempt: false }.into());
+		assert!(!ExemptAccounts::<Test>::contains_key(1));
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, deposit + 10);
+		assert_eq!(Balances::reserved_balance(1), deposit + 10);
+
+		// A revocation the account can't afford fails, leaving the exemption in place
+		assert_ok!(Identity::set_deposit_exemption(RuntimeOrigin::root(), 1, true));
+		BasicDeposit::set(2000);
+		assert_noop!(
+			Identity::set_deposit_exemption(RuntimeOrigin::root(), 1, false),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert!(ExemptAccounts::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn judgement_u8_conversion_round_trips() {
+	let judgements = [
+		Judgement::Unknown,
+		Judgement::Reasonable,
+		Judgement::KnownGood,
+		Judgement::Erroneous,
+		Judgement::LowQuality,
+		Judgement::Requested,
+	];
+	for (value, judgement) in judgements.into_iter().enumerate() {
+		assert_eq!(u8::from(judgement), value as u8);
+		assert_eq!(Judgement::try_from(value as u8), Ok(judgement));
+	}
+
+	// Every other value is rejected, reporting the value
+	for value in judgements.len() as u8..=u8::MAX {
+		assert_eq!(Judgement::try_from(value), Err(InvalidJudgement(value)));
+	}
+}
+
+#[test]
+fn errors_keep_their_indices() {
+	// Module errors are decoded by index, so errors are only ever appended
+	let index = |error: Error<Test>| match DispatchError::from(error) {
+		DispatchError::Module(sp_runtime::ModuleError { error, .. }) => error[0],
+		_ => unreachable!(),
+	};
+	assert_eq!(index(Error::<Test>::JudgementGiven), 3);
+	assert_eq!(index(Error::<Test>::InvalidTarget), 5);
+	assert_eq!(index(Error::<Test>::TooManyJudgements), 6);
+}
+
+#[test]
+fn judgement_names_round_trip() {
+	let names = ["Unknown", "Reasonable", "KnownGood", "Erroneous", "LowQuality", "Requested"];
+	for (value, name) in names.into_iter().enumerate() {
+		let judgement: Judgement = name.parse().unwrap();
+		assert_eq!(u8::from(judgement), value as u8);
+		assert_eq!(judgement.to_string(), name);
+		assert_eq!(serde_json::to_string(&judgement).unwrap(), format!("\"{name}\""));
+	}
+
+	// Names are case-sensitive, and numbers aren't names
+	assert_eq!("knowngood".parse::<Judgement>(), Err(InvalidJudgementName));
+	assert_eq!("2".parse::<Judgement>(), Err(InvalidJudgementName));
+	assert!(serde_json::from_str::<Judgement>("\"knowngood\"").is_err());
+}
+
+#[cfg(feature = "interop")]
+#[test]
+fn identities_and_judgements_convert_to_and_from_upstream() {
+	use crate::interop::InteropError;
+	use frame_support::traits::ConstU32;
+	use upstream_identity::{legacy, Data};
+
+	type Upstream = legacy::IdentityInfo<ConstU32<100>>;
+	let raw = |bytes: &[u8]| Data::Raw(bytes.to_vec().try_into().unwrap());
+
+	let info = IdentityInfoOf::<Test> {
+		display: b"display".to_vec().try_into().unwrap(),
+		legal: Default::default(),
+		web: b"web".to_vec().try_into().unwrap(),
+		email: Default::default(),
+		avatar: Some(b"bafy".to_vec().try_into().unwrap()),
+	};
+	let upstream = Upstream::try_from(info.clone()).unwrap();
+	assert_eq!(upstream.display, raw(b"display"));
+	assert_eq!(upstream.legal, Data::None);
+	assert_eq!(upstream.web, raw(b"web"));
+	assert_eq!(upstream.image, raw(b"bafy"));
+	assert_eq!(IdentityInfoOf::<Test>::try_from(upstream.clone()), Ok(info));
+
+	// Upstream holds at most 32 bytes a field, and nothing converts back that this pallet lacks
+	let long = IdentityInfoOf::<Test> {
+		display: vec![b'X'; 33].try_into().unwrap(),
+		..Default::default()
+	};
+	assert_eq!(Upstream::try_from(long), Err(InteropError::FieldTooLong));
+	let twitter = Upstream { twitter: raw(b"@who"), ..upstream.clone() };
+	assert_eq!(IdentityInfoOf::<Test>::try_from(twitter), Err(InteropError::ExtraField));
+	let hashed = Upstream { email: Data::BlakeTwo256([0; 32]), ..upstream };
+	assert_eq!(IdentityInfoOf::<Test>::try_from(hashed), Err(InteropError::HashedField));
+
+	// A paid request is `Requested`, which doesn't convert back without its fee
+	type UpstreamJudgement = upstream_identity::Judgement<u64>;
+	for judgement in [
+		Judgement::Unknown,
+		Judgement::Reasonable,
+		Judgement::KnownGood,
+		Judgement::Erroneous,
+		Judgement::LowQuality,
+	] {
+		let upstream = UpstreamJudgement::try_from(judgement).unwrap();
+		assert_eq!(Judgement::try_from(upstream), Ok(judgement));
+	}
+	assert_eq!(Judgement::try_from(UpstreamJudgement::FeePaid(50)), Ok(Judgement::Requested));
+	assert_eq!(
+		UpstreamJudgement::try_from(Judgement::Requested),
+		Err(InteropError::UnmatchedJudgement)
+	);
+	assert_eq!(
+		Judgement::try_from(UpstreamJudgement::OutOfDate),
+		Err(InteropError::UnmatchedJudgement)
+	);
+}
+
+#[test]
+fn consolidate_judgements_moves_double_map_judgements_inline() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_remote(
+			RuntimeOrigin::signed(2000),
+			3,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		for (id, judgement) in [
+			(0, Judgement::KnownGood),
+			(1, Judgement::LowQuality),
+			(2, Judgement::KnownGood),
+			(3, Judgement::KnownGood),
+		] {
+			assert_ok!(Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				id,
+				1,
+				judgement,
+				note(b"kept")
+			));
+		}
+
+		assert_ok!(Identity::consolidate_judgements(RuntimeOrigin::signed(1), 1));
+		System::assert_last_event(Event::JudgementsConsolidated { who: 1, moved: 3 }.into());
+
+		// Double map judgements are added or replace non-sticky ones, sticky inline ones stay
+		// next to the double map ones under their IDs
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(
+			registration.judgements.to_vec(),
+			vec![
+				(0, (Judgement::KnownGood, 1)),
+				(1, (Judgement::LowQuality, 1)),
+				(2, (Judgement::Erroneous, 1)),
+				(3, (Judgement::KnownGood, 1)),
+			]
+		);
+		assert_eq!(CounterForJudgements::<Test>::get(1), 1);
+		assert_eq!(
+			JudgementsDoubleMap::<Test>::iter_prefix(1).collect::<Vec<_>>(),
+			vec![(2, (Judgement::KnownGood, 1))]
+		);
+		assert_eq!((registration.known_good, registration.erroneous), (3, 1));
+		// Notes stay with their judgement IDs, the replaced remote judgement loses its source
+		assert_eq!(JudgementNotes::<Test>::iter_prefix(1).count(), 4);
+		assert_eq!(RemoteJudgementSources::<Test>::get(1, 3), None);
+	});
+}
+
+#[test]
+fn consolidate_judgements_fails_when_they_do_not_fit() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_judgement_limit(RuntimeOrigin::root(), 2));
+		for id in 0..2 {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				id,
+				1,
+				Judgement::Reasonable,
+				None
+			));
+		}
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			5,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+
+		assert_noop!(
+			Identity::consolidate_judgements(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::TooManyJudgements
+		);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 5), Some((Judgement::Reasonable, 1)));
+	});
+}
+
+#[test]
+fn consolidate_judgements_requires_owner_or_force_origin() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		assert_noop!(
+			Identity::consolidate_judgements(RuntimeOrigin::signed(2), 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::consolidate_judgements(RuntimeOrigin::signed(2), 2),
+			Error::<Test>::NoIdentity
+		);
+		assert_ok!(Identity::consolidate_judgements(RuntimeOrigin::root(), 1));
+	});
+}
+
+#[test]
+fn force_recount_judgements_repairs_a_drifted_counter() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		for id in 0..3 {
+			assert_ok!(Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				id,
+				1,
+				Judgement::Reasonable,
+				None
+			));
+		}
+		CounterForJudgements::<Test>::insert(1, 7);
+
+		assert_noop!(
+			Identity::force_recount_judgements(RuntimeOrigin::signed(1), 1),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::force_recount_judgements(RuntimeOrigin::root(), 2),
+			Error::<Test>::NoIdentity
+		);
+
+		assert_ok!(Identity::force_recount_judgements(RuntimeOrigin::root(), 1));
+		System::assert_last_event(
+			Event::CounterRepaired { who: 1, old_count: 7, new_count: 3 }.into(),
+		);
+		assert_eq!(CounterForJudgements::<Test>::get(1), 3);
+
+		// A correct counter is left alone
+		System::reset_events();
+		assert_ok!(Identity::force_recount_judgements(RuntimeOrigin::root(), 1));
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn audit_identity_reports_and_repairs_inconsistencies() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		for (id, judgement) in [(0, Judgement::KnownGood), (1, Judgement::Reasonable)] {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				id,
+				1,
+				judgement,
+				None
+			));
+		}
+		for id in 2..4 {
+			assert_ok!(Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				id,
+				1,
+				Judgement::Reasonable,
+				None
+			));
+		}
+		let consistent = Identity::identity_of(&1).unwrap();
+		let score = Scores::<Test>::get(1);
+
+		// A consistent identity passes, and has nothing to repair
+		assert_ok!(Identity::audit_identity(RuntimeOrigin::root(), 1, true));
+		System::assert_last_event(
+			Event::AuditResult {
+				who: 1,
+				recorded_double_map: 2,
+				actual_double_map: 2,
+				inline_sorted: true,
+				repaired: false,
+			}
+			.into(),
+		);
+
+		CounterForJudgements::<Test>::insert(1, 5);
+		crate::IdentityOf::<Test>::mutate(1, |reg| {
+			let reg = reg.as_mut().unwrap();
+			reg.judgements = vec![
+				(1, (Judgement::Reasonable, 1)),
+				(0, (Judgement::KnownGood, 1)),
+				(0, (Judgement::Reasonable, 1)),
+			]
+			.try_into()
+			.unwrap();
+		});
+		let corrupted = Identity::identity_of(&1).unwrap();
+
+		assert_noop!(
+			Identity::audit_identity(RuntimeOrigin::signed(2), 1, true),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::audit_identity(RuntimeOrigin::signed(2), 2, false),
+			Error::<Test>::NoIdentity
+		);
+		// Anyone can audit, leaving the identity as it is
+		assert_ok!(Identity::audit_identity(RuntimeOrigin::signed(2), 1, false));
+		System::assert_last_event(
+			Event::AuditResult {
+				who: 1,
+				recorded_double_map: 5,
+				actual_double_map: 2,
+				inline_sorted: false,
+				repaired: false,
+			}
+			.into(),
+		);
+		assert_eq!(Identity::identity_of(&1), Some(corrupted));
+		assert_eq!(CounterForJudgements::<Test>::get(1), 5);
+
+		assert_ok!(Identity::audit_identity(RuntimeOrigin::root(), 1, true));
+		System::assert_last_event(
+			Event::AuditResult {
+				who: 1,
+				recorded_double_map: 5,
+				actual_double_map: 2,
+				inline_sorted: false,
+				repaired: true,
+			}
+			.into(),
+		);
+		assert_eq!(Identity::identity_of(&1), Some(consistent));
+		assert_eq!(CounterForJudgements::<Test>::get(1), 2);
+		assert_eq!(Scores::<Test>::get(1), score);
+	});
+}
+
+#[test]
+fn shadow_writes_copy_judgements_into_the_other_pattern() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(ShadowJudgementsDoubleMap::<Test>::iter().count(), 0);
+
+		ShadowWrites::set(true);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		for (id, judgement) in [(2, Judgement::Reasonable), (1, Judgement::KnownGood)] {
+			assert_ok!(Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				id,
+				1,
+				judgement,
+				None
+			));
+		}
+		assert_eq!(ShadowJudgementsDoubleMap::<Test>::get(1, 0), Some((Judgement::Reasonable, 1)));
+		assert_eq!(
+			ShadowJudgementsInline::<Test>::get(1).to_vec(),
+			vec![(1, (Judgement::KnownGood, 1)), (2, (Judgement::Reasonable, 1))]
+		);
+		assert_ok!(Identity::do_try_state());
+
+		// The copies follow the judgements they copy
+		assert_ok!(Identity::clear_judgements(RuntimeOrigin::signed(1)));
+		assert_eq!(ShadowJudgementsDoubleMap::<Test>::get(1, 0), None);
+		assert_eq!(
+			ShadowJudgementsInline::<Test>::get(1).to_vec(),
+			vec![(1, (Judgement::KnownGood, 1))]
+		);
+		assert_ok!(Identity::do_try_state());
+
+		assert_ok!(Identity::consolidate_judgements(RuntimeOrigin::signed(1), 1));
+		assert!(!ShadowJudgementsInline::<Test>::contains_key(1));
+		assert_eq!(ShadowJudgementsDoubleMap::<Test>::get(1, 1), Some((Judgement::KnownGood, 1)));
+		assert_ok!(Identity::do_try_state());
+
+		ShadowJudgementsDoubleMap::<Test>::insert(1, 1, (Judgement::Reasonable, 1));
+		assert!(Identity::do_try_state().is_err());
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(ShadowJudgementsDoubleMap::<Test>::iter().count(), 0);
+		assert_ok!(Identity::do_try_state());
+	});
+}
+
+#[test]
+fn identity_holds_a_consumer_reference() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(System::consumers(&1), 0);
+
+		// One reference from the pallet, one from the reserved deposit
+		set_display_identity(1);
+		assert_eq!(System::consumers(&1), 2);
+		// Updating the identity doesn't take another one
+		set_display_identity(1);
+		assert_eq!(System::consumers(&1), 2);
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(System::consumers(&1), 0);
+	});
+}
+
+#[test]
+fn account_with_identity_cannot_be_reaped() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		let free = Balances::free_balance(1);
+
+		assert_noop!(
+			Balances::transfer_allow_death(RuntimeOrigin::signed(1), 2, free),
+			sp_runtime::TokenError::Frozen
+		);
+		assert!(System::account_exists(&1));
+
+		// Once the identity is cleared the account can go
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), 2, 1000));
+		assert!(!System::account_exists(&1));
+	});
+}
+
+#[test]
+fn registration_requires_a_provider() {
+	new_test_ext().execute_with(|| {
+		// Account 6 was never endowed, so has no provider to keep it alive
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: BoundedVec::default(),
+			web: BoundedVec::default(),
+			email: BoundedVec::default(),
+			avatar: None,
+		};
+		assert_noop!(
+			Identity::set_identity(RuntimeOrigin::signed(6), info),
+			sp_runtime::DispatchError::NoProviders
+		);
+	});
+}
+
+#[test]
+fn judgement_id_type_is_configurable() {
+	new_test_ext().execute_with(|| {
+		// The second registry identifies judgement providers by `u64`
+		let judgement_id = u64::from(u32::MAX) + 1;
+		assert_ok!(OrgIdentity::set_identity(RuntimeOrigin::signed(1), empty_info()));
+		assert_ok!(OrgIdentity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			judgement_id,
+			1,
+			Judgement::Reasonable,
+			None,
+		));
+		assert_eq!(
+			OrgIdentity::identity_of(&1).unwrap().judgements.into_inner(),
+			vec![(judgement_id, (Judgement::Reasonable, 0))]
+		);
+		assert_noop!(
+			OrgIdentity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				u64::MAX,
+				1,
+				Judgement::Reasonable,
+				None,
+			),
+			Error::<Test, Instance2>::ReservedJudgementId
+		);
+	});
+}
+
+#[test]
+fn instances_are_independent() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		let info = Identity::identity_of(&1).unwrap().info;
+		assert!(OrgIdentity::identity_of(&1).is_none());
+
+		// The second registry keeps its own records and charges its own deposit
+		assert_ok!(OrgIdentity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		System::assert_last_event(
+			crate::Event::<Test, Instance2>::IdentitySet {
+				who: 1,
+				display: info.display,
+				bytes: 7,
+			}
+			.into(),
+		);
+		let deposit = Identity::identity_of(&1).unwrap().deposit;
+		let org_deposit = OrgIdentity::identity_of(&1).unwrap().deposit;
+		assert_eq!(org_deposit, deposit + 90);
+		assert_eq!(Balances::reserved_balance(1), deposit + org_deposit);
+
+		// Judgements given in one registry do not show up in the other
+		assert_ok!(OrgIdentity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None,
+		));
+		assert_eq!(OrgIdentity::identity_of(&1).unwrap().judgements.len(), 1);
+		assert!(Identity::identity_of(&1).unwrap().judgements.is_empty());
+
+		// Clearing one leaves the other in place
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert!(Identity::identity_of(&1).is_none());
+		assert!(OrgIdentity::identity_of(&1).is_some());
+		assert_eq!(Balances::reserved_balance(1), org_deposit);
+	});
+}
+
+#[test]
+fn requested_judgement_pays_the_registrar() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		System::assert_last_event(
+			Event::RegistrarSet { judgement_id: 0, account: 5, fee: 50 }.into(),
+		);
+		// The fee, and the deposit of a note of `MaxNoteLength` bytes
+		assert_eq!(Identity::estimate_judgement_cost(0), Some((50, 32)));
+		set_display_identity(1);
+		let deposit = Balances::reserved_balance(1);
+
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+		System::assert_last_event(
+			Event::JudgementRequested { who: 1, judgement_id: 0, fee: 50 }.into(),
+		);
+		assert_eq!(JudgementRequests::<Test>::get(1, 0), Some(50));
+		assert_eq!(Balances::reserved_balance(1), deposit + 50);
+
+		// Judgements under other IDs leave the request pending
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(JudgementRequests::<Test>::get(1, 0), Some(50));
+
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(JudgementRequests::<Test>::get(1, 0), None);
+		assert_eq!(Balances::reserved_balance(1), deposit);
+		// The fee stays reserved on the registrar, next to its bond, until claimed
+		assert_eq!(AccruedFees::<Test>::get(5), 50);
+		assert_eq!(Balances::reserved_balance(5), 150);
+		assert_ok!(Identity::claim_fees(RuntimeOrigin::signed(5)));
+		System::assert_last_event(Event::FeesClaimed { who: 5, amount: 50 }.into());
+		assert_eq!(AccruedFees::<Test>::get(5), 0);
+		assert_eq!(Balances::reserved_balance(5), 100);
+		assert_eq!(Balances::free_balance(5), 950);
+		assert_noop!(Identity::claim_fees(RuntimeOrigin::signed(5)), Error::<Test>::NoFees);
+	});
+}
+
+#[test]
+fn slashed_requester_pays_only_what_is_left_of_the_fee() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		set_display_identity(1);
+		let deposit = Balances::reserved_balance(1);
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+
+		// Slashed elsewhere, 1 holds only 20 of the fee
+		let _ = <Balances as ReservableCurrency<_>>::slash_reserved(&1, deposit + 30);
+		assert_eq!(Balances::reserved_balance(1), 20);
+
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(JudgementRequests::<Test>::get(1, 0), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(AccruedFees::<Test>::get(5), 20);
+		assert_eq!(Balances::reserved_balance(5), 120);
+		assert_eq!(TotalDeposits::<Test>::get(), Identity::recompute_total_deposits());
+	});
+}
+
+#[test]
+fn request_judgement_fails_for_invalid_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50),
+			Error::<Test>::NoIdentity
+		);
+
+		set_display_identity(1);
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(1), 1, 50),
+			Error::<Test>::UnknownRegistrar
+		);
+		assert_eq!(Identity::estimate_judgement_cost(1), None);
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(1), 0, 49),
+			Error::<Test>::FeeChanged
+		);
+
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50),
+			Error::<Test>::AlreadyRequested
+		);
+
+		// A sticky verdict can't be asked to be revised
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 1, 5, 50));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(1), 1, 50),
+			Error::<Test>::StickyJudgement
+		);
+
+		// Pending requests count against the judgement limit
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 2, 5, 50));
+		assert_ok!(Identity::set_judgement_limit(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(1), 2, 50),
+			Error::<Test>::TooManyRequests
+		);
+	});
+}
+
+#[test]
+fn judgement_ids_only_become_requested_without_a_verdict() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		set_display_identity(1);
+		assert_eq!(Identity::judgement_of(1, 0), None);
+
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+		assert_eq!(Identity::judgement_of(1, 0), Some(Judgement::Requested));
+
+		// `Requested` isn't a verdict a provider can give
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::Requested,
+				None
+			),
+			Error::<Test>::InvalidJudgementTransition
+		);
+		assert_noop!(
+			Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::Requested,
+				None
+			),
+			Error::<Test>::InvalidJudgementTransition
+		);
+		assert_noop!(
+			Identity::provide_judgements_inline(
+				RuntimeOrigin::root(),
+				1,
+				vec![(1, Judgement::Reasonable), (0, Judgement::Requested)].try_into().unwrap()
+			),
+			Error::<Test>::InvalidJudgementTransition
+		);
+
+		// A verdict settles the request
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(Identity::judgement_of(1, 0), Some(Judgement::Reasonable));
+		assert!(!JudgementRequests::<Test>::contains_key(1, 0));
+
+		// and can't be requested again, only revised by its provider
+		assert_noop!(
+			Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50),
+			Error::<Test>::InvalidJudgementTransition
+		);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		assert_eq!(Identity::judgement_of(1, 0), Some(Judgement::Erroneous));
+	});
+}
+
+#[test]
+fn set_identity_with_judgement_request_is_atomic() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: BoundedVec::default(),
+			web: BoundedVec::default(),
+			email: BoundedVec::default(),
+			avatar: None,
+		};
+
+		// A failed request leaves no identity behind
+		assert_noop!(
+			Identity::set_identity_with_judgement_request(
+				RuntimeOrigin::signed(1),
+				info.clone(),
+				0,
+				49
+			),
+			Error::<Test>::FeeChanged
+		);
+		assert!(Identity::identity_of(&1).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		assert_ok!(Identity::set_identity_with_judgement_request(
+			RuntimeOrigin::signed(1),
+			info,
+			0,
+			50
+		));
+		let deposit = Identity::identity_of(&1).unwrap().deposit;
+		assert_eq!(JudgementRequests::<Test>::get(1, 0), Some(50));
+		assert_eq!(Balances::reserved_balance(1), deposit + 50);
+	});
+}
+
+#[test]
+fn clear_identity_refunds_pending_fees() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		set_display_identity(1);
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+		let reserved = Balances::reserved_balance(1);
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::IdentityCleared { who: 1, deposit: reserved }.into());
+		assert_eq!(JudgementRequests::<Test>::get(1, 0), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(5), 900);
+	});
+}
+
+#[test]
+fn provide_judgements_inline_matches_individual_calls() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		set_display_identity(2);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 3, 5, 50));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 3, 50));
+
+		let items =
+			vec![(3, Judgement::Reasonable), (1, Judgement::KnownGood), (3, Judgement::LowQuality)];
+		assert_ok!(Identity::provide_judgements_inline(
+			RuntimeOrigin::root(),
+			1,
+			items.clone().try_into().unwrap()
+		));
+		for (judgement_id, judgement) in items {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				judgement_id,
+				2,
+				judgement,
+				None
+			));
+		}
+
+		let batched = Identity::identity_of(&1).unwrap();
+		assert_eq!(batched.judgements, Identity::identity_of(&2).unwrap().judgements);
+		assert_eq!(
+			batched.judgements.to_vec(),
+			vec![(1, (Judgement::KnownGood, 1)), (3, (Judgement::LowQuality, 1))]
+		);
+		assert_eq!(batched.status, VerificationStatus::Verified);
+		assert_eq!(Badges::get(), vec![1, 2]);
+		// The requested judgement was paid for
+		assert_eq!(JudgementRequests::<Test>::get(1, 3), None);
+		assert_eq!(AccruedFees::<Test>::get(5), 50);
+	});
+}
+
+#[test]
+fn provide_judgements_inline_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+
+		assert_noop!(
+			Identity::provide_judgements_inline(
+				RuntimeOrigin::root(),
+				1,
+				vec![(1, Judgement::Reasonable), (2, Judgement::Reasonable)].try_into().unwrap()
+			),
+			Error::<Test>::StickyJudgement
+		);
+
+		assert_ok!(Identity::set_judgement_limit(RuntimeOrigin::root(), 2));
+		assert_noop!(
+			Identity::provide_judgements_inline(
+				RuntimeOrigin::root(),
+				1,
+				vec![(1, Judgement::Reasonable), (3, Judgement::Reasonable)].try_into().unwrap()
+			),
+			Error::<Test>::TooManyJudgements
+		);
+		assert_noop!(
+			Identity::provide_judgements_inline(
+				RuntimeOrigin::root(),
+				2,
+				vec![(1, Judgement::Reasonable)].try_into().unwrap()
+			),
+			Error::<Test>::InvalidTarget
+		);
+	});
+}
+
+#[test]
+fn clear_judgements_keeps_identity_and_sticky_judgements() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		let deposit = Balances::reserved_balance(1);
+
+		// Inline: a sticky judgement with a note and a non-sticky remote one with a note
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			note(b"checked")
+		));
+		assert_ok!(Identity::provide_judgement_remote(
+			RuntimeOrigin::signed(2000),
+			1,
+			1,
+			Judgement::Reasonable,
+			note(b"fine")
+		));
+		// Double map: a non-sticky judgement under the sticky inline ID, sharing its note, and
+		// one of its own
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			note(b"checked")
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::LowQuality,
+			note(b"blurry")
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			3,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+
+		assert_ok!(Identity::clear_judgements(RuntimeOrigin::signed(1)));
+		System::assert_last_event(
+			Event::JudgementsCleared { who: 1, inline: 1, double_map: 2 }.into(),
+		);
+
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.judgements.to_vec(), vec![(0, (Judgement::KnownGood, 1))]);
+		assert_eq!(registration.status, VerificationStatus::Verified);
+		assert_eq!(CounterForJudgements::<Test>::get(1), 1);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 3), Some((Judgement::Erroneous, 1)));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 0), None);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 2), None);
+		assert_eq!(RemoteJudgementSources::<Test>::get(1, 1), None);
+
+		// Only the note of the sticky judgement is left, and paid for
+		assert_eq!(JudgementNotes::<Test>::iter_prefix(1).count(), 1);
+		assert_eq!(registration.notes_deposit, 7);
+		assert_eq!(Balances::reserved_balance(1), deposit + 7);
+	});
+}
+
+#[test]
+fn clear_judgements_requires_identity() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::clear_judgements(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoIdentity
+		);
+	});
+}
+
+#[test]
+fn verification_requires_a_quorum_of_providers() {
+	new_test_ext().execute_with(|| {
+		VerificationThreshold::set(2);
+		set_display_identity(1);
+		assert_eq!(Identity::verification_status(&1), VerificationStatus::Unverified);
+
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		System::assert_has_event(
+			Event::VerificationStatusChanged {
+				who: 1,
+				status: VerificationStatus::PartiallyVerified,
+			}
+			.into(),
+		);
+		assert!(Badges::get().is_empty());
+
+		// The same provider in the other storage pattern doesn't count twice
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.known_good, 1);
+		assert_eq!(registration.status, VerificationStatus::PartiallyVerified);
+
+		// A second provider reaches the quorum
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		System::assert_has_event(
+			Event::VerificationStatusChanged { who: 1, status: VerificationStatus::Verified }
+				.into(),
+		);
+		assert_eq!(Identity::identity_of(&1).unwrap().known_good, 2);
+		assert!(Identity::is_verified(&1));
+		assert_eq!(Badges::get(), vec![1]);
+	});
+}
+
+#[test]
+fn consolidation_recounts_known_good_providers() {
+	new_test_ext().execute_with(|| {
+		VerificationThreshold::set(2);
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert!(Identity::is_verified(&1));
+
+		// The double map `KnownGood` stays next to the sticky inline verdict under its ID, and
+		// still counts
+		assert_ok!(Identity::consolidate_judgements(RuntimeOrigin::signed(1), 1));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 0), Some((Judgement::KnownGood, 1)));
+		assert_eq!(Identity::identity_of(&1).unwrap().known_good, 2);
+		assert!(Identity::is_verified(&1));
+		assert_eq!(Badges::get(), vec![1]);
+	});
+}
+
+#[test]
+fn inspector_reports_identities() {
+	new_test_ext().execute_with(|| {
+		assert!(!<Identity as InspectIdentity<u64>>::has_identity(&1));
+		assert_eq!(
+			<Identity as InspectIdentity<u64>>::verification_status(&1),
+			VerificationStatus::Unverified
+		);
+
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert!(<Identity as InspectIdentity<u64>>::has_identity(&1));
+		assert_eq!(
+			<Identity as InspectIdentity<u64>>::verification_status(&1),
+			VerificationStatus::Verified
+		);
+	});
+}
+
+#[test]
+fn verification_status_follows_the_threshold() {
+	assert_eq!(VerificationStatus::from_providers(0, 2), VerificationStatus::Unverified);
+	assert_eq!(VerificationStatus::from_providers(1, 2), VerificationStatus::PartiallyVerified);
+	assert_eq!(VerificationStatus::from_providers(2, 2), VerificationStatus::Verified);
+	// A threshold of zero counts as one
+	assert_eq!(VerificationStatus::from_providers(0, 0), VerificationStatus::Unverified);
+	assert_eq!(VerificationStatus::from_providers(1, 0), VerificationStatus::Verified);
+}
+
+#[test]
+fn verification_level_follows_the_sticky_judgements() {
+	assert_eq!(VerificationLevel::from_judgements(0, 0), VerificationLevel::Bronze);
+	assert_eq!(VerificationLevel::from_judgements(1, 0), VerificationLevel::Silver);
+	assert_eq!(VerificationLevel::from_judgements(2, 0), VerificationLevel::Gold);
+	assert_eq!(VerificationLevel::from_judgements(5, 0), VerificationLevel::Gold);
+	// A single `Erroneous` judgement outweighs any number of `KnownGood` ones
+	assert_eq!(VerificationLevel::from_judgements(5, 1), VerificationLevel::None);
+}
+
+#[test]
+fn verification_level_rises_with_known_good_providers() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Identity::verification_level(&1), VerificationLevel::None);
+		set_display_identity(1);
+		assert_eq!(Identity::verification_level(&1), VerificationLevel::Bronze);
+
+		// Non-sticky verdicts leave the level alone
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(Identity::verification_level(&1), VerificationLevel::Bronze);
+
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		System::assert_has_event(
+			Event::VerificationLevelChanged { who: 1, level: VerificationLevel::Silver }.into(),
+		);
+		// The same provider in the other storage pattern counts once
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_eq!(Identity::verification_level(&1), VerificationLevel::Silver);
+
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		System::assert_has_event(
+			Event::VerificationLevelChanged { who: 1, level: VerificationLevel::Gold }.into(),
+		);
+		assert_eq!(Identity::identity_of(&1).unwrap().level, VerificationLevel::Gold);
+		assert_eq!(
+			<Identity as InspectIdentity<u64>>::verification_level(&1),
+			VerificationLevel::Gold
+		);
+		assert_eq!(Identity::level_of(1), VerificationLevel::Gold);
+	});
+}
+
+#[test]
+fn erroneous_judgement_drops_the_verification_level() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		System::assert_has_event(
+			Event::VerificationLevelChanged { who: 1, level: VerificationLevel::None }.into(),
+		);
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!((registration.known_good, registration.erroneous), (2, 1));
+		assert_eq!(registration.level, VerificationLevel::None);
+		// The status only counts `KnownGood` judgements
+		assert_eq!(registration.status, VerificationStatus::Verified);
+
+		// Updating the identity keeps the sticky judgement
+		set_display_identity(1);
+		assert_eq!(Identity::verification_level(&1), VerificationLevel::None);
+
+		// Overturning it restores the level
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Identity::resolve_dispute(RuntimeOrigin::root(), 1, 0, false));
+		System::assert_has_event(
+			Event::VerificationLevelChanged { who: 1, level: VerificationLevel::Gold }.into(),
+		);
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.erroneous, 0);
+		assert_eq!(registration.level, VerificationLevel::Gold);
+	});
+}
+
+#[test]
+fn score_follows_judgement_changes() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_eq!(Identity::score_of(&1), 0);
+
+		// Reasonable +1, KnownGood +3, LowQuality -1, Erroneous -3
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+		assert_eq!(Identity::score_of(&1), 3);
+
+		// Replacing a judgement swaps its contribution
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		assert_eq!(Identity::score_of(&1), -1);
+
+		// Updating the identity drops the non-sticky inline judgement
+		set_display_identity(1);
+		assert_eq!(Identity::score_of(&1), 0);
+
+		assert_ok!(Identity::provide_judgements_inline(
+			RuntimeOrigin::root(),
+			1,
+			vec![(2, Judgement::Reasonable), (3, Judgement::LowQuality)].try_into().unwrap()
+		));
+		assert_eq!(Identity::score_of(&1), 0);
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			4,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::clear_judgements(RuntimeOrigin::signed(1)));
+		assert_eq!(Identity::score_of(&1), 0);
+
+		// Consolidation moves judgements without changing the score: the double map `Erroneous`
+		// under ID 0 moves inline, the one under ID 1 stays next to the sticky inline `KnownGood`
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		assert_eq!(Identity::score_of(&1), -3);
+		assert_ok!(Identity::consolidate_judgements(RuntimeOrigin::signed(1), 1));
+		assert_eq!(Identity::score_of(&1), -3);
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert!(!Scores::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn judgements_given_are_reported() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_remote(
+			RuntimeOrigin::signed(2000),
+			2,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+		assert_ok!(Identity::provide_judgements_inline(
+			RuntimeOrigin::root(),
+			1,
+			vec![(3, Judgement::Unknown), (4, Judgement::Erroneous)].try_into().unwrap()
+		));
+		// Failed judgements are not reported
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				4,
+				1,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::StickyJudgement
+		);
+
+		assert_eq!(
+			JudgementLog::get(),
+			vec![
+				(1, 0, Judgement::Reasonable),
+				(1, 1, Judgement::KnownGood),
+				(1, 2, Judgement::LowQuality),
+				(1, 3, Judgement::Unknown),
+				(1, 4, Judgement::Erroneous),
+			]
+		);
+	});
+}
+
+#[test]
+fn dispute_judgement_reserves_challenge_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::dispute_judgement(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NoIdentity
+		);
+		set_display_identity(1);
+		assert_noop!(
+			Identity::dispute_judgement(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NotFound
+		);
+
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		let reserved = Balances::reserved_balance(1);
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		assert_eq!(Disputes::<Test>::get(1, 0), Some(20));
+		assert_eq!(Balances::reserved_balance(1), reserved + 20);
+		System::assert_last_event(
+			Event::JudgementDisputed { who: 1, judgement_id: 0, deposit: 20 }.into(),
+		);
+
+		assert_noop!(
+			Identity::dispute_judgement(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::AlreadyDisputed
+		);
+	});
+}
+
+#[test]
+fn dispute_judgement_finds_double_map_judgements() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			3,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 3));
+		assert!(Disputes::<Test>::contains_key(1, 3));
+	});
+}
+
+#[test]
+fn disputes_are_limited() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		for judgement_id in 0..2 {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				judgement_id,
+				1,
+				Judgement::Reasonable,
+				None
+			));
+		}
+		assert_ok!(Identity::set_judgement_limit(RuntimeOrigin::root(), 1));
+
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		assert_noop!(
+			Identity::dispute_judgement(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::TooManyDisputes
+		);
+	});
+}
+
+#[test]
+fn disputed_judgements_are_frozen() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			note(b"inline")
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 1));
+
+		// No judgement can be given under a disputed ID, in either storage pattern
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::KnownGood,
+				None
+			),
+			Error::<Test>::JudgementDisputed
+		);
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				1,
+				1,
+				Judgement::KnownGood,
+				None
+			),
+			Error::<Test>::JudgementDisputed
+		);
+		assert_noop!(
+			Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				Judgement::KnownGood,
+				None
+			),
+			Error::<Test>::JudgementDisputed
+		);
+		assert_noop!(
+			Identity::provide_judgement_remote(
+				RuntimeOrigin::signed(2000),
+				0,
+				1,
+				Judgement::KnownGood,
+				None
+			),
+			Error::<Test>::JudgementDisputed
+		);
+		assert_noop!(
+			Identity::provide_judgements_inline(
+				RuntimeOrigin::root(),
+				1,
+				vec![(2, Judgement::Reasonable), (0, Judgement::KnownGood)].try_into().unwrap()
+			),
+			Error::<Test>::JudgementDisputed
+		);
+
+		// Nor can the identity holder drop them
+		assert_ok!(Identity::clear_judgements(RuntimeOrigin::signed(1)));
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().judgements.to_vec(),
+			vec![(0, (Judgement::Reasonable, 1))]
+		);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 1), Some((Judgement::LowQuality, 1)));
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), note(b"inline"));
+
+		set_display_identity(1);
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().judgements.to_vec(),
+			vec![(0, (Judgement::Reasonable, 1))]
+		);
+		assert_eq!(JudgementNotes::<Test>::get(1, 0), note(b"inline"));
+	});
+}
+
+#[test]
+fn consolidation_keeps_disputed_inline_judgements() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+
+		assert_ok!(Identity::consolidate_judgements(RuntimeOrigin::signed(1), 1));
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().judgements.to_vec(),
+			vec![(0, (Judgement::Reasonable, 1))]
+		);
+		assert!(Disputes::<Test>::contains_key(1, 0));
+	});
+}
+
+#[test]
+fn clear_identity_withdraws_disputes() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		let reserved = Balances::reserved_balance(1);
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert!(!Disputes::<Test>::contains_key(1, 0));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::IdentityCleared { who: 1, deposit: reserved }.into());
+	});
+}
+
+#[test]
+fn set_registrar_reserves_bond() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::set_registrar(RuntimeOrigin::root(), 0, 10, 50),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_eq!(Balances::reserved_balance(5), 100);
+		assert_eq!(Registrars::<Test>::get(0).unwrap().bond, 100);
+
+		// A replaced registrar gets its bond back
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 4, 50));
+		assert_eq!(Balances::reserved_balance(5), 0);
+		assert_eq!(Balances::reserved_balance(4), 100);
+	});
+}
+
+#[test]
+fn resolve_dispute_fails_for_invalid_resolutions() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+
+		assert_noop!(
+			Identity::resolve_dispute(RuntimeOrigin::root(), 1, 0, true),
+			Error::<Test>::NotDisputed
+		);
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		assert_noop!(
+			Identity::resolve_dispute(RuntimeOrigin::signed(1), 1, 0, false),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn upheld_dispute_slashes_challenge_deposit() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::LowQuality,
+			None
+		));
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		let reserved = Balances::reserved_balance(1);
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(Identity::resolve_dispute(RuntimeOrigin::root(), 1, 0, true));
+		System::assert_last_event(
+			Event::DisputeResolved { who: 1, judgement_id: 0, upheld: true, slashed: 0 }.into(),
+		);
+		assert!(!Disputes::<Test>::contains_key(1, 0));
+		assert_eq!(Balances::reserved_balance(1), reserved - 20);
+		assert_eq!(Balances::total_issuance(), issuance - 20);
+
+		// The judgement stands and can be revised again
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().judgements.to_vec(),
+			vec![(0, (Judgement::LowQuality, 1))]
+		);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+	});
+}
+
+#[test]
+fn overturned_dispute_removes_judgement_and_slashes_registrar() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		set_display_identity(1);
+		let deposit = Balances::reserved_balance(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			note(b"inline")
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			note(b"inline")
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(Identity::verification_status(&1), VerificationStatus::Verified);
+		assert_eq!(Badges::get(), vec![1]);
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+
+		// Even sticky judgements are removed
+		assert_ok!(Identity::resolve_dispute(RuntimeOrigin::root(), 1, 0, false));
+		System::assert_last_event(
+			Event::DisputeResolved { who: 1, judgement_id: 0, upheld: false, slashed: 50 }.into(),
+		);
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.judgements.to_vec(), vec![(1, (Judgement::Reasonable, 1))]);
+		assert_eq!(CounterForJudgements::<Test>::get(1), 0);
+		assert_eq!(registration.known_good, 0);
+		assert_eq!(registration.status, VerificationStatus::Unverified);
+		assert_eq!(registration.notes_deposit, 0);
+		assert!(!JudgementsDoubleMap::<Test>::contains_key(1, 0));
+		assert!(!JudgementNotes::<Test>::contains_key(1, 0));
+		assert_eq!(Identity::score_of(&1), 1);
+		assert!(Badges::get().is_empty());
+
+		// The challenger gets the deposit back, the registrar loses half its bond
+		assert!(!Disputes::<Test>::contains_key(1, 0));
+		assert_eq!(Balances::reserved_balance(1), deposit);
+		assert_eq!(Balances::reserved_balance(5), 50);
+		assert_eq!(Registrars::<Test>::get(0).unwrap().bond, 50);
+	});
+}
+
+#[test]
+fn register_provider_holds_the_bond() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::register_provider(RuntimeOrigin::signed(5), 0, 10, 49),
+			Error::<Test>::BondTooLow
+		);
+
+		assert_ok!(Identity::register_provider(RuntimeOrigin::signed(5), 0, 10, 100));
+		System::assert_last_event(
+			Event::ProviderRegistered { judgement_id: 0, account: 5, fee: 10, bond: 100 }.into(),
+		);
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::ProviderBond, &5), 100);
+		// Holds count towards the reserved balance, the bond being the only one
+		assert_eq!(Balances::reserved_balance(5), 100);
+		assert_eq!(ProviderBonds::<Test>::get(0), Some(100));
+		let registrar = Registrars::<Test>::get(0).unwrap();
+		assert_eq!((registrar.account, registrar.fee, registrar.bond), (5, 10, 0));
+		// Provider bonds aren't held through the deposit backend
+		assert_eq!(TotalDeposits::<Test>::get(), 0);
+
+		// Judgement IDs can't be taken over, whoever registered them
+		assert_noop!(
+			Identity::register_provider(RuntimeOrigin::signed(4), 0, 10, 100),
+			Error::<Test>::RegistrarExists
+		);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 1, 3, 50));
+		assert_noop!(
+			Identity::register_provider(RuntimeOrigin::signed(4), 1, 10, 100),
+			Error::<Test>::RegistrarExists
+		);
+	});
+}
+
+#[test]
+fn provider_bond_can_be_topped_up_and_withdrawn() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::register_provider(RuntimeOrigin::signed(5), 0, 10, 100));
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 1, 4, 50));
+
+		assert_ok!(Identity::top_up_provider_bond(RuntimeOrigin::signed(5), 0, 50));
+		System::assert_last_event(Event::ProviderBondChanged { judgement_id: 0, bond: 150 }.into());
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::ProviderBond, &5), 150);
+
+		// At least `MinProviderBond` stays held
+		assert_noop!(
+			Identity::withdraw_provider_bond(RuntimeOrigin::signed(5), 0, 101),
+			Error::<Test>::BondTooLow
+		);
+		assert_noop!(
+			Identity::withdraw_provider_bond(RuntimeOrigin::signed(5), 0, 151),
+			Error::<Test>::BondTooLow
+		);
+		assert_ok!(Identity::withdraw_provider_bond(RuntimeOrigin::signed(5), 0, 100));
+		System::assert_last_event(Event::ProviderBondChanged { judgement_id: 0, bond: 50 }.into());
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::ProviderBond, &5), 50);
+		assert_eq!(ProviderBonds::<Test>::get(0), Some(50));
+
+		// Only the provider itself can change its bond, and only if it registered itself
+		assert_noop!(
+			Identity::top_up_provider_bond(RuntimeOrigin::signed(4), 0, 50),
+			Error::<Test>::NotRegistrar
+		);
+		assert_noop!(
+			Identity::top_up_provider_bond(RuntimeOrigin::signed(5), 2, 50),
+			Error::<Test>::UnknownRegistrar
+		);
+		assert_noop!(
+			Identity::withdraw_provider_bond(RuntimeOrigin::signed(4), 1, 10),
+			Error::<Test>::NotSelfRegistered
+		);
+	});
+}
+
+#[test]
+fn overturned_dispute_slashes_the_provider_bond() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::register_provider(RuntimeOrigin::signed(5), 0, 10, 100));
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(Identity::resolve_dispute(RuntimeOrigin::root(), 1, 0, false));
+		System::assert_last_event(
+			Event::DisputeResolved { who: 1, judgement_id: 0, upheld: false, slashed: 50 }.into(),
+		);
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::ProviderBond, &5), 50);
+		assert_eq!(ProviderBonds::<Test>::get(0), Some(50));
+		assert_eq!(Balances::total_issuance(), issuance - 50);
+
+		// Nothing is left to withdraw, but the provider can restore its bond
+		assert_noop!(
+			Identity::withdraw_provider_bond(RuntimeOrigin::signed(5), 0, 1),
+			Error::<Test>::BondTooLow
+		);
+		assert_ok!(Identity::top_up_provider_bond(RuntimeOrigin::signed(5), 0, 50));
+		assert_eq!(ProviderBonds::<Test>::get(0), Some(100));
+	});
+}
+
+#[test]
+fn set_registrar_releases_a_replaced_provider_bond() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::register_provider(RuntimeOrigin::signed(5), 0, 10, 100));
+
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 4, 50));
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::ProviderBond, &5), 0);
+		assert!(!ProviderBonds::<Test>::contains_key(0));
+		assert_eq!(Registrars::<Test>::get(0).unwrap().bond, 100);
+		assert_eq!(Balances::reserved_balance(4), 100);
+
+		// The registrar set in its place has no provider bond to change
+		assert_noop!(
+			Identity::top_up_provider_bond(RuntimeOrigin::signed(4), 0, 50),
+			Error::<Test>::NotSelfRegistered
+		);
+	});
+}
+
+#[test]
+fn known_good_judgement_discounts_the_deposit_until_lost() {
+	new_test_ext().execute_with(|| {
+		VerifiedDepositDiscount::set(Perbill::from_percent(50));
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 1, 4, 50));
+		set_display_identity(1);
+		assert_eq!(Balances::reserved_balance(1), 21);
+
+		// The first `KnownGood` returns half the deposit, rounded down
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		System::assert_has_event(Event::DepositDiscountGranted { who: 1, deposit: 11 }.into());
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 11);
+		assert_eq!(Balances::reserved_balance(1), 11);
+
+		// Further ones and updates of the identity keep the discount
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		set_display_identity(1);
+		assert_eq!(Balances::reserved_balance(1), 11);
+		assert_eq!(Identity::registration_cost(1, Identity::identity_of(&1).unwrap().info).0, 11);
+
+		// Losing one of them keeps it too, losing the last reserves it again
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Identity::resolve_dispute(RuntimeOrigin::root(), 1, 0, false));
+		assert_eq!(Balances::reserved_balance(1), 11);
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 1));
+		assert_ok!(Identity::resolve_dispute(RuntimeOrigin::root(), 1, 1, false));
+		System::assert_has_event(Event::DepositDiscountRevoked { who: 1, deposit: 21 }.into());
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 21);
+		assert_eq!(Balances::reserved_balance(1), 21);
+
+		// Clearing returns what is reserved
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn unaffordable_discount_revocation_is_recorded_as_debt() {
+	new_test_ext().execute_with(|| {
+		VerifiedDepositDiscount::set(Perbill::from_percent(50));
+		RevalidationPeriod::set(10);
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 11);
+
+		// Too little is left above the existential deposit (500) to reserve the discount again
+		// once the judgement expires
+		let free = Balances::free_balance(1);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(1), 2, free - 505));
+		run_hooks_to(11);
+		assert!(!Identity::is_verified(&1));
+		System::assert_has_event(Event::DepositDebtIncurred { who: 1, debt: 10 }.into());
+		assert_eq!(DepositDebts::<Test>::get(1), Some(10));
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 11);
+		assert_eq!(TotalDeposits::<Test>::get(), Identity::recompute_total_deposits());
+
+		// Repricing the deposit pays the debt off
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), 1, 100));
+		assert_ok!(Identity::poke_deposit(RuntimeOrigin::signed(1)));
+		assert_eq!(DepositDebts::<Test>::get(1), None);
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 21);
+		assert_eq!(TotalDeposits::<Test>::get(), Identity::recompute_total_deposits());
+	});
+}
+
+#[test]
+fn deposit_decays_while_the_identity_stays_unchanged() {
+	new_test_ext().execute_with(|| {
+		DepositDecayPeriod::set(10);
+		assert_noop!(Identity::poke_deposit(RuntimeOrigin::signed(1)), Error::<Test>::NoIdentity);
+		set_display_identity(1);
+		assert_eq!(Balances::reserved_balance(1), 21);
+
+		// Nothing decays during the first period
+		System::set_block_number(11);
+		assert_ok!(Identity::poke_deposit(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 21);
+
+		// Then the 10 above the floor of 11 decay over the next one
+		System::set_block_number(16);
+		assert_ok!(Identity::poke_deposit(RuntimeOrigin::signed(1)));
+		System::assert_last_event(
+			Event::DepositPoked { who: 1, old_deposit: 21, new_deposit: 16 }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 16);
+		System::set_block_number(100);
+		assert_ok!(Identity::poke_deposit(RuntimeOrigin::signed(1)));
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 11);
+		assert_eq!(Balances::reserved_balance(1), 11);
+
+		// Editing the identity restarts the clock
+		set_display_identity(1);
+		assert_eq!(Balances::reserved_balance(1), 21);
+		System::set_block_number(115);
+		assert_ok!(Identity::poke_deposit(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 16);
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(UnchangedSince::<Test>::get(1), None);
+	});
+}
+
+#[test]
+fn hold_deposits_back_deposits_with_fungible_holds() {
+	new_test_ext().execute_with(|| {
+		type Holds = HoldDeposits<Balances, IdentityHoldReason>;
+		let issuance = Balances::total_issuance();
+
+		assert_ok!(Holds::hold(&1, 100));
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::IdentityDeposit, &1), 100);
+		assert_eq!(Balances::free_balance(1), 900);
+		assert_noop!(Holds::hold(&1, 1000), TokenError::FundsUnavailable);
+
+		assert_eq!(Holds::release(&1, 30), 0);
+		assert_eq!(Holds::slash(&1, 20), 0);
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::IdentityDeposit, &1), 50);
+		assert_eq!(Balances::total_issuance(), issuance - 20);
+
+		// What isn't held is reported back
+		assert_eq!(Holds::transfer(&1, &2, 80), Ok(30));
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::IdentityDeposit, &2), 50);
+		assert_eq!(Holds::release(&1, 10), 10);
+		assert_eq!(Holds::slash(&2, 60), 10);
+		assert_eq!(Balances::balance_on_hold(&MockHoldReason::IdentityDeposit, &2), 0);
+	});
+}
+
+#[test]
+fn cleared_deposit_is_held_for_release_delay() {
+	new_test_ext().execute_with(|| {
+		DepositReleaseDelay::set(10);
+		set_display_identity(1);
+		let deposit = Balances::reserved_balance(1);
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::IdentityCleared { who: 1, deposit }.into());
+		assert_eq!(Balances::reserved_balance(1), deposit);
+		assert_eq!(
+			PendingReleases::<Test>::get(1),
+			Some(PendingRelease { amount: deposit, at: 11 })
+		);
+
+		System::set_block_number(5);
+		assert_noop!(
+			Identity::withdraw_deposit(RuntimeOrigin::signed(1)),
+			Error::<Test>::ReleaseNotDue
+		);
+
+		// Clearing again adds to the held deposit and restarts the delay
+		set_display_identity(1);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(
+			PendingReleases::<Test>::get(1),
+			Some(PendingRelease { amount: 2 * deposit, at: 15 })
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			Identity::withdraw_deposit(RuntimeOrigin::signed(1)),
+			Error::<Test>::ReleaseNotDue
+		);
+
+		System::set_block_number(15);
+		assert_ok!(Identity::withdraw_deposit(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::DepositWithdrawn { who: 1, amount: 2 * deposit }.into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(PendingReleases::<Test>::get(1), None);
+
+		assert_noop!(
+			Identity::withdraw_deposit(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoPendingRelease
+		);
+	});
+}
+
+#[test]
+fn clear_identity_keeps_tombstone_with_sticky_judgements() {
+	use sp_runtime::traits::Hash;
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(2);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		assert_eq!(Tombstones::<Test>::get(2), None);
+
+		KeepTombstones::set(true);
+		System::set_block_number(5);
+		set_display_identity(1);
+		let info = Identity::identity_of(&1).unwrap().info;
+		for (judgement_id, judgement) in [(0, Judgement::KnownGood), (1, Judgement::Reasonable)] {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				judgement_id,
+				1,
+				judgement,
+				None
+			));
+		}
+		for (judgement_id, judgement) in
+			[(0, Judgement::Erroneous), (2, Judgement::Erroneous), (3, Judgement::Reasonable)]
+		{
+			assert_ok!(Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				judgement_id,
+				1,
+				judgement,
+				None
+			));
+		}
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		let tombstone = Tombstones::<Test>::get(1).unwrap();
+		assert_eq!(tombstone.info_hash, <Test as frame_system::Config>::Hashing::hash_of(&info));
+		assert_eq!(tombstone.cleared_at, 5);
+		// The inline judgement is kept over the double map one under the same ID
+		assert_eq!(
+			tombstone.judgements.to_vec(),
+			vec![(0, Judgement::KnownGood), (2, Judgement::Erroneous)]
+		);
+	});
+}
+
+#[test]
+fn reap_tombstone_waits_for_lifetime() {
+	new_test_ext().execute_with(|| {
+		KeepTombstones::set(true);
+		assert_noop!(
+			Identity::reap_tombstone(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::NoTombstone
+		);
+		set_display_identity(1);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+
+		System::set_block_number(50);
+		assert_noop!(
+			Identity::reap_tombstone(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::TombstoneNotExpired
+		);
+
+		System::set_block_number(51);
+		assert_ok!(Identity::reap_tombstone(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::TombstoneReaped { who: 1 }.into());
+		assert_eq!(Tombstones::<Test>::get(1), None);
+	});
+}
+
+#[test]
+fn release_delay_follows_block_number_provider() {
+	new_test_ext().execute_with(|| {
+		DepositReleaseDelay::set(10);
+		RelayBlockNumber::set(100);
+		assert_ok!(OrgIdentity::set_identity(RuntimeOrigin::signed(1), empty_info()));
+		assert_ok!(OrgIdentity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(PendingReleases::<Test, Instance2>::get(1).unwrap().at, 110);
+
+		// The system block number doesn't count for an instance with a provider of its own
+		System::set_block_number(110);
+		assert_noop!(
+			OrgIdentity::withdraw_deposit(RuntimeOrigin::signed(1)),
+			Error::<Test, Instance2>::ReleaseNotDue
+		);
+
+		RelayBlockNumber::set(110);
+		assert_ok!(OrgIdentity::withdraw_deposit(RuntimeOrigin::signed(1)));
+	});
+}
+
+#[test]
+fn clear_field_returns_its_deposit() {
+	new_test_ext().execute_with(|| {
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: b"legal".to_vec().try_into().unwrap(),
+			web: b"web".to_vec().try_into().unwrap(),
+			email: b"email".to_vec().try_into().unwrap(),
+			avatar: None,
+		};
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.field_bytes, [8, 6, 4, 6, 0]);
+		assert_eq!(registration.deposit, 10 + 24);
+
+		// "legal" and its length prefix are refunded, the empty field's prefix stays
+		assert_ok!(Identity::clear_field(RuntimeOrigin::signed(1), IdentityField::Legal));
+		System::assert_last_event(
+			Event::IdentityFieldCleared { who: 1, field: IdentityField::Legal }.into(),
+		);
+		let registration = Identity::identity_of(&1).unwrap();
+		assert!(registration.info.legal.is_empty());
+		assert_eq!(registration.field_bytes, [8, 1, 4, 6, 0]);
+		assert_eq!(registration.deposit, 10 + 19);
+		assert_eq!(registration.field_bytes, registration.info.field_sizes());
+		assert_eq!(Balances::reserved_balance(1), registration.deposit);
+		// Judgements are kept
+		assert_eq!(registration.judgements.to_vec(), vec![(0, (Judgement::Reasonable, 1))]);
+
+		assert_noop!(
+			Identity::clear_field(RuntimeOrigin::signed(2), IdentityField::Web),
+			Error::<Test>::NoIdentity
+		);
+	});
+}
+
+#[test]
+fn avatar_is_charged_updated_and_cleared_like_a_field() {
+	new_test_ext().execute_with(|| {
+		// The content identifier is opaque, so bytes that aren't valid UTF-8 are fine
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			avatar: Some(b"bafy\xff".to_vec().try_into().unwrap()),
+			..Default::default()
+		};
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(IdentityInfoOf::<Test>::encoded_avatar_size(5), 7);
+		assert_eq!(registration.field_bytes, [8, 1, 1, 1, 7]);
+		assert_eq!(registration.deposit, 10 + 18);
+		assert_eq!(registration.info.field(IdentityField::Avatar), b"bafy\xff");
+
+		let update = IdentityInfo {
+			avatar: Some(b"bafybeigdy".to_vec().try_into().unwrap()),
+			..Default::default()
+		};
+		assert_ok!(Identity::update_identity_fields(
+			RuntimeOrigin::signed(1),
+			IdentityField::Avatar.into(),
+			update.clone()
+		));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.info, IdentityInfo { avatar: update.avatar, ..info });
+		assert_eq!(registration.field_bytes, [8, 1, 1, 1, 12]);
+		assert_eq!(registration.deposit, 10 + 23);
+		assert_eq!(Balances::reserved_balance(1), registration.deposit);
+
+		// An unset avatar costs nothing
+		assert_ok!(Identity::clear_field(RuntimeOrigin::signed(1), IdentityField::Avatar));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.info.avatar, None);
+		assert_eq!(registration.field_bytes, [8, 1, 1, 1, 0]);
+		assert_eq!(registration.deposit, 10 + 11);
+		assert_eq!(registration.field_bytes, registration.info.field_sizes());
+		assert_eq!(Balances::reserved_balance(1), registration.deposit);
+	});
+}
+
+#[test]
+fn migration_to_v1_fills_in_field_bytes() {
+	use crate::migrations::{v1, v6, v7};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let info = v7::IdentityInfo::<Test> {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: BoundedVec::default(),
+			web: b"web".to_vec().try_into().unwrap(),
+			email: BoundedVec::default(),
+		};
+		let deposit = 10 + info.encoded_size() as u128;
+		// The first deployed encoding: the four fields, the judgements with `KnownGood` at index
+		// 2, the double map count and the deposit
+		let old = (
+			b"display".to_vec(),
+			Vec::<u8>::new(),
+			b"web".to_vec(),
+			Vec::<u8>::new(),
+			vec![(0u32, 2u8)],
+			0u32,
+			deposit,
+		);
+		frame_support::storage::unhashed::put_raw(
+			&crate::IdentityOf::<Test>::hashed_key_for(1),
+			&old.encode(),
+		);
+		JudgementNotes::<Test>::insert(1, 0, note(b"note").unwrap());
+		v6::JudgementsDoubleMap::<Test, ()>::insert(1, 0, Judgement::KnownGood);
+		v6::JudgementsDoubleMap::<Test, ()>::insert(1, 1, Judgement::KnownGood);
+		StorageVersion::new(0).put::<Identity>();
+
+		v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(1));
+		let registration = v1::IdentityOf::<Test, ()>::get(1).unwrap();
+		assert_eq!(registration.info, info);
+		assert_eq!(registration.judgements.to_vec(), vec![(0, Judgement::KnownGood)]);
+		assert_eq!(registration.field_bytes, [8, 1, 4, 1]);
+		assert_eq!(registration.deposit, deposit);
+		assert_eq!(registration.notes_deposit, 4);
+		// The inline and double map judgements under ID 0 are from the same provider
+		assert_eq!(registration.known_good, 2);
+		assert_eq!(registration.status, VerificationStatus::Verified);
+
+		// Running it again leaves the migrated registration alone
+		v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(v1::IdentityOf::<Test, ()>::get(1), Some(registration));
+	});
+}
+
+#[test]
+fn migration_to_v2_adds_verified_fields() {
+	use crate::migrations::{v1, v2, v7};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let info = v7::IdentityInfo::<Test> {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: BoundedVec::default(),
+			web: BoundedVec::default(),
+			email: BoundedVec::default(),
+		};
+		let old = v1::Registration::<Test> {
+			field_bytes: info.field_sizes(),
+			info: info.clone(),
+			judgements: vec![(0, Judgement::KnownGood)].try_into().unwrap(),
+			judgements_count_double_map: 0,
+			deposit: 10 + info.encoded_size() as u128,
+			notes_deposit: 0,
+			known_good: 1,
+			status: VerificationStatus::Verified,
+		};
+		v1::IdentityOf::<Test, ()>::insert(1, old.clone());
+		StorageVersion::new(1).put::<Identity>();
+
+		v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(2));
+		let registration = v2::IdentityOf::<Test, ()>::get(1).unwrap();
+		assert_eq!(registration.info, info);
+		assert_eq!(registration.field_bytes, old.field_bytes);
+		assert_eq!(registration.judgements, old.judgements);
+		assert_eq!(registration.status, VerificationStatus::Verified);
+		assert_eq!(registration.verified_fields, IdentityFields::empty());
+
+		// Running it again leaves the migrated registration alone
+		v2::MigrateV1ToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(v2::IdentityOf::<Test, ()>::get(1), Some(registration));
+	});
+}
+
+/// A registration in the layout of storage versions 2 and 3, holding `judgements` inline and
+/// `judgements_count_double_map` in the double map.
+fn v2_registration(
+	judgements: Vec<(u32, Judgement)>,
+	judgements_count_double_map: u32,
+) -> crate::migrations::v2::Registration<Test> {
+	let info = crate::migrations::v7::IdentityInfo::<Test> {
+		display: b"display".to_vec().try_into().unwrap(),
+		legal: BoundedVec::default(),
+		web: BoundedVec::default(),
+		email: BoundedVec::default(),
+	};
+	crate::migrations::v2::Registration {
+		field_bytes: info.field_sizes(),
+		deposit: 10 + info.encoded_size() as u128,
+		info,
+		judgements: judgements.try_into().unwrap(),
+		judgements_count_double_map,
+		notes_deposit: 0,
+		known_good: 0,
+		status: VerificationStatus::Unverified,
+		verified_fields: IdentityFields::empty(),
+	}
+}
+
+#[test]
+fn migration_to_v3_indexes_judgements() {
+	use crate::migrations::{v2, v3, v6};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		v2::IdentityOf::<Test, ()>::insert(1, v2_registration(vec![(0, Judgement::Reasonable)], 0));
+		v2::IdentityOf::<Test, ()>::insert(2, v2_registration(vec![], 1));
+		v6::JudgementsDoubleMap::<Test, ()>::insert(2, 1, Judgement::Reasonable);
+		StorageVersion::new(2).put::<Identity>();
+
+		v3::MigrateV2ToV3::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(3));
+		assert_eq!(Identity::judged_by(0, None, 10), vec![1]);
+		assert_eq!(Identity::judged_by(1, None, 10), vec![2]);
+	});
+}
+
+#[test]
+fn migration_to_v4_moves_the_double_map_counter_out() {
+	use crate::migrations::{v2, v4};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let old = v2_registration(vec![(0, Judgement::Reasonable)], 2);
+		v2::IdentityOf::<Test, ()>::insert(1, old.clone());
+		v2::IdentityOf::<Test, ()>::insert(2, v2_registration(vec![], 0));
+		StorageVersion::new(3).put::<Identity>();
+
+		v4::MigrateV3ToV4::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(4));
+		let registration = v4::IdentityOf::<Test, ()>::get(1).unwrap();
+		assert_eq!(registration.info, old.info);
+		assert_eq!(registration.judgements, old.judgements);
+		assert_eq!(registration.deposit, old.deposit);
+		assert_eq!(CounterForJudgements::<Test>::get(1), 2);
+		// A zero counter isn't stored
+		assert!(v4::IdentityOf::<Test, ()>::get(2).is_some());
+		assert!(!CounterForJudgements::<Test>::contains_key(2));
+
+		// Running it again leaves the migrated registration alone
+		v4::MigrateV3ToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(v4::IdentityOf::<Test, ()>::get(1), Some(registration));
+	});
+}
+
+/// The judgements of `registration` without the blocks they were given in, as stored before
+/// storage version 7.
+fn undated_judgements(
+	registration: &crate::Registration<Test>,
+) -> BoundedVec<(u32, Judgement), MaxJudgements> {
+	BoundedVec::truncate_from(
+		registration
+			.judgements
+			.iter()
+			.map(|(judgement_id, (judgement, _))| (*judgement_id, *judgement))
+			.collect(),
+	)
+}
+
+/// Rewrite the double map judgements on `who` without the blocks they were given in, as stored
+/// before storage version 7.
+fn undate_double_map_judgements(who: u64) {
+	use crate::migrations::v6;
+
+	let judgements: Vec<_> = JudgementsDoubleMap::<Test>::iter_prefix(who).collect();
+	for (judgement_id, (judgement, _)) in judgements {
+		v6::JudgementsDoubleMap::<Test, ()>::insert(who, judgement_id, judgement);
+	}
+}
+
+/// The identity information of `registration` and the sizes of its fields without the avatar, as
+/// stored before storage version 8.
+fn info_without_avatar(
+	registration: &crate::Registration<Test>,
+) -> (crate::migrations::v7::IdentityInfo<Test>, [u32; 4]) {
+	let IdentityInfo { display, legal, web, email, .. } = registration.info.clone();
+	let [d, l, w, e, _] = registration.field_bytes;
+	(crate::migrations::v7::IdentityInfo { display, legal, web, email }, [d, l, w, e])
+}
+
+/// `registration` in the layout of storage versions 8 and 9, without `preferred_registrars`.
+fn without_preferred_registrars(
+	registration: &crate::Registration<Test>,
+) -> crate::migrations::v9::Registration<Test> {
+	crate::migrations::v9::Registration {
+		info: registration.info.clone(),
+		judgements: registration.judgements.clone(),
+		deposit: registration.deposit,
+		field_bytes: registration.field_bytes,
+		notes_deposit: registration.notes_deposit,
+		known_good: registration.known_good,
+		erroneous: registration.erroneous,
+		status: registration.status,
+		level: registration.level,
+		verified_fields: registration.verified_fields,
+	}
+}
+
+/// Rewrite `who`'s registration in the layout of storage versions 4 and 5, without `erroneous`
+/// and `level`, and its double map judgements undated.
+fn downgrade_to_v4(who: u64) {
+	let registration = Identity::identity_of(&who).unwrap();
+	let (info, field_bytes) = info_without_avatar(&registration);
+	undate_double_map_judgements(who);
+	crate::migrations::v4::IdentityOf::<Test, ()>::insert(
+		who,
+		crate::migrations::v4::Registration {
+			judgements: undated_judgements(&registration),
+			info,
+			deposit: registration.deposit,
+			field_bytes,
+			notes_deposit: registration.notes_deposit,
+			known_good: registration.known_good,
+			status: registration.status,
+			verified_fields: registration.verified_fields,
+		},
+	);
+}
+
+#[test]
+fn migration_to_v5_adds_up_the_deposits_held() {
+	use crate::migrations::v5;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		downgrade_to_v4(1);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 10));
+		TotalDeposits::<Test>::kill();
+		StorageVersion::new(4).put::<Identity>();
+
+		v5::MigrateV4ToV5::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(5));
+		assert_eq!(
+			TotalDeposits::<Test>::get(),
+			Balances::reserved_balance(1) + Balances::reserved_balance(5)
+		);
+	});
+}
+
+#[test]
+fn migration_to_v6_derives_verification_levels() {
+	use crate::migrations::v6;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		set_display_identity(2);
+		set_display_identity(3);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Erroneous,
+			None
+		));
+		// Under an ID already counted inline
+		JudgementsDoubleMap::<Test>::insert(1, 0, (Judgement::Erroneous, 1));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			2,
+			Judgement::KnownGood,
+			None
+		));
+		for who in 1..=3 {
+			downgrade_to_v4(who);
+		}
+		StorageVersion::new(5).put::<Identity>();
+
+		v6::MigrateV5ToV6::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(6));
+		let registration = v6::IdentityOf::<Test, ()>::get(1).unwrap();
+		assert_eq!(registration.erroneous, 2);
+		assert_eq!(registration.level, VerificationLevel::None);
+		let registration = v6::IdentityOf::<Test, ()>::get(2).unwrap();
+		assert_eq!((registration.known_good, registration.erroneous), (1, 0));
+		assert_eq!(registration.level, VerificationLevel::Silver);
+		assert_eq!(v6::IdentityOf::<Test, ()>::get(3).unwrap().level, VerificationLevel::Bronze);
+
+		// Running it again leaves the migrated registrations alone
+		v6::MigrateV5ToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(v6::IdentityOf::<Test, ()>::get(2), Some(registration));
+	});
+}
+
+#[test]
+fn migration_to_v7_dates_judgements_with_the_upgrade_block() {
+	use crate::migrations::{v6, v7};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		let current = Identity::identity_of(&1).unwrap();
+		let (info, field_bytes) = info_without_avatar(&current);
+		v6::IdentityOf::<Test, ()>::insert(
+			1,
+			v6::Registration {
+				judgements: undated_judgements(&current),
+				info,
+				deposit: current.deposit,
+				field_bytes,
+				notes_deposit: current.notes_deposit,
+				known_good: current.known_good,
+				erroneous: current.erroneous,
+				status: current.status,
+				level: current.level,
+				verified_fields: current.verified_fields,
+			},
+		);
+		undate_double_map_judgements(1);
+		StorageVersion::new(6).put::<Identity>();
+		System::set_block_number(5);
+
+		v7::MigrateV6ToV7::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(7));
+		let registration = v7::IdentityOf::<Test, ()>::get(1).unwrap();
+		assert_eq!(registration.judgements.to_vec(), vec![(0, (Judgement::KnownGood, 5))]);
+		assert_eq!(registration.known_good, current.known_good);
+		assert_eq!(registration.deposit, current.deposit);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 1), Some((Judgement::Reasonable, 5)));
+
+		// Running it again leaves the migrated judgements alone
+		v7::MigrateV6ToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(v7::IdentityOf::<Test, ()>::get(1), Some(registration));
+	});
+}
+
+#[test]
+fn migration_to_v8_adds_an_unset_avatar() {
+	use crate::migrations::{v7, v8, v9};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		let current = Identity::identity_of(&1).unwrap();
+		let (info, field_bytes) = info_without_avatar(&current);
+		v7::IdentityOf::<Test, ()>::insert(
+			1,
+			v7::Registration {
+				info: info.clone(),
+				judgements: current.judgements.clone(),
+				deposit: current.deposit,
+				field_bytes,
+				notes_deposit: current.notes_deposit,
+				known_good: current.known_good,
+				erroneous: current.erroneous,
+				status: current.status,
+				level: current.level,
+				verified_fields: current.verified_fields,
+			},
+		);
+		let state_root = sp_core::H256::repeat_byte(1);
+		let mirrored = v7::RemoteIdentity::<Test> {
+			info,
+			status: VerificationStatus::Verified,
+			verified_fields: IdentityFields::empty(),
+			state_root,
+		};
+		frame_support::storage::unhashed::put(
+			&RemoteIdentities::<Test>::hashed_key_for(2),
+			&mirrored,
+		);
+		StorageVersion::new(7).put::<Identity>();
+
+		v8::MigrateV7ToV8::<Test>::on_runtime_upgrade();
+
+		// Nothing but the unset avatar is added, so the deposit still matches the fields
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(8));
+		let registration = v9::IdentityOf::<Test, ()>::get(1).unwrap();
+		assert_eq!(registration, without_preferred_registrars(&current));
+		assert_eq!(registration.info.avatar, None);
+		assert_eq!(registration.field_bytes, registration.info.field_sizes());
+		let mirrored = RemoteIdentities::<Test>::get(2).unwrap();
+		assert_eq!(mirrored.info, current.info);
+		assert_eq!(mirrored.state_root, state_root);
+
+		// Running it again leaves the migrated registration alone
+		v8::MigrateV7ToV8::<Test>::on_runtime_upgrade();
+		assert_eq!(v9::IdentityOf::<Test, ()>::get(1), Some(registration));
+	});
+}
+
+#[test]
+fn migration_to_v9_downgrades_stale_judgements() {
+	use crate::migrations::v9;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		for judgement_id in [0, 1] {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				judgement_id,
+				1,
+				Judgement::KnownGood,
+				None
+			));
+		}
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		// The judgement under 0 went stale at version 8, and was no longer counted
+		let mut stale = without_preferred_registrars(&Identity::identity_of(&1).unwrap());
+		stale.known_good = 1;
+		v9::IdentityOf::<Test, ()>::insert(1, stale);
+		v9::StaleJudgements::<Test, ()>::insert(1, 0, ());
+		// A stale judgement of an identity since cleared
+		v9::StaleJudgements::<Test, ()>::insert(2, 0, ());
+		let score = Scores::<Test>::get(1);
+		StorageVersion::new(8).put::<Identity>();
+
+		v9::MigrateV8ToV9::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(9));
+		assert_eq!(v9::StaleJudgements::<Test, ()>::iter().count(), 0);
+		let registration = v9::IdentityOf::<Test, ()>::get(1).unwrap();
+		assert_eq!(
+			registration.judgements.to_vec(),
+			vec![(0, (Judgement::Reasonable, 1)), (1, (Judgement::KnownGood, 1))]
+		);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 0), Some((Judgement::Reasonable, 1)));
+		assert_eq!(registration.known_good, 1);
+		assert_eq!(registration.status, VerificationStatus::Verified);
+		assert!(Scores::<Test>::get(1) < score);
+		assert!(v9::IdentityOf::<Test, ()>::get(2).is_none());
+	});
+}
+
+#[test]
+fn migration_to_v10_adds_no_preferred_registrars() {
+	use crate::migrations::{v10, v9};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		let current = Identity::identity_of(&1).unwrap();
+		v9::IdentityOf::<Test, ()>::insert(1, without_preferred_registrars(&current));
+		StorageVersion::new(9).put::<Identity>();
+
+		v10::MigrateV9ToV10::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Identity::on_chain_storage_version(), StorageVersion::new(10));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration, current);
+		assert!(registration.preferred_registrars.is_empty());
+
+		// Running it again leaves the migrated registration alone
+		v10::MigrateV9ToV10::<Test>::on_runtime_upgrade();
+		assert_eq!(Identity::identity_of(&1), Some(registration));
+	});
+}
+
+#[cfg(feature = "interop")]
+#[test]
+fn upstream_identities_are_imported() {
+	use crate::migrations::import::{upstream, ImportIdentities, UpstreamRegistration};
+	use frame_support::{
+		migrations::{SteppedMigration, SteppedMigrationError},
+		parameter_types,
+		traits::{ConstU32, ReservableCurrency},
+		weights::{Weight, WeightMeter},
+	};
+	use upstream_identity::{legacy, Data};
+
+	parameter_types! {
+		pub const UpstreamName: &'static str = "UpstreamIdentity";
+	}
+	type Registration = UpstreamRegistration<Test, (), ConstU32<20>, ConstU32<100>>;
+	type Upstream = upstream::IdentityOf<UpstreamName, u64, Registration>;
+	type Subs = upstream::SubsOf<UpstreamName, u64, (u128, BoundedVec<u64, ConstU32<10>>)>;
+	type Supers = upstream::SuperOf<UpstreamName, u64, (u64, Data)>;
+	type Import =
+		ImportIdentities<Test, (), UpstreamName, ConstU32<20>, ConstU32<100>, ConstU32<10>>;
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 1, 5, 10));
+		let raw = |bytes: &[u8]| Data::Raw(bytes.to_vec().try_into().unwrap());
+		let info = legacy::IdentityInfo {
+			additional: Default::default(),
+			display: raw(b"upstream"),
+			legal: Data::None,
+			web: Data::None,
+			riot: Data::None,
+			email: Data::None,
+			pgp_fingerprint: None,
+			image: Data::None,
+			twitter: Data::None,
+		};
+		// Registrar 3 is unknown here, so its fee is refunded
+		let judgements = vec![
+			(0, upstream_identity::Judgement::Reasonable),
+			(1, upstream_identity::Judgement::FeePaid(10)),
+			(2, upstream_identity::Judgement::OutOfDate),
+			(3, upstream_identity::Judgement::FeePaid(7)),
+		];
+		assert_ok!(<Balances as ReservableCurrency<_>>::reserve(&1, 42));
+		Upstream::insert(
+			1,
+			Registration {
+				judgements: judgements.try_into().unwrap(),
+				deposit: 20,
+				info: info.clone(),
+			},
+		);
+		Subs::insert(1, (5, BoundedVec::truncate_from(vec![10])));
+		Supers::insert(10, (1, raw(b"sub")));
+		// An identity with a field this pallet lacks can't be imported
+		assert_ok!(<Balances as ReservableCurrency<_>>::reserve(&2, 20));
+		let twitter = legacy::IdentityInfo { twitter: raw(b"@two"), ..info.clone() };
+		Upstream::insert(
+			2,
+			Registration { judgements: Default::default(), deposit: 20, info: twitter },
+		);
+		// Nor one of an account already holding an identity here
+		set_display_identity(3);
+		let local = Identity::identity_of(&3).unwrap();
+		assert_ok!(<Balances as ReservableCurrency<_>>::reserve(&3, 20));
+		Upstream::insert(3, Registration { judgements: Default::default(), deposit: 20, info });
+
+		#[cfg(feature = "try-runtime")]
+		let state = Import::pre_upgrade().unwrap();
+		assert!(matches!(
+			Import::step(None, &mut WeightMeter::with_limit(Weight::zero())),
+			Err(SteppedMigrationError::InsufficientWeight { .. })
+		));
+		// One identity per step, then a step to find there are no more
+		let mut cursor = None;
+		for remaining in [2, 1, 0] {
+			let mut meter = WeightMeter::with_limit(Import::weight_per_identity());
+			cursor = Import::step(cursor, &mut meter).unwrap();
+			assert!(cursor.is_some());
+			assert_eq!(Upstream::iter_keys().count(), remaining);
+		}
+		let mut meter = WeightMeter::with_limit(Import::weight_per_identity());
+		assert_eq!(Import::step(cursor, &mut meter).unwrap(), None);
+		#[cfg(feature = "try-runtime")]
+		assert_ok!(Import::post_upgrade(state));
+
+		let reg = crate::IdentityOf::<Test>::get(1).unwrap();
+		assert_eq!(reg.info.display.to_vec(), b"upstream".to_vec());
+		assert_eq!(Identity::judgement_of(1, 0), Some(Judgement::Reasonable));
+		assert_eq!(Identity::judgement_of(1, 1), Some(Judgement::Requested));
+		assert_eq!(Identity::judgement_of(1, 2), None);
+		assert_eq!(Identity::judgement_of(1, 3), None);
+		assert_eq!(JudgementRequests::<Test>::get(1, 1), Some(10));
+		assert_eq!(Balances::reserved_balance(1), reg.deposit + 10);
+		assert_eq!(Subs::get(1), None);
+		assert_eq!(Supers::get(10), None);
+
+		assert!(crate::IdentityOf::<Test>::get(2).is_none());
+		assert_eq!(Balances::reserved_balance(2), 0);
+		System::assert_has_event(
+			Event::UpstreamIdentityDropped { who: 2, error: Error::<Test>::InvalidFields.into() }
+				.into(),
+		);
+		assert_eq!(Identity::identity_of(&3), Some(local.clone()));
+		assert_eq!(Balances::reserved_balance(3), local.deposit);
+		System::assert_has_event(
+			Event::UpstreamIdentityDropped { who: 3, error: Error::<Test>::IdentityExists.into() }
+				.into(),
+		);
+		assert_eq!(TotalDeposits::<Test>::get(), Identity::recompute_total_deposits());
+	});
+}
+
+#[test]
+fn identity_fields_are_a_bit_mask() {
+	let fields = IdentityFields::from(IdentityField::Display) | IdentityField::Email;
+	assert_eq!(fields.bits(), 0b1001);
+	assert!(fields.contains(IdentityField::Email));
+	assert!(!fields.contains(IdentityField::Web));
+	assert_eq!(
+		fields.iter().collect::<Vec<_>>(),
+		vec![IdentityField::Display, IdentityField::Email]
+	);
+	assert_eq!(IdentityFields::all().iter().collect::<Vec<_>>(), IdentityField::ALL.to_vec());
+	assert!(IdentityFields::all().is_valid());
+	assert!(!IdentityFields::decode(&mut &[0b10_0000u8][..]).unwrap().is_valid());
+}
+
+#[test]
+fn set_verified_fields_works() {
+	new_test_ext().execute_with(|| {
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: BoundedVec::default(),
+			web: b"web".to_vec().try_into().unwrap(),
+			email: b"email".to_vec().try_into().unwrap(),
+			avatar: None,
+		};
+		let fields = IdentityFields::from(IdentityField::Display) | IdentityField::Email;
+		assert_noop!(
+			Identity::set_verified_fields(RuntimeOrigin::root(), 1, fields),
+			Error::<Test>::NoIdentity
+		);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+
+		assert_noop!(
+			Identity::set_verified_fields(RuntimeOrigin::signed(1), 1, fields),
+			DispatchError::BadOrigin
+		);
+		// The legal name is empty
+		assert_noop!(
+			Identity::set_verified_fields(RuntimeOrigin::root(), 1, fields | IdentityField::Legal),
+			Error::<Test>::EmptyField
+		);
+		let invalid = IdentityFields::decode(&mut &[0b10_0001u8][..]).unwrap();
+		assert_noop!(
+			Identity::set_verified_fields(RuntimeOrigin::root(), 1, invalid),
+			Error::<Test>::InvalidFields
+		);
+
+		assert_ok!(Identity::set_verified_fields(RuntimeOrigin::root(), 1, fields));
+		System::assert_last_event(Event::VerifiedFieldsSet { who: 1, fields }.into());
+		assert_eq!(Identity::identity_of(&1).unwrap().verified_fields, fields);
+
+		// Setting the same display name again keeps it verified, changing the email doesn't
+		let new_info =
+			IdentityInfo { email: b"other".to_vec().try_into().unwrap(), ..info.clone() };
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), new_info));
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().verified_fields,
+			IdentityField::Display.into()
+		);
+
+		// Clearing a field drops it too
+		assert_ok!(Identity::clear_field(RuntimeOrigin::signed(1), IdentityField::Display));
+		assert_eq!(Identity::identity_of(&1).unwrap().verified_fields, IdentityFields::empty());
+	});
+}
+
+#[test]
+fn attest_kyc_works() {
+	new_test_ext().execute_with(|| {
+		let kyc_hash = sp_core::H256::repeat_byte(1);
+		assert_noop!(
+			Identity::set_kyc_hash(RuntimeOrigin::signed(1), Some(kyc_hash)),
+			Error::<Test>::NoIdentity
+		);
+		assert_noop!(
+			Identity::attest_kyc(RuntimeOrigin::root(), 0, 1, kyc_hash),
+			Error::<Test>::InvalidTarget
+		);
+		set_display_identity(1);
+		assert_noop!(
+			Identity::attest_kyc(RuntimeOrigin::root(), 0, 1, kyc_hash),
+			Error::<Test>::NoKycHash
+		);
+
+		assert_ok!(Identity::set_kyc_hash(RuntimeOrigin::signed(1), Some(kyc_hash)));
+		System::assert_last_event(Event::KycHashSet { who: 1, kyc_hash: Some(kyc_hash) }.into());
+		assert_eq!(KycHashes::<Test>::get(1), Some(kyc_hash));
+
+		assert_noop!(
+			Identity::attest_kyc(RuntimeOrigin::signed(1), 0, 1, kyc_hash),
+			DispatchError::BadOrigin
+		);
+		// The registrar checked another document
+		assert_noop!(
+			Identity::attest_kyc(RuntimeOrigin::root(), 0, 1, sp_core::H256::repeat_byte(2)),
+			Error::<Test>::KycHashChanged
+		);
+
+		assert_ok!(Identity::attest_kyc(RuntimeOrigin::root(), 0, 1, kyc_hash));
+		System::assert_last_event(Event::KycAttested { who: 1, judgement_id: 0 }.into());
+		assert_eq!(KycAttestations::<Test>::get(1), Some(0));
+
+		// Clearing the identity drops both
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert!(!KycHashes::<Test>::contains_key(1));
+		assert!(!KycAttestations::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn changing_the_identity_invalidates_the_kyc_attestation() {
+	new_test_ext().execute_with(|| {
+		let kyc_hash = sp_core::H256::repeat_byte(1);
+		let attest = || {
+			assert_ok!(Identity::attest_kyc(RuntimeOrigin::root(), 0, 1, kyc_hash));
+			System::reset_events();
+		};
+		let invalidated = || {
+			System::assert_has_event(Event::KycAttestationInvalidated { who: 1 }.into());
+			assert!(!KycAttestations::<Test>::contains_key(1));
+			// The hash itself stays
+			assert_eq!(KycHashes::<Test>::get(1), Some(kyc_hash));
+		};
+		set_display_identity(1);
+		assert_ok!(Identity::set_kyc_hash(RuntimeOrigin::signed(1), Some(kyc_hash)));
+		attest();
+
+		// Setting the same hash again keeps it attested
+		assert_ok!(Identity::set_kyc_hash(RuntimeOrigin::signed(1), Some(kyc_hash)));
+		assert_eq!(KycAttestations::<Test>::get(1), Some(0));
+
+		set_display_identity(1);
+		invalidated();
+		attest();
+
+		assert_ok!(Identity::update_identity_fields(
+			RuntimeOrigin::signed(1),
+			IdentityField::Email.into(),
+			IdentityInfo { email: b"email".to_vec().try_into().unwrap(), ..Default::default() }
+		));
+		invalidated();
+		attest();
+
+		assert_ok!(Identity::clear_field(RuntimeOrigin::signed(1), IdentityField::Email));
+		invalidated();
+		attest();
+
+		let other = sp_core::H256::repeat_byte(2);
+		assert_ok!(Identity::set_kyc_hash(RuntimeOrigin::signed(1), Some(other)));
+		System::assert_has_event(Event::KycAttestationInvalidated { who: 1 }.into());
+		assert!(!KycAttestations::<Test>::contains_key(1));
+		assert_ok!(Identity::set_kyc_hash(RuntimeOrigin::signed(1), None));
+		System::assert_last_event(Event::KycHashSet { who: 1, kyc_hash: None }.into());
+		assert!(!KycHashes::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn redact_fields_keeps_the_registration_and_sticky_judgements() {
+	new_test_ext().execute_with(|| {
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: b"legal name".to_vec().try_into().unwrap(),
+			web: BoundedVec::default(),
+			email: b"email".to_vec().try_into().unwrap(),
+			avatar: None,
+		};
+		let fields = IdentityFields::from(IdentityField::Legal) | IdentityField::Email;
+		assert_noop!(
+			Identity::redact_fields(RuntimeOrigin::root(), 1, fields),
+			Error::<Test>::NoIdentity
+		);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_field_judgement(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			IdentityField::Legal,
+			Judgement::KnownGood
+		));
+		assert_ok!(Identity::provide_field_judgement(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			IdentityField::Email,
+			Judgement::Reasonable
+		));
+		assert_ok!(Identity::set_verified_fields(
+			RuntimeOrigin::root(),
+			1,
+			IdentityFields::from(IdentityField::Display) | IdentityField::Legal
+		));
+		let before = Identity::identity_of(&1).unwrap();
+		let reserved = Balances::reserved_balance(1);
+
+		assert_noop!(
+			Identity::redact_fields(RuntimeOrigin::signed(1), 1, fields),
+			DispatchError::BadOrigin
+		);
+		let invalid = IdentityFields::decode(&mut &[0b10_0010u8][..]).unwrap();
+		assert_noop!(
+			Identity::redact_fields(RuntimeOrigin::root(), 1, invalid),
+			Error::<Test>::InvalidFields
+		);
+
+		assert_ok!(Identity::redact_fields(RuntimeOrigin::root(), 1, fields));
+
+		// The deposit for the redacted bytes is refunded
+		let refunded = (info.legal.len() + info.email.len()) as u128;
+		assert_eq!(Balances::reserved_balance(1), reserved - refunded);
+		System::assert_last_event(Event::FieldsRedacted { who: 1, fields, refunded }.into());
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.info, IdentityInfo { display: info.display, ..Default::default() });
+		assert_eq!(registration.field_bytes, registration.info.field_sizes());
+		assert_eq!(registration.deposit, before.deposit - refunded);
+		// Every whole-identity judgement is kept, and the sticky field judgements
+		assert_eq!(registration.judgements, before.judgements);
+		assert_eq!(registration.known_good, 1);
+		assert_eq!(registration.verified_fields, IdentityField::Display.into());
+		assert_eq!(
+			FieldJudgements::<Test>::get(1).to_vec(),
+			vec![(0, IdentityField::Legal, Judgement::KnownGood)]
+		);
+
+		// Redacting an empty field again refunds nothing
+		assert_ok!(Identity::redact_fields(RuntimeOrigin::root(), 1, IdentityField::Legal.into()));
+		System::assert_last_event(
+			Event::FieldsRedacted { who: 1, fields: IdentityField::Legal.into(), refunded: 0 }
+				.into(),
+		);
+	});
+}
+
+#[test]
+fn provide_field_judgement_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::provide_field_judgement(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				IdentityField::Display,
+				Judgement::Reasonable
+			),
+			Error::<Test>::InvalidTarget
+		);
+		set_display_identity(1);
+		assert_noop!(
+			Identity::provide_field_judgement(
+				RuntimeOrigin::signed(1),
+				0,
+				1,
+				IdentityField::Display,
+				Judgement::Reasonable
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Identity::provide_field_judgement(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				IdentityField::Web,
+				Judgement::Reasonable
+			),
+			Error::<Test>::EmptyField
+		);
+
+		assert_ok!(Identity::provide_field_judgement(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			IdentityField::Display,
+			Judgement::KnownGood
+		));
+		System::assert_last_event(
+			Event::FieldJudgementGiven { target: 1, field: IdentityField::Display }.into(),
+		);
+		assert_ok!(Identity::provide_field_judgement(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			IdentityField::Display,
+			Judgement::LowQuality
+		));
+		// A non-sticky field judgement is replaced, a sticky one isn't
+		assert_ok!(Identity::provide_field_judgement(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			IdentityField::Display,
+			Judgement::Reasonable
+		));
+		assert_noop!(
+			Identity::provide_field_judgement(
+				RuntimeOrigin::root(),
+				1,
+				1,
+				IdentityField::Display,
+				Judgement::Erroneous
+			),
+			Error::<Test>::StickyJudgement
+		);
+		assert_eq!(
+			FieldJudgements::<Test>::get(1).to_vec(),
+			vec![
+				(0, IdentityField::Display, Judgement::Reasonable),
+				(1, IdentityField::Display, Judgement::KnownGood)
+			]
+		);
+		// Field judgements are apart from those on the whole identity
+		let registration = Identity::identity_of(&1).unwrap();
+		assert!(registration.judgements.is_empty());
+		assert_eq!(registration.known_good, 0);
+	});
+}
+
+#[test]
+fn editing_a_field_drops_only_its_non_sticky_field_judgements() {
+	new_test_ext().execute_with(|| {
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: BoundedVec::default(),
+			web: b"web".to_vec().try_into().unwrap(),
+			email: b"email".to_vec().try_into().unwrap(),
+			avatar: None,
+		};
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		for (judgement_id, field, judgement) in [
+			(0, IdentityField::Display, Judgement::Reasonable),
+			(0, IdentityField::Email, Judgement::Reasonable),
+			(1, IdentityField::Email, Judgement::KnownGood),
+			(1, IdentityField::Web, Judgement::Reasonable),
+		] {
+			assert_ok!(Identity::provide_field_judgement(
+				RuntimeOrigin::root(),
+				judgement_id,
+				1,
+				field,
+				judgement
+			));
+		}
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+
+		// Editing the email drops every non-sticky whole-identity judgement, but only the
+		// non-sticky judgements on the email among the field judgements
+		let new_info = IdentityInfo { email: b"other".to_vec().try_into().unwrap(), ..info };
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), new_info));
+		assert!(Identity::identity_of(&1).unwrap().judgements.is_empty());
+		assert_eq!(
+			FieldJudgements::<Test>::get(1).to_vec(),
+			vec![
+				(0, IdentityField::Display, Judgement::Reasonable),
+				(1, IdentityField::Web, Judgement::Reasonable),
+				(1, IdentityField::Email, Judgement::KnownGood),
+			]
+		);
+
+		assert_ok!(Identity::clear_field(RuntimeOrigin::signed(1), IdentityField::Web));
+		assert_eq!(
+			FieldJudgements::<Test>::get(1).to_vec(),
+			vec![
+				(0, IdentityField::Display, Judgement::Reasonable),
+				(1, IdentityField::Email, Judgement::KnownGood),
+			]
+		);
+
+		assert_ok!(Identity::clear_judgements(RuntimeOrigin::signed(1)));
+		assert_eq!(
+			FieldJudgements::<Test>::get(1).to_vec(),
+			vec![(1, IdentityField::Email, Judgement::KnownGood)]
+		);
+
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert!(!FieldJudgements::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn set_beneficiary_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::set_beneficiary(RuntimeOrigin::signed(1), 2),
+			Error::<Test>::NoIdentity
+		);
+		set_display_identity(1);
+		assert_noop!(
+			Identity::set_beneficiary(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::InvalidTarget
+		);
+
+		assert_ok!(Identity::set_beneficiary(RuntimeOrigin::signed(1), 2));
+		System::assert_last_event(Event::BeneficiarySet { who: 1, beneficiary: 2 }.into());
+		assert_eq!(Beneficiaries::<Test>::get(1).map(|b| (b.account, b.since)), Some((2, 1)));
+
+		// Clearing the identity drops the nomination
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert!(!Beneficiaries::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn claim_identity_requires_inactivity() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_noop!(
+			Identity::claim_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::NotBeneficiary
+		);
+		assert_ok!(Identity::set_beneficiary(RuntimeOrigin::signed(1), 2));
+		assert_noop!(
+			Identity::claim_identity(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::NotBeneficiary
+		);
+		assert_noop!(
+			Identity::claim_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::StillActive
+		);
+
+		// Renewing the nomination restarts the inactivity period
+		System::set_block_number(100);
+		assert_ok!(Identity::set_beneficiary(RuntimeOrigin::signed(1), 2));
+		System::set_block_number(101);
+		assert_noop!(
+			Identity::claim_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::StillActive
+		);
+
+		// The beneficiary can't hold an identity of its own
+		System::set_block_number(200);
+		set_display_identity(2);
+		assert_noop!(
+			Identity::claim_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::IdentityExists
+		);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		assert_ok!(Identity::claim_identity(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::IdentityClaimed { original: 1, beneficiary: 2 }.into());
+	});
+}
+
+#[test]
+fn claim_identity_counts_from_the_last_activity() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_beneficiary(RuntimeOrigin::signed(1), 2));
+
+		// An active holder can't be claimed against, however long ago the nomination was
+		System::set_block_number(90);
+		set_display_identity(1);
+		System::set_block_number(150);
+		assert_noop!(
+			Identity::claim_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::StillActive
+		);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 10));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 10));
+		System::set_block_number(200);
+		assert_noop!(
+			Identity::claim_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::StillActive
+		);
+
+		System::set_block_number(250);
+		assert_ok!(Identity::claim_identity(RuntimeOrigin::signed(2), 1));
+	});
+}
+
+#[test]
+fn claim_identity_moves_registration_and_deposits() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			note(b"checked")
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			2,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 2));
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 1, 5, 50));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 1, 50));
+		let kyc_hash = sp_core::H256::repeat_byte(1);
+		assert_ok!(Identity::set_kyc_hash(RuntimeOrigin::signed(1), Some(kyc_hash)));
+		assert_ok!(Identity::attest_kyc(RuntimeOrigin::root(), 0, 1, kyc_hash));
+		assert_eq!(Badges::get(), vec![1]);
+		let registration = Identity::identity_of(&1).unwrap();
+		let reserved = Balances::reserved_balance(1);
+		assert_eq!(reserved, registration.total_deposit() + 50 + 20);
+
+		assert_ok!(Identity::set_beneficiary(RuntimeOrigin::signed(1), 2));
+		System::set_block_number(101);
+		assert_ok!(Identity::claim_identity(RuntimeOrigin::signed(2), 1));
+
+		assert_eq!(Identity::identity_of(&1), None);
+		assert_eq!(Identity::identity_of(&2), Some(registration));
+		assert_eq!(JudgementNotes::<Test>::get(2, 0), note(b"checked"));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(2, 2), Some((Judgement::KnownGood, 1)));
+		assert_eq!(Disputes::<Test>::get(2, 2), Some(20));
+		assert_eq!(JudgementRequests::<Test>::get(2, 1), Some(50));
+		assert_eq!(KycHashes::<Test>::get(2), Some(kyc_hash));
+		assert_eq!(KycAttestations::<Test>::get(2), Some(0));
+		assert!(!JudgementNotes::<Test>::contains_key(1, 0));
+		assert!(!KycHashes::<Test>::contains_key(1));
+		assert!(!JudgementsDoubleMap::<Test>::contains_key(1, 2));
+		assert!(!Beneficiaries::<Test>::contains_key(1));
+		assert_eq!(Badges::get(), vec![2]);
+		assert_eq!(Identity::judged_by(0, None, 10), vec![2]);
+		assert_eq!(Identity::judged_by(2, None, 10), vec![2]);
+
+		// The deposits move along and are returned to the beneficiary on clearing
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), reserved);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 1000 + reserved);
+		assert_eq!(JudgedBy::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn claim_identity_reprices_the_deposit_for_the_beneficiary() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Identity::set_deposit_exemption(RuntimeOrigin::root(), 1, true));
+		set_display_identity(1);
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 0);
+		assert_ok!(Identity::set_beneficiary(RuntimeOrigin::signed(1), 2));
+		System::set_block_number(101);
+
+		// Taking over the identity counts as setting one
+		MaxSetsPerPeriod::set(1);
+		set_display_identity(2);
+		let deposit = Identity::identity_of(&2).unwrap().deposit;
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		assert_noop!(
+			Identity::claim_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::TooManyIdentitySets
+		);
+
+		// The exemption stays with the original account, the beneficiary pays the deposit
+		System::set_block_number(111);
+		assert_ok!(Identity::claim_identity(RuntimeOrigin::signed(2), 1));
+		assert_eq!(Identity::identity_of(&2).unwrap().deposit, deposit);
+		assert_eq!(Balances::reserved_balance(2), deposit);
+		assert!(ExemptAccounts::<Test>::contains_key(1));
+		assert!(!ExemptAccounts::<Test>::contains_key(2));
+		assert_ok!(Identity::do_try_state());
+	});
+}
+
+#[test]
+fn judged_by_follows_judgements_in_both_patterns() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=3 {
+			set_display_identity(who);
+		}
+		for who in [1, 3] {
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				0,
+				who,
+				Judgement::Reasonable,
+				None
+			));
+		}
+		for who in [2, 3] {
+			assert_ok!(Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				0,
+				who,
+				Judgement::Reasonable,
+				None
+			));
+		}
+		let mut judged = Identity::judged_by(0, None, 10);
+		judged.sort();
+		assert_eq!(judged, vec![1, 2, 3]);
+		assert!(Identity::judged_by(1, None, 10).is_empty());
+
+		// Dropping the inline judgement keeps an account judged by its double map one
+		set_display_identity(3);
+		assert!(JudgedBy::<Test>::contains_key(0, 3));
+		assert_ok!(Identity::clear_judgements(RuntimeOrigin::signed(3)));
+		assert!(!JudgedBy::<Test>::contains_key(0, 3));
+
+		set_display_identity(1);
+		assert!(!JudgedBy::<Test>::contains_key(0, 1));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		assert!(Identity::judged_by(0, None, 10).is_empty());
+	});
+}
+
+#[test]
+fn recent_judgements_keep_the_latest_activity() {
+	use crate::JudgementActivity;
+
+	new_test_ext().execute_with(|| {
+		assert!(Identity::recent_judgements(10).is_empty());
+		set_display_identity(1);
+		set_display_identity(2);
+
+		for (block, judgement_id) in (1..=3).zip([0, 1, 2]) {
+			System::set_block_number(block);
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				judgement_id,
+				1,
+				Judgement::Reasonable,
+				None
+			));
+		}
+		assert_eq!(
+			Identity::recent_judgements(10),
+			vec![
+				JudgementActivity {
+					target: 1,
+					judgement_id: 2,
+					judgement: Judgement::Reasonable,
+					at: 3
+				},
+				JudgementActivity {
+					target: 1,
+					judgement_id: 1,
+					judgement: Judgement::Reasonable,
+					at: 2
+				},
+				JudgementActivity {
+					target: 1,
+					judgement_id: 0,
+					judgement: Judgement::Reasonable,
+					at: 1
+				},
+			]
+		);
+
+		// Past `MaxRecentJudgements` the oldest are overwritten
+		System::set_block_number(4);
+		for judgement_id in [3, 4] {
+			assert_ok!(Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				judgement_id,
+				2,
+				Judgement::KnownGood,
+				None
+			));
+		}
+		let recent = Identity::recent_judgements(10);
+		assert_eq!(recent.len(), 4);
+		assert_eq!(
+			recent.iter().map(|activity| activity.judgement_id).collect::<Vec<_>>(),
+			vec![4, 3, 2, 1]
+		);
+		assert_eq!(recent[0].target, 2);
+		assert_eq!(Identity::recent_judgements(2), recent[..2].to_vec());
+	});
+}
+
+#[test]
+fn judgements_per_block_are_limited_per_provider() {
+	new_test_ext().execute_with(|| {
+		MaxJudgementsPerBlock::set(2);
+		set_display_identity(1);
+		set_display_identity(2);
+
+		// Up to the limit, in either storage pattern
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			2,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(JudgementsInBlock::<Test>::get(0), 2);
+
+		// One more judgement, or field judgement, under the same ID is one too many
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				0,
+				2,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::TooManyJudgementsInBlock
+		);
+		assert_noop!(
+			Identity::provide_field_judgement(
+				RuntimeOrigin::root(),
+				0,
+				1,
+				IdentityField::Display,
+				Judgement::Reasonable
+			),
+			Error::<Test>::TooManyJudgementsInBlock
+		);
+
+		// Other providers have their own limit
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			2,
+			Judgement::Reasonable,
+			None
+		));
+
+		// Bridged judgements aren't given by a provider of this chain, nor limited
+		for target in [1, 2, 1] {
+			assert_ok!(Identity::receive_remote_judgement(
+				RuntimeOrigin::signed(1500),
+				1000,
+				target,
+				Judgement::Reasonable
+			));
+		}
+		assert_eq!(JudgementsInBlock::<Test>::get(1000), 0);
+
+		// The next block starts afresh
+		System::set_block_number(2);
+		Identity::on_initialize(2);
+		assert_eq!(JudgementsInBlock::<Test>::iter().count(), 0);
+		assert_ok!(Identity::provide_field_judgement(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			IdentityField::Display,
+			Judgement::Reasonable
+		));
+		assert_eq!(JudgementsInBlock::<Test>::get(0), 1);
+	});
+}
+
+#[test]
+fn identity_sets_are_limited_per_period() {
+	new_test_ext().execute_with(|| {
+		MaxSetsPerPeriod::set(2);
+		set_display_identity(1);
+		let update =
+			IdentityInfo { web: b"web".to_vec().try_into().unwrap(), ..Default::default() };
+		assert_ok!(Identity::update_identity_fields(
+			RuntimeOrigin::signed(1),
+			IdentityField::Web.into(),
+			update.clone()
+		));
+		assert_eq!(IdentitySets::<Test>::get(1), Some((1, 2)));
+
+		// Any way of setting the identity is one too many
+		let info = Identity::identity_of(&1).unwrap().info;
+		assert_noop!(
+			Identity::set_identity(RuntimeOrigin::signed(1), info.clone()),
+			Error::<Test>::TooManyIdentitySets
+		);
+		assert_noop!(
+			Identity::update_identity_fields(
+				RuntimeOrigin::signed(1),
+				IdentityField::Web.into(),
+				update
+			),
+			Error::<Test>::TooManyIdentitySets
+		);
+		assert_noop!(
+			Identity::set_identity_with_judgement_request(
+				RuntimeOrigin::signed(1),
+				info.clone(),
+				0,
+				10
+			),
+			Error::<Test>::TooManyIdentitySets
+		);
+
+		// Clearing the identity doesn't reset the count
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_noop!(
+			Identity::set_identity(RuntimeOrigin::signed(1), info),
+			Error::<Test>::TooManyIdentitySets
+		);
+
+		// Other accounts have their own count
+		set_display_identity(2);
+		assert_eq!(IdentitySets::<Test>::get(2), Some((1, 1)));
+	});
+}
+
+#[test]
+fn identity_set_window_rolls_over_after_the_period() {
+	new_test_ext().execute_with(|| {
+		MaxSetsPerPeriod::set(1);
+		IdentitySetPeriod::set(10);
+		System::set_block_number(5);
+		set_display_identity(1);
+
+		// The window started at block 5 still runs at block 14
+		System::set_block_number(14);
+		let info = Identity::identity_of(&1).unwrap().info;
+		assert_noop!(
+			Identity::set_identity(RuntimeOrigin::signed(1), info.clone()),
+			Error::<Test>::TooManyIdentitySets
+		);
+
+		// A new window starts with the first set after it
+		System::set_block_number(15);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		assert_eq!(IdentitySets::<Test>::get(1), Some((15, 1)));
+		System::set_block_number(24);
+		assert_noop!(
+			Identity::set_identity(RuntimeOrigin::signed(1), info.clone()),
+			Error::<Test>::TooManyIdentitySets
+		);
+
+		// Without a limit, nothing is counted
+		MaxSetsPerPeriod::set(0);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
+		assert_eq!(IdentitySets::<Test>::get(1), Some((15, 1)));
+	});
+}
+
+/// Run the hooks of every block after the current one up to `n`.
+fn run_hooks_to(n: u64) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Identity::on_initialize(next);
+	}
+}
+
+#[test]
+fn expired_known_good_judgements_are_downgraded_to_reasonable() {
+	new_test_ext().execute_with(|| {
+		RevalidationPeriod::set(10);
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert!(Identity::is_verified(&1));
+
+		run_hooks_to(10);
+		assert!(Identity::is_verified(&1));
+
+		// Downgraded rather than dropped, keeping the block it was given in
+		run_hooks_to(11);
+		System::assert_has_event(Event::JudgementDowngraded { who: 1, judgement_id: 0 }.into());
+		assert!(!Identity::is_verified(&1));
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.judgements.to_vec(), vec![(0, (Judgement::Reasonable, 1))]);
+		assert_eq!(registration.known_good, 0);
+		set_display_identity(2);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			2,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(Scores::<Test>::get(1), Scores::<Test>::get(2));
+
+		// No longer sticky, the provider can give it again
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert!(Identity::is_verified(&1));
+		assert_eq!(RevalidationDue::<Test>::iter_keys().collect::<Vec<_>>(), vec![(21, (1, 0))]);
+	});
+}
+
+#[test]
+fn reconfirmed_judgements_expire_a_period_later() {
+	new_test_ext().execute_with(|| {
+		RevalidationPeriod::set(10);
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+
+		run_hooks_to(5);
+		assert_ok!(Identity::reconfirm_judgement(RuntimeOrigin::root(), 0, 1));
+		System::assert_last_event(Event::JudgementReconfirmed { who: 1, judgement_id: 0 }.into());
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 0), Some((Judgement::KnownGood, 5)));
+		assert_eq!(RevalidationDue::<Test>::iter_keys().collect::<Vec<_>>(), vec![(15, (1, 0))]);
+
+		// Not yet due
+		run_hooks_to(14);
+		assert!(Identity::is_verified(&1));
+
+		run_hooks_to(15);
+		assert!(!Identity::is_verified(&1));
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 0), Some((Judgement::Reasonable, 5)));
+		assert_eq!(RevalidationDue::<Test>::iter().count(), 0);
+
+		// Downgraded, it can't be reconfirmed
+		assert_noop!(
+			Identity::reconfirm_judgement(RuntimeOrigin::root(), 0, 1),
+			Error::<Test>::NotKnownGood
+		);
+	});
+}
+
+#[test]
+fn expiry_checks_downgrade_mixed_batches() {
+	new_test_ext().execute_with(|| {
+		RevalidationPeriod::set(10);
+		MaxExpiryChecks::set(2);
+		let give_inline = |who: u64, judgement| {
+			set_display_identity(who);
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				who as u32,
+				who,
+				judgement,
+				None
+			));
+		};
+		// Expires in block 11
+		give_inline(1, Judgement::KnownGood);
+		// Reconfirmed, expires in block 15
+		give_inline(2, Judgement::KnownGood);
+		// Never expires
+		give_inline(3, Judgement::Erroneous);
+		// Cleared before it expires
+		give_inline(4, Judgement::KnownGood);
+		// Expires in block 11, in both storage patterns
+		give_inline(5, Judgement::KnownGood);
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			5,
+			5,
+			Judgement::KnownGood,
+			None
+		));
+		run_hooks_to(5);
+		assert_ok!(Identity::reconfirm_judgement(RuntimeOrigin::root(), 2, 2));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(4)));
+
+		// Three checks are due in block 11, one is left for the next
+		run_hooks_to(11);
+		assert_eq!(RevalidationDue::<Test>::iter_prefix(11).count(), 1);
+		run_hooks_to(12);
+		assert_eq!(RevalidationDue::<Test>::iter_prefix(11).count(), 0);
+
+		System::assert_has_event(Event::JudgementDowngraded { who: 1, judgement_id: 1 }.into());
+		System::assert_has_event(Event::JudgementDowngraded { who: 5, judgement_id: 5 }.into());
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().judgements.to_vec(),
+			vec![(1, (Judgement::Reasonable, 1))]
+		);
+		let registration = Identity::identity_of(&5).unwrap();
+		assert_eq!(registration.judgements.to_vec(), vec![(5, (Judgement::Reasonable, 1))]);
+		assert_eq!(JudgementsDoubleMap::<Test>::get(5, 5), Some((Judgement::Reasonable, 1)));
+		assert_eq!(registration.known_good, 0);
+		assert!(Identity::is_verified(&2));
+		let registration = Identity::identity_of(&3).unwrap();
+		assert_eq!(registration.judgements.to_vec(), vec![(3, (Judgement::Erroneous, 1))]);
+		assert_eq!(registration.erroneous, 1);
+
+		run_hooks_to(15);
+		System::assert_has_event(Event::JudgementDowngraded { who: 2, judgement_id: 2 }.into());
+		assert!((1..=5).all(|who| !Identity::is_verified(&who)));
+	});
+}
+
+#[test]
+fn reconfirm_judgement_needs_a_known_good_judgement() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::reconfirm_judgement(RuntimeOrigin::root(), 0, 1),
+			Error::<Test>::NoIdentity
+		);
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_noop!(
+			Identity::reconfirm_judgement(RuntimeOrigin::root(), 0, 1),
+			Error::<Test>::NotKnownGood
+		);
+		assert_noop!(
+			Identity::reconfirm_judgement(RuntimeOrigin::root(), SystemJudgementId::get(), 1),
+			Error::<Test>::ReservedJudgementId
+		);
+		assert_noop!(
+			Identity::reconfirm_judgement(RuntimeOrigin::signed(1), 0, 1),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn provider_stats_count_the_judgements_given() {
+	use crate::ProviderStatistics;
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(Identity::provider_stats(0), ProviderStatistics::default());
+		set_display_identity(1);
+		set_display_identity(2);
+
+		System::set_block_number(3);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			2,
+			Judgement::KnownGood,
+			None
+		));
+		System::set_block_number(5);
+		assert_ok!(Identity::provide_field_judgement(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			IdentityField::Display,
+			Judgement::Erroneous
+		));
+		// A judgement that fails isn't counted
+		assert_noop!(
+			Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				0,
+				2,
+				Judgement::Reasonable,
+				None
+			),
+			Error::<Test>::StickyJudgement
+		);
+
+		assert_eq!(
+			Identity::provider_stats(0),
+			ProviderStatistics {
+				total: 3,
+				reasonable: 1,
+				known_good: 1,
+				erroneous: 1,
+				last_active: 5,
+				..Default::default()
+			}
+		);
+		assert_eq!(Identity::provider_stats(1), ProviderStatistics::default());
+	});
+}
+
+#[test]
+fn did_document_lists_web_and_email_services() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Identity::did_document(1), None);
+
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			email: b"me@example.com".to_vec().try_into().unwrap(),
+			..Default::default()
+		};
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info));
+		let document = Identity::did_document(1).unwrap();
+		assert_eq!(document.method, b"bench".to_vec());
+		assert_eq!(document.controller, 1);
+		// No service for the empty web field
+		assert_eq!(
+			document.services,
+			vec![DidService {
+				kind: DidServiceKind::Email,
+				endpoint: b"mailto:me@example.com".to_vec(),
+				verified: false,
+			}]
+		);
+
+		assert_ok!(Identity::update_identity_fields(
+			RuntimeOrigin::signed(1),
+			IdentityField::Web.into(),
+			IdentityInfo {
+				web: b"https://example.com".to_vec().try_into().unwrap(),
+				..Default::default()
+			}
+		));
+		assert_ok!(Identity::set_verified_fields(
+			RuntimeOrigin::root(),
+			1,
+			IdentityField::Web.into()
+		));
+		let services = Identity::did_document(1).unwrap().services;
+		assert_eq!(services.len(), 2);
+		assert_eq!(
+			services[0],
+			DidService {
+				kind: DidServiceKind::LinkedDomains,
+				endpoint: b"https://example.com".to_vec(),
+				verified: true,
+			}
+		);
+		assert!(!services[1].verified);
+	});
+}
+
+#[test]
+fn judged_by_pages_through_accounts() {
+	new_test_ext().execute_with(|| {
+		for who in 1..=5 {
+			set_display_identity(who);
+			assert_ok!(Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				0,
+				who,
+				Judgement::Reasonable,
+				None
+			));
+		}
+
+		let mut judged = Vec::new();
+		let mut start_after = None;
+		loop {
+			let page = Identity::judged_by(0, start_after, 2);
+			assert!(page.len() <= 2);
+			let Some(last) = page.last().copied() else { break };
+			judged.extend(page);
+			start_after = Some(last);
+		}
+		judged.sort();
+		assert_eq!(judged, vec![1, 2, 3, 4, 5]);
+	});
+}
+
+/// Give `who` an identity with a `KnownGood` judgement, so it can vouch.
+fn set_trusted_identity(who: u64) {
+	set_display_identity(who);
+	assert_ok!(Identity::provide_judgement_inline(
+		RuntimeOrigin::root(),
+		0,
+		who,
+		Judgement::KnownGood,
+		None
+	));
+}
+
+#[test]
+fn vouch_for_requires_trusted_voucher() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		set_display_identity(2);
+		assert_noop!(Identity::vouch_for(RuntimeOrigin::signed(2), 1), Error::<Test>::NotTrusted);
+		assert_noop!(Identity::vouch_for(RuntimeOrigin::signed(3), 1), Error::<Test>::NotTrusted);
+
+		set_trusted_identity(2);
+		assert_noop!(
+			Identity::vouch_for(RuntimeOrigin::signed(2), 2),
+			Error::<Test>::InvalidTarget
+		);
+		assert_noop!(
+			Identity::vouch_for(RuntimeOrigin::signed(2), 3),
+			Error::<Test>::InvalidTarget
+		);
+
+		assert_ok!(Identity::vouch_for(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::Vouched { voucher: 2, target: 1 }.into());
+		assert_noop!(
+			Identity::vouch_for(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::AlreadyVouched
+		);
+		assert_eq!(Vouches::<Test>::get(1).into_inner(), vec![2]);
+
+		// Clearing the identity drops its vouches
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert!(!Vouches::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn vouches_reaching_threshold_give_system_judgement() {
+	new_test_ext().execute_with(|| {
+		let system_id = SystemJudgementId::get();
+		set_display_identity(1);
+		for who in 2..=5 {
+			set_trusted_identity(who);
+		}
+
+		assert_ok!(Identity::vouch_for(RuntimeOrigin::signed(2), 1));
+		assert!(Identity::identity_of(&1).unwrap().judgements.is_empty());
+
+		assert_ok!(Identity::vouch_for(RuntimeOrigin::signed(3), 1));
+		System::assert_last_event(Event::JudgementGiven { target: 1 }.into());
+		assert_eq!(
+			Identity::identity_of(&1).unwrap().judgements.into_inner(),
+			vec![(system_id, (Judgement::Reasonable, 1))]
+		);
+		assert_eq!(JudgementLog::get().last(), Some(&(1, system_id, Judgement::Reasonable)));
+
+		// Further vouches are recorded up to the bound, without judging again
+		assert_ok!(Identity::vouch_for(RuntimeOrigin::signed(4), 1));
+		System::assert_last_event(Event::Vouched { voucher: 4, target: 1 }.into());
+		assert_noop!(
+			Identity::vouch_for(RuntimeOrigin::signed(5), 1),
+			Error::<Test>::TooManyVouches
+		);
+	});
+}
+
+#[test]
+fn system_judgement_id_is_reserved() {
+	new_test_ext().execute_with(|| {
+		let system_id = SystemJudgementId::get();
+		set_display_identity(1);
+		assert_noop!(
+			Identity::provide_judgement_inline(
+				RuntimeOrigin::root(),
+				system_id,
+				1,
+				Judgement::KnownGood,
+				None
+			),
+			Error::<Test>::ReservedJudgementId
+		);
+		assert_noop!(
+			Identity::provide_judgement_double_map(
+				RuntimeOrigin::root(),
+				system_id,
+				1,
+				Judgement::KnownGood,
+				None
+			),
+			Error::<Test>::ReservedJudgementId
+		);
+		assert_noop!(
+			Identity::provide_judgement_remote(
+				RuntimeOrigin::signed(2000),
+				system_id,
+				1,
+				Judgement::KnownGood,
+				None
+			),
+			Error::<Test>::ReservedJudgementId
+		);
+		assert_noop!(
+			Identity::provide_judgements_inline(
+				RuntimeOrigin::root(),
+				1,
+				vec![(0, Judgement::Reasonable), (system_id, Judgement::KnownGood)]
+					.try_into()
+					.unwrap()
+			),
+			Error::<Test>::ReservedJudgementId
+		);
+		assert_noop!(
+			Identity::set_registrar(RuntimeOrigin::root(), system_id, 5, 50),
+			Error::<Test>::ReservedJudgementId
+		);
+	});
+}
+
+/// Sign a judgement under `judgement_id` with the test key of `signer`, at the current nonce.
+fn sign_judgement(
+	signer: u64,
+	judgement_id: u32,
+	target: u64,
+	judgement: Judgement,
+) -> TestSignature {
+	let nonce = ProviderNonces::<Test>::get(judgement_id);
+	TestSignature(signer, Identity::judgement_payload(&judgement_id, &target, judgement, nonce))
+}
+
+#[test]
+fn set_provider_key_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Identity::set_provider_key(RuntimeOrigin::signed(5), 0, Some(50)),
+			Error::<Test>::UnknownRegistrar
+		);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_noop!(
+			Identity::set_provider_key(RuntimeOrigin::signed(4), 0, Some(50)),
+			Error::<Test>::NotRegistrar
+		);
+
+		assert_ok!(Identity::set_provider_key(RuntimeOrigin::signed(5), 0, Some(50)));
+		System::assert_last_event(Event::ProviderKeySet { judgement_id: 0, key: Some(50) }.into());
+		assert_eq!(ProviderKeys::<Test>::get(0), Some(50));
+		assert_ok!(Identity::set_provider_key(RuntimeOrigin::signed(5), 0, None));
+		assert_eq!(ProviderKeys::<Test>::get(0), None);
+
+		// A new registrar doesn't inherit the key of the previous one
+		assert_ok!(Identity::set_provider_key(RuntimeOrigin::signed(5), 0, Some(50)));
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 4, 50));
+		assert_eq!(ProviderKeys::<Test>::get(0), None);
+	});
+}
+
+#[test]
+fn provide_judgement_with_proof_works() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 50));
+
+		let signature = sign_judgement(50, 0, 1, Judgement::KnownGood);
+		assert_noop!(
+			Identity::provide_judgement_with_proof(
+				RuntimeOrigin::signed(3),
+				0,
+				1,
+				Judgement::KnownGood,
+				signature.clone()
+			),
+			Error::<Test>::NoProviderKey
+		);
+		assert_ok!(Identity::set_provider_key(RuntimeOrigin::signed(5), 0, Some(50)));
+
+		// Any account can submit the judgement
+		assert_ok!(Identity::provide_judgement_with_proof(
+			RuntimeOrigin::signed(3),
+			0,
+			1,
+			Judgement::KnownGood,
+			signature.clone()
+		));
+		System::assert_last_event(Event::JudgementGiven { target: 1 }.into());
+		let registration = Identity::identity_of(&1).unwrap();
+		assert_eq!(registration.judgements.into_inner(), vec![(0, (Judgement::KnownGood, 1))]);
+		assert_eq!(registration.status, VerificationStatus::Verified);
+		// The request is settled with the registrar
+		assert!(!JudgementRequests::<Test>::contains_key(1, 0));
+		assert_eq!(AccruedFees::<Test>::get(5), 50);
+		assert_eq!(ProviderNonces::<Test>::get(0), 1);
+
+		// The signature can't be replayed
+		assert_noop!(
+			Identity::provide_judgement_with_proof(
+				RuntimeOrigin::signed(3),
+				0,
+				1,
+				Judgement::KnownGood,
+				signature
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn provide_judgement_with_proof_rejects_invalid_signatures() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_ok!(Identity::set_provider_key(RuntimeOrigin::signed(5), 0, Some(50)));
+
+		// Signed by another key
+		assert_noop!(
+			Identity::provide_judgement_with_proof(
+				RuntimeOrigin::signed(3),
+				0,
+				1,
+				Judgement::KnownGood,
+				sign_judgement(51, 0, 1, Judgement::KnownGood)
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// Signed for another judgement, target or judgement ID
+		for (judgement_id, target, judgement) in [
+			(0, 1, Judgement::Reasonable),
+			(0, 2, Judgement::KnownGood),
+			(1, 1, Judgement::KnownGood),
+		] {
+			assert_noop!(
+				Identity::provide_judgement_with_proof(
+					RuntimeOrigin::signed(3),
+					0,
+					1,
+					Judgement::KnownGood,
+					sign_judgement(50, judgement_id, target, judgement)
+				),
+				Error::<Test>::InvalidSignature
+			);
+		}
+		assert_eq!(ProviderNonces::<Test>::get(0), 0);
+	});
+}
+
+#[test]
+fn rotated_provider_key_is_valid_for_grace_period() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		set_display_identity(2);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_noop!(
+			Identity::rotate_provider_key(RuntimeOrigin::signed(5), 0, 51),
+			Error::<Test>::NoProviderKey
+		);
+		assert_ok!(Identity::set_provider_key(RuntimeOrigin::signed(5), 0, Some(50)));
+		assert_noop!(
+			Identity::rotate_provider_key(RuntimeOrigin::signed(4), 0, 51),
+			Error::<Test>::NotRegistrar
+		);
+
+		assert_ok!(Identity::rotate_provider_key(RuntimeOrigin::signed(5), 0, 51));
+		System::assert_last_event(
+			Event::ProviderKeyRotated { judgement_id: 0, key: 51, previous_valid_until: 11 }.into(),
+		);
+		assert_eq!(ProviderKeys::<Test>::get(0), Some(51));
+		assert_eq!(PreviousProviderKeys::<Test>::get(0), Some((50, 11)));
+
+		// Both keys are accepted during the grace period
+		let signature = sign_judgement(50, 0, 1, Judgement::Reasonable);
+		assert_ok!(Identity::provide_judgement_with_proof(
+			RuntimeOrigin::signed(3),
+			0,
+			1,
+			Judgement::Reasonable,
+			signature
+		));
+		let signature = sign_judgement(51, 0, 2, Judgement::Reasonable);
+		assert_ok!(Identity::provide_judgement_with_proof(
+			RuntimeOrigin::signed(3),
+			0,
+			2,
+			Judgement::Reasonable,
+			signature
+		));
+
+		// After it only the new one, and the judgements already given stand
+		System::set_block_number(11);
+		assert_noop!(
+			Identity::provide_judgement_with_proof(
+				RuntimeOrigin::signed(3),
+				0,
+				1,
+				Judgement::KnownGood,
+				sign_judgement(50, 0, 1, Judgement::KnownGood)
+			),
+			Error::<Test>::InvalidSignature
+		);
+		assert_ok!(Identity::provide_judgement_with_proof(
+			RuntimeOrigin::signed(3),
+			0,
+			1,
+			Judgement::KnownGood,
+			sign_judgement(51, 0, 1, Judgement::KnownGood)
+		));
+		assert_eq!(
+			Identity::identity_of(&2).unwrap().judgements.into_inner(),
+			vec![(0, (Judgement::Reasonable, 1))]
+		);
+	});
+}
+
+#[test]
+fn set_provider_key_revokes_rotated_key() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_ok!(Identity::set_provider_key(RuntimeOrigin::signed(5), 0, Some(50)));
+		assert_ok!(Identity::rotate_provider_key(RuntimeOrigin::signed(5), 0, 51));
+
+		// The rotated out key was compromised
+		assert_ok!(Identity::set_provider_key(RuntimeOrigin::signed(5), 0, Some(52)));
+		assert_eq!(PreviousProviderKeys::<Test>::get(0), None);
+		assert_noop!(
+			Identity::provide_judgement_with_proof(
+				RuntimeOrigin::signed(3),
+				0,
+				1,
+				Judgement::KnownGood,
+				sign_judgement(50, 0, 1, Judgement::KnownGood)
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn pause_and_unpause_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Identity::pause(RuntimeOrigin::signed(1)), DispatchError::BadOrigin);
+		assert_noop!(Identity::unpause(RuntimeOrigin::root()), Error::<Test>::NotPaused);
+
+		assert_ok!(Identity::pause(RuntimeOrigin::root()));
+		System::assert_last_event(Event::Paused.into());
+		assert!(Paused::<Test>::get());
+		assert_noop!(Identity::pause(RuntimeOrigin::root()), Error::<Test>::Paused);
+		assert_noop!(
+			Identity::set_identity(RuntimeOrigin::signed(1), empty_info()),
+			Error::<Test>::Paused
+		);
+		// The other instance is independent
+		assert_ok!(OrgIdentity::set_identity(RuntimeOrigin::signed(1), empty_info()));
+
+		// Existing identities can still be cleared
+		assert_ok!(Identity::unpause(RuntimeOrigin::root()));
+		set_display_identity(2);
+		assert_ok!(Identity::pause(RuntimeOrigin::root()));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+
+		assert_noop!(Identity::unpause(RuntimeOrigin::signed(1)), DispatchError::BadOrigin);
+		assert_ok!(Identity::unpause(RuntimeOrigin::root()));
+		System::assert_last_event(Event::Unpaused.into());
+		assert!(!Paused::<Test>::get());
+		set_display_identity(1);
+	});
+}
+
+#[test]
+fn calls_are_categorized_for_proxies() {
+	use crate::Call;
+	// What a runtime's `InstanceFilter` allows an identity manager proxy
+	let identity_manager =
+		|call: &Call<Test>| Identity::categorize(call) == CallCategory::IdentityManagement;
+
+	assert!(identity_manager(&Call::set_identity { info: Default::default() }));
+	assert!(identity_manager(&Call::clear_identity {}));
+	assert!(identity_manager(&Call::dispute_judgement { judgement_id: 0 }));
+	assert!(!identity_manager(&Call::provide_judgement_inline {
+		judgement_id: 0,
+		target: 1,
+		judgement: Judgement::KnownGood,
+		note: None,
+	}));
+	assert_eq!(Identity::categorize(&Call::claim_fees {}), CallCategory::Judgement);
+	assert_eq!(
+		Identity::categorize(&Call::register_provider { judgement_id: 0, fee: 10, bond: 100 }),
+		CallCategory::Judgement
+	);
+	assert!(identity_manager(&Call::set_kyc_hash { kyc_hash: None }));
+	assert!(identity_manager(&Call::set_preferred_registrars { registrars: Default::default() }));
+	assert_eq!(
+		Identity::categorize(&Call::attest_kyc {
+			judgement_id: 0,
+			target: 1,
+			kyc_hash: Default::default()
+		}),
+		CallCategory::Judgement
+	);
+	assert_eq!(Identity::categorize(&Call::pause {}), CallCategory::Admin);
+}
+
+/// Whether `call` is blocked while the pallet is paused. Exhaustive, so that every new call has
+/// to be classified.
+fn blocked_while_paused(call: &crate::Call<Test>) -> bool {
+	use crate::Call;
+	match call {
+		Call::set_identity { .. } |
+		Call::set_identity_legacy { .. } |
+		Call::set_identity_with_judgement_request { .. } |
+		Call::update_identity_fields { .. } |
+		Call::claim_identity { .. } |
+		Call::request_judgement { .. } |
+		Call::provide_judgement_inline { .. } |
+		Call::provide_judgement_double_map { .. } |
+		Call::provide_judgement_remote { .. } |
+		Call::provide_judgements_inline { .. } |
+		Call::provide_judgement_with_proof { .. } |
+		Call::set_verified_fields { .. } |
+		Call::provide_field_judgement { .. } |
+		Call::mirror_remote_identity { .. } |
+		Call::request_audit { .. } |
+		Call::vouch_for { .. } |
+		Call::register_provider { .. } |
+		Call::set_kyc_hash { .. } |
+		Call::attest_kyc { .. } |
+		Call::provide_judgement_with_reason { .. } |
+		Call::reconfirm_judgement { .. } |
+		Call::set_preferred_registrars { .. } => true,
+		// Already paused
+		Call::pause { .. } => true,
+		Call::clear_identity { .. } |
+		Call::clear_judgements { .. } |
+		Call::clear_field { .. } |
+		Call::withdraw_deposit { .. } |
+		Call::claim_fees { .. } |
+		Call::dispute_judgement { .. } |
+		Call::resolve_dispute { .. } |
+		Call::set_beneficiary { .. } |
+		Call::set_judgement_limit { .. } |
+		Call::reconcile_deposits { .. } |
+		Call::consolidate_judgements { .. } |
+		Call::force_recount_judgements { .. } |
+		Call::audit_identity { .. } |
+		Call::set_registrar { .. } |
+		Call::redact_fields { .. } |
+		Call::set_deposit_exemption { .. } |
+		Call::reap_tombstone { .. } |
+		Call::poke_deposit { .. } |
+		Call::renew_identity { .. } |
+		Call::expire_identity { .. } |
+		Call::reap_identity { .. } |
+		Call::pay_rent { .. } |
+		Call::reap_delinquent_identity { .. } |
+		Call::set_provider_key { .. } |
+		Call::rotate_provider_key { .. } |
+		Call::top_up_provider_bond { .. } |
+		Call::withdraw_provider_bond { .. } |
+		Call::unpause { .. } => false,
+		#[cfg(feature = "runtime-benchmarks")]
+		Call::populate_identities { .. } => false,
+		Call::__Ignore(..) => unreachable!(),
+	}
+}
+
+#[test]
+fn pause_filters_every_call() {
+	use crate::Call;
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 50));
+		assert_ok!(Identity::pause(RuntimeOrigin::root()));
+
+		let info = empty_info;
+		let signed = RuntimeOrigin::signed;
+		let root = RuntimeOrigin::root;
+		let calls: Vec<(Call<Test>, RuntimeOrigin)> = vec![
+			(Call::set_identity { info: info() }, signed(2)),
+			(
+				Call::update_identity_fields { fields: IdentityField::Web.into(), info: info() },
+				signed(1),
+			),
+			(
+				Call::provide_judgement_inline {
+					judgement_id: 0,
+					target: 1,
+					judgement: Judgement::Reasonable,
+					note: None,
+				},
+				root(),
+			),
+			(
+				Call::provide_judgement_double_map {
+					judgement_id: 0,
+					target: 1,
+					judgement: Judgement::Reasonable,
+					note: None,
+				},
+				root(),
+			),
+			(Call::clear_identity {}, signed(1)),
+			(
+				Call::set_identity_legacy {
+					display: Default::default(),
+					legal: Default::default(),
+					web: Default::default(),
+					email: Default::default(),
+				},
+				signed(2),
+			),
+			(Call::set_judgement_limit { limit: 10 }, root()),
+			(Call::reconcile_deposits { limit: 10 }, signed(2)),
+			(
+				Call::provide_judgement_remote {
+					judgement_id: 0,
+					target: 1,
+					judgement: Judgement::Reasonable,
+					note: None,
+				},
+				signed(2000),
+			),
+			(Call::consolidate_judgements { target: 1 }, signed(1)),
+			(Call::force_recount_judgements { target: 1 }, root()),
+			(Call::set_registrar { judgement_id: 1, account: 4, fee: 50 }, root()),
+			(Call::request_judgement { judgement_id: 0, max_fee: 50 }, signed(1)),
+			(
+				Call::set_identity_with_judgement_request {
+					info: info(),
+					judgement_id: 0,
+					max_fee: 50,
+				},
+				signed(2),
+			),
+			(
+				Call::provide_judgements_inline {
+					target: 1,
+					items: vec![(0, Judgement::Reasonable)].try_into().unwrap(),
+				},
+				root(),
+			),
+			(Call::clear_judgements {}, signed(1)),
+			(Call::dispute_judgement { judgement_id: 0 }, signed(1)),
+			(Call::resolve_dispute { target: 1, judgement_id: 0, uphold: true }, root()),
+			(Call::withdraw_deposit {}, signed(1)),
+			(Call::clear_field { field: IdentityField::Display }, signed(1)),
+			(Call::claim_fees {}, signed(5)),
+			(Call::set_beneficiary { beneficiary: 2 }, signed(1)),
+			(Call::claim_identity { original: 1 }, signed(2)),
+			(Call::vouch_for { target: 1 }, signed(2)),
+			(Call::set_provider_key { judgement_id: 0, key: Some(50) }, signed(5)),
+			(Call::rotate_provider_key { judgement_id: 0, key: 51 }, signed(5)),
+			(
+				Call::provide_judgement_with_proof {
+					judgement_id: 0,
+					target: 1,
+					judgement: Judgement::Reasonable,
+					signature: TestSignature(50, vec![]),
+				},
+				signed(3),
+			),
+			(Call::pause {}, root()),
+			(Call::unpause {}, root()),
+			(
+				Call::set_verified_fields { target: 1, fields: IdentityField::Display.into() },
+				root(),
+			),
+			(
+				Call::provide_field_judgement {
+					judgement_id: 0,
+					target: 1,
+					field: IdentityField::Display,
+					judgement: Judgement::Reasonable,
+				},
+				root(),
+			),
+			(Call::mirror_remote_identity { who: 1, proof: StorageProof::empty() }, signed(2)),
+			(Call::request_audit { target: 1 }, root()),
+			(Call::set_deposit_exemption { who: 1, exempt: true }, root()),
+			(Call::reap_tombstone { who: 1 }, signed(2)),
+			(Call::audit_identity { target: 1, repair: false }, signed(2)),
+			(Call::poke_deposit {}, signed(1)),
+			(Call::renew_identity {}, signed(1)),
+			(Call::expire_identity { who: 1 }, signed(2)),
+			(Call::reap_identity { who: 1 }, signed(2)),
+			(Call::pay_rent {}, signed(1)),
+			(Call::reap_delinquent_identity { who: 1 }, signed(2)),
+			(Call::register_provider { judgement_id: 2, fee: 10, bond: 100 }, signed(3)),
+			(Call::top_up_provider_bond { judgement_id: 0, amount: 10 }, signed(5)),
+			(Call::withdraw_provider_bond { judgement_id: 0, amount: 10 }, signed(5)),
+			(Call::set_kyc_hash { kyc_hash: None }, signed(1)),
+			(Call::attest_kyc { judgement_id: 0, target: 1, kyc_hash: Default::default() }, root()),
+			(Call::redact_fields { target: 1, fields: IdentityFields::all() }, root()),
+			(
+				Call::provide_judgement_with_reason {
+					judgement_id: 0,
+					target: 1,
+					judgement: Judgement::LowQuality,
+					reason: ReasonCode::DataMismatch,
+					note: None,
+				},
+				root(),
+			),
+			(Call::reconfirm_judgement { judgement_id: 0, target: 1 }, root()),
+			(Call::set_preferred_registrars { registrars: Default::default() }, signed(1)),
+		];
+		#[cfg(feature = "runtime-benchmarks")]
+		let calls = [calls, vec![(Call::populate_identities { start: 0, count: 1 }, root())]].concat();
+		assert_eq!(calls.len(), Call::<Test>::get_call_names().len());
+
+		for (call, origin) in calls {
+			let name = call.get_call_name();
+			let blocked = blocked_while_paused(&call);
+			// Dispatched in a transaction of its own, so that allowed calls don't affect the rest
+			let result = frame_support::storage::with_transaction(|| {
+				let result = call.dispatch_bypass_filter(origin);
+				sp_runtime::TransactionOutcome::Rollback(Ok::<_, DispatchError>(result))
+			})
+			.unwrap();
+			let paused = result.is_err_and(|e| e.error == Error::<Test>::Paused.into());
+			assert_eq!(paused, blocked, "`{name}` is {}blocked", if paused { "" } else { "not " });
+		}
+	});
+}
+#[test]
+fn integrity_test_checks_registration_size_budget() {
+	new_test_ext().execute_with(|| {
+		let size = crate::Registration::<Test>::max_encoded_len() as u32;
+		MaxRegistrationSize::set(size);
+		<Identity as Hooks<u64>>::integrity_test();
+	});
+}
+
+#[test]
+#[should_panic(expected = "bytes of `MaxRegistrationSize`")]
+fn integrity_test_fails_over_registration_size_budget() {
+	new_test_ext().execute_with(|| {
+		let size = crate::Registration::<Test>::max_encoded_len() as u32;
+		MaxRegistrationSize::set(size - 1);
+		<Identity as Hooks<u64>>::integrity_test();
+	});
+}
+
+#[test]
+fn identities_are_searched_by_display_prefix() {
+	new_test_ext().execute_with(|| {
+		DisplayIndexLength::set(3);
+		let set_display = |who: u64, display: &[u8]| {
+			let info =
+				IdentityInfo { display: display.to_vec().try_into().unwrap(), ..empty_info() };
+			assert_ok!(Identity::set_identity(RuntimeOrigin::signed(who), info));
+		};
+		set_display(1, b"Alice");
+		set_display(2, b"alicia");
+		set_display(3, b"Bob");
+		set_display(4, b"Al");
+
+		let search = |prefix: &[u8], limit| {
+			let mut found = Identity::search_display(prefix.to_vec(), None, limit);
+			found.sort();
+			found
+		};
+		assert_eq!(search(b"ali", 10), vec![1, 2]);
+		assert_eq!(search(b"ALIC", 10), vec![1, 2]);
+		assert_eq!(search(b"alice", 10), vec![1]);
+		assert_eq!(search(b"bob", 10), vec![3]);
+		// Shorter than the index, only display names as short are found
+		assert_eq!(search(b"al", 10), vec![4]);
+		assert_eq!(search(b"", 10), Vec::<u64>::new());
+
+		// Paginated
+		let first = Identity::search_display(b"ali".to_vec(), None, 1);
+		assert_eq!(first.len(), 1);
+		let second = Identity::search_display(b"ali".to_vec(), first.last().copied(), 1);
+		assert_eq!(second.len(), 1);
+		assert_ne!(first, second);
+		assert!(Identity::search_display(b"ali".to_vec(), second.last().copied(), 1).is_empty());
+
+		// Kept up with changes and clears of the display name
+		set_display(2, b"Bobby");
+		assert_eq!(search(b"ali", 10), vec![1]);
+		assert_eq!(search(b"bob", 10), vec![2, 3]);
+		assert_ok!(Identity::clear_field(RuntimeOrigin::signed(3), IdentityField::Display));
+		assert_eq!(search(b"bob", 10), vec![2]);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		assert!(search(b"bob", 10).is_empty());
+	});
+}
+
+#[test]
+fn expired_identities_are_reaped_after_the_grace_period() {
+	new_test_ext().execute_with(|| {
+		// Without a lifetime, identities never expire
+		set_display_identity(1);
+		assert_eq!(IdentityExpiries::<Test>::get(1), None);
+		assert_noop!(
+			Identity::expire_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::IdentityNotExpired
+		);
+
+		IdentityLifetime::set(10);
+		set_display_identity(1);
+		assert_eq!(IdentityExpiries::<Test>::get(1), Some(IdentityExpiry::Expires(11)));
+		let reserved = Balances::reserved_balance(1);
+
+		System::set_block_number(10);
+		assert_noop!(
+			Identity::expire_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::IdentityNotExpired
+		);
+		assert_noop!(
+			Identity::reap_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::GracePeriodNotOver
+		);
+
+		System::set_block_number(12);
+		assert_ok!(Identity::expire_identity(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::IdentityExpired { who: 1 }.into());
+		assert_eq!(IdentityExpiries::<Test>::get(1), Some(IdentityExpiry::Expired(12)));
+		assert_noop!(
+			Identity::expire_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::IdentityNotExpired
+		);
+
+		// The grace period counts from when the identity was marked expired
+		System::set_block_number(21);
+		assert_noop!(
+			Identity::reap_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::GracePeriodNotOver
+		);
+
+		System::set_block_number(22);
+		assert_ok!(Identity::reap_identity(RuntimeOrigin::signed(2), 1));
+		System::assert_last_event(Event::IdentityReaped { who: 1, deposit: reserved }.into());
+		assert_eq!(Identity::identity_of(&1), None);
+		assert_eq!(IdentityExpiries::<Test>::get(1), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn expired_identities_can_be_renewed_during_the_grace_period() {
+	new_test_ext().execute_with(|| {
+		IdentityLifetime::set(10);
+		assert_noop!(Identity::renew_identity(RuntimeOrigin::signed(1)), Error::<Test>::NoIdentity);
+		set_display_identity(1);
+
+		System::set_block_number(11);
+		assert_ok!(Identity::expire_identity(RuntimeOrigin::signed(2), 1));
+		System::set_block_number(15);
+		assert_ok!(Identity::renew_identity(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::IdentityRenewed { who: 1, expires_at: Some(25) }.into());
+		assert_eq!(IdentityExpiries::<Test>::get(1), Some(IdentityExpiry::Expires(25)));
+
+		// Renewed, the identity can't be reaped, nor marked expired again before its new expiry
+		System::set_block_number(24);
+		assert_noop!(
+			Identity::reap_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::GracePeriodNotOver
+		);
+		assert_noop!(
+			Identity::expire_identity(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::IdentityNotExpired
+		);
+
+		// Setting the identity again renews it as well
+		System::set_block_number(25);
+		assert_ok!(Identity::expire_identity(RuntimeOrigin::signed(2), 1));
+		set_display_identity(1);
+		assert_eq!(IdentityExpiries::<Test>::get(1), Some(IdentityExpiry::Expires(35)));
+	});
+}
+
+/// Leave `who` exactly the existential deposit, too little to pay any rent.
+fn drain(who: u64) {
+	let free = Balances::free_balance(who);
+	assert_ok!(Balances::transfer_keep_alive(RuntimeOrigin::signed(who), 5, free - 500));
+}
+
+#[test]
+fn identities_pay_rent_in_place_of_deposits() {
+	new_test_ext().execute_with(|| {
+		IdentityRent::set(3);
+		set_display_identity(1);
+		System::assert_has_event(Event::RentPaid { who: 1, amount: 3, paid_until: 11 }.into());
+		assert_eq!(Identity::identity_of(&1).unwrap().deposit, 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 997);
+		assert_eq!(CollectedFees::get(), 3);
+		assert_eq!(RentStates::<Test>::get(1), Some(RentState::PaidUntil(11)));
+
+		// Nothing is due again before the period is over
+		set_display_identity(1);
+		assert_eq!(CollectedFees::get(), 3);
+		assert_noop!(Identity::pay_rent(RuntimeOrigin::signed(1)), Error::<Test>::NoRentDue);
+		assert_noop!(Identity::pay_rent(RuntimeOrigin::signed(2)), Error::<Test>::NoRentDue);
+
+		// Then it's collected as it falls due
+		run_hooks_to(10);
+		assert_eq!(CollectedFees::get(), 3);
+		run_hooks_to(11);
+		System::assert_last_event(Event::RentPaid { who: 1, amount: 3, paid_until: 21 }.into());
+		assert_eq!(RentStates::<Test>::get(1), Some(RentState::PaidUntil(21)));
+		assert_eq!(Balances::free_balance(1), 994);
+
+		// Clearing the identity stops the rent
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(1)));
+		assert_eq!(RentStates::<Test>::get(1), None);
+		assert_eq!(RentDue::<Test>::iter_prefix(21).count(), 0);
+		run_hooks_to(21);
+		assert_eq!(Balances::free_balance(1), 994);
+	});
+}
+
+#[test]
+fn delinquent_identities_are_reaped_after_the_grace_period() {
+	new_test_ext().execute_with(|| {
+		IdentityRent::set(3);
+		set_display_identity(1);
+		set_display_identity(2);
+		drain(1);
+
+		run_hooks_to(11);
+		System::assert_has_event(Event::IdentityDelinquent { who: 1 }.into());
+		assert_eq!(RentStates::<Test>::get(1), Some(RentState::Delinquent(11)));
+		assert_eq!(RentStates::<Test>::get(2), Some(RentState::PaidUntil(21)));
+
+		// Paying identities are never reaped, and delinquent ones only after the grace period
+		assert_noop!(
+			Identity::reap_delinquent_identity(RuntimeOrigin::signed(3), 2),
+			Error::<Test>::GracePeriodNotOver
+		);
+		System::set_block_number(20);
+		assert_noop!(
+			Identity::reap_delinquent_identity(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::GracePeriodNotOver
+		);
+
+		System::set_block_number(21);
+		assert_ok!(Identity::reap_delinquent_identity(RuntimeOrigin::signed(3), 1));
+		System::assert_last_event(Event::IdentityReaped { who: 1, deposit: 0 }.into());
+		assert_eq!(Identity::identity_of(&1), None);
+		assert_eq!(RentStates::<Test>::get(1), None);
+		assert_eq!(Balances::free_balance(1), 500);
+	});
+}
+
+#[test]
+fn delinquent_identities_pay_every_period_owed() {
+	new_test_ext().execute_with(|| {
+		IdentityRent::set(3);
+		set_display_identity(1);
+		drain(1);
+		run_hooks_to(11);
+		assert_eq!(RentStates::<Test>::get(1), Some(RentState::Delinquent(11)));
+
+		// Delinquent identities are no longer collected from
+		assert_ok!(Balances::transfer_keep_alive(RuntimeOrigin::signed(2), 1, 100));
+		run_hooks_to(25);
+		assert_eq!(Balances::free_balance(1), 600);
+
+		// Paying up charges the periods from the one that fell due in block 11 to the current one
+		assert_ok!(Identity::pay_rent(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::RentPaid { who: 1, amount: 6, paid_until: 31 }.into());
+		assert_eq!(RentStates::<Test>::get(1), Some(RentState::PaidUntil(31)));
+		assert_noop!(Identity::pay_rent(RuntimeOrigin::signed(1)), Error::<Test>::NoRentDue);
+		assert_noop!(
+			Identity::reap_delinquent_identity(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::GracePeriodNotOver
+		);
+
+		// And the rent is collected again as it falls due
+		run_hooks_to(31);
+		assert_eq!(RentStates::<Test>::get(1), Some(RentState::PaidUntil(41)));
+		assert_eq!(Balances::free_balance(1), 591);
+	});
+}
+
+#[test]
+fn reconciling_deposits_switches_identities_to_rent() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		let deposit = Identity::identity_of(&1).unwrap().deposit;
+		assert_eq!(RentStates::<Test>::get(1), None);
+
+		IdentityRent::set(3);
+		assert_ok!(Identity::reconcile_deposits(RuntimeOrigin::root(), 10));
+		System::assert_has_event(Event::RentPaid { who: 1, amount: 3, paid_until: 11 }.into());
+		System::assert_has_event(
+			Event::DepositReconciled { who: 1, old_deposit: deposit, new_deposit: 0 }.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(RentStates::<Test>::get(1), Some(RentState::PaidUntil(11)));
+
+		// Exempt accounts pay no rent
+		assert_ok!(Identity::set_deposit_exemption(RuntimeOrigin::root(), 2, true));
+		set_display_identity(2);
+		assert_eq!(RentStates::<Test>::get(2), None);
+		assert_noop!(Identity::pay_rent(RuntimeOrigin::signed(2)), Error::<Test>::NoRentDue);
+	});
+}
+
+#[test]
+fn total_deposits_follow_every_hold_release_and_slash() {
+	new_test_ext().execute_with(|| {
+		let check = || {
+			let reserved: u128 = (1..=5).map(Balances::reserved_balance).sum();
+			assert_eq!(TotalDeposits::<Test>::get(), reserved);
+			assert_ok!(Identity::do_try_state());
+		};
+		check();
+
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 0, 5, 10));
+		set_display_identity(1);
+		set_display_identity(2);
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 0, 10));
+		check();
+
+		// The fee moves over to the registrar, and the note is held for
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Erroneous,
+			note(b"no")
+		));
+		check();
+
+		assert_ok!(Identity::dispute_judgement(RuntimeOrigin::signed(1), 0));
+		check();
+		assert_ok!(Identity::resolve_dispute(RuntimeOrigin::root(), 1, 0, true));
+		check();
+
+		assert_ok!(Identity::claim_fees(RuntimeOrigin::signed(5)));
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		check();
+
+		TotalDeposits::<Test>::mutate(|total| *total += 1);
+		assert!(Identity::do_try_state().is_err());
+	});
+}
+
+#[test]
+fn update_identity_fields_replaces_only_the_given_fields() {
+	new_test_ext().execute_with(|| {
+		let info = IdentityInfo {
+			display: b"display".to_vec().try_into().unwrap(),
+			legal: BoundedVec::default(),
+			web: b"web".to_vec().try_into().unwrap(),
+			email: b"email".to_vec().try_into().unwrap(),
+			avatar: None,
+		};
+		let update = IdentityInfo {
+			display: b"ignored".to_vec().try_into().unwrap(),
+			email: b"much longer email".to_vec().try_into().unwrap(),
+			..empty_info()
+		};
+		let email = IdentityFields::from(IdentityField::Email);
+		assert_noop!(
+			Identity::update_identity_fields(RuntimeOrigin::signed(1), email, update.clone()),
+			Error::<Test>::NoIdentity
+		);
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(1), info.clone()));
+		let verified = IdentityFields::from(IdentityField::Display) | IdentityField::Email;
+		assert_ok!(Identity::set_verified_fields(RuntimeOrigin::root(), 1, verified));
+
+		let invalid = IdentityFields::decode(&mut &[0b10_0000u8][..]).unwrap();
+		assert_noop!(
+			Identity::update_identity_fields(RuntimeOrigin::signed(1), invalid, update.clone()),
+			Error::<Test>::InvalidFields
+		);
+
+		assert_ok!(Identity::update_identity_fields(RuntimeOrigin::signed(1), email, update));
+		let registration = Identity::identity_of(&1).unwrap();
+		let expected =
+			IdentityInfo { email: b"much longer email".to_vec().try_into().unwrap(), ..info };
+		assert_eq!(registration.info, expected);
+		// Only the replaced field is no longer verified
+		assert_eq!(registration.verified_fields, IdentityField::Display.into());
+
+		// The same as setting the whole identity
+		assert_ok!(Identity::set_identity(RuntimeOrigin::signed(2), expected.clone()));
+		let set = Identity::identity_of(&2).unwrap();
+		assert_eq!(registration.field_bytes, set.field_bytes);
+		assert_eq!(registration.deposit, set.deposit);
+		assert_eq!(Balances::reserved_balance(1), Balances::reserved_balance(2));
+		System::assert_last_event(
+			Event::IdentitySet { who: 2, display: expected.display, bytes: 27 }.into(),
+		);
+	});
+}
+
+#[test]
+fn verified_senders_are_prioritized() {
+	use crate::priority::PrioritizeVerified;
+	use frame_support::{dispatch::DispatchInfo, traits::ConstU64};
+	use sp_runtime::{
+		traits::{TransactionExtension, TxBaseImplication},
+		transaction_validity::TransactionSource::External,
+	};
+
+	type Prioritize = PrioritizeVerified<Test, Identity, ConstU64<100>>;
+	let priority_of = |origin: RuntimeOrigin| {
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let info = DispatchInfo::default();
+		let (valid, _, _) = Prioritize::new()
+			.validate(origin, &call, &info, 0, (), &TxBaseImplication(&call), External)
+			.unwrap();
+		valid.priority
+	};
+
+	new_test_ext().execute_with(|| {
+		// Neither an unsigned transaction nor one without an identity is boosted
+		assert_eq!(priority_of(RuntimeOrigin::none()), 0);
+		assert_eq!(priority_of(RuntimeOrigin::signed(1)), 0);
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			None
+		));
+		assert_eq!(priority_of(RuntimeOrigin::signed(1)), 0);
+
+		// Any `KnownGood` judgement is enough, even short of `VerificationThreshold`
+		VerificationThreshold::set(2);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_eq!(Identity::verification_status(&1), VerificationStatus::PartiallyVerified);
+		assert_eq!(priority_of(RuntimeOrigin::signed(1)), 100);
+		assert_eq!(priority_of(RuntimeOrigin::signed(2)), 0);
+
+		// The signer's registration is read at up to its maximum size
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let size = crate::Registration::<Test>::max_encoded_len() as u64;
+		assert_eq!(Prioritize::new().weight(&call).proof_size(), size);
+	});
+}
+
+#[test]
+fn priority_boost_adds_up_and_saturates() {
+	use crate::priority::PrioritizeVerified;
+	use frame_support::traits::ConstU64;
+	use sp_runtime::transaction_validity::ValidTransaction;
+
+	type Prioritize = PrioritizeVerified<Test, Identity, ConstU64<100>>;
+	type PrioritizeMax = PrioritizeVerified<Test, Identity, ConstU64<{ u64::MAX }>>;
+	assert_eq!(Prioritize::boost(VerificationStatus::Unverified), 0);
+	assert_eq!(Prioritize::boost(VerificationStatus::PartiallyVerified), 100);
+	assert_eq!(Prioritize::boost(VerificationStatus::Verified), 100);
+
+	// The boost is added to the priority the other extensions give, e.g. for a tip
+	let boosted = |boost, tipped| {
+		ValidTransaction { priority: tipped, ..Default::default() }
+			.combine_with(ValidTransaction { priority: boost, ..Default::default() })
+			.priority
+	};
+	assert_eq!(boosted(Prioritize::boost(VerificationStatus::Verified), 50), 150);
+	assert_eq!(boosted(Prioritize::boost(VerificationStatus::Unverified), 50), 50);
+	assert_eq!(boosted(PrioritizeMax::boost(VerificationStatus::Verified), 50), u64::MAX);
+}
+
+#[test]
+fn identity_of_a_killed_account_is_removed() {
+	use frame_support::traits::OnKilledAccount;
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_inline(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::Reasonable,
+			note(b"inline")
+		));
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			1,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		assert_ok!(Identity::set_registrar(RuntimeOrigin::root(), 2, 5, 50));
+		assert_ok!(Identity::request_judgement(RuntimeOrigin::signed(1), 2, 50));
+		assert_ok!(OrgIdentity::set_identity(RuntimeOrigin::signed(1), empty_info()));
+		let deposit = Identity::identity_of(&1).unwrap().total_deposit() + 50;
+		// A cleared identity whose deposit is still to be released
+		DepositReleaseDelay::set(10);
+		set_display_identity(2);
+		assert_ok!(Identity::clear_identity(RuntimeOrigin::signed(2)));
+		let pending = PendingReleases::<Test>::get(2).unwrap().amount;
+
+		// The accounts die with their references forcibly dropped, as frame_system reports
+		<Test as frame_system::Config>::OnKilledAccount::on_killed_account(&1);
+		<Test as frame_system::Config>::OnKilledAccount::on_killed_account(&2);
+
+		assert!(Identity::identity_of(&1).is_none());
+		assert!(OrgIdentity::identity_of(&1).is_none());
+		assert_eq!(JudgementsDoubleMap::<Test>::get(1, 1), None);
+		assert_eq!(CounterForJudgements::<Test>::get(1), 0);
+		assert!(!JudgedBy::<Test>::contains_key(0, 1));
+		assert!(!JudgedBy::<Test>::contains_key(1, 1));
+		assert_eq!(JudgementNotes::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(JudgementRequests::<Test>::get(1, 2), None);
+		assert_eq!(PendingReleases::<Test>::get(2), None);
+		System::assert_has_event(Event::IdentityKilled { who: 1, deposit }.into());
+		System::assert_last_event(Event::IdentityKilled { who: 2, deposit: pending }.into());
+		// What they held is no longer counted, only the registrar's bond is
+		assert_eq!(TotalDeposits::<Test>::get(), RegistrarBond::get());
+		assert_ok!(Identity::do_try_state());
+
+		// An account without an identity dies silently
+		let events = System::events().len();
+		<Test as frame_system::Config>::OnKilledAccount::on_killed_account(&3);
+		assert_eq!(System::events().len(), events);
+	});
+}
+
+#[test]
+fn identity_of_a_killed_account_is_kept_for_its_beneficiary() {
+	use frame_support::traits::OnKilledAccount;
+
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		assert_ok!(Identity::set_beneficiary(RuntimeOrigin::signed(1), 2));
+		let registration = Identity::identity_of(&1).unwrap();
+		let deposit = registration.total_deposit();
+
+		// The account dies with its references forcibly dropped, and its balance with it
+		frame_system::Account::<Test>::remove(1);
+		<Test as frame_system::Config>::OnKilledAccount::on_killed_account(&1);
+		assert_eq!(Identity::identity_of(&1), Some(registration.clone()));
+		assert!(Beneficiaries::<Test>::contains_key(1));
+		assert_ok!(Identity::do_try_state());
+
+		// The beneficiary needn't wait for the account to be inactive, and holds the deposit
+		assert_ok!(Identity::claim_identity(RuntimeOrigin::signed(2), 1));
+		assert_eq!(Identity::identity_of(&2), Some(registration));
+		assert_eq!(Balances::reserved_balance(2), deposit);
+		assert_eq!(TotalDeposits::<Test>::get(), deposit);
+		assert_ok!(Identity::do_try_state());
+	});
+}
+
+#[cfg(feature = "chain-extension")]
+#[test]
+fn chain_extension_dispatches_on_function_id() {
+	use crate::chain_extension::{IdentityExtension, DISPLAY_OF, IDENTITY_OF, IS_VERIFIED};
+	type Extension = IdentityExtension;
+
+	new_test_ext().execute_with(|| {
+		let size = crate::Registration::<Test>::max_encoded_len() as u64;
+		assert_eq!(Extension::weight::<Test>().proof_size(), size);
+
+		assert_eq!(Extension::output::<Test>(IDENTITY_OF, &1), Ok(None::<()>.encode()));
+		assert_eq!(Extension::output::<Test>(IS_VERIFIED, &1), Ok(false.encode()));
+		assert_eq!(Extension::output::<Test>(DISPLAY_OF, &1), Ok(None::<()>.encode()));
+
+		set_display_identity(1);
+		assert_ok!(Identity::provide_judgement_double_map(
+			RuntimeOrigin::root(),
+			0,
+			1,
+			Judgement::KnownGood,
+			None
+		));
+		let info = Identity::identity_of(&1).unwrap().info;
+		assert_eq!(Extension::output::<Test>(IDENTITY_OF, &1), Ok(Some(info).encode()));
+		assert_eq!(Extension::output::<Test>(IS_VERIFIED, &1), Ok(true.encode()));
+		assert_eq!(
+			Extension::output::<Test>(DISPLAY_OF, &1),
+			Ok(Some(b"display".to_vec()).encode())
+		);
+
+		assert_eq!(
+			Extension::output::<Test>(3, &1),
+			Err(DispatchError::Other("Unknown identity chain extension function"))
+		);
 	});
 }
@@ -1,33 +1,89 @@
+//! Weights of `pallet_identity`.
+//!
+//! [`WeightInfo`] is implemented once per target runtime, each in its own module under
+//! `weights/`, and a runtime picks its set through `Config::WeightInfo`:
+//!
+//! - [`mock`]: hand-written stub weights on `()`, for the mock runtime
+//!
+//! A target runtime gets its own module once its weights are generated, with the
+//! `frame-omni-bencher` command in the README. Until then it can only run on the stubs, which
+//! aren't measurements of anything.
+//!
+//! Besides the calls and the entry points other pallets call into, such as
+//! `receive_remote_judgement`, the functions cover what `on_initialize` does every block: each
+//! part of it has a function named after it, benchmarked on its own and returned as its weight.
+
 use frame_support::weights::Weight;
 
+pub mod mock;
+
 /// Weight functions needed for pallet_identity.
 pub trait WeightInfo {
-	fn set_identity(b: u32) -> Weight;
-	fn set_identity_update(b: u32, j: u32) -> Weight;
-	fn provide_judgement_inline(j: u32) -> Weight;
-	fn provide_judgement_double_map() -> Weight;
+	fn set_identity(d: u32, l: u32, w: u32, e: u32, a: u32) -> Weight;
+	fn set_identity_update(d: u32, l: u32, w: u32, e: u32, a: u32, j: u32) -> Weight;
+	fn provide_judgement_inline(j: u32, n: u32) -> Weight;
+	fn provide_judgement_double_map(n: u32) -> Weight;
 	fn clear_identity_inline_usage(j: u32) -> Weight;
 	fn clear_identity_double_map_usage(j: u32) -> Weight;
-}
-
-/// Dummy weight implementation for unit type
-impl WeightInfo for () {
-	fn set_identity(_b: u32) -> Weight {
-		Weight::from_parts(10_000, 0)
-	}
-	fn set_identity_update(_b: u32, _j: u32) -> Weight {
-		Weight::from_parts(20_000, 0)
-	}
-	fn provide_judgement_inline(_j: u32) -> Weight {
-		Weight::from_parts(15_000, 0)
-	}
-	fn provide_judgement_double_map() -> Weight {
-		Weight::from_parts(12_000, 0)
-	}
-	fn clear_identity_inline_usage(_j: u32) -> Weight {
-		Weight::from_parts(8_000, 0)
-	}
-	fn clear_identity_double_map_usage(_j: u32) -> Weight {
-		Weight::from_parts(25_000, 0)
-	}
+	fn set_identity_legacy(d: u32, l: u32, w: u32, e: u32, a: u32, j: u32) -> Weight;
+	fn set_judgement_limit() -> Weight;
+	fn provide_judgement_remote(j: u32, n: u32) -> Weight;
+	fn reconcile_deposits(n: u32) -> Weight;
+	fn consolidate_judgements(d: u32) -> Weight;
+	fn force_recount_judgements(j: u32) -> Weight;
+	fn set_registrar() -> Weight;
+	fn request_judgement(j: u32) -> Weight;
+	fn provide_judgements_inline(j: u32, k: u32) -> Weight;
+	fn clear_judgements_inline_usage(j: u32) -> Weight;
+	fn clear_judgements_double_map_usage(j: u32) -> Weight;
+	fn clear_judgements_with_notes(j: u32, n: u32) -> Weight;
+	fn update_score() -> Weight;
+	fn dispute_judgement(d: u32) -> Weight;
+	fn resolve_dispute_upheld() -> Weight;
+	fn resolve_dispute_overturned(j: u32) -> Weight;
+	fn withdraw_deposit() -> Weight;
+	fn clear_field() -> Weight;
+	fn claim_fees() -> Weight;
+	fn set_beneficiary() -> Weight;
+	fn claim_identity(j: u32) -> Weight;
+	fn vouch_for() -> Weight;
+	fn set_provider_key() -> Weight;
+	fn provide_judgement_with_proof(j: u32) -> Weight;
+	fn rotate_provider_key() -> Weight;
+	fn pause() -> Weight;
+	fn unpause() -> Weight;
+	fn binary_search_judgements(j: u32) -> Weight;
+	fn set_verified_fields() -> Weight;
+	fn provide_field_judgement(j: u32) -> Weight;
+	fn mirror_remote_identity(p: u32) -> Weight;
+	fn receive_remote_judgement(j: u32) -> Weight;
+	fn request_audit(r: u32) -> Weight;
+	fn price_identity_by_encoding(b: u32) -> Weight;
+	fn price_identity_by_lengths(b: u32) -> Weight;
+	fn set_deposit_exemption() -> Weight;
+	fn keep_tombstone(j: u32) -> Weight;
+	fn reap_tombstone() -> Weight;
+	fn retain_sticky_judgements(j: u32) -> Weight;
+	fn audit_identity(j: u32) -> Weight;
+	fn populate_identities(n: u32) -> Weight;
+	fn read_identity() -> Weight;
+	fn read_identity_whitelisted() -> Weight;
+	fn poke_deposit() -> Weight;
+	fn replace_judgement_double_map() -> Weight;
+	fn reset_judgements_in_block(p: u32) -> Weight;
+	fn renew_identity() -> Weight;
+	fn expire_identity() -> Weight;
+	fn update_identity_fields(d: u32, l: u32, w: u32, e: u32, a: u32, j: u32) -> Weight;
+	fn register_provider() -> Weight;
+	fn top_up_provider_bond() -> Weight;
+	fn withdraw_provider_bond() -> Weight;
+	fn set_kyc_hash() -> Weight;
+	fn attest_kyc() -> Weight;
+	fn redact_fields() -> Weight;
+	fn provide_judgement_with_reason(j: u32, n: u32) -> Weight;
+	fn reconfirm_judgement(j: u32) -> Weight;
+	fn downgrade_expired_judgements(n: u32) -> Weight;
+	fn set_preferred_registrars(p: u32) -> Weight;
+	fn pay_rent() -> Weight;
+	fn collect_due_rent(n: u32) -> Weight;
 }
@@ -0,0 +1,235 @@
+//! Weights of the mock runtime.
+//!
+//! Not generated: round numbers standing in for measurements, so that the tests of the pallet
+//! don't change whenever the benchmarks are rerun. They also stand in for the generated weights of
+//! the other runtimes until those are generated.
+
+use crate::WeightInfo;
+use frame_support::weights::Weight;
+
+/// Stub weights, used by the mock runtime
+impl WeightInfo for () {
+	fn set_identity(_d: u32, _l: u32, _w: u32, _e: u32, _a: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn set_identity_update(_d: u32, _l: u32, _w: u32, _e: u32, _a: u32, _j: u32) -> Weight {
+		Weight::from_parts(20_000, 0)
+	}
+	fn provide_judgement_inline(_j: u32, _n: u32) -> Weight {
+		Weight::from_parts(15_000, 0)
+	}
+	fn provide_judgement_double_map(_n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn clear_identity_inline_usage(_j: u32) -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn clear_identity_double_map_usage(_j: u32) -> Weight {
+		Weight::from_parts(25_000, 0)
+	}
+	fn set_identity_legacy(_d: u32, _l: u32, _w: u32, _e: u32, _a: u32, _j: u32) -> Weight {
+		Weight::from_parts(20_000, 0)
+	}
+	fn set_judgement_limit() -> Weight {
+		Weight::from_parts(5_000, 0)
+	}
+	fn provide_judgement_remote(_j: u32, _n: u32) -> Weight {
+		Weight::from_parts(16_000, 0)
+	}
+	fn reconcile_deposits(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(30_000, 0).saturating_mul(n.into()))
+	}
+	fn consolidate_judgements(d: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(3_000, 0).saturating_mul(d.into()))
+	}
+	fn force_recount_judgements(j: u32) -> Weight {
+		Weight::from_parts(8_000, 0)
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(j.into()))
+	}
+	fn set_registrar() -> Weight {
+		Weight::from_parts(5_000, 0)
+	}
+	fn request_judgement(j: u32) -> Weight {
+		Weight::from_parts(15_000, 0)
+			.saturating_add(Weight::from_parts(1_000, 0).saturating_mul(j.into()))
+	}
+	fn provide_judgements_inline(_j: u32, k: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(k.into()))
+	}
+	fn clear_judgements_inline_usage(_j: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn clear_judgements_double_map_usage(_j: u32) -> Weight {
+		Weight::from_parts(25_000, 0)
+	}
+	fn clear_judgements_with_notes(j: u32, n: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(j.into()))
+			.saturating_add(Weight::from_parts(100, 0).saturating_mul(n.into()))
+	}
+	fn update_score() -> Weight {
+		Weight::from_parts(3_000, 0)
+	}
+	fn dispute_judgement(d: u32) -> Weight {
+		Weight::from_parts(15_000, 0)
+			.saturating_add(Weight::from_parts(1_000, 0).saturating_mul(d.into()))
+	}
+	fn resolve_dispute_upheld() -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn resolve_dispute_overturned(_j: u32) -> Weight {
+		Weight::from_parts(20_000, 0)
+	}
+	fn withdraw_deposit() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn clear_field() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn claim_fees() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn set_beneficiary() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn claim_identity(j: u32) -> Weight {
+		Weight::from_parts(30_000, 0)
+			.saturating_add(Weight::from_parts(5_000, 0).saturating_mul(j.into()))
+	}
+	fn vouch_for() -> Weight {
+		Weight::from_parts(25_000, 0)
+	}
+	fn set_provider_key() -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn provide_judgement_with_proof(j: u32) -> Weight {
+		Weight::from_parts(60_000, 0)
+			.saturating_add(Weight::from_parts(1_000, 0).saturating_mul(j.into()))
+	}
+	fn rotate_provider_key() -> Weight {
+		Weight::from_parts(12_000, 0)
+	}
+	fn pause() -> Weight {
+		Weight::from_parts(5_000, 0)
+	}
+	fn unpause() -> Weight {
+		Weight::from_parts(5_000, 0)
+	}
+	fn binary_search_judgements(_j: u32) -> Weight {
+		Weight::from_parts(1_000, 0)
+	}
+	fn set_verified_fields() -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn provide_field_judgement(j: u32) -> Weight {
+		Weight::from_parts(12_000, 0)
+			.saturating_add(Weight::from_parts(500, 0).saturating_mul(j.into()))
+	}
+	fn mirror_remote_identity(p: u32) -> Weight {
+		Weight::from_parts(40_000, 0)
+			.saturating_add(Weight::from_parts(50, 0).saturating_mul(p.into()))
+	}
+	fn receive_remote_judgement(_j: u32) -> Weight {
+		Weight::from_parts(15_000, 0)
+	}
+	fn request_audit(r: u32) -> Weight {
+		Weight::from_parts(12_000, 0)
+			.saturating_add(Weight::from_parts(1_000, 0).saturating_mul(r.into()))
+	}
+	fn price_identity_by_encoding(b: u32) -> Weight {
+		Weight::from_parts(1_000, 0)
+			.saturating_add(Weight::from_parts(20, 0).saturating_mul(b.into()))
+	}
+	fn price_identity_by_lengths(_b: u32) -> Weight {
+		Weight::from_parts(500, 0)
+	}
+	fn set_deposit_exemption() -> Weight {
+		Weight::from_parts(12_000, 0)
+	}
+	fn keep_tombstone(j: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(j.into()))
+	}
+	fn reap_tombstone() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn retain_sticky_judgements(j: u32) -> Weight {
+		Weight::from_parts(1_000, 0)
+			.saturating_add(Weight::from_parts(500, 0).saturating_mul(j.into()))
+	}
+	fn audit_identity(j: u32) -> Weight {
+		Weight::from_parts(10_000, 0)
+			.saturating_add(Weight::from_parts(2_000, 0).saturating_mul(j.into()))
+	}
+	fn populate_identities(n: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(40_000, 0).saturating_mul(n.into()))
+	}
+	fn read_identity() -> Weight {
+		Weight::from_parts(5_000, 0)
+	}
+	fn read_identity_whitelisted() -> Weight {
+		Weight::from_parts(5_000, 0)
+	}
+	fn poke_deposit() -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn replace_judgement_double_map() -> Weight {
+		Weight::from_parts(6_000, 0)
+	}
+	fn reset_judgements_in_block(p: u32) -> Weight {
+		Weight::from_parts(2_000, 0)
+			.saturating_add(Weight::from_parts(1_000, 0).saturating_mul(p.into()))
+	}
+	fn renew_identity() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn expire_identity() -> Weight {
+		Weight::from_parts(6_000, 0)
+	}
+	fn update_identity_fields(_d: u32, _l: u32, _w: u32, _e: u32, _a: u32, _j: u32) -> Weight {
+		Weight::from_parts(15_000, 0)
+	}
+	fn register_provider() -> Weight {
+		Weight::from_parts(15_000, 0)
+	}
+	fn top_up_provider_bond() -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn withdraw_provider_bond() -> Weight {
+		Weight::from_parts(10_000, 0)
+	}
+	fn set_kyc_hash() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn attest_kyc() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn redact_fields() -> Weight {
+		Weight::from_parts(15_000, 0)
+	}
+	fn provide_judgement_with_reason(_j: u32, _n: u32) -> Weight {
+		Weight::from_parts(16_000, 0)
+	}
+	fn reconfirm_judgement(_j: u32) -> Weight {
+		Weight::from_parts(12_000, 0)
+	}
+	fn downgrade_expired_judgements(n: u32) -> Weight {
+		Weight::from_parts(2_000, 0)
+			.saturating_add(Weight::from_parts(20_000, 0).saturating_mul(n.into()))
+	}
+	fn set_preferred_registrars(p: u32) -> Weight {
+		Weight::from_parts(5_000, 0)
+			.saturating_add(Weight::from_parts(3_000, 0).saturating_mul(p.into()))
+	}
+	fn pay_rent() -> Weight {
+		Weight::from_parts(12_000, 0)
+	}
+	fn collect_due_rent(n: u32) -> Weight {
+		Weight::from_parts(2_000, 0)
+			.saturating_add(Weight::from_parts(15_000, 0).saturating_mul(n.into()))
+	}
+}
@@ -0,0 +1,58 @@
+//! Benchmarking setup for pallet-reputation
+//!
+//! Both operations catch up on decay, which is the only work that could grow with the state.
+//! The worst case is a record last updated in era 0 read many eras later, so every benchmark
+//! starts from such a stale record.
+
+use super::*;
+
+use crate::Pallet as Reputation;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_runtime::traits::TrailingZeroInput;
+
+/// Store a large reputation for `who` in era 0 and move the chain many eras ahead
+fn add_stale_reputation<T: Config>(who: &T::AccountId) {
+	Reputations::<T>::insert(who, ReputationRecord { points: u64::MAX, era: 0 });
+	let era_length = T::EraLength::get().max(One::one());
+	frame_system::Pallet::<T>::set_block_number(era_length.saturating_mul(1_000u32.into()));
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn poke_reputation() {
+		let caller: T::AccountId = whitelisted_caller();
+		let target: T::AccountId = account("target", 0, 0);
+		add_stale_reputation::<T>(&target);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), target.clone());
+
+		// Depending on the decay rate the record is either up to date or decayed away.
+		assert!(Reputations::<T>::get(&target).is_none_or(|record| record.era == 1_000));
+	}
+
+	#[benchmark]
+	fn on_judgement_given() {
+		let target: T::AccountId = account("target", 0, 0);
+		let judgement_id = T::JudgementId::decode(&mut TrailingZeroInput::zeroes())
+			.expect("Infinite input decodes into any type.");
+		add_stale_reputation::<T>(&target);
+
+		#[block]
+		{
+			<Reputation<T> as OnJudgementGiven<T::AccountId, T::JudgementId>>::on_judgement_given(
+				&target,
+				&judgement_id,
+				Judgement::KnownGood,
+			);
+		}
+
+		assert_eq!(Reputations::<T>::get(&target).map(|record| record.era), Some(1_000));
+	}
+
+	impl_benchmark_test_suite!(Reputation, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,199 @@
+//! # Reputation Pallet
+//!
+//! A companion to the simplified Identity pallet which turns the judgements given there into a
+//! reputation per account.
+//!
+//! ## Overview
+//!
+//! The pallet is plugged into the Identity pallet as its `OnJudgementGiven` handler. Every
+//! judgement adds the points `T::JudgementPoints` assigns to it, and unfavourable verdicts take
+//! points away. Reputation decays by `T::DecayPerEra` each era of `T::EraLength` blocks, so it
+//! reflects recent judgements more than old ones.
+//!
+//! Decay is applied lazily: a record remembers the era it was last brought up to date in and
+//! catches up on the eras since whenever it is touched. Reads through
+//! [`Pallet::reputation_of`] always see the decayed value.
+//!
+//! ## Benchmarking Focus
+//!
+//! - **`on_judgement_given`**: The cross-pallet hook. It is not a call, so it is measured with a
+//!   `#[block]` benchmark, and its weight is not accounted for by the Identity pallet.
+//! - **`poke_reputation`**: Stores the decayed reputation of an account.
+//!
+//! Catching up on `e` eras of decay is O(log e), computing the retained share by exponentiation
+//! by squaring rather than decaying era by era, so both are constant in the worst case.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::*, traits::Get};
+use frame_system::pallet_prelude::*;
+use pallet_identity::{Judgement, OnJudgementGiven};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Convert, One, SaturatedConversion, Saturating},
+	Perbill,
+};
+
+pub use weights::WeightInfo;
+
+/// Index of an era, the period over which reputation decays.
+pub type EraIndex = u32;
+
+/// An account's reputation as of the start of an era.
+#[derive(
+	Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct ReputationRecord {
+	/// Reputation points.
+	pub points: u64,
+	/// The era the points were last brought up to date in.
+	pub era: EraIndex,
+}
+
+#[frame_support::pallet(dev_mode)]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Configure the pallet by specifying the parameters and types on which it depends.
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Points each judgement is worth, e.g. `pallet_identity::DefaultJudgementScore`.
+		/// Negative points take reputation away, down to zero.
+		type JudgementPoints: Convert<Judgement, i32>;
+
+		/// Identifier of the judgement providers, as in `pallet_identity::Config::JudgementId`.
+		type JudgementId: Parameter + Member + MaxEncodedLen;
+
+		/// Number of blocks in an era.
+		#[pallet::constant]
+		type EraLength: Get<BlockNumberFor<Self>>;
+
+		/// Share of its reputation an account loses each era.
+		#[pallet::constant]
+		type DecayPerEra: Get<Perbill>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// Reputation of each account that was judged, as of the era it was last updated in.
+	#[pallet::storage]
+	pub type Reputations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ReputationRecord, OptionQuery>;
+
+	/// Pallets use events to inform users when important changes are made.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A judgement changed an account's reputation.
+		ReputationChanged { who: T::AccountId, judgement_id: T::JudgementId, points: u64 },
+		/// An account's stored reputation was brought up to date with its decay.
+		ReputationDecayed { who: T::AccountId, points: u64 },
+	}
+
+	/// Errors inform users that something went wrong.
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account has no reputation.
+		NoReputation,
+	}
+
+	/// Dispatchable functions allow users to interact with the pallet and invoke state changes.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Store an account's reputation decayed to the current era.
+		///
+		/// Reads through `reputation_of` see the decay anyway; this brings the stored record in
+		/// line for those reading storage directly. A record decayed to nothing is removed.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `who`: the account whose reputation to update.
+		///
+		/// Emits `ReputationDecayed` if successful.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::poke_reputation())]
+		pub fn poke_reputation(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let record = Reputations::<T>::get(&who).ok_or(Error::<T>::NoReputation)?;
+			let record = Self::decayed(record);
+			if record.points == 0 {
+				Reputations::<T>::remove(&who);
+			} else {
+				Reputations::<T>::insert(&who, record);
+			}
+
+			Self::deposit_event(Event::ReputationDecayed { who, points: record.points });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The era the current block is in.
+		pub fn current_era() -> EraIndex {
+			let era_length = T::EraLength::get().max(One::one());
+			(frame_system::Pallet::<T>::block_number() / era_length).saturated_into()
+		}
+
+		/// The reputation of an account as of the current era.
+		pub fn reputation_of(who: &T::AccountId) -> u64 {
+			Reputations::<T>::get(who).map_or(0, |record| Self::decayed(record).points)
+		}
+
+		/// Bring `record` up to date with the decay of the eras since it was last updated.
+		fn decayed(record: ReputationRecord) -> ReputationRecord {
+			let era = Self::current_era().max(record.era);
+			let elapsed = era - record.era;
+			let retained =
+				(Perbill::one() - T::DecayPerEra::get()).saturating_pow(elapsed as usize);
+			ReputationRecord { points: retained.mul_floor(record.points), era }
+		}
+	}
+
+	impl<T: Config> OnJudgementGiven<T::AccountId, T::JudgementId> for Pallet<T> {
+		fn on_judgement_given(
+			who: &T::AccountId,
+			judgement_id: &T::JudgementId,
+			judgement: Judgement,
+		) {
+			let mut record = Reputations::<T>::get(who)
+				.map(Self::decayed)
+				.unwrap_or(ReputationRecord { points: 0, era: Self::current_era() });
+
+			let points = T::JudgementPoints::convert(judgement);
+			record.points = if points >= 0 {
+				record.points.saturating_add(points as u64)
+			} else {
+				record.points.saturating_sub(points.unsigned_abs() as u64)
+			};
+			Reputations::<T>::insert(who, record);
+
+			Self::deposit_event(Event::ReputationChanged {
+				who: who.clone(),
+				judgement_id: judgement_id.clone(),
+				points: record.points,
+			});
+		}
+	}
+}
@@ -0,0 +1,204 @@
+use crate as pallet_reputation;
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, Randomness},
+};
+use frame_system as system;
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::{BlakeTwo256, IdentityLookup},
+	BuildStorage, Perbill,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Identity: pallet_identity,
+		Reputation: pallet_reputation,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = sp_core::H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u128>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u128;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU128<500>;
+	type AccountStore = System;
+	type WeightInfo = pallet_balances::weights::SubstrateWeight<Test>;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = ();
+	type DoneSlashHandler = ();
+}
+
+/// Uses indices as judgement IDs, and signs benchmark payloads with the test key of account 0.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct MockSigner;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_identity::BenchmarkHelper<u32, UintAuthorityId, TestSignature> for MockSigner {
+	fn judgement_id(index: u32) -> u32 {
+		index
+	}
+
+	fn sign_message(message: &[u8]) -> (UintAuthorityId, TestSignature) {
+		(UintAuthorityId(0), TestSignature(0, message.to_vec()))
+	}
+}
+
+/// Randomness that never varies: audits aren't exercised here.
+pub struct ZeroRandomness;
+impl Randomness<sp_core::H256, u64> for ZeroRandomness {
+	fn random(_subject: &[u8]) -> (sp_core::H256, u64) {
+		(Default::default(), 0)
+	}
+}
+
+impl pallet_identity::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type DepositBackend = pallet_identity::ReserveDeposits<Balances>;
+	type BasicDeposit = ConstU128<10>;
+	type ByteDeposit = ConstU128<1>;
+	type VerifiedDepositDiscount = VerifiedDepositDiscount;
+	type DepositDecayPeriod = ConstU64<0>;
+	type DepositDecayFloor = DepositDecayFloor;
+	type RegistrationFee = ConstU128<0>;
+	type RegistrationFeeDestination = ();
+	type IdentityRent = ConstU128<0>;
+	type RentPeriod = ConstU64<0>;
+	type RentGracePeriod = ConstU64<0>;
+	type MaxRentCollections = ConstU32<0>;
+	type RentDestination = ();
+	type ChallengeDeposit = ConstU128<20>;
+	type BlockNumberProvider = System;
+	type DepositReleaseDelay = ConstU64<0>;
+	type KeepTombstones = ConstBool<false>;
+	type TombstoneLifetime = ConstU64<0>;
+	type InactivityPeriod = ConstU64<100>;
+	type IdentityLifetime = ConstU64<0>;
+	type ExpiryGracePeriod = ConstU64<0>;
+	type RevalidationPeriod = ConstU64<0>;
+	type MaxExpiryChecks = ConstU32<8>;
+	type JudgementId = u32;
+	type MaxJudgements = ConstU32<20>;
+	type MaxBatch = ConstU32<5>;
+	type MaxRecentJudgements = ConstU32<0>;
+	type MaxJudgementsPerBlock = ConstU32<0>;
+	type MaxSetsPerPeriod = ConstU32<0>;
+	type IdentitySetPeriod = ConstU64<0>;
+	type ShadowWrites = ConstBool<false>;
+	type MaxDisplayLength = ConstU32<64>;
+	type DisplayIndexLength = ConstU32<0>;
+	type MaxLegalLength = ConstU32<64>;
+	type MaxWebLength = ConstU32<100>;
+	type MaxEmailLength = ConstU32<64>;
+	type MaxCidLength = ConstU32<64>;
+	type MaxNoteLength = ConstU32<32>;
+	type MaxRegistrationSize = ConstU32<720>;
+	type JudgementOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type RemoteRegistrarId = u32;
+	type RemoteJudgementOrigin = frame_system::EnsureNever<u32>;
+	type OffchainSignature = TestSignature;
+	type SigningPublicKey = UintAuthorityId;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockSigner;
+	type KeyRotationGracePeriod = ConstU64<10>;
+	type ForceOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type PauseOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type DisputeResolutionOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type NoDepositOrigin = frame_system::EnsureRoot<Self::AccountId>;
+	type RegistrarBond = ConstU128<100>;
+	type OverturnSlash = OverturnSlash;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type ProviderBondCurrency = Balances;
+	type ProviderBondSlash = ();
+	type MinProviderBond = ConstU128<50>;
+	type JudgementScore = pallet_identity::DefaultJudgementScore;
+	type VerificationThreshold = ConstU32<1>;
+	type MaxVouches = ConstU32<3>;
+	type VouchThreshold = ConstU32<2>;
+	type SystemJudgementId = ConstU32<{ u32::MAX }>;
+	type OnVerified = ();
+	type OnJudgementGiven = Reputation;
+	type BridgeOrigin = frame_system::EnsureNever<u32>;
+	// No judgement IDs are reserved for bridged judgements
+	type BridgedJudgementIds = BridgedJudgementIds;
+	type ForeignStateRoot = ();
+	type MaxRemoteProofSize = ConstU32<8192>;
+	type Randomness = ZeroRandomness;
+	type MaxAuditCandidates = ConstU32<8>;
+	type MaxPreferredRegistrars = ConstU32<3>;
+	type DidMethod = DidMethod;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const EraLength: u64 = 10;
+	pub const DecayPerEra: Perbill = Perbill::from_percent(50);
+	pub const OverturnSlash: Perbill = Perbill::from_percent(50);
+	pub const VerifiedDepositDiscount: Perbill = Perbill::zero();
+	pub const DepositDecayFloor: Perbill = Perbill::one();
+	/// Empty: no judgements are bridged here.
+	pub const BridgedJudgementIds: core::ops::RangeInclusive<u32> =
+		core::ops::RangeInclusive::new(1, 0);
+	pub const DidMethod: &'static str = "bench";
+}
+
+impl pallet_reputation::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type JudgementPoints = pallet_identity::DefaultJudgementScore;
+	type JudgementId = u32;
+	type EraLength = EraLength;
+	type DecayPerEra = DecayPerEra;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 1000), (2, 1000), (3, 1000)],
+		dev_accounts: None,
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::from(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
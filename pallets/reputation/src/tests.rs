@@ -0,0 +1,109 @@
+use crate::{mock::*, pallet::Reputations, Error, Event, ReputationRecord};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use pallet_identity::{IdentityInfo, Judgement};
+
+fn set_display_identity(who: u64) {
+	let info = IdentityInfo {
+		display: b"display".to_vec().try_into().unwrap(),
+		legal: BoundedVec::default(),
+		web: BoundedVec::default(),
+		email: BoundedVec::default(),
+		avatar: None,
+	};
+	assert_ok!(Identity::set_identity(RuntimeOrigin::signed(who), info));
+}
+
+fn judge(judgement_id: u32, who: u64, judgement: Judgement) {
+	assert_ok!(Identity::provide_judgement_inline(
+		RuntimeOrigin::root(),
+		judgement_id,
+		who,
+		judgement,
+		None,
+	));
+}
+
+#[test]
+fn judgements_accumulate_reputation() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		judge(0, 1, Judgement::KnownGood);
+		judge(1, 1, Judgement::Reasonable);
+
+		assert_eq!(Reputation::reputation_of(&1), 4);
+		assert_eq!(Reputations::<Test>::get(1), Some(ReputationRecord { points: 4, era: 0 }));
+		System::assert_has_event(
+			Event::<Test>::ReputationChanged { who: 1, judgement_id: 1, points: 4 }.into(),
+		);
+
+		// Other accounts are unaffected.
+		assert_eq!(Reputation::reputation_of(&2), 0);
+	});
+}
+
+#[test]
+fn unfavourable_judgements_take_reputation_away() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+
+		judge(0, 1, Judgement::KnownGood);
+		judge(1, 1, Judgement::LowQuality);
+		assert_eq!(Reputation::reputation_of(&1), 2);
+
+		// Reputation does not go below zero.
+		judge(2, 1, Judgement::Erroneous);
+		assert_eq!(Reputation::reputation_of(&1), 0);
+	});
+}
+
+#[test]
+fn reputation_decays_per_era() {
+	new_test_ext().execute_with(|| {
+		set_display_identity(1);
+		judge(0, 1, Judgement::KnownGood);
+		judge(1, 1, Judgement::KnownGood);
+		judge(2, 1, Judgement::KnownGood);
+		judge(3, 1, Judgement::KnownGood);
+		assert_eq!(Reputation::reputation_of(&1), 12);
+
+		// Still the same era.
+		System::set_block_number(9);
+		assert_eq!(Reputation::reputation_of(&1), 12);
+
+		// Half is lost each era, read lazily without touching storage.
+		System::set_block_number(10);
+		assert_eq!(Reputation::reputation_of(&1), 6);
+		System::set_block_number(25);
+		assert_eq!(Reputation::reputation_of(&1), 3);
+		assert_eq!(Reputations::<Test>::get(1), Some(ReputationRecord { points: 12, era: 0 }));
+
+		// A new judgement lands on top of the decayed reputation.
+		judge(4, 1, Judgement::Reasonable);
+		assert_eq!(Reputations::<Test>::get(1), Some(ReputationRecord { points: 4, era: 2 }));
+	});
+}
+
+#[test]
+fn poke_reputation_stores_decay() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Reputation::poke_reputation(RuntimeOrigin::signed(2), 1),
+			Error::<Test>::NoReputation
+		);
+
+		set_display_identity(1);
+		judge(0, 1, Judgement::KnownGood);
+		judge(1, 1, Judgement::Reasonable);
+
+		System::set_block_number(10);
+		assert_ok!(Reputation::poke_reputation(RuntimeOrigin::signed(2), 1));
+		assert_eq!(Reputations::<Test>::get(1), Some(ReputationRecord { points: 2, era: 1 }));
+		System::assert_last_event(Event::<Test>::ReputationDecayed { who: 1, points: 2 }.into());
+
+		// A record decayed to nothing is removed.
+		System::set_block_number(30);
+		assert_ok!(Reputation::poke_reputation(RuntimeOrigin::signed(2), 1));
+		assert_eq!(Reputations::<Test>::get(1), None);
+	});
+}
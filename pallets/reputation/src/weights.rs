@@ -0,0 +1,17 @@
+use frame_support::weights::Weight;
+
+/// Weight functions needed for pallet_reputation.
+pub trait WeightInfo {
+	fn poke_reputation() -> Weight;
+	fn on_judgement_given() -> Weight;
+}
+
+/// Dummy weight implementation for unit type
+impl WeightInfo for () {
+	fn poke_reputation() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+	fn on_judgement_given() -> Weight {
+		Weight::from_parts(8_000, 0)
+	}
+}
@@ -0,0 +1,11 @@
+//! Tests of the identity pallet against live chain state
+//!
+//! The tests in `tests/` pull storage of a running chain over RPC with
+//! `frame-remote-externalities`, and run the pallet's migrations and `try_state` checks on it.
+//! They are ignored by default, as they need network access and take a while:
+//!
+//! ```sh
+//! SKIP_WASM_BUILD=1 cargo test -- --ignored
+//! ```
+//!
+//! `REMOTE_URI` sets the node to pull from.
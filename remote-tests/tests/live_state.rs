@@ -0,0 +1,67 @@
+use bench_runtime::{AllPalletsWithSystem, Runtime, System};
+use frame::{
+	deps::frame_support::{
+		storage::migration::move_pallet,
+		traits::{ConstU32, OnRuntimeUpgrade, TryState, TryStateSelect},
+		StoragePrefixedMap,
+	},
+	runtime::{prelude::*, types_common::OpaqueBlock},
+};
+use frame_remote_externalities::{Builder, Mode, OnlineConfig};
+use pallet_identity::migrations::import::{
+	ImportIdentities, UpstreamIdentityOf, UpstreamRegistration,
+};
+
+/// A public node of the Polkadot People chain, which runs the polkadot-sdk `pallet-identity`.
+const DEFAULT_URI: &str = "wss://polkadot-people-rpc.polkadot.io:443";
+
+parameter_types! {
+	pub const UpstreamName: &'static str = "UpstreamIdentity";
+}
+
+/// The bounds of the People chain's identities: `MaxRegistrars` and `MaxAdditionalFields`.
+type MaxRegistrars = ConstU32<20>;
+type FieldLimit = ConstU32<100>;
+
+type Upstream = UpstreamIdentityOf<
+	UpstreamName,
+	<Runtime as frame_system::Config>::AccountId,
+	UpstreamRegistration<Runtime, (), MaxRegistrars, FieldLimit>,
+>;
+type Import = ImportIdentities<Runtime, (), UpstreamName, MaxRegistrars, FieldLimit>;
+
+#[tokio::test]
+#[ignore = "pulls live chain state over RPC"]
+async fn live_identities_are_imported() {
+	let uri = std::env::var("REMOTE_URI").unwrap_or_else(|_| DEFAULT_URI.into());
+	let mut ext = Builder::<OpaqueBlock>::new()
+		.mode(Mode::Online(OnlineConfig {
+			transport: uri.into(),
+			pallets: vec!["Identity".into()],
+			// The deposits to release and hold again
+			hashed_prefixes: vec![frame_system::Account::<Runtime>::final_prefix().to_vec()],
+			..Default::default()
+		}))
+		.build()
+		.await
+		.expect("Pulling the remote state failed");
+
+	ext.execute_with(|| {
+		// The upstream pallet is named `Identity` too: rename it, as a fork would
+		move_pallet(b"Identity", b"UpstreamIdentity");
+		let upstream = Upstream::iter_keys().count();
+		assert!(upstream > 0, "No identities were pulled");
+
+		Import::on_runtime_upgrade();
+
+		let imported = pallet_identity::IdentityOf::<Runtime>::iter_keys().count();
+		assert_eq!(
+			Upstream::iter_keys().count(),
+			0,
+			"Upstream identities were left behind, {imported} of {upstream} imported"
+		);
+		assert!(imported > 0, "None of the {upstream} identities could be imported");
+		AllPalletsWithSystem::try_state(System::block_number(), TryStateSelect::All)
+			.expect("The imported identities break the pallets' invariants");
+	});
+}
@@ -2,11 +2,12 @@
 
 extern crate alloc;
 
-use alloc::{vec, vec::Vec};
+use alloc::vec::Vec;
 use frame::{
 	deps::frame_support::{
 		genesis_builder_helper::{build_state, get_preset},
 		runtime,
+		traits::Randomness,
 		weights::{FixedFee, Weight},
 	},
 	prelude::*,
@@ -14,10 +15,16 @@ use frame::{
 		apis::{self, impl_runtime_apis},
 		prelude::*,
 	},
-	traits::One,
+	traits::{One, Verify},
 };
 use pallet_transaction_payment::{ConstFeeMultiplier, FeeDetails, Multiplier, RuntimeDispatchInfo};
 
+#[cfg(test)]
+mod tests;
+
+#[cfg(test)]
+mod weight_regression;
+
 #[runtime_version]
 const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: alloc::borrow::Cow::Borrowed("bench-exercise-template"),
@@ -45,7 +52,8 @@ mod runtime {
 		RuntimeHoldReason,
 		RuntimeSlashReason,
 		RuntimeLockId,
-		RuntimeTask
+		RuntimeTask,
+		RuntimeViewFunction
 	)]
 	pub struct Runtime;
 
@@ -70,6 +78,10 @@ mod runtime {
 	#[runtime::pallet_index(4)]
 	pub type Identity = pallet_identity;
 
+	/// Reputation accumulated from the judgements of the Identity pallet
+	#[runtime::pallet_index(5)]
+	pub type Reputation = pallet_reputation;
+
 	/// Timestamp pallet (required by some runtime APIs)
 	#[runtime::pallet_index(99)]
 	pub type Timestamp = pallet_timestamp;
@@ -94,6 +106,9 @@ impl frame_system::Config for Runtime {
 
 	// This is needed to make pjs-apps work and send txs
 	type AccountId = frame::runtime::types_common::AccountId;
+
+	// Don't leave the identity of a reaped account behind
+	type OnKilledAccount = Identity;
 }
 
 // Implements the types required for the balances pallet.
@@ -112,11 +127,22 @@ impl pallet_sudo::Config for Runtime {}
 
 parameter_types! {
 	pub FeeMultiplier: Multiplier = Multiplier::one();
+	pub const VerifiedFeeDiscount: Perbill = Perbill::from_percent(25);
+	/// Priority added to transactions of accounts with a `KnownGood` judgement, on top of the
+	/// priority their tip buys.
+	pub const VerifiedPriorityBoost: u64 = 1_000_000;
 }
 
 #[derive_impl(pallet_transaction_payment::config_preludes::TestDefaultConfig)]
 impl pallet_transaction_payment::Config for Runtime {
-	type OnChargeTransaction = pallet_transaction_payment::FungibleAdapter<Balances, ()>;
+	// Accounts with a `KnownGood` judgement pay less
+	type OnChargeTransaction = pallet_identity::fee_discount::DiscountVerified<
+		pallet_transaction_payment::FungibleAdapter<Balances, ()>,
+		Identity,
+		VerifiedFeeDiscount,
+	>;
+	// Which includes looking up the signer's identity for the discount
+	type WeightInfo = pallet_identity::fee_discount::DiscountVerifiedWeight<Runtime, (), Identity>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
 	// These two define what the transaction fee would.
@@ -131,19 +157,195 @@ impl pallet_timestamp::Config for Runtime {}
 parameter_types! {
 	pub const BasicDeposit: Balance = 10;
 	pub const ByteDeposit: Balance = 1;
+	pub const VerifiedDepositDiscount: Perbill = Perbill::from_percent(20);
+	// About a month of 6 second blocks
+	pub const DepositDecayPeriod: BlockNumberFor<Runtime> = 432_000;
+	pub const DepositDecayFloor: Perbill = Perbill::from_percent(50);
+	pub const RegistrationFee: Balance = 2;
+	// Identities hold deposits; set to charge them rent instead
+	pub const IdentityRent: Balance = 0;
+	// About a month of 6 second blocks
+	pub const RentPeriod: BlockNumberFor<Runtime> = 432_000;
+	// About a month of 6 second blocks
+	pub const RentGracePeriod: BlockNumberFor<Runtime> = 432_000;
+	pub const MaxRentCollections: u32 = 32;
+	pub const ChallengeDeposit: Balance = 50;
+	pub const DepositReleaseDelay: BlockNumberFor<Runtime> = 100;
+	pub const KeepTombstones: bool = true;
+	// About a year of 6 second blocks
+	pub const TombstoneLifetime: BlockNumberFor<Runtime> = 5_256_000;
+	// About 30 days of 6 second blocks
+	pub const InactivityPeriod: BlockNumberFor<Runtime> = 432_000;
+	// About a year of 6 second blocks
+	pub const IdentityLifetime: BlockNumberFor<Runtime> = 5_256_000;
+	// About 30 days of 6 second blocks
+	pub const ExpiryGracePeriod: BlockNumberFor<Runtime> = 432_000;
+	// About a year of 6 second blocks
+	pub const RevalidationPeriod: BlockNumberFor<Runtime> = 5_256_000;
+	pub const MaxExpiryChecks: u32 = 32;
+	// About a day of 6 second blocks
+	pub const KeyRotationGracePeriod: BlockNumberFor<Runtime> = 14_400;
+	pub const RegistrarBond: Balance = 1_000;
+	pub const OverturnSlash: Perbill = Perbill::from_percent(50);
+	pub const MinProviderBond: Balance = 1_000;
 	pub const MaxJudgements: u32 = 20;
-	pub const MaxFieldLength: u32 = 64;
+	pub const MaxBatch: u32 = 10;
+	pub const MaxRecentJudgements: u32 = 100;
+	pub const MaxJudgementsPerBlock: u32 = 50;
+	pub const MaxSetsPerPeriod: u32 = 5;
+	// About a day of 6 second blocks
+	pub const IdentitySetPeriod: BlockNumberFor<Runtime> = 14_400;
+	// Set on a test network to compare the storage patterns
+	pub const ShadowWrites: bool = false;
+	pub const MaxDisplayLength: u32 = 64;
+	pub const DisplayIndexLength: u32 = 4;
+	pub const MaxLegalLength: u32 = 64;
+	pub const MaxWebLength: u32 = 100;
+	pub const MaxEmailLength: u32 = 64;
+	pub const MaxCidLength: u32 = 64;
+	pub const MaxNoteLength: u32 = 32;
+	pub const MaxRegistrationSize: u32 = 640;
+	pub const VerificationThreshold: u32 = 1;
+	pub const MaxVouches: u32 = 16;
+	pub const VouchThreshold: u32 = 3;
+	pub const SystemJudgementId: u32 = u32::MAX;
+	pub const MaxRemoteProofSize: u32 = 8192;
+	pub const BridgedJudgementIds: core::ops::RangeInclusive<u32> = 1_000_000..=1_999_999;
+	pub const MaxAuditCandidates: u32 = 16;
+	pub const MaxPreferredRegistrars: u32 = 3;
+	pub const DidMethod: &'static str = "bench";
+	// Set by governance through `System::set_storage` once there is a chain to mirror from
+	pub storage ForeignStateRoot: Option<<Runtime as frame_system::Config>::Hash> = None;
+}
+
+/// Trusts the state root stored in `ForeignStateRoot`.
+pub struct StoredForeignStateRoot;
+impl pallet_identity::ForeignStateRoot<<Runtime as frame_system::Config>::Hash>
+	for StoredForeignStateRoot
+{
+	fn state_root() -> Option<<Runtime as frame_system::Config>::Hash> {
+		ForeignStateRoot::get()
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_state_root(root: <Runtime as frame_system::Config>::Hash) {
+		ForeignStateRoot::set(&Some(root));
+	}
+}
+
+/// Randomness derived from the parent block hash. Block authors can predict and bias it, which
+/// is tolerable for assigning audits but for little else.
+pub struct ParentHashRandomness;
+impl Randomness<<Runtime as frame_system::Config>::Hash, BlockNumberFor<Runtime>>
+	for ParentHashRandomness
+{
+	fn random(
+		subject: &[u8],
+	) -> (<Runtime as frame_system::Config>::Hash, BlockNumberFor<Runtime>) {
+		let block_number = System::block_number();
+		let seed = (subject, System::parent_hash()).using_encoded(BlakeTwo256::hash);
+		(seed, block_number.saturating_sub(One::one()))
+	}
 }
 
 /// Configure the pallet-identity in pallets/identity.
 impl pallet_identity::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
+	// Slashed funds are burned
+	type DepositBackend = pallet_identity::ReserveDeposits<Balances, ()>;
 	type BasicDeposit = BasicDeposit;
 	type ByteDeposit = ByteDeposit;
+	type VerifiedDepositDiscount = VerifiedDepositDiscount;
+	type DepositDecayPeriod = DepositDecayPeriod;
+	type DepositDecayFloor = DepositDecayFloor;
+	type RegistrationFee = RegistrationFee;
+	// Registration fees are burned
+	type RegistrationFeeDestination = ();
+	type IdentityRent = IdentityRent;
+	type RentPeriod = RentPeriod;
+	type RentGracePeriod = RentGracePeriod;
+	type MaxRentCollections = MaxRentCollections;
+	// Rent is burned too
+	type RentDestination = ();
+	type ChallengeDeposit = ChallengeDeposit;
+	type BlockNumberProvider = System;
+	type DepositReleaseDelay = DepositReleaseDelay;
+	type KeepTombstones = KeepTombstones;
+	type TombstoneLifetime = TombstoneLifetime;
+	type InactivityPeriod = InactivityPeriod;
+	type IdentityLifetime = IdentityLifetime;
+	type ExpiryGracePeriod = ExpiryGracePeriod;
+	type RevalidationPeriod = RevalidationPeriod;
+	type MaxExpiryChecks = MaxExpiryChecks;
+	type JudgementId = u32;
 	type MaxJudgements = MaxJudgements;
-	type MaxFieldLength = MaxFieldLength;
+	type MaxBatch = MaxBatch;
+	type MaxRecentJudgements = MaxRecentJudgements;
+	type MaxJudgementsPerBlock = MaxJudgementsPerBlock;
+	type MaxSetsPerPeriod = MaxSetsPerPeriod;
+	type IdentitySetPeriod = IdentitySetPeriod;
+	type ShadowWrites = ShadowWrites;
+	type MaxDisplayLength = MaxDisplayLength;
+	type DisplayIndexLength = DisplayIndexLength;
+	type MaxLegalLength = MaxLegalLength;
+	type MaxWebLength = MaxWebLength;
+	type MaxEmailLength = MaxEmailLength;
+	type MaxCidLength = MaxCidLength;
+	type MaxNoteLength = MaxNoteLength;
+	type MaxRegistrationSize = MaxRegistrationSize;
 	type JudgementOrigin = EnsureRoot<AccountId>;
+	// This runtime has no XCM yet, so root stands in for remote registrar 0
+	type RemoteRegistrarId = u32;
+	type RemoteJudgementOrigin = EnsureRootWithSuccess<AccountId, ConstU32<0>>;
+	type OffchainSignature = Signature;
+	type SigningPublicKey = <Signature as Verify>::Signer;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+	type KeyRotationGracePeriod = KeyRotationGracePeriod;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type PauseOrigin = EnsureRoot<AccountId>;
+	type DisputeResolutionOrigin = EnsureRoot<AccountId>;
+	type NoDepositOrigin = EnsureRoot<AccountId>;
+	type RegistrarBond = RegistrarBond;
+	type OverturnSlash = OverturnSlash;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type ProviderBondCurrency = Balances;
+	// Slashed provider bonds are burned
+	type ProviderBondSlash = ();
+	type MinProviderBond = MinProviderBond;
+	type JudgementScore = pallet_identity::DefaultJudgementScore;
+	type VerificationThreshold = VerificationThreshold;
+	type MaxVouches = MaxVouches;
+	type VouchThreshold = VouchThreshold;
+	type SystemJudgementId = SystemJudgementId;
+	type OnVerified = ();
+	type OnJudgementGiven = Reputation;
+	// This runtime has no bridge yet, so root stands in for remote registrar 0
+	type BridgeOrigin = EnsureRootWithSuccess<AccountId, ConstU32<0>>;
+	type BridgedJudgementIds = BridgedJudgementIds;
+	type ForeignStateRoot = StoredForeignStateRoot;
+	type MaxRemoteProofSize = MaxRemoteProofSize;
+	type Randomness = ParentHashRandomness;
+	type MaxAuditCandidates = MaxAuditCandidates;
+	type MaxPreferredRegistrars = MaxPreferredRegistrars;
+	type DidMethod = DidMethod;
+	// Stub weights until this runtime's are generated into `pallet_identity::weights`
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const EraLength: BlockNumberFor<Runtime> = 100;
+	pub const DecayPerEra: Perbill = Perbill::from_percent(10);
+}
+
+/// Configure the pallet-reputation in pallets/reputation.
+impl pallet_reputation::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type JudgementPoints = pallet_identity::DefaultJudgementScore;
+	type JudgementId = u32;
+	type EraLength = EraLength;
+	type DecayPerEra = DecayPerEra;
 	type WeightInfo = ();
 }
 
@@ -157,6 +359,7 @@ type SignedExtra = (
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_identity::priority::PrioritizeVerified<Runtime, Identity, VerifiedPriorityBoost>,
 );
 
 // Many of the types in this runtime are being pulled in from `derive_impl`. We use the almighty
@@ -165,11 +368,32 @@ type SignedExtra = (
 type AccountId = <Runtime as frame_system::Config>::AccountId;
 type Balance = <Runtime as pallet_balances::Config>::Balance;
 type Nonce = <Runtime as frame_system::Config>::Nonce;
+type Signature = frame::runtime::types_common::Signature;
 type Block = frame::runtime::types_common::BlockOf<Runtime, SignedExtra>;
 type Header = HeaderFor<Runtime>;
 
-type RuntimeExecutive =
-	Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPalletsWithSystem>;
+/// Migrations to run on the next runtime upgrade.
+type Migrations = (
+	pallet_identity::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_identity::migrations::v2::MigrateV1ToV2<Runtime>,
+	pallet_identity::migrations::v3::MigrateV2ToV3<Runtime>,
+	pallet_identity::migrations::v4::MigrateV3ToV4<Runtime>,
+	pallet_identity::migrations::v5::MigrateV4ToV5<Runtime>,
+	pallet_identity::migrations::v6::MigrateV5ToV6<Runtime>,
+	pallet_identity::migrations::v7::MigrateV6ToV7<Runtime>,
+	pallet_identity::migrations::v8::MigrateV7ToV8<Runtime>,
+	pallet_identity::migrations::v9::MigrateV8ToV9<Runtime>,
+	pallet_identity::migrations::v10::MigrateV9ToV10<Runtime>,
+);
+
+type RuntimeExecutive = Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPalletsWithSystem,
+	Migrations,
+>;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benches {
@@ -179,6 +403,7 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_sudo, Sudo]
 		[pallet_identity, Identity]
+		[pallet_reputation, Reputation]
 		[pallet_timestamp, Timestamp]
 	);
 }
@@ -267,6 +492,15 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl frame::deps::frame_support::view_functions::runtime_api::RuntimeViewFunction<Block> for Runtime {
+		fn execute_view_function(
+			id: frame::deps::frame_support::view_functions::ViewFunctionId,
+			input: Vec<u8>,
+		) -> Result<Vec<u8>, frame::deps::frame_support::view_functions::ViewFunctionDispatchError> {
+			Runtime::execute_view_function(id, input)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<
 		Block,
 		Balance,
@@ -357,7 +591,11 @@ impl_runtime_apis! {
 			// have a backtrace here. If any of the pre/post migration checks fail, we shall stop
 			// right here and right now.
 			let weight = RuntimeExecutive::try_runtime_upgrade(checks).unwrap();
-			(weight, <Runtime as frame_system::Config>::BlockWeights::get().max_block)
+			let max_block = <<Runtime as frame_system::Config>::BlockWeights as Get<
+				frame_system::limits::BlockWeights,
+			>>::get()
+			.max_block;
+			(weight, max_block)
 		}
 
 		fn execute_block(
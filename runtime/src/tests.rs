@@ -0,0 +1,126 @@
+use super::*;
+use frame::{
+	deps::{
+		frame_support::dispatch::{DispatchInfo, PostDispatchInfo},
+		sp_keyring::Sr25519Keyring as AccountKeyring,
+	},
+	testing_prelude::{assert_ok, BuildStorage, TestState},
+};
+use pallet_identity::{IdentityInfo, Judgement};
+use pallet_transaction_payment::OnChargeTransaction;
+
+type FeeCharger = <Runtime as pallet_transaction_payment::Config>::OnChargeTransaction;
+
+fn new_test_ext() -> TestState {
+	let config = RuntimeGenesisConfig {
+		balances: BalancesConfig {
+			balances: vec![
+				(AccountKeyring::Alice.to_account_id(), 1_000),
+				(AccountKeyring::Bob.to_account_id(), 1_000),
+			],
+			..Default::default()
+		},
+		..Default::default()
+	};
+	let mut ext = TestState::new(config.build_storage().unwrap());
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Set an identity for `who`, judged `KnownGood` under judgement ID 0
+fn verify(who: &AccountId) {
+	let info = IdentityInfo {
+		display: b"display".to_vec().try_into().unwrap(),
+		legal: Default::default(),
+		web: Default::default(),
+		email: Default::default(),
+		avatar: None,
+	};
+	assert_ok!(Identity::set_identity(RuntimeOrigin::signed(who.clone()), info));
+	assert_ok!(Identity::provide_judgement_inline(
+		RuntimeOrigin::root(),
+		0,
+		who.clone().into(),
+		Judgement::KnownGood,
+		None
+	));
+}
+
+/// Withdraw a fee of `fee`, including `tip`, from `who`, correct it to `corrected_fee` and
+/// return what `who` paid in the end
+fn charge(who: &AccountId, fee: Balance, corrected_fee: Balance, tip: Balance) -> Balance {
+	let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+	let info = DispatchInfo::default();
+	let before = Balances::free_balance(who);
+	let withdrawn =
+		<FeeCharger as OnChargeTransaction<Runtime>>::withdraw_fee(who, &call, &info, fee, tip)
+			.unwrap();
+	assert_ok!(<FeeCharger as OnChargeTransaction<Runtime>>::correct_and_deposit_fee(
+		who,
+		&info,
+		&PostDispatchInfo::default(),
+		corrected_fee,
+		tip,
+		withdrawn,
+	));
+	before - Balances::free_balance(who)
+}
+
+#[test]
+fn verified_accounts_get_a_fee_discount() {
+	new_test_ext().execute_with(|| {
+		let alice = AccountKeyring::Alice.to_account_id();
+		let bob = AccountKeyring::Bob.to_account_id();
+		verify(&alice);
+
+		// A quarter off everything but the tip
+		assert_eq!(charge(&alice, 110, 110, 10), 85);
+		assert_eq!(charge(&bob, 110, 110, 10), 110);
+
+		// Corrected fees are discounted alike
+		assert_eq!(charge(&alice, 110, 50, 10), 40);
+		assert_eq!(charge(&bob, 110, 50, 10), 50);
+	});
+}
+
+#[test]
+fn the_discount_is_decided_when_the_fee_is_withdrawn() {
+	new_test_ext().execute_with(|| {
+		let alice = AccountKeyring::Alice.to_account_id();
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let info = DispatchInfo::default();
+		let before = Balances::free_balance(&alice);
+
+		// The transaction Alice pays for verifies Alice, after the fee was withdrawn
+		let withdrawn = <FeeCharger as OnChargeTransaction<Runtime>>::withdraw_fee(
+			&alice, &call, &info, 100, 0,
+		)
+		.unwrap();
+		verify(&alice);
+		let deposit = Balances::reserved_balance(&alice);
+		assert_ok!(<FeeCharger as OnChargeTransaction<Runtime>>::correct_and_deposit_fee(
+			&alice,
+			&info,
+			&PostDispatchInfo::default(),
+			100,
+			0,
+			withdrawn,
+		));
+
+		let registration_fee = RegistrationFee::get();
+		assert_eq!(before - Balances::free_balance(&alice) - deposit - registration_fee, 100);
+	});
+}
+
+#[test]
+fn charging_a_fee_weighs_the_identity_lookup() {
+	use pallet_transaction_payment::WeightInfo;
+	type ChargeWeight = <Runtime as pallet_transaction_payment::Config>::WeightInfo;
+
+	let lookup = <Identity as pallet_identity::InspectIdentity<AccountId>>::lookup_weight();
+	assert_eq!(
+		ChargeWeight::charge_transaction_payment(),
+		<() as WeightInfo>::charge_transaction_payment() + lookup
+	);
+	assert!(lookup.proof_size() > 0);
+}
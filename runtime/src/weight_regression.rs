@@ -0,0 +1,302 @@
+//! Weight regression tests.
+//!
+//! Every weight function of `pallet_identity`, as this runtime is configured with them, is
+//! evaluated at the largest components this runtime's `pallet_identity::Config` allows and
+//! compared with the reference values recorded below. A weight which grew by more than the
+//! tolerance fails the test, so that a change making an extrinsic more expensive is noticed when
+//! the weights are regenerated. After an intended change, record the new values in `REFERENCE`.
+//!
+//! The tolerance is `DEFAULT_TOLERANCE_PERCENT`, or the `WEIGHT_REGRESSION_TOLERANCE`
+//! environment variable in percent, e.g. to allow for a noisier benchmarking machine.
+//!
+//! The same worst cases are also checked to fit in a block: each has to stay within
+//! `DEFAULT_BLOCK_SHARE_PERCENT`, or `WEIGHT_BLOCK_SHARE` percent, of the most a normal extrinsic
+//! may weigh under this runtime's `BlockWeights`, in either dimension.
+
+use super::*;
+use frame::deps::frame_support::dispatch::DispatchClass;
+use frame_system::limits::BlockWeights;
+use pallet_identity::WeightInfo;
+
+/// Tolerated growth of a weight over its reference, in percent.
+const DEFAULT_TOLERANCE_PERCENT: u64 = 10;
+
+/// Share of the weight a normal extrinsic may take in a block that a worst case may take, in
+/// percent.
+const DEFAULT_BLOCK_SHARE_PERCENT: u64 = 50;
+
+// Components bounded by a call argument rather than the configuration, at the top of their
+// benchmarked range.
+const RECONCILE_BATCH: u32 = 10;
+const POPULATE_BATCH: u32 = 10;
+const PROVIDERS_IN_BLOCK: u32 = 100;
+
+/// Reference weights as `(name, ref_time, proof_size)`.
+const REFERENCE: &[(&str, u64, u64)] = &[
+	("set_identity", 10000, 0),
+	("set_identity_update", 20000, 0),
+	("provide_judgement_inline", 15000, 0),
+	("provide_judgement_double_map", 10000, 0),
+	("clear_identity_inline_usage", 8000, 0),
+	("clear_identity_double_map_usage", 25000, 0),
+	("set_identity_legacy", 20000, 0),
+	("set_judgement_limit", 5000, 0),
+	("provide_judgement_remote", 16000, 0),
+	("reconcile_deposits", 305000, 0),
+	("consolidate_judgements", 70000, 0),
+	("force_recount_judgements", 48000, 0),
+	("set_registrar", 5000, 0),
+	("request_judgement", 35000, 0),
+	("provide_judgements_inline", 60000, 0),
+	("clear_judgements_inline_usage", 10000, 0),
+	("clear_judgements_double_map_usage", 25000, 0),
+	("clear_judgements_with_notes", 53200, 0),
+	("update_score", 3000, 0),
+	("dispute_judgement", 35000, 0),
+	("resolve_dispute_upheld", 10000, 0),
+	("resolve_dispute_overturned", 20000, 0),
+	("withdraw_deposit", 8000, 0),
+	("clear_field", 8000, 0),
+	("claim_fees", 8000, 0),
+	("set_beneficiary", 8000, 0),
+	("claim_identity", 130000, 0),
+	("vouch_for", 25000, 0),
+	("set_provider_key", 10000, 0),
+	("provide_judgement_with_proof", 80000, 0),
+	("rotate_provider_key", 12000, 0),
+	("pause", 5000, 0),
+	("unpause", 5000, 0),
+	("binary_search_judgements", 1000, 0),
+	("set_verified_fields", 10000, 0),
+	("provide_field_judgement", 22000, 0),
+	("mirror_remote_identity", 449600, 0),
+	("receive_remote_judgement", 15000, 0),
+	("request_audit", 28000, 0),
+	("price_identity_by_encoding", 3000, 0),
+	("price_identity_by_lengths", 500, 0),
+	("set_deposit_exemption", 12000, 0),
+	("keep_tombstone", 50000, 0),
+	("reap_tombstone", 8000, 0),
+	("retain_sticky_judgements", 11000, 0),
+	("audit_identity", 50000, 0),
+	("populate_identities", 405000, 0),
+	("read_identity", 5000, 0),
+	("read_identity_whitelisted", 5000, 0),
+	("poke_deposit", 10000, 0),
+	("replace_judgement_double_map", 6000, 0),
+	("reset_judgements_in_block", 102000, 0),
+	("renew_identity", 8000, 0),
+	("expire_identity", 6000, 0),
+	("update_identity_fields", 15000, 0),
+	("register_provider", 15000, 0),
+	("top_up_provider_bond", 10000, 0),
+	("withdraw_provider_bond", 10000, 0),
+	("set_kyc_hash", 8000, 0),
+	("attest_kyc", 8000, 0),
+	("redact_fields", 15000, 0),
+	("provide_judgement_with_reason", 16000, 0),
+	("reconfirm_judgement", 12000, 0),
+	("downgrade_expired_judgements", 642000, 0),
+	("set_preferred_registrars", 14000, 0),
+	("pay_rent", 12000, 0),
+	("collect_due_rent", 482000, 0),
+];
+
+/// Every weight function `T` is configured with, at the largest components `T` allows.
+fn weights<T, W>() -> Vec<(&'static str, Weight)>
+where
+	T: pallet_identity::Config<WeightInfo = W>,
+	W: WeightInfo,
+{
+	let (d, l, w, e) = (
+		T::MaxDisplayLength::get(),
+		T::MaxLegalLength::get(),
+		T::MaxWebLength::get(),
+		T::MaxEmailLength::get(),
+	);
+	let a = T::MaxCidLength::get();
+	let (j, n) = (T::MaxJudgements::get(), T::MaxNoteLength::get());
+	vec![
+		("set_identity", W::set_identity(d, l, w, e, a)),
+		("set_identity_update", W::set_identity_update(d, l, w, e, a, j)),
+		("provide_judgement_inline", W::provide_judgement_inline(j, n)),
+		("provide_judgement_double_map", W::provide_judgement_double_map(n)),
+		("clear_identity_inline_usage", W::clear_identity_inline_usage(j)),
+		("clear_identity_double_map_usage", W::clear_identity_double_map_usage(j)),
+		("set_identity_legacy", W::set_identity_legacy(d, l, w, e, a, j)),
+		("set_judgement_limit", W::set_judgement_limit()),
+		("provide_judgement_remote", W::provide_judgement_remote(j, n)),
+		("reconcile_deposits", W::reconcile_deposits(RECONCILE_BATCH)),
+		("consolidate_judgements", W::consolidate_judgements(j)),
+		("force_recount_judgements", W::force_recount_judgements(j)),
+		("set_registrar", W::set_registrar()),
+		("request_judgement", W::request_judgement(j)),
+		("provide_judgements_inline", W::provide_judgements_inline(j, T::MaxBatch::get())),
+		("clear_judgements_inline_usage", W::clear_judgements_inline_usage(j)),
+		("clear_judgements_double_map_usage", W::clear_judgements_double_map_usage(j)),
+		("clear_judgements_with_notes", W::clear_judgements_with_notes(j, n)),
+		("update_score", W::update_score()),
+		("dispute_judgement", W::dispute_judgement(j)),
+		("resolve_dispute_upheld", W::resolve_dispute_upheld()),
+		("resolve_dispute_overturned", W::resolve_dispute_overturned(j)),
+		("withdraw_deposit", W::withdraw_deposit()),
+		("clear_field", W::clear_field()),
+		("claim_fees", W::claim_fees()),
+		("set_beneficiary", W::set_beneficiary()),
+		("claim_identity", W::claim_identity(j)),
+		("vouch_for", W::vouch_for()),
+		("set_provider_key", W::set_provider_key()),
+		("provide_judgement_with_proof", W::provide_judgement_with_proof(j)),
+		("rotate_provider_key", W::rotate_provider_key()),
+		("pause", W::pause()),
+		("unpause", W::unpause()),
+		("binary_search_judgements", W::binary_search_judgements(j)),
+		("set_verified_fields", W::set_verified_fields()),
+		("provide_field_judgement", W::provide_field_judgement(j)),
+		("mirror_remote_identity", W::mirror_remote_identity(T::MaxRemoteProofSize::get())),
+		("receive_remote_judgement", W::receive_remote_judgement(j)),
+		("request_audit", W::request_audit(T::MaxAuditCandidates::get())),
+		("price_identity_by_encoding", W::price_identity_by_encoding(d.max(l).max(w).max(e))),
+		("price_identity_by_lengths", W::price_identity_by_lengths(d.max(l).max(w).max(e))),
+		("set_deposit_exemption", W::set_deposit_exemption()),
+		("keep_tombstone", W::keep_tombstone(j)),
+		("reap_tombstone", W::reap_tombstone()),
+		("retain_sticky_judgements", W::retain_sticky_judgements(j)),
+		("audit_identity", W::audit_identity(j)),
+		("populate_identities", W::populate_identities(POPULATE_BATCH)),
+		("read_identity", W::read_identity()),
+		("read_identity_whitelisted", W::read_identity_whitelisted()),
+		("poke_deposit", W::poke_deposit()),
+		("replace_judgement_double_map", W::replace_judgement_double_map()),
+		("reset_judgements_in_block", W::reset_judgements_in_block(PROVIDERS_IN_BLOCK)),
+		("renew_identity", W::renew_identity()),
+		("expire_identity", W::expire_identity()),
+		("update_identity_fields", W::update_identity_fields(d, l, w, e, a, j)),
+		("register_provider", W::register_provider()),
+		("top_up_provider_bond", W::top_up_provider_bond()),
+		("withdraw_provider_bond", W::withdraw_provider_bond()),
+		("set_kyc_hash", W::set_kyc_hash()),
+		("attest_kyc", W::attest_kyc()),
+		("redact_fields", W::redact_fields()),
+		("provide_judgement_with_reason", W::provide_judgement_with_reason(j, n)),
+		("reconfirm_judgement", W::reconfirm_judgement(j)),
+		(
+			"downgrade_expired_judgements",
+			W::downgrade_expired_judgements(T::MaxExpiryChecks::get()),
+		),
+		("set_preferred_registrars", W::set_preferred_registrars(T::MaxPreferredRegistrars::get())),
+		("pay_rent", W::pay_rent()),
+		("collect_due_rent", W::collect_due_rent(T::MaxRentCollections::get())),
+	]
+}
+
+/// The tolerance in percent: `WEIGHT_REGRESSION_TOLERANCE` if set, else the default.
+fn tolerance_percent() -> u64 {
+	std::env::var("WEIGHT_REGRESSION_TOLERANCE")
+		.map(|value| value.parse().expect("`WEIGHT_REGRESSION_TOLERANCE` must be a percentage."))
+		.unwrap_or(DEFAULT_TOLERANCE_PERCENT)
+}
+
+/// Describe every weight in `weights` which grew by more than `tolerance` percent over its
+/// reference, in either dimension.
+fn regressions(
+	weights: &[(&'static str, Weight)],
+	reference: &[(&str, u64, u64)],
+	tolerance: u64,
+) -> Vec<String> {
+	let limit = |value: u64| value.saturating_mul(100 + tolerance) / 100;
+	weights
+		.iter()
+		.filter_map(|(name, weight)| {
+			let &(_, ref_time, proof_size) = reference
+				.iter()
+				.find(|(reference, ..)| reference == name)
+				.unwrap_or_else(|| panic!("No reference weight for `{name}`."));
+			(weight.ref_time() > limit(ref_time) || weight.proof_size() > limit(proof_size))
+				.then(|| format!("{name}: {weight:?}, reference ({ref_time}, {proof_size})"))
+		})
+		.collect()
+}
+
+/// The block share in percent: `WEIGHT_BLOCK_SHARE` if set, else the default.
+fn block_share_percent() -> u64 {
+	std::env::var("WEIGHT_BLOCK_SHARE")
+		.map(|value| value.parse().expect("`WEIGHT_BLOCK_SHARE` must be a percentage."))
+		.unwrap_or(DEFAULT_BLOCK_SHARE_PERCENT)
+}
+
+/// Describe every weight in `weights` which takes more than `share` percent of `limit`, in either
+/// dimension.
+fn overweight(weights: &[(&'static str, Weight)], limit: Weight, share: u64) -> Vec<String> {
+	let allowed = |value: u64| value.saturating_mul(share) / 100;
+	let allowed = Weight::from_parts(allowed(limit.ref_time()), allowed(limit.proof_size()));
+	weights
+		.iter()
+		.filter(|(_, weight)| weight.any_gt(allowed))
+		.map(|(name, weight)| format!("{name}: {weight:?}, allowed {allowed:?}"))
+		.collect()
+}
+
+#[test]
+fn weights_have_not_regressed() {
+	let tolerance = tolerance_percent();
+	let regressions = regressions(&weights::<Runtime, _>(), REFERENCE, tolerance);
+	assert!(
+		regressions.is_empty(),
+		"Weights grew by more than {tolerance}%:\n{}",
+		regressions.join("\n")
+	);
+}
+
+#[test]
+fn every_reference_is_checked() {
+	let weights = weights::<Runtime, _>();
+	for (name, ..) in REFERENCE {
+		assert!(weights.iter().any(|(weight, _)| weight == name), "`{name}` isn't a weight.");
+	}
+	assert_eq!(weights.len(), REFERENCE.len());
+}
+
+#[test]
+fn worst_cases_fit_in_a_block() {
+	let share = block_share_percent();
+	let block_weights: BlockWeights = <Runtime as frame_system::Config>::BlockWeights::get();
+	let normal = block_weights.get(DispatchClass::Normal);
+	let limit = normal.max_extrinsic.unwrap_or(block_weights.max_block);
+	let overweight = overweight(&weights::<Runtime, _>(), limit, share);
+	assert!(
+		overweight.is_empty(),
+		"Worst cases take more than {share}% of a normal extrinsic's share of a block:\n{}",
+		overweight.join("\n")
+	);
+}
+
+#[test]
+fn regressions_are_measured_against_tolerance() {
+	let reference = [("call", 1_000, 100)];
+	let check = |ref_time, proof_size, tolerance| {
+		regressions(&[("call", Weight::from_parts(ref_time, proof_size))], &reference, tolerance)
+			.len()
+	};
+	// Shrinking or growing within the tolerance is fine
+	assert_eq!(check(500, 50, 10), 0);
+	assert_eq!(check(1_100, 110, 10), 0);
+	// Growing beyond it, in either dimension, is not
+	assert_eq!(check(1_101, 100, 10), 1);
+	assert_eq!(check(1_000, 111, 10), 1);
+	assert_eq!(check(1_101, 100, 20), 0);
+}
+
+#[test]
+fn overweight_is_measured_against_the_block_share() {
+	let limit = Weight::from_parts(1_000, 100);
+	let check = |ref_time, proof_size, share| {
+		overweight(&[("call", Weight::from_parts(ref_time, proof_size))], limit, share).len()
+	};
+	// Up to the share of the limit fits
+	assert_eq!(check(500, 50, 50), 0);
+	// Beyond it, in either dimension, does not
+	assert_eq!(check(501, 50, 50), 1);
+	assert_eq!(check(500, 51, 50), 1);
+	assert_eq!(check(501, 51, 100), 0);
+}